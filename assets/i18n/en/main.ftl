@@ -3,3 +3,53 @@
 
 # Game title
 menu-title = Vigilant Doodle
+
+## HUD
+hud-wave = Wave { $wave }
+hud-score = Score: { $score }
+hud-next-wave = Next wave in { $seconds }s
+
+## Inventory
+inventory-title = Inventory
+inventory-slot = { $index }. { $name } ×{ $count }
+inventory-empty-slot = { $index }. —
+inventory-weapon = Weapon: { $name }
+inventory-nothing = None
+
+## Items
+item-medkit = Medkit
+item-battery = Battery
+item-knife = Knife
+
+## Interaction
+hud-interact = [E] { $action }
+interact-open-door = Open door
+interact-close-door = Close door
+interact-door-locked = Locked — find a switch
+interact-use-switch = Pull switch
+interact-open-chest = Open chest
+interact-chest-empty = Empty chest
+interact-read-note = Read note
+note-close-hint = Press E to close
+
+## Objectives
+hud-objectives = Objectives
+objective-done = √ { $text }
+objective-pending = ○ { $text }
+objective-kill = Defeat enemies { $current }/{ $target }
+objective-kill-archetype = Defeat { $name } { $current }/{ $target }
+objective-reach = Reach the marked area
+objective-collect = Collect { $name } { $current }/{ $target }
+objective-survive = Survive { $seconds }s
+
+## Enemies
+enemy-grunt = Grunts
+enemy-scout = Scouts
+enemy-brute = Brutes
+
+## Progression
+hud-level = Lv { $level }  XP { $xp }/{ $next }
+hud-level-max = Lv { $level } (max)
+
+## Notes
+note-courtyard-gate = The gate to the north yard only opens from the lever by the pillar. Whatever they keep back there, they did not want it found.
@@ -13,3 +13,16 @@ menu-quit = Quit
 game-paused = Paused
 game-resume = Resume
 game-back-to-menu = Back to Menu
+
+# Level complete
+level-complete-title = Level Complete
+level-complete-summary = Levels cleared: { $count }
+level-complete-next = Next Level
+
+# Level up
+level-up-title = Level { $level }!
+level-up-choices = Choose an upgrade ({ $remaining } left)
+upgrade-speed = Speed { $from } → { $to }
+upgrade-max-health = Max health { $from } → { $to }
+upgrade-jump-count = Jumps { $from } → { $to }
+upgrade-jump-count-maxed = Jumps { $from } (max)
@@ -30,6 +30,12 @@ settings-vsync = V-Sync
 # Gameplay settings
 settings-difficulty = Difficulty
 settings-auto-save = Auto Save
+settings-auto-face = Auto-face Nearest Enemy
+settings-lock-mode = Lock-on Mode
+settings-lock-mode-soft = Soft Lock
+settings-lock-mode-hard = Hard Lock
+settings-on = On
+settings-off = Off
 
 # Control settings
 settings-mouse-sensitivity = Mouse Sensitivity
@@ -3,3 +3,53 @@
 
 # 游戏标题
 menu-title = Vigilant Doodle
+
+## HUD
+hud-wave = 第 { $wave } 波
+hud-score = 分数：{ $score }
+hud-next-wave = 下一波：{ $seconds } 秒
+
+## 背包
+inventory-title = 背包
+inventory-slot = { $index }. { $name } ×{ $count }
+inventory-empty-slot = { $index }. —
+inventory-weapon = 武器：{ $name }
+inventory-nothing = 无
+
+## 物品
+item-medkit = 医疗包
+item-battery = 电池
+item-knife = 小刀
+
+## 交互
+hud-interact = [E] { $action }
+interact-open-door = 开门
+interact-close-door = 关门
+interact-door-locked = 门锁着——找找开关
+interact-use-switch = 拉动开关
+interact-open-chest = 打开宝箱
+interact-chest-empty = 空宝箱
+interact-read-note = 阅读纸条
+note-close-hint = 按 E 关闭
+
+## 关卡目标
+hud-objectives = 目标
+objective-done = √ { $text }
+objective-pending = ○ { $text }
+objective-kill = 击败敌人 { $current }/{ $target }
+objective-kill-archetype = 击败{ $name } { $current }/{ $target }
+objective-reach = 到达标记区域
+objective-collect = 收集{ $name } { $current }/{ $target }
+objective-survive = 坚持 { $seconds } 秒
+
+## 敌人
+enemy-grunt = 步兵
+enemy-scout = 斥候
+enemy-brute = 重装兵
+
+## 经验值与升级
+hud-level = 等级 { $level }  经验 { $xp }/{ $next }
+hud-level-max = 等级 { $level }（已满级）
+
+## 纸条
+note-courtyard-gate = 北院的大门只能用石柱旁的拉杆打开。不管他们在里面藏了什么，显然不想让人找到。
@@ -13,3 +13,16 @@ menu-quit = 退出游戏
 game-paused = 暂停
 game-resume = 继续
 game-back-to-menu = 返回主菜单
+
+# 通关界面
+level-complete-title = 关卡完成
+level-complete-summary = 已通关：{ $count } 关
+level-complete-next = 下一关
+
+# 升级界面
+level-up-title = 升到 { $level } 级！
+level-up-choices = 选择一项提升（剩余 { $remaining } 次）
+upgrade-speed = 移动速度 { $from } → { $to }
+upgrade-max-health = 生命上限 { $from } → { $to }
+upgrade-jump-count = 跳跃次数 { $from } → { $to }
+upgrade-jump-count-maxed = 跳跃次数 { $from }（已达上限）
@@ -30,6 +30,12 @@ settings-vsync = 垂直同步
 # 游戏性设置
 settings-difficulty = 难度
 settings-auto-save = 自动保存
+settings-auto-face = 自动朝向最近敌人
+settings-lock-mode = 锁定模式
+settings-lock-mode-soft = 软锁定
+settings-lock-mode-hard = 硬锁定
+settings-on = 开
+settings-off = 关
 
 # 控制设置
 settings-mouse-sensitivity = 鼠标灵敏度
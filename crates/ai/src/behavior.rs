@@ -2,21 +2,41 @@
 //!
 //! 负责根据 AI 状态执行具体的行为逻辑
 
+use bevy::ecs::entity::EntityHashSet;
 use bevy::prelude::*;
 
+use super::behavior_tree::{BehaviorTree, TickContext};
+use super::blackboard::Blackboard;
 use super::detection::{DetectionConfig, DetectionResult, DetectionSystem};
-use super::enemy_ai::{AIEvent, EnemyAIConfig, EnemyAIState, EnemyTarget};
-use vigilant_doodle_gameplay::Player;
+use super::enemy_ai::{AIEvent, EnemyAIConfig, EnemyAIState, EnemyTarget, PlayerSpotted};
+use super::lod::AiLod;
+use super::pathfinding::{steer_along_path, NavPath};
+use super::steering::SteeringAgent;
+use vigilant_doodle_gameplay::{
+    EnemyAttack, Flashlight, MovementSystemSet, Player, SpatialIndex, SpatialIndexSet, SpatialKind,
+};
+use vigilant_doodle_world::TimeOfDay;
+
+/// 到达包围站位的判定距离
+const SLOT_ARRIVE_DISTANCE: f32 = 0.1;
 
 /// 行为系统集合
+///
+/// 与移动系统一样在 `FixedUpdate` 中按固定步长运行
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum BehaviorSystemSet {
+    /// 调度阶段：LOD 分级与分时更新
+    Scheduling,
     /// 检测阶段：检测玩家
     Detection,
     /// 决策阶段：状态转换
     Decision,
+    /// 导航阶段：路径规划
+    Navigation,
     /// 执行阶段：执行行为
     Execution,
+    /// 转向阶段：局部避让并积分移动
+    Steering,
 }
 
 /// AI 行为插件（内部使用）
@@ -26,63 +46,133 @@ impl Plugin for BehaviorSystemPlugin {
     fn build(&self, app: &mut App) {
         use vigilant_doodle_core::state::GameState;
 
-        app.configure_sets(
-            Update,
-            (
-                BehaviorSystemSet::Detection,
-                BehaviorSystemSet::Decision,
-                BehaviorSystemSet::Execution,
-            )
-                .chain()
-                .run_if(in_state(GameState::Playing)),
-        )
-        .add_systems(
-            Update,
-            (
-                detect_player_system.in_set(BehaviorSystemSet::Detection),
-                update_ai_state_system.in_set(BehaviorSystemSet::Decision),
+        app.add_message::<EnemyAttack>()
+            .add_message::<PlayerSpotted>()
+            .configure_sets(
+                FixedUpdate,
                 (
-                    chase_behavior_system,
-                    idle_behavior_system,
+                    BehaviorSystemSet::Scheduling,
+                    BehaviorSystemSet::Detection,
+                    BehaviorSystemSet::Decision,
+                    BehaviorSystemSet::Navigation,
+                    BehaviorSystemSet::Execution,
+                    BehaviorSystemSet::Steering,
                 )
-                    .in_set(BehaviorSystemSet::Execution),
-            ),
-        );
+                    .chain()
+                    .after(SpatialIndexSet)
+                    .before(MovementSystemSet::Separation)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    detect_player_system.in_set(BehaviorSystemSet::Detection),
+                    (
+                        update_ai_state_system,
+                        (sync_blackboard_system, tick_behavior_tree_system).chain(),
+                    )
+                        .in_set(BehaviorSystemSet::Decision),
+                    (
+                        chase_behavior_system,
+                        navigate_behavior_system,
+                        attack_behavior_system,
+                        idle_behavior_system,
+                    )
+                        .in_set(BehaviorSystemSet::Execution),
+                ),
+            );
     }
 }
 
+/// 检测系统的跨帧状态
+#[derive(Default)]
+pub(crate) struct DetectionTracking {
+    /// 所有检测配置中最大的检测 / 失去目标范围
+    query_radius: f32,
+    /// 上一帧持有目标的敌人（离开查询范围时需要失去目标）
+    tracked: EntityHashSet,
+}
+
 /// 检测玩家系统
 ///
-/// 检测玩家并更新目标信息
-fn detect_player_system(
-    player_query: Query<(Entity, &Transform), With<Player>>,
+/// 通过空间索引只处理玩家附近的敌人，并更新目标信息；
+/// 检测范围按当前日照缩放（夜间更难发现玩家），被手电筒光束照到的敌人按白天的范围检测
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn detect_player_system(
+    player_query: Query<(Entity, &Transform, Option<&Flashlight>), With<Player>>,
     mut enemy_query: Query<(
         &Transform,
         &DetectionConfig,
         &mut EnemyTarget,
         &EnemyAIState,
+        Option<&AiLod>,
     )>,
+    changed_configs: Query<&DetectionConfig, Changed<DetectionConfig>>,
+    spatial_index: Res<SpatialIndex>,
+    time_of_day: Option<Res<TimeOfDay>>,
+    mut tracking: Local<DetectionTracking>,
+    mut spotted: MessageWriter<PlayerSpotted>,
 ) {
-    let Ok((player_entity, player_transform)) = player_query.single() else {
+    // 索引重建后玩家在本帧内的移动余量
+    const QUERY_MARGIN: f32 = 1.0;
+
+    for config in changed_configs.iter() {
+        tracking.query_radius = tracking
+            .query_radius
+            .max(config.detection_range)
+            .max(config.lose_target_range);
+    }
+
+    let Ok((player_entity, player_transform, flashlight)) = player_query.single() else {
         return;
     };
+    let player_pos = player_transform.translation;
+    let daylight = time_of_day.map_or(1.0, |time| time.daylight());
+
+    let mut candidates: Vec<Entity> = spatial_index
+        .query_radius(player_pos, tracking.query_radius + QUERY_MARGIN)
+        .filter(|entry| entry.kind == SpatialKind::Enemy)
+        .map(|entry| entry.entity)
+        .collect();
+    // 范围外仍持有目标的敌人也需要处理（失去目标）
+    candidates.extend(std::mem::take(&mut tracking.tracked));
+    // 按实体排序去重，保证处理顺序确定
+    candidates.sort_unstable();
+    candidates.dedup();
 
-    for (enemy_transform, detection_config, mut target, state) in enemy_query.iter_mut() {
+    for enemy in candidates {
+        let Ok((enemy_transform, detection_config, mut target, state, lod)) =
+            enemy_query.get_mut(enemy)
+        else {
+            continue;
+        };
+        // 未轮到更新的敌人保持原状，持有目标的留到下次处理
+        if lod.is_some_and(|lod| !lod.active) {
+            if target.has_target() || state.has_target() {
+                tracking.tracked.insert(enemy);
+            }
+            continue;
+        }
         let enemy_pos = enemy_transform.translation;
-        let player_pos = player_transform.translation;
+        let lit =
+            flashlight.is_some_and(|flashlight| flashlight.illuminates(player_pos, enemy_pos));
 
-        // 执行检测
+        // 执行检测（按是否持有目标实体判断：搜索中或经小队警报行动的敌人没有目标实体，需要重新发现）
         let detection_result = DetectionSystem::detect(
-            detection_config,
+            &detection_config.in_daylight(if lit { 1.0 } else { daylight }),
             enemy_pos,
             player_pos,
-            state.has_target(),
+            target.has_target(),
         );
 
         match detection_result {
             DetectionResult::NewTarget => {
                 // 发现新目标
                 target.set_target(player_entity, player_pos);
+                spotted.write(PlayerSpotted {
+                    enemy,
+                    position: player_pos,
+                });
                 debug!("[AI] 敌人发现玩家！");
             }
             DetectionResult::KeepTarget => {
@@ -98,16 +188,26 @@ fn detect_player_system(
                 // 无目标，不做操作
             }
         }
+
+        if target.has_target() || state.has_target() {
+            tracking.tracked.insert(enemy);
+        }
     }
 }
 
 /// 更新 AI 状态系统
 ///
-/// 根据检测结果更新 AI 状态
+/// 根据检测结果更新 AI 状态（仅用于未挂载行为树的敌人）
 fn update_ai_state_system(
-    mut enemy_query: Query<(&EnemyTarget, &mut EnemyAIState)>,
+    mut enemy_query: Query<
+        (&EnemyTarget, &mut EnemyAIState, Option<&AiLod>),
+        Without<BehaviorTree>,
+    >,
 ) {
-    for (target, mut state) in enemy_query.iter_mut() {
+    for (target, mut state, lod) in enemy_query.iter_mut() {
+        if lod.is_some_and(|lod| !lod.active) {
+            continue;
+        }
         let old_state = *state;
 
         // 根据目标状态生成事件
@@ -133,49 +233,233 @@ fn update_ai_state_system(
     }
 }
 
+/// 同步黑板系统
+///
+/// 将检测结果和自身位置写入黑板，供行为树决策
+pub(crate) fn sync_blackboard_system(
+    mut enemy_query: Query<(&Transform, &EnemyTarget, &mut Blackboard, Option<&AiLod>)>,
+    time: Res<Time>,
+) {
+    for (transform, target, mut blackboard, lod) in enemy_query.iter_mut() {
+        let Some(delta) = AiLod::step_for(lod, time.delta_secs()) else {
+            continue;
+        };
+        let target_position = if target.has_target() {
+            target.last_known_position
+        } else {
+            None
+        };
+        blackboard.perceive(transform.translation, target_position, delta);
+    }
+}
+
+/// 行为树决策系统
+///
+/// 执行行为树并将意图写回 AI 状态
+pub(crate) fn tick_behavior_tree_system(
+    mut enemy_query: Query<(
+        &mut BehaviorTree,
+        &mut Blackboard,
+        &EnemyAIConfig,
+        &mut EnemyAIState,
+        Option<&AiLod>,
+    )>,
+    time: Res<Time>,
+) {
+    for (mut tree, mut blackboard, config, mut state, lod) in enemy_query.iter_mut() {
+        let Some(delta) = AiLod::step_for(lod, time.delta_secs()) else {
+            continue;
+        };
+        let ctx = TickContext { config, delta };
+        tree.tick(&mut blackboard, &ctx);
+
+        if *state != blackboard.intent {
+            debug!(
+                "[AI] 行为树状态转换: {} -> {}",
+                state.display_name(),
+                blackboard.intent.display_name()
+            );
+            *state = blackboard.intent;
+        }
+    }
+}
+
+/// 朝目标点水平移动
+///
+/// 挂载 `SteeringAgent` 时只写入期望速度，由 Steering 阶段统一避让并积分；
+/// 否则直接移动并转向。距离小于等于 `stop_distance` 时不移动，返回是否需要移动
+fn move_towards(
+    transform: &mut Transform,
+    agent: Option<Mut<SteeringAgent>>,
+    target_pos: Vec3,
+    speed: f32,
+    stop_distance: f32,
+    delta: f32,
+) -> bool {
+    let to_target = (target_pos - transform.translation).with_y(0.0);
+    let distance = to_target.length();
+
+    // 如果距离足够近，停止移动
+    if distance <= stop_distance {
+        return false;
+    }
+
+    let direction = to_target / distance;
+    // 不越过停止距离
+    let speed = speed.min((distance - stop_distance) / delta.max(f32::EPSILON));
+
+    if let Some(mut agent) = agent {
+        agent.preferred_velocity = direction * speed;
+        return true;
+    }
+
+    // 只在水平面移动（Y 轴保持不变）
+    let movement = direction * speed * delta;
+    transform.translation.x += movement.x;
+    transform.translation.z += movement.z;
+
+    // 朝向目标
+    transform.look_to(direction, Vec3::Y);
+    true
+}
+
 /// 追击行为系统
 ///
-/// 执行追击玩家的行为
+/// 执行追击玩家的行为（有导航路径时沿路径前进）
 fn chase_behavior_system(
     mut enemy_query: Query<(
         &mut Transform,
         &EnemyAIConfig,
         &EnemyTarget,
         &EnemyAIState,
+        Option<&Blackboard>,
+        Option<&mut NavPath>,
+        Option<&mut SteeringAgent>,
     )>,
     time: Res<Time>,
 ) {
-    for (mut transform, config, target, state) in enemy_query.iter_mut() {
+    for (mut transform, config, target, state, blackboard, path, agent) in enemy_query.iter_mut() {
         // 只在追击状态执行
         if *state != EnemyAIState::Chasing {
             continue;
         }
 
-        // 获取目标位置
-        let Some(target_pos) = target.last_known_position else {
+        // 获取目标位置（行为树驱动时使用黑板中的移动目标）
+        let target_pos = match blackboard {
+            Some(blackboard) => blackboard.move_target,
+            None => target.last_known_position,
+        };
+        let Some(target_pos) = target_pos else {
             continue;
         };
 
-        let enemy_pos = transform.translation;
-        let to_target = target_pos - enemy_pos;
-        let distance = to_target.length();
+        // 包围站位需要精确到达
+        let stop_distance = if blackboard.is_some_and(|b| b.surround_slot.is_some()) {
+            SLOT_ARRIVE_DISTANCE
+        } else {
+            config.stop_distance
+        };
+
+        let (steer_pos, stop_distance) =
+            steer_along_path(path, transform.translation, target_pos, stop_distance);
+        move_towards(
+            &mut transform,
+            agent,
+            steer_pos,
+            config.move_speed,
+            stop_distance,
+            time.delta_secs(),
+        );
+    }
+}
 
-        // 如果距离足够近，停止移动
-        if distance <= config.stop_distance {
+/// 导航行为系统
+///
+/// 执行巡逻、搜索、包抄等朝黑板移动目标前进的行为
+fn navigate_behavior_system(
+    mut enemy_query: Query<(
+        &mut Transform,
+        &EnemyAIConfig,
+        &EnemyAIState,
+        &Blackboard,
+        Option<&mut NavPath>,
+        Option<&mut SteeringAgent>,
+    )>,
+    time: Res<Time>,
+) {
+    // 巡逻时放慢脚步
+    const PATROL_SPEED_FACTOR: f32 = 0.5;
+
+    for (mut transform, config, state, blackboard, path, agent) in enemy_query.iter_mut() {
+        let speed = match state {
+            EnemyAIState::Patrol => config.move_speed * PATROL_SPEED_FACTOR,
+            EnemyAIState::Searching | EnemyAIState::Flanking | EnemyAIState::Watching => {
+                config.move_speed
+            }
+            // 攻击者只在分配了包围站位时缓慢调整位置
+            EnemyAIState::Attacking if blackboard.surround_slot.is_some() => {
+                config.move_speed * PATROL_SPEED_FACTOR
+            }
+            _ => continue,
+        };
+
+        let Some(target_pos) = blackboard.move_target else {
             continue;
-        }
+        };
+
+        // 包抄点不需要停在目标外，只需到达
+        let stop_distance = match state {
+            EnemyAIState::Attacking => SLOT_ARRIVE_DISTANCE,
+            EnemyAIState::Flanking if blackboard.move_target != blackboard.target_position => 0.0,
+            _ => config.stop_distance,
+        };
+
+        let (steer_pos, stop_distance) =
+            steer_along_path(path, transform.translation, target_pos, stop_distance);
+        move_towards(
+            &mut transform,
+            agent,
+            steer_pos,
+            speed,
+            stop_distance,
+            time.delta_secs(),
+        );
+    }
+}
 
-        let direction = to_target.normalize();
-        let movement = direction * config.move_speed * time.delta_secs();
+/// 攻击行为系统
+///
+/// 面向目标，并在行为树发起攻击的帧发送攻击消息
+fn attack_behavior_system(
+    mut enemy_query: Query<(
+        Entity,
+        &mut Transform,
+        &EnemyTarget,
+        &EnemyAIState,
+        &Blackboard,
+    )>,
+    mut attack_writer: MessageWriter<EnemyAttack>,
+) {
+    for (entity, mut transform, target, state, blackboard) in enemy_query.iter_mut() {
+        if *state != EnemyAIState::Attacking {
+            continue;
+        }
 
-        // 只在水平面移动（Y 轴保持不变）
-        transform.translation.x += movement.x;
-        transform.translation.z += movement.z;
+        if let Some(target_pos) = blackboard.target_position {
+            let look_direction = (target_pos - transform.translation).with_y(0.0);
+            if look_direction.length_squared() > 0.001 {
+                transform.look_to(look_direction, Vec3::Y);
+            }
+        }
 
-        // 朝向目标
-        let look_direction = Vec3::new(direction.x, 0.0, direction.z);
-        if look_direction.length() > 0.001 {
-            transform.look_to(look_direction, Vec3::Y);
+        if blackboard.attack_requested
+            && let Some(target_entity) = target.entity
+        {
+            attack_writer.write(EnemyAttack {
+                attacker: entity,
+                target: target_entity,
+            });
+            debug!("[AI] 敌人发起攻击！");
         }
     }
 }
@@ -195,6 +479,16 @@ fn idle_behavior_system(
 #[cfg(test)]
 mod tests {
     use super::*;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use vigilant_doodle_core::state::GameState;
+    use vigilant_doodle_core::BalanceConfig;
+    use vigilant_doodle_gameplay::{Enemy, SpatialIndexPlugin};
+    use vigilant_doodle_world::StaticObstacle;
+    use crate::pathfinding::NavigationPlugin;
+    use crate::squad::{AlertConfig, SquadMember, SquadPlugin};
+    use crate::steering::{SteeringConfig, SteeringPlugin};
 
     #[test]
     fn test_behavior_system_sets() {
@@ -206,4 +500,268 @@ mod tests {
         assert_ne!(detection, decision);
         assert_ne!(decision, execution);
     }
+
+    /// 无窗口测试 App：每帧 0.1 秒并执行一次固定步长，直接进入 Playing 状态
+    fn headless_app() -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                100,
+            )))
+            .insert_resource(Time::<Fixed>::from_duration(Duration::from_millis(100)))
+            .insert_state(GameState::Playing)
+            .add_plugins((SpatialIndexPlugin, BehaviorSystemPlugin));
+        app
+    }
+
+    fn spawn_enemy(app: &mut App, position: Vec3) -> Entity {
+        app.world_mut()
+            .spawn((
+                Transform::from_translation(position),
+                Enemy,
+                EnemyAIState::default(),
+                EnemyAIConfig::default(),
+                DetectionConfig::default(),
+                EnemyTarget::default(),
+                Blackboard::with_home(position),
+                BehaviorTree::default_enemy(),
+            ))
+            .id()
+    }
+
+    #[test]
+    fn test_night_detection_and_flashlight() {
+        let mut app = headless_app();
+        let mut time = TimeOfDay::default();
+        time.set_hour(0.0);
+        app.insert_resource(time);
+
+        // 夜间检测范围减半（35 -> 17.5），只有被光束照到的敌人能发现 25 米外的玩家
+        let balance = BalanceConfig::default();
+        let mut flashlight = Flashlight::from_balance(&balance);
+        flashlight.aim_at(Vec3::X);
+        app.world_mut().spawn((
+            Transform::default(),
+            Player::from_balance(&balance),
+            flashlight,
+        ));
+        let lit = spawn_enemy(&mut app, Vec3::new(25.0, 0.0, 0.0));
+        let dark = spawn_enemy(&mut app, Vec3::new(-25.0, 0.0, 0.0));
+
+        for _ in 0..3 {
+            app.update();
+        }
+        let world = app.world();
+        assert!(world.get::<EnemyTarget>(lit).unwrap().has_target());
+        assert!(!world.get::<EnemyTarget>(dark).unwrap().has_target());
+    }
+
+    #[test]
+    fn test_headless_tree_chases_and_attacks_player() {
+        let mut app = headless_app();
+        app.world_mut().spawn((
+            Transform::default(),
+            Player::from_balance(&BalanceConfig::default()),
+        ));
+        let enemy = spawn_enemy(&mut app, Vec3::new(10.0, 0.0, 0.0));
+
+        for _ in 0..3 {
+            app.update();
+        }
+        assert_eq!(
+            *app.world().get::<EnemyAIState>(enemy).unwrap(),
+            EnemyAIState::Chasing
+        );
+
+        for _ in 0..30 {
+            app.update();
+        }
+        let world = app.world();
+        assert_eq!(
+            *world.get::<EnemyAIState>(enemy).unwrap(),
+            EnemyAIState::Attacking
+        );
+        assert!(world.get::<Blackboard>(enemy).unwrap().attack_cooldown > 0.0);
+        assert!(world.get::<Transform>(enemy).unwrap().translation.x < 10.0);
+    }
+
+    #[test]
+    fn test_searching_enemy_reacquires_player() {
+        let mut app = headless_app();
+        app.world_mut().spawn((
+            Transform::default(),
+            Player::from_balance(&BalanceConfig::default()),
+        ));
+        // 搜索中的敌人只有最后已知位置，没有目标实体
+        let enemy = spawn_enemy(&mut app, Vec3::new(10.0, 0.0, 0.0));
+        let mut entity = app.world_mut().entity_mut(enemy);
+        *entity.get_mut::<EnemyAIState>().unwrap() = EnemyAIState::Searching;
+        entity.get_mut::<EnemyTarget>().unwrap().last_known_position = Some(Vec3::X * 20.0);
+
+        for _ in 0..3 {
+            app.update();
+        }
+        let world = app.world();
+        assert!(world.get::<EnemyTarget>(enemy).unwrap().has_target());
+        assert_eq!(
+            *world.get::<EnemyAIState>(enemy).unwrap(),
+            EnemyAIState::Chasing
+        );
+    }
+
+    #[test]
+    fn test_headless_chase_goes_around_obstacle() {
+        let mut app = headless_app();
+        app.add_plugins(NavigationPlugin);
+        app.world_mut().spawn((
+            Transform::default(),
+            Player::from_balance(&BalanceConfig::default()),
+        ));
+        // 敌人与玩家之间的墙
+        let wall = StaticObstacle::new(Vec2::new(0.5, 4.0), 3.0);
+        let wall_position = Vec3::new(3.0, 0.0, 0.0);
+        app.world_mut()
+            .spawn((Transform::from_translation(wall_position), wall));
+        let enemy = spawn_enemy(&mut app, Vec3::new(6.0, 0.0, 0.0));
+        app.world_mut().entity_mut(enemy).insert(NavPath::default());
+
+        let footprint = wall.footprint(wall_position);
+        for _ in 0..60 {
+            app.update();
+            let position = app.world().get::<Transform>(enemy).unwrap().translation;
+            assert!(!footprint.contains(Vec2::new(position.x, position.z)));
+        }
+
+        let position = app.world().get::<Transform>(enemy).unwrap().translation;
+        assert!(position.x < 2.5);
+        assert_eq!(
+            *app.world().get::<EnemyAIState>(enemy).unwrap(),
+            EnemyAIState::Attacking
+        );
+    }
+
+    #[test]
+    fn test_headless_crowd_surrounds_player() {
+        let mut app = headless_app();
+        app.add_plugins(SteeringPlugin);
+        app.world_mut().spawn((
+            Transform::default(),
+            Player::from_balance(&BalanceConfig::default()),
+        ));
+        // 四个敌人从同一侧挤向玩家
+        let enemies: Vec<Entity> = [0.0, 0.5, -0.5, 1.0]
+            .into_iter()
+            .map(|z| {
+                let enemy = spawn_enemy(&mut app, Vec3::new(8.0, 0.0, z));
+                app.world_mut()
+                    .entity_mut(enemy)
+                    .insert((SteeringConfig::default(), SteeringAgent::new(0.6)));
+                enemy
+            })
+            .collect();
+
+        for _ in 0..80 {
+            app.update();
+        }
+
+        let positions: Vec<Vec3> = enemies
+            .iter()
+            .map(|enemy| app.world().get::<Transform>(*enemy).unwrap().translation)
+            .collect();
+        for (i, a) in positions.iter().enumerate() {
+            assert!(a.length() < EnemyAIConfig::default().attack_range + 0.5);
+            for b in positions.iter().skip(i + 1) {
+                assert!(a.distance(*b) > 1.2, "{a} 与 {b} 过近");
+            }
+        }
+    }
+
+    #[test]
+    fn test_headless_alert_reaches_squad_after_delay() {
+        let mut app = headless_app();
+        app.add_plugins(SquadPlugin);
+        let player = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                Player::from_balance(&BalanceConfig::default()),
+            ))
+            .id();
+        let spotter = spawn_enemy(&mut app, Vec3::new(10.0, 0.0, 0.0));
+        // 同伴视野很短，只能依靠警报得知玩家位置
+        let ally = spawn_enemy(&mut app, Vec3::new(20.0, 0.0, 0.0));
+        app.world_mut().entity_mut(ally).insert(DetectionConfig {
+            detection_range: 3.0,
+            lose_target_range: 5.0,
+            ..default()
+        });
+        for enemy in [spotter, ally] {
+            app.world_mut()
+                .entity_mut(enemy)
+                .insert((AlertConfig::default(), SquadMember { squad: 0 }));
+        }
+
+        for _ in 0..2 {
+            app.update();
+        }
+        assert!(
+            app.world()
+                .get::<Blackboard>(ally)
+                .unwrap()
+                .last_known_position
+                .is_none()
+        );
+
+        for _ in 0..8 {
+            app.update();
+        }
+        assert_eq!(
+            *app.world().get::<EnemyAIState>(ally).unwrap(),
+            EnemyAIState::Searching
+        );
+        assert_eq!(
+            app.world().get::<Blackboard>(spotter).unwrap().squad_role,
+            Some(crate::squad::SquadRole::Chaser)
+        );
+
+        // 发现者持续看到玩家，小队黑板把最新位置同步给同伴
+        let moved = Vec3::new(0.0, 0.0, 4.0);
+        app.world_mut()
+            .get_mut::<Transform>(player)
+            .unwrap()
+            .translation = moved;
+        app.update();
+        assert_eq!(
+            app.world()
+                .get::<Blackboard>(ally)
+                .unwrap()
+                .last_known_position,
+            Some(moved)
+        );
+    }
+
+    #[test]
+    fn test_headless_runs_are_deterministic() {
+        let run = || {
+            let mut app = headless_app();
+            app.world_mut().spawn((
+                Transform::from_xyz(0.0, 0.0, 2.0),
+                Player::from_balance(&BalanceConfig::default()),
+            ));
+            let enemy = spawn_enemy(&mut app, Vec3::new(12.0, 0.0, -4.0));
+            app.world_mut()
+                .get_mut::<Blackboard>(enemy)
+                .unwrap()
+                .flank_weight = 0.8;
+
+            (0..40)
+                .map(|_| {
+                    app.update();
+                    app.world().get::<Transform>(enemy).unwrap().translation
+                })
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(run(), run());
+    }
 }
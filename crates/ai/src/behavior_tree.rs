@@ -0,0 +1,614 @@
+//! 行为树 / 效用 AI 决策层
+//!
+//! 在 Decision 阶段为每个敌人执行一棵行为树，替代单一状态对应单一行为的硬编码转换：
+//! - 组合节点：选择器（Selector）、序列（Sequence）、效用选择器（Utility）
+//! - 装饰节点：取反（Inverter）、恒成功（Succeeder）、冷却（Cooldown）
+//! - 叶子节点：条件（Condition）与动作（Action）
+//!
+//! 行为树只读写 `Blackboard`，不直接访问 ECS，因此可以在无窗口的测试中按固定步长确定性地执行。
+
+use bevy::prelude::*;
+
+use super::blackboard::{horizontal_distance, Blackboard};
+use super::enemy_ai::{EnemyAIConfig, EnemyAIState};
+use super::squad::SquadRole;
+
+/// 节点执行结果
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BtStatus {
+    /// 执行成功
+    Success,
+    /// 执行失败
+    Failure,
+    /// 执行中（下一帧继续）
+    Running,
+}
+
+/// 单次执行的上下文
+pub struct TickContext<'a> {
+    /// 敌人行为参数
+    pub config: &'a EnemyAIConfig,
+    /// 本帧时间步长（秒）
+    pub delta: f32,
+}
+
+/// 条件节点
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum BtCondition {
+    /// 当前持有可见目标
+    HasTarget,
+    /// 记得目标最后已知位置
+    HasLastKnownPosition,
+    /// 目标在攻击范围内（使用 `EnemyAIConfig::attack_range`）
+    InAttackRange,
+    /// 目标在指定距离内
+    TargetWithin(f32),
+    /// 指定时间内见过目标
+    SeenWithin(f32),
+    /// 攻击冷却完毕
+    AttackReady,
+    /// 配置了巡逻路线
+    HasPatrolRoute,
+    /// 被分配了指定的小队角色
+    HasRole(SquadRole),
+}
+
+impl BtCondition {
+    fn evaluate(&self, blackboard: &Blackboard, ctx: &TickContext) -> bool {
+        match *self {
+            Self::HasTarget => blackboard.has_target,
+            Self::HasLastKnownPosition => blackboard.last_known_position.is_some(),
+            Self::InAttackRange => {
+                blackboard.has_target && blackboard.distance_to_target <= ctx.config.attack_range
+            }
+            Self::TargetWithin(range) => blackboard.distance_to_target <= range,
+            Self::SeenWithin(seconds) => blackboard.time_since_seen <= seconds,
+            Self::AttackReady => blackboard.attack_cooldown <= 0.0,
+            Self::HasPatrolRoute => !blackboard.patrol_route.is_empty(),
+            Self::HasRole(role) => blackboard.squad_role == Some(role),
+        }
+    }
+}
+
+/// 动作节点
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BtAction {
+    /// 原地待命
+    Idle,
+    /// 直线追击目标
+    Chase,
+    /// 攻击目标（按攻击间隔发起）
+    Attack,
+    /// 从侧面包抄目标
+    Flank,
+    /// 前往最后已知位置并停留搜索
+    Search,
+    /// 沿巡逻路线移动
+    Patrol,
+    /// 与目标保持警戒距离
+    Watch,
+}
+
+impl BtAction {
+    fn run(&self, blackboard: &mut Blackboard, ctx: &TickContext) -> BtStatus {
+        match self {
+            Self::Idle => {
+                blackboard.intent = EnemyAIState::Idle;
+                blackboard.move_target = None;
+                BtStatus::Success
+            }
+            Self::Chase => {
+                let Some(target) = blackboard
+                    .target_position
+                    .or(blackboard.last_known_position)
+                else {
+                    return BtStatus::Failure;
+                };
+                blackboard.intent = EnemyAIState::Chasing;
+                blackboard.move_target = Some(target);
+                BtStatus::Running
+            }
+            Self::Attack => {
+                if !blackboard.has_target {
+                    return BtStatus::Failure;
+                }
+                blackboard.intent = EnemyAIState::Attacking;
+                blackboard.move_target = None;
+                if blackboard.attack_cooldown <= 0.0 {
+                    blackboard.attack_requested = true;
+                    blackboard.attack_cooldown = ctx.config.attack_interval;
+                }
+                BtStatus::Running
+            }
+            Self::Flank => {
+                let Some(target) = blackboard.target_position else {
+                    return BtStatus::Failure;
+                };
+                let to_target = (target - blackboard.self_position)
+                    .with_y(0.0)
+                    .normalize_or_zero();
+                if to_target == Vec3::ZERO {
+                    return BtStatus::Failure;
+                }
+
+                blackboard.intent = EnemyAIState::Flanking;
+                // 距离足够近时直接贴近目标，否则先绕到目标侧面
+                blackboard.move_target = if blackboard.distance_to_target
+                    <= ctx.config.flank_distance
+                {
+                    Some(target)
+                } else {
+                    let side = Vec3::new(-to_target.z, 0.0, to_target.x) * blackboard.flank_side;
+                    Some(target + side * ctx.config.flank_distance)
+                };
+                BtStatus::Running
+            }
+            Self::Search => {
+                let Some(last_known) = blackboard.last_known_position else {
+                    return BtStatus::Failure;
+                };
+                blackboard.intent = EnemyAIState::Searching;
+
+                if horizontal_distance(blackboard.self_position, last_known)
+                    > ctx.config.stop_distance
+                {
+                    blackboard.move_target = Some(last_known);
+                    return BtStatus::Running;
+                }
+
+                // 到达最后已知位置后原地搜索一段时间
+                blackboard.move_target = None;
+                blackboard.search_elapsed += ctx.delta;
+                if blackboard.search_elapsed >= ctx.config.search_duration {
+                    blackboard.forget_target();
+                    BtStatus::Success
+                } else {
+                    BtStatus::Running
+                }
+            }
+            Self::Patrol => {
+                let Some(mut point) = blackboard.current_patrol_point() else {
+                    return BtStatus::Failure;
+                };
+                if horizontal_distance(blackboard.self_position, point) <= ctx.config.stop_distance
+                {
+                    blackboard.advance_patrol();
+                    point = blackboard.current_patrol_point().unwrap_or(point);
+                }
+                blackboard.intent = EnemyAIState::Patrol;
+                blackboard.move_target = Some(point);
+                BtStatus::Running
+            }
+            Self::Watch => {
+                let Some(target) = blackboard.target_position else {
+                    return BtStatus::Failure;
+                };
+                let away = (blackboard.self_position - target)
+                    .with_y(0.0)
+                    .normalize_or(Vec3::X);
+
+                blackboard.intent = EnemyAIState::Watching;
+                blackboard.move_target = Some(target + away * ctx.config.watch_distance);
+                BtStatus::Running
+            }
+        }
+    }
+}
+
+/// 效用评分函数
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Scorer {
+    /// 固定分数
+    Constant(f32),
+    /// 离目标越近分数越高（range 外为 0）
+    Proximity { range: f32 },
+    /// 最近见过目标的程度（memory 秒后衰减为 0）
+    Recency { memory: f32 },
+    /// 黑板中的包抄倾向
+    FlankWeight,
+}
+
+impl Scorer {
+    fn score(&self, blackboard: &Blackboard) -> f32 {
+        match *self {
+            Self::Constant(value) => value,
+            Self::Proximity { range } => {
+                if range <= 0.0 {
+                    return 0.0;
+                }
+                (1.0 - blackboard.distance_to_target / range).clamp(0.0, 1.0)
+            }
+            Self::Recency { memory } => {
+                if memory <= 0.0 {
+                    return 0.0;
+                }
+                (1.0 - blackboard.time_since_seen / memory).clamp(0.0, 1.0)
+            }
+            Self::FlankWeight => blackboard.flank_weight,
+        }
+    }
+}
+
+/// 效用选择器的候选项
+#[derive(Debug, Clone)]
+pub struct UtilityOption {
+    pub scorer: Scorer,
+    pub node: BtNode,
+}
+
+/// 行为树节点
+#[derive(Debug, Clone)]
+pub enum BtNode {
+    /// 依次执行子节点，直到某个子节点不返回 Failure
+    Selector(Vec<BtNode>),
+    /// 依次执行子节点，直到某个子节点不返回 Success
+    Sequence(Vec<BtNode>),
+    /// 按评分从高到低尝试子节点（评分 <= 0 的跳过，同分保持声明顺序）
+    Utility(Vec<UtilityOption>),
+    /// 结果取反（Running 保持不变）
+    Inverter(Box<BtNode>),
+    /// 子节点结束后总是返回 Success
+    Succeeder(Box<BtNode>),
+    /// 子节点成功后进入冷却，冷却期间直接返回 Failure
+    Cooldown {
+        duration: f32,
+        ready_at: f32,
+        child: Box<BtNode>,
+    },
+    /// 条件叶子
+    Condition(BtCondition),
+    /// 动作叶子
+    Action(BtAction),
+}
+
+impl BtNode {
+    pub fn selector(children: Vec<BtNode>) -> Self {
+        Self::Selector(children)
+    }
+
+    pub fn sequence(children: Vec<BtNode>) -> Self {
+        Self::Sequence(children)
+    }
+
+    pub fn utility(options: Vec<(Scorer, BtNode)>) -> Self {
+        Self::Utility(
+            options
+                .into_iter()
+                .map(|(scorer, node)| UtilityOption { scorer, node })
+                .collect(),
+        )
+    }
+
+    pub fn inverter(child: BtNode) -> Self {
+        Self::Inverter(Box::new(child))
+    }
+
+    pub fn succeeder(child: BtNode) -> Self {
+        Self::Succeeder(Box::new(child))
+    }
+
+    pub fn cooldown(duration: f32, child: BtNode) -> Self {
+        Self::Cooldown {
+            duration,
+            ready_at: 0.0,
+            child: Box::new(child),
+        }
+    }
+
+    pub fn condition(condition: BtCondition) -> Self {
+        Self::Condition(condition)
+    }
+
+    pub fn action(action: BtAction) -> Self {
+        Self::Action(action)
+    }
+
+    fn tick(&mut self, blackboard: &mut Blackboard, ctx: &TickContext, now: f32) -> BtStatus {
+        match self {
+            Self::Selector(children) => {
+                for child in children.iter_mut() {
+                    match child.tick(blackboard, ctx, now) {
+                        BtStatus::Failure => continue,
+                        status => return status,
+                    }
+                }
+                BtStatus::Failure
+            }
+            Self::Sequence(children) => {
+                for child in children.iter_mut() {
+                    match child.tick(blackboard, ctx, now) {
+                        BtStatus::Success => continue,
+                        status => return status,
+                    }
+                }
+                BtStatus::Success
+            }
+            Self::Utility(options) => {
+                let scores: Vec<f32> = options
+                    .iter()
+                    .map(|option| option.scorer.score(blackboard))
+                    .collect();
+                let mut order: Vec<usize> = (0..options.len())
+                    .filter(|&index| scores[index] > 0.0)
+                    .collect();
+                // sort_by 是稳定排序，同分时保持声明顺序，保证结果确定
+                order.sort_by(|&a, &b| scores[b].total_cmp(&scores[a]));
+
+                for index in order {
+                    match options[index].node.tick(blackboard, ctx, now) {
+                        BtStatus::Failure => continue,
+                        status => return status,
+                    }
+                }
+                BtStatus::Failure
+            }
+            Self::Inverter(child) => match child.tick(blackboard, ctx, now) {
+                BtStatus::Success => BtStatus::Failure,
+                BtStatus::Failure => BtStatus::Success,
+                BtStatus::Running => BtStatus::Running,
+            },
+            Self::Succeeder(child) => match child.tick(blackboard, ctx, now) {
+                BtStatus::Running => BtStatus::Running,
+                _ => BtStatus::Success,
+            },
+            Self::Cooldown {
+                duration,
+                ready_at,
+                child,
+            } => {
+                if now < *ready_at {
+                    return BtStatus::Failure;
+                }
+                let status = child.tick(blackboard, ctx, now);
+                if status == BtStatus::Success {
+                    *ready_at = now + *duration;
+                }
+                status
+            }
+            Self::Condition(condition) => {
+                if condition.evaluate(blackboard, ctx) {
+                    BtStatus::Success
+                } else {
+                    BtStatus::Failure
+                }
+            }
+            Self::Action(action) => action.run(blackboard, ctx),
+        }
+    }
+}
+
+/// 行为树组件
+///
+/// 每个敌人持有独立的行为树实例（装饰节点的运行时状态保存在节点内部）
+#[derive(Component, Debug, Clone)]
+pub struct BehaviorTree {
+    root: BtNode,
+    /// 行为树累计运行时间（秒），冷却节点以此为时钟
+    elapsed: f32,
+    /// 最近一次执行结果
+    pub last_status: BtStatus,
+}
+
+impl BehaviorTree {
+    pub fn new(root: BtNode) -> Self {
+        Self {
+            root,
+            elapsed: 0.0,
+            last_status: BtStatus::Failure,
+        }
+    }
+
+    /// 默认敌人行为树
+    ///
+    /// 优先级：攻击 > 警戒（小队警戒者）> 追击/包抄（效用评分）> 搜索 > 巡逻 > 闲置
+    pub fn default_enemy() -> Self {
+        use BtAction::*;
+        use BtCondition::*;
+
+        Self::new(BtNode::selector(vec![
+            BtNode::sequence(vec![
+                BtNode::condition(InAttackRange),
+                BtNode::action(Attack),
+            ]),
+            BtNode::sequence(vec![
+                BtNode::condition(HasTarget),
+                BtNode::condition(HasRole(SquadRole::Watcher)),
+                BtNode::action(Watch),
+            ]),
+            BtNode::sequence(vec![
+                BtNode::condition(HasTarget),
+                BtNode::utility(vec![
+                    (Scorer::Constant(0.5), BtNode::action(Chase)),
+                    (Scorer::FlankWeight, BtNode::action(Flank)),
+                ]),
+            ]),
+            BtNode::sequence(vec![
+                BtNode::condition(HasLastKnownPosition),
+                BtNode::action(Search),
+            ]),
+            BtNode::sequence(vec![
+                BtNode::condition(HasPatrolRoute),
+                BtNode::action(Patrol),
+            ]),
+            BtNode::action(Idle),
+        ]))
+    }
+
+    /// 执行一次行为树
+    ///
+    /// 每帧从根节点重新评估，决策结果写入 `blackboard.intent` 与 `blackboard.move_target`
+    pub fn tick(&mut self, blackboard: &mut Blackboard, ctx: &TickContext) -> BtStatus {
+        self.elapsed += ctx.delta;
+        blackboard.attack_requested = false;
+
+        let status = self.root.tick(blackboard, ctx, self.elapsed);
+        self.last_status = status;
+        status
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DT: f32 = 0.1;
+
+    /// 无窗口测试工具：按固定步长执行行为树并模拟移动
+    struct TreeHarness {
+        tree: BehaviorTree,
+        blackboard: Blackboard,
+        config: EnemyAIConfig,
+    }
+
+    impl TreeHarness {
+        fn new(tree: BehaviorTree, blackboard: Blackboard) -> Self {
+            Self {
+                tree,
+                blackboard,
+                config: EnemyAIConfig::default(),
+            }
+        }
+
+        /// 执行一帧：感知 -> 决策 -> 朝 move_target 移动
+        fn step(&mut self, target: Option<Vec3>) -> EnemyAIState {
+            let position = self.blackboard.self_position;
+            self.blackboard.perceive(position, target, DT);
+
+            let ctx = TickContext {
+                config: &self.config,
+                delta: DT,
+            };
+            self.tree.tick(&mut self.blackboard, &ctx);
+
+            if let Some(move_target) = self.blackboard.move_target {
+                let to_target = (move_target - position).with_y(0.0);
+                let step = self.config.move_speed * DT;
+                self.blackboard.self_position += to_target.clamp_length_max(step);
+            }
+            self.blackboard.intent
+        }
+    }
+
+    #[test]
+    fn test_selector_and_sequence() {
+        let mut blackboard = Blackboard::default();
+        let config = EnemyAIConfig::default();
+        let ctx = TickContext {
+            config: &config,
+            delta: DT,
+        };
+
+        let mut sequence = BtNode::sequence(vec![
+            BtNode::condition(BtCondition::HasTarget),
+            BtNode::action(BtAction::Idle),
+        ]);
+        assert_eq!(sequence.tick(&mut blackboard, &ctx, 0.0), BtStatus::Failure);
+
+        let mut selector = BtNode::selector(vec![sequence, BtNode::action(BtAction::Idle)]);
+        assert_eq!(selector.tick(&mut blackboard, &ctx, 0.0), BtStatus::Success);
+
+        let mut inverter = BtNode::inverter(BtNode::condition(BtCondition::HasTarget));
+        assert_eq!(inverter.tick(&mut blackboard, &ctx, 0.0), BtStatus::Success);
+    }
+
+    #[test]
+    fn test_cooldown_decorator() {
+        let mut blackboard = Blackboard::default();
+        let config = EnemyAIConfig::default();
+        let ctx = TickContext {
+            config: &config,
+            delta: DT,
+        };
+
+        let mut node = BtNode::cooldown(1.0, BtNode::action(BtAction::Idle));
+        assert_eq!(node.tick(&mut blackboard, &ctx, 0.0), BtStatus::Success);
+        assert_eq!(node.tick(&mut blackboard, &ctx, 0.5), BtStatus::Failure);
+        assert_eq!(node.tick(&mut blackboard, &ctx, 1.0), BtStatus::Success);
+    }
+
+    #[test]
+    fn test_utility_prefers_higher_score() {
+        let mut blackboard = Blackboard::default();
+        blackboard.perceive(Vec3::ZERO, Some(Vec3::new(20.0, 0.0, 0.0)), DT);
+        let config = EnemyAIConfig::default();
+        let ctx = TickContext {
+            config: &config,
+            delta: DT,
+        };
+
+        let mut node = BtNode::utility(vec![
+            (Scorer::Constant(0.5), BtNode::action(BtAction::Chase)),
+            (Scorer::FlankWeight, BtNode::action(BtAction::Flank)),
+        ]);
+
+        node.tick(&mut blackboard, &ctx, 0.0);
+        assert_eq!(blackboard.intent, EnemyAIState::Chasing);
+
+        blackboard.flank_weight = 0.9;
+        node.tick(&mut blackboard, &ctx, 0.0);
+        assert_eq!(blackboard.intent, EnemyAIState::Flanking);
+        // 包抄点应偏离目标所在直线
+        assert!(blackboard.move_target.unwrap().z.abs() > 1.0);
+    }
+
+    #[test]
+    fn test_default_tree_chase_then_attack() {
+        let mut harness = TreeHarness::new(
+            BehaviorTree::default_enemy(),
+            Blackboard::with_home(Vec3::new(10.0, 0.0, 0.0)),
+        );
+        let player = Vec3::ZERO;
+
+        assert_eq!(harness.step(Some(player)), EnemyAIState::Chasing);
+
+        let mut attacked = false;
+        for _ in 0..50 {
+            if harness.step(Some(player)) == EnemyAIState::Attacking {
+                attacked |= harness.blackboard.attack_requested;
+            }
+        }
+        assert!(attacked);
+        assert_eq!(harness.blackboard.intent, EnemyAIState::Attacking);
+    }
+
+    #[test]
+    fn test_default_tree_search_then_patrol() {
+        let home = Vec3::new(10.0, 0.0, 0.0);
+        let mut harness = TreeHarness::new(
+            BehaviorTree::default_enemy(),
+            Blackboard::with_home(home)
+                .with_patrol_route(Blackboard::square_patrol_route(home, 3.0)),
+        );
+
+        // 先看到目标再失去
+        harness.step(Some(Vec3::new(5.0, 0.0, 0.0)));
+        assert_eq!(harness.step(None), EnemyAIState::Searching);
+
+        // 搜索结束后回到巡逻
+        let steps = ((harness.config.search_duration + 2.0) / DT) as usize;
+        for _ in 0..steps {
+            harness.step(None);
+        }
+        assert!(harness.blackboard.last_known_position.is_none());
+        assert_eq!(harness.step(None), EnemyAIState::Patrol);
+    }
+
+    #[test]
+    fn test_tree_is_deterministic() {
+        let run = || {
+            let mut harness = TreeHarness::new(
+                BehaviorTree::default_enemy(),
+                Blackboard::with_home(Vec3::new(8.0, 0.0, 3.0)),
+            );
+            harness.blackboard.flank_weight = 0.7;
+            let mut trace = Vec::new();
+            for frame in 0..40 {
+                let target = (frame < 25).then_some(Vec3::new(0.0, 0.0, frame as f32 * 0.1));
+                trace.push((harness.step(target), harness.blackboard.self_position));
+            }
+            trace
+        };
+
+        assert_eq!(run(), run());
+    }
+}
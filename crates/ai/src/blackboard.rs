@@ -0,0 +1,260 @@
+//! 敌人黑板（Blackboard）
+//!
+//! 每个敌人独立持有的决策记忆，供行为树读写：
+//! - 自身与目标的位置信息
+//! - 目标最后已知位置（用于搜索）
+//! - 巡逻路线与进度
+//! - 攻击冷却、包抄方向等行为状态
+
+use bevy::prelude::*;
+
+use super::enemy_ai::EnemyAIState;
+use super::squad::SquadRole;
+
+/// 敌人黑板组件
+///
+/// 由 Decision 阶段的同步系统从 `EnemyTarget` / `Transform` 写入感知数据，
+/// 再由行为树读取并写回决策结果（`intent` 与 `move_target`）
+#[derive(Component, Debug, Clone)]
+pub struct Blackboard {
+    /// 自身当前位置
+    pub self_position: Vec3,
+    /// 出生点（巡逻与返回的参考点）
+    pub home_position: Vec3,
+
+    /// 当前是否持有可见目标
+    pub has_target: bool,
+    /// 当前目标位置（仅在持有目标时有效）
+    pub target_position: Option<Vec3>,
+    /// 目标最后已知位置（失去目标后保留，搜索完成后清除）
+    pub last_known_position: Option<Vec3>,
+    /// 到目标（或最后已知位置）的水平距离
+    pub distance_to_target: f32,
+    /// 距离上次看到目标经过的时间（秒）
+    pub time_since_seen: f32,
+
+    /// 巡逻路线点
+    pub patrol_route: Vec<Vec3>,
+    /// 当前巡逻点索引
+    pub patrol_index: usize,
+
+    /// 攻击剩余冷却时间（秒）
+    pub attack_cooldown: f32,
+    /// 本帧是否发起攻击（由攻击行为写入，执行阶段消费）
+    pub attack_requested: bool,
+
+    /// 搜索已持续的时间（秒）
+    pub search_elapsed: f32,
+
+    /// 包抄方向（+1 右侧，-1 左侧）
+    pub flank_side: f32,
+    /// 包抄倾向（0-1，效用评分使用，0 表示从不包抄）
+    pub flank_weight: f32,
+    /// 包围站位（靠近目标时由站位分配系统写入）
+    pub surround_slot: Option<Vec3>,
+    /// 小队角色（交战时由小队系统分配）
+    pub squad_role: Option<SquadRole>,
+
+    /// 行为树给出的意图状态
+    pub intent: EnemyAIState,
+    /// 行为树给出的移动目标点（None 表示原地不动）
+    pub move_target: Option<Vec3>,
+}
+
+impl Default for Blackboard {
+    fn default() -> Self {
+        Self {
+            self_position: Vec3::ZERO,
+            home_position: Vec3::ZERO,
+            has_target: false,
+            target_position: None,
+            last_known_position: None,
+            distance_to_target: f32::INFINITY,
+            time_since_seen: f32::INFINITY,
+            patrol_route: Vec::new(),
+            patrol_index: 0,
+            attack_cooldown: 0.0,
+            attack_requested: false,
+            search_elapsed: 0.0,
+            flank_side: 1.0,
+            flank_weight: 0.0,
+            surround_slot: None,
+            squad_role: None,
+            intent: EnemyAIState::Idle,
+            move_target: None,
+        }
+    }
+}
+
+impl Blackboard {
+    /// 以出生点创建黑板
+    pub fn with_home(home: Vec3) -> Self {
+        Self {
+            self_position: home,
+            home_position: home,
+            ..default()
+        }
+    }
+
+    /// 设置巡逻路线
+    pub fn with_patrol_route(mut self, route: Vec<Vec3>) -> Self {
+        self.patrol_route = route;
+        self.patrol_index = 0;
+        self
+    }
+
+    /// 以出生点为中心生成方形巡逻路线
+    pub fn square_patrol_route(center: Vec3, radius: f32) -> Vec<Vec3> {
+        vec![
+            center + Vec3::new(radius, 0.0, radius),
+            center + Vec3::new(-radius, 0.0, radius),
+            center + Vec3::new(-radius, 0.0, -radius),
+            center + Vec3::new(radius, 0.0, -radius),
+        ]
+    }
+
+    /// 写入感知数据（每帧由同步系统调用）
+    pub fn perceive(&mut self, self_position: Vec3, target_position: Option<Vec3>, delta: f32) {
+        self.self_position = self_position;
+
+        match target_position {
+            Some(position) => {
+                self.has_target = true;
+                self.target_position = Some(position);
+                self.last_known_position = Some(position);
+                self.time_since_seen = 0.0;
+                self.search_elapsed = 0.0;
+            }
+            None => {
+                self.has_target = false;
+                self.target_position = None;
+                self.time_since_seen += delta;
+            }
+        }
+
+        self.distance_to_target = self
+            .last_known_position
+            .map(|position| horizontal_distance(self_position, position))
+            .unwrap_or(f32::INFINITY);
+
+        self.attack_cooldown = (self.attack_cooldown - delta).max(0.0);
+    }
+
+    /// 收到同伴警报：记下玩家位置，随后前往搜索
+    pub fn hear_alert(&mut self, position: Vec3) {
+        self.last_known_position = Some(position);
+        self.time_since_seen = 0.0;
+        self.search_elapsed = 0.0;
+        self.distance_to_target = horizontal_distance(self.self_position, position);
+    }
+
+    /// 同步小队情报：仅在已察觉玩家且小队情报比自己的记忆更新时覆盖
+    ///
+    /// 尚未察觉的成员需要先收到警报，小队黑板不会绕过警报延迟
+    pub fn share_knowledge(&mut self, position: Vec3, time_since_seen: f32) {
+        if self.has_target
+            || self.last_known_position.is_none()
+            || self.time_since_seen <= time_since_seen
+        {
+            return;
+        }
+        self.last_known_position = Some(position);
+        self.time_since_seen = time_since_seen;
+        self.search_elapsed = 0.0;
+        self.distance_to_target = horizontal_distance(self.self_position, position);
+    }
+
+    /// 当前巡逻点
+    pub fn current_patrol_point(&self) -> Option<Vec3> {
+        if self.patrol_route.is_empty() {
+            return None;
+        }
+        self.patrol_route
+            .get(self.patrol_index % self.patrol_route.len())
+            .copied()
+    }
+
+    /// 前进到下一个巡逻点
+    pub fn advance_patrol(&mut self) {
+        if !self.patrol_route.is_empty() {
+            self.patrol_index = (self.patrol_index + 1) % self.patrol_route.len();
+        }
+    }
+
+    /// 遗忘最后已知位置（搜索结束时调用）
+    pub fn forget_target(&mut self) {
+        self.last_known_position = None;
+        self.distance_to_target = f32::INFINITY;
+        self.search_elapsed = 0.0;
+    }
+}
+
+/// 水平面（XZ）距离
+pub fn horizontal_distance(a: Vec3, b: Vec3) -> f32 {
+    Vec2::new(a.x - b.x, a.z - b.z).length()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_perceive_keeps_last_known_position() {
+        let mut blackboard = Blackboard::default();
+
+        blackboard.perceive(Vec3::ZERO, Some(Vec3::new(3.0, 0.0, 4.0)), 0.1);
+        assert!(blackboard.has_target);
+        assert_eq!(blackboard.distance_to_target, 5.0);
+        assert_eq!(blackboard.time_since_seen, 0.0);
+
+        blackboard.perceive(Vec3::ZERO, None, 0.5);
+        assert!(!blackboard.has_target);
+        assert_eq!(
+            blackboard.last_known_position,
+            Some(Vec3::new(3.0, 0.0, 4.0))
+        );
+        assert_eq!(blackboard.time_since_seen, 0.5);
+
+        blackboard.forget_target();
+        assert!(blackboard.last_known_position.is_none());
+    }
+
+    #[test]
+    fn test_share_knowledge_prefers_fresher_memory() {
+        let mut blackboard = Blackboard::default();
+
+        // 尚未察觉时不接收小队情报
+        blackboard.share_knowledge(Vec3::new(1.0, 0.0, 0.0), 0.0);
+        assert!(blackboard.last_known_position.is_none());
+
+        blackboard.perceive(Vec3::ZERO, Some(Vec3::new(5.0, 0.0, 0.0)), 0.1);
+        blackboard.perceive(Vec3::ZERO, None, 0.5);
+
+        // 小队情报更旧，不覆盖
+        blackboard.share_knowledge(Vec3::new(-5.0, 0.0, 0.0), 2.0);
+        assert_eq!(
+            blackboard.last_known_position,
+            Some(Vec3::new(5.0, 0.0, 0.0))
+        );
+
+        // 小队情报更新，覆盖
+        blackboard.share_knowledge(Vec3::new(0.0, 0.0, 8.0), 0.0);
+        assert_eq!(
+            blackboard.last_known_position,
+            Some(Vec3::new(0.0, 0.0, 8.0))
+        );
+        assert_eq!(blackboard.distance_to_target, 8.0);
+    }
+
+    #[test]
+    fn test_patrol_route_wraps() {
+        let route = Blackboard::square_patrol_route(Vec3::ZERO, 2.0);
+        let mut blackboard = Blackboard::with_home(Vec3::ZERO).with_patrol_route(route.clone());
+
+        for point in &route {
+            assert_eq!(blackboard.current_patrol_point(), Some(*point));
+            blackboard.advance_patrol();
+        }
+        assert_eq!(blackboard.current_patrol_point(), Some(route[0]));
+    }
+}
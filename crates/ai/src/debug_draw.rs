@@ -0,0 +1,224 @@
+//! AI 调试可视化
+//!
+//! 开启后用 Gizmos 为每个敌人绘制：
+//! - 检测范围（黄）与失去目标范围（橙），按当前日照缩放
+//! - 视野扇形（视野小于 360° 时）
+//! - 剩余导航路径（青）与到当前目标的连线（红）
+//! - 目标最后已知位置标记（紫）
+//! - 包围站位标记（绿）
+//!
+//! 状态文字标签与黑板面板由集成层负责（需要字体与 UI），这里只提供
+//! 开关资源与标签文本。
+
+use bevy::prelude::*;
+
+use super::blackboard::Blackboard;
+use super::detection::DetectionConfig;
+use super::enemy_ai::EnemyAIState;
+use super::lod::{AiLod, LodTier};
+use super::pathfinding::NavPath;
+use vigilant_doodle_world::TimeOfDay;
+
+const DETECTION_COLOR: Color = Color::srgb(0.95, 0.85, 0.2);
+const LOSE_TARGET_COLOR: Color = Color::srgb(0.95, 0.5, 0.15);
+const FOV_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const PATH_COLOR: Color = Color::srgb(0.2, 0.85, 0.9);
+const TARGET_COLOR: Color = Color::srgb(0.95, 0.2, 0.2);
+const LAST_KNOWN_COLOR: Color = Color::srgb(0.75, 0.3, 0.95);
+const SLOT_COLOR: Color = Color::srgb(0.3, 0.9, 0.4);
+
+/// 视野扇形弧线的分段数
+const FOV_ARC_SEGMENTS: usize = 16;
+/// 绘制高度偏移（避免与地面重叠闪烁）
+const DRAW_HEIGHT: f32 = 0.05;
+
+/// AI 调试可视化开关
+#[derive(Resource, Debug, Clone, Default)]
+pub struct AiDebugOverlay {
+    /// 是否绘制调试信息
+    pub enabled: bool,
+}
+
+impl AiDebugOverlay {
+    /// 切换开关
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        info!(
+            "[AI] 调试可视化已{}",
+            if self.enabled { "开启" } else { "关闭" }
+        );
+    }
+}
+
+/// AI 调试可视化插件
+pub struct AiDebugPlugin;
+
+impl Plugin for AiDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AiDebugOverlay>().add_systems(
+            Update,
+            draw_ai_debug_system.run_if(|overlay: Res<AiDebugOverlay>| overlay.enabled),
+        );
+    }
+}
+
+/// 敌人头顶的调试标签文本：状态，附带小队角色与非近处的 LOD 等级
+pub fn debug_label(
+    state: EnemyAIState,
+    blackboard: Option<&Blackboard>,
+    lod: Option<&AiLod>,
+) -> String {
+    let mut label = state.display_name().to_string();
+    if let Some(role) = blackboard.and_then(|blackboard| blackboard.squad_role) {
+        label.push_str(" · ");
+        label.push_str(role.display_name());
+    }
+    match lod.map(|lod| lod.tier) {
+        Some(LodTier::Far) => label.push_str(" [远]"),
+        Some(LodTier::Dormant) => label.push_str(" [休眠]"),
+        _ => {}
+    }
+    label
+}
+
+/// 视野扇形的轮廓点：从原点出发，沿弧线扫过视野，再回到原点
+///
+/// 视野不小于 360° 时返回 None（由检测范围圆表示）
+pub fn fov_cone_points(
+    origin: Vec3,
+    forward: Vec3,
+    field_of_view: f32,
+    range: f32,
+) -> Option<Vec<Vec3>> {
+    let forward = Vec3::new(forward.x, 0.0, forward.z).normalize_or_zero();
+    if field_of_view >= 360.0 || forward == Vec3::ZERO {
+        return None;
+    }
+
+    let half = field_of_view.max(0.0).to_radians() * 0.5;
+    let mut points = Vec::with_capacity(FOV_ARC_SEGMENTS + 3);
+    points.push(origin);
+    for i in 0..=FOV_ARC_SEGMENTS {
+        let angle = -half + 2.0 * half * i as f32 / FOV_ARC_SEGMENTS as f32;
+        points.push(origin + Quat::from_rotation_y(angle) * forward * range);
+    }
+    points.push(origin);
+    Some(points)
+}
+
+/// 调试绘制系统
+fn draw_ai_debug_system(
+    mut gizmos: Gizmos,
+    enemy_query: Query<(
+        &Transform,
+        &DetectionConfig,
+        Option<&Blackboard>,
+        Option<&NavPath>,
+    )>,
+    time_of_day: Option<Res<TimeOfDay>>,
+) {
+    let daylight = time_of_day.map_or(1.0, |time| time.daylight());
+    // Gizmos 的圆默认位于 XY 平面，旋转到地面（XZ 平面）
+    let ground = Quat::from_rotation_arc(Vec3::Z, Vec3::Y);
+
+    for (transform, detection, blackboard, nav_path) in enemy_query.iter() {
+        let detection = detection.in_daylight(daylight);
+        let position = transform.translation.with_y(DRAW_HEIGHT);
+
+        gizmos.circle(
+            Isometry3d::new(position, ground),
+            detection.detection_range,
+            DETECTION_COLOR,
+        );
+        gizmos.circle(
+            Isometry3d::new(position, ground),
+            detection.lose_target_range,
+            LOSE_TARGET_COLOR,
+        );
+
+        if let Some(points) = fov_cone_points(
+            position,
+            transform.forward().as_vec3(),
+            detection.field_of_view,
+            detection.detection_range,
+        ) {
+            gizmos.linestrip(points, FOV_COLOR);
+        }
+
+        if let Some(nav_path) = nav_path
+            && !nav_path.remaining().is_empty()
+        {
+            gizmos.linestrip(
+                std::iter::once(position).chain(
+                    nav_path
+                        .remaining()
+                        .iter()
+                        .map(|waypoint| waypoint.with_y(DRAW_HEIGHT)),
+                ),
+                PATH_COLOR,
+            );
+        }
+
+        let Some(blackboard) = blackboard else {
+            continue;
+        };
+
+        if let Some(target) = blackboard.target_position {
+            gizmos.line(position, target.with_y(DRAW_HEIGHT), TARGET_COLOR);
+        }
+        if let Some(last_known) = blackboard.last_known_position {
+            gizmos.sphere(
+                Isometry3d::from_translation(last_known.with_y(0.5)),
+                0.3,
+                LAST_KNOWN_COLOR,
+            );
+        }
+        if let Some(slot) = blackboard.surround_slot {
+            gizmos.circle(
+                Isometry3d::new(slot.with_y(DRAW_HEIGHT), ground),
+                0.25,
+                SLOT_COLOR,
+            );
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::squad::SquadRole;
+
+    #[test]
+    fn test_fov_cone_points() {
+        assert!(fov_cone_points(Vec3::ZERO, Vec3::NEG_Z, 360.0, 10.0).is_none());
+
+        let points = fov_cone_points(Vec3::ZERO, Vec3::NEG_Z, 90.0, 10.0).unwrap();
+        assert_eq!(points.first(), Some(&Vec3::ZERO));
+        assert_eq!(points.last(), Some(&Vec3::ZERO));
+
+        // 弧线两端位于前方 ±45°，中点正对前方
+        let arc = &points[1..points.len() - 1];
+        let mid = arc[arc.len() / 2];
+        assert!(mid.distance(Vec3::new(0.0, 0.0, -10.0)) < 1e-4);
+        for end in [arc[0], arc[arc.len() - 1]] {
+            assert!((end.length() - 10.0).abs() < 1e-4);
+            assert!((end.normalize().dot(Vec3::NEG_Z) - 45f32.to_radians().cos()).abs() < 1e-4);
+        }
+    }
+
+    #[test]
+    fn test_debug_label() {
+        let blackboard = Blackboard {
+            squad_role: Some(SquadRole::Flanker),
+            ..default()
+        };
+        let mut lod = AiLod::default();
+        lod.tier = LodTier::Far;
+
+        assert_eq!(debug_label(EnemyAIState::Patrol, None, None), "巡逻");
+        assert_eq!(
+            debug_label(EnemyAIState::Flanking, Some(&blackboard), Some(&lod)),
+            "包抄 · 包抄者 [远]"
+        );
+    }
+}
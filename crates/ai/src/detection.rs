@@ -4,6 +4,7 @@
 //! - 视野范围检测
 //! - 距离计算
 //! - 失去目标判定
+//! - 夜间缩短检测范围
 
 use bevy::prelude::*;
 
@@ -23,6 +24,9 @@ pub struct DetectionConfig {
     /// 0-360，180 表示前方半圆
     #[allow(dead_code)]
     pub field_of_view: f32,
+
+    /// 完全入夜时检测与失去目标范围的倍率
+    pub night_range_multiplier: f32,
 }
 
 impl Default for DetectionConfig {
@@ -31,6 +35,22 @@ impl Default for DetectionConfig {
             detection_range: 35.0,
             lose_target_range: 50.0,  // 更大的范围避免反复切换
             field_of_view: 360.0,      // 全方位检测
+            night_range_multiplier: 0.5,
+        }
+    }
+}
+
+impl DetectionConfig {
+    /// 按日照程度（0=深夜，1=白天）缩放检测与失去目标范围
+    pub fn in_daylight(&self, daylight: f32) -> Self {
+        let scale = self
+            .night_range_multiplier
+            .lerp(1.0, daylight.clamp(0.0, 1.0))
+            .clamp(0.0, 1.0);
+        Self {
+            detection_range: self.detection_range * scale,
+            lose_target_range: self.lose_target_range * scale,
+            ..self.clone()
         }
     }
 }
@@ -140,6 +160,25 @@ mod tests {
         assert!(DetectionSystem::should_lose_target(&config, enemy_pos, player_pos_out));
     }
 
+    #[test]
+    fn test_night_range() {
+        let config = DetectionConfig::default();
+        let enemy_pos = Vec3::ZERO;
+        let player_pos = Vec3::new(30.0, 0.0, 0.0);
+
+        // 白天可以发现，夜间范围减半后无法发现
+        let day = config.in_daylight(1.0);
+        assert!(DetectionSystem::can_detect_player(
+            &day, enemy_pos, player_pos
+        ));
+        let night = config.in_daylight(0.0);
+        assert_eq!(night.detection_range, 17.5);
+        assert_eq!(night.lose_target_range, 25.0);
+        assert!(!DetectionSystem::can_detect_player(
+            &night, enemy_pos, player_pos
+        ));
+    }
+
     #[test]
     fn test_direction_calculation() {
         let enemy_pos = Vec3::ZERO;
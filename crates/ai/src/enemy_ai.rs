@@ -11,8 +11,7 @@ pub enum EnemyAIState {
     #[default]
     Idle,
 
-    /// 巡逻：在区域内随机移动（未来扩展）
-    #[allow(dead_code)]
+    /// 巡逻：沿巡逻路线移动
     Patrol,
 
     /// 追击：持续追踪玩家
@@ -20,14 +19,18 @@ pub enum EnemyAIState {
     /// 直到超出 lose_target_range 才会停止
     Chasing,
 
-    /// 攻击：在攻击范围内（未来扩展）
-    #[allow(dead_code)]
+    /// 包抄：绕到玩家侧面再贴近（由行为树驱动）
+    Flanking,
+
+    /// 攻击：在攻击范围内
     Attacking,
 
-    /// 搜索：失去目标后，朝向玩家最后已知位置移动（未来扩展）
-    #[allow(dead_code)]
+    /// 搜索：失去目标后，朝向玩家最后已知位置移动
     Searching,
 
+    /// 警戒：小队中保持距离监视玩家
+    Watching,
+
     /// 撤退：低血量时逃跑（未来扩展）
     #[allow(dead_code)]
     Retreating,
@@ -55,20 +58,33 @@ impl EnemyAIState {
             Self::Idle => "闲置",
             Self::Patrol => "巡逻",
             Self::Chasing => "追击",
+            Self::Flanking => "包抄",
             Self::Attacking => "攻击",
             Self::Searching => "搜索",
+            Self::Watching => "警戒",
             Self::Retreating => "撤退",
         }
     }
 
     /// 判断当前状态是否需要移动
     pub fn should_move(&self) -> bool {
-        matches!(self, Self::Chasing | Self::Patrol | Self::Searching | Self::Retreating)
+        matches!(
+            self,
+            Self::Chasing
+                | Self::Flanking
+                | Self::Patrol
+                | Self::Searching
+                | Self::Watching
+                | Self::Retreating
+        )
     }
 
     /// 判断当前状态是否有目标
     pub fn has_target(&self) -> bool {
-        matches!(self, Self::Chasing | Self::Attacking | Self::Searching)
+        matches!(
+            self,
+            Self::Chasing | Self::Flanking | Self::Attacking | Self::Searching | Self::Watching
+        )
     }
 }
 
@@ -92,6 +108,17 @@ pub enum AIEvent {
     TookDamage,
 }
 
+/// 敌人发现玩家消息
+///
+/// 检测阶段在敌人首次发现玩家时发出，用于向同伴传播警报
+#[derive(Message, Debug, Clone, Copy)]
+pub struct PlayerSpotted {
+    /// 发现玩家的敌人
+    pub enemy: Entity,
+    /// 玩家位置
+    pub position: Vec3,
+}
+
 /// 敌人目标信息
 ///
 /// 存储敌人追踪的目标信息
@@ -140,6 +167,16 @@ pub struct EnemyAIConfig {
     pub stop_distance: f32,
     /// 旋转速度（朝向目标的速度）
     pub rotation_speed: f32,
+    /// 攻击范围
+    pub attack_range: f32,
+    /// 攻击间隔（秒）
+    pub attack_interval: f32,
+    /// 到达最后已知位置后的搜索时长（秒）
+    pub search_duration: f32,
+    /// 包抄时绕到目标侧面的距离
+    pub flank_distance: f32,
+    /// 警戒者与目标保持的距离
+    pub watch_distance: f32,
 }
 
 impl Default for EnemyAIConfig {
@@ -148,6 +185,11 @@ impl Default for EnemyAIConfig {
             move_speed: 7.0,
             stop_distance: 1.5,
             rotation_speed: 10.0,
+            attack_range: 2.0,
+            attack_interval: 1.2,
+            search_duration: 4.0,
+            flank_distance: 6.0,
+            watch_distance: 9.0,
         }
     }
 }
@@ -184,7 +226,7 @@ mod tests {
         assert!(!target.has_target());
 
         // 设置目标
-        let entity = Entity::from_raw(123);
+        let entity = Entity::from_raw_u32(123).unwrap();
         target.set_target(entity, Vec3::ZERO);
         assert!(target.has_target());
         assert_eq!(target.entity, Some(entity));
@@ -3,18 +3,45 @@
 //! 提供敌人 AI 的完整实现，包括：
 //! - 状态机（State Machine）
 //! - 检测系统（Detection）
+//! - 行为树 / 效用决策（Behavior Tree）
+//! - 导航网格与寻路（Navigation）
+//! - 局部避让与群体转向（Steering）
+//! - 小队协同与警报（Squad）
+//! - 细节层级与分时更新（LOD）
+//! - 调试可视化（Debug Draw）
 //! - 行为执行（Behavior）
 
 #![allow(clippy::type_complexity)]
 
 mod behavior;
+mod behavior_tree;
+mod blackboard;
+mod debug_draw;
 mod detection;
 mod enemy_ai;
+mod lod;
+mod nav_grid;
+mod pathfinding;
+mod squad;
+mod steering;
 
 // 公开导出
-pub use behavior::BehaviorSystemPlugin;
+pub use behavior::{BehaviorSystemPlugin, BehaviorSystemSet};
+pub use behavior_tree::{
+    BehaviorTree, BtAction, BtCondition, BtNode, BtStatus, Scorer, TickContext, UtilityOption,
+};
+pub use blackboard::Blackboard;
+pub use debug_draw::{debug_label, fov_cone_points, AiDebugOverlay, AiDebugPlugin};
 pub use detection::{DetectionConfig, DetectionSystem};
-pub use enemy_ai::{AIEvent, EnemyAIConfig, EnemyAIState, EnemyTarget};
+pub use enemy_ai::{AIEvent, EnemyAIConfig, EnemyAIState, EnemyTarget, PlayerSpotted};
+pub use lod::{AiLod, AiLodConfig, AiLodPlugin, LodTier};
+pub use nav_grid::NavGrid;
+pub use pathfinding::{find_path, NavPath, NavigationPlugin};
+pub use squad::{
+    assign_roles, group_into_squads, AlertConfig, AlertQueue, EnemyAlerted, SquadBoard,
+    SquadMember, SquadPlugin, SquadRole, Squads,
+};
+pub use steering::{SteeringAgent, SteeringConfig, SteeringPlugin};
 
 use bevy::prelude::*;
 
@@ -27,7 +54,13 @@ impl Plugin for EnemyAIPlugin {
     fn build(&self, app: &mut App) {
         info!("[AI] 加载敌人 AI 系统...");
 
-        app.add_plugins(BehaviorSystemPlugin);
+        app.add_plugins((
+            BehaviorSystemPlugin,
+            NavigationPlugin,
+            SteeringPlugin,
+            SquadPlugin,
+            AiLodPlugin,
+        ));
 
         info!("[AI] 敌人 AI 系统加载完成");
     }
@@ -0,0 +1,352 @@
+//! AI 细节层级（LOD）与分时更新
+//!
+//! 按与玩家的距离和相机视野为敌人分级，决定本帧是否执行“思考”类系统
+//! （检测、决策、路径规划）：
+//! - 近处：每帧更新（不论是否在视野内，身后的敌人也能及时反应）
+//! - 远处（视野内）：按较低频率更新
+//! - 休眠（视野外）：很少更新
+//!
+//! 没有 3D 相机时（如无界面运行）按距离分级：超出远处范围即休眠。
+//! 非近处敌人的更新受每帧预算限制，按等待时间从长到短、实体序号从小到大选取，
+//! 固定步长下结果确定。被跳过的帧时间会累积，下次更新时一次性传入。
+//! 行为执行与转向每帧运行，保证移动平滑。
+
+use bevy::camera::primitives::{Frustum, Sphere};
+use bevy::prelude::*;
+
+use super::behavior::BehaviorSystemSet;
+use vigilant_doodle_gameplay::Player;
+
+/// LOD 等级
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum LodTier {
+    /// 近处：每帧更新
+    #[default]
+    Near,
+    /// 远处：视野内，降频更新
+    Far,
+    /// 休眠：视野外，很少更新
+    Dormant,
+}
+
+/// AI LOD 组件
+///
+/// 未挂载此组件的敌人每帧更新
+#[derive(Component, Debug, Clone, Default)]
+pub struct AiLod {
+    /// 当前等级
+    pub tier: LodTier,
+    /// 本帧是否执行思考类系统
+    pub active: bool,
+    /// 本次更新应使用的时间步长（含被跳过帧累积的时间）
+    pub step: f32,
+    /// 距离上次更新经过的时间（秒）
+    since_update: f32,
+}
+
+impl AiLod {
+    /// 本帧思考类系统使用的时间步长，未轮到更新时返回 None
+    pub fn step_for(lod: Option<&Self>, frame_delta: f32) -> Option<f32> {
+        match lod {
+            Some(lod) if !lod.active => None,
+            Some(lod) => Some(lod.step),
+            None => Some(frame_delta),
+        }
+    }
+}
+
+/// AI LOD 参数资源
+#[derive(Resource, Debug, Clone)]
+pub struct AiLodConfig {
+    /// 近处范围（此范围内每帧更新）
+    pub near_distance: f32,
+    /// 远处范围（没有相机时超出后进入休眠）
+    pub far_distance: f32,
+    /// 远处敌人的更新间隔（秒）
+    pub far_interval: f32,
+    /// 休眠敌人的更新间隔（秒）
+    pub dormant_interval: f32,
+    /// 每帧最多更新的非近处敌人数量
+    pub max_updates_per_frame: usize,
+}
+
+impl Default for AiLodConfig {
+    fn default() -> Self {
+        Self {
+            near_distance: 25.0,
+            far_distance: 60.0,
+            far_interval: 0.25,
+            dormant_interval: 1.0,
+            max_updates_per_frame: 64,
+        }
+    }
+}
+
+impl AiLodConfig {
+    /// 按与玩家的距离和是否在相机视野内分级
+    ///
+    /// `in_view` 为 None 表示没有相机，此时按距离分级
+    pub fn tier_for(&self, distance: f32, in_view: Option<bool>) -> LodTier {
+        if distance <= self.near_distance {
+            LodTier::Near
+        } else if in_view.unwrap_or(distance <= self.far_distance) {
+            LodTier::Far
+        } else {
+            LodTier::Dormant
+        }
+    }
+
+    /// 等级对应的更新间隔（秒）
+    pub fn interval(&self, tier: LodTier) -> f32 {
+        match tier {
+            LodTier::Near => 0.0,
+            LodTier::Far => self.far_interval,
+            LodTier::Dormant => self.dormant_interval,
+        }
+    }
+}
+
+/// AI LOD 插件（内部使用）
+pub struct AiLodPlugin;
+
+impl Plugin for AiLodPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<AiLodConfig>().add_systems(
+            FixedUpdate,
+            schedule_ai_lod_system.in_set(BehaviorSystemSet::Scheduling),
+        );
+    }
+}
+
+/// 视野判定时敌人包围球的半径（略大于模型，刚离开屏幕边缘的敌人仍算在视野内）
+const VIEW_MARGIN: f32 = 2.0;
+
+/// LOD 调度系统
+///
+/// 为每个敌人分级并决定本帧是否更新
+fn schedule_ai_lod_system(
+    mut enemy_query: Query<(Entity, &Transform, &mut AiLod)>,
+    player_query: Query<&Transform, (With<Player>, Without<AiLod>)>,
+    camera_query: Query<&Frustum, With<Camera3d>>,
+    config: Res<AiLodConfig>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+    let player_pos = player_query
+        .single()
+        .ok()
+        .map(|transform| transform.translation);
+
+    let mut due: Vec<(Entity, f32)> = Vec::new();
+    for (entity, transform, mut lod) in enemy_query.iter_mut() {
+        // 没有玩家时全部视为近处
+        lod.tier = player_pos.map_or(LodTier::Near, |player_pos| {
+            let bounds = Sphere {
+                center: transform.translation.into(),
+                radius: VIEW_MARGIN,
+            };
+            // 视野只看左右上下与近平面，远平面之外仍算在视野内
+            let in_view = (!camera_query.is_empty()).then(|| {
+                camera_query
+                    .iter()
+                    .any(|frustum| frustum.intersects_sphere(&bounds, false))
+            });
+            config.tier_for(transform.translation.distance(player_pos), in_view)
+        });
+        lod.since_update += delta;
+        lod.active = false;
+
+        if lod.tier == LodTier::Near {
+            lod.active = true;
+            lod.step = lod.since_update;
+            lod.since_update = 0.0;
+        } else if lod.since_update >= config.interval(lod.tier) {
+            due.push((entity, lod.since_update));
+        }
+    }
+
+    // 预算内优先更新等待最久的敌人，同等待时间按实体排序保证确定
+    due.sort_by(|a, b| b.1.total_cmp(&a.1).then(a.0.cmp(&b.0)));
+    for (entity, _) in due.into_iter().take(config.max_updates_per_frame) {
+        if let Ok((_, _, mut lod)) = enemy_query.get_mut(entity) {
+            lod.active = true;
+            lod.step = lod.since_update;
+            lod.since_update = 0.0;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::camera::CameraProjection;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use vigilant_doodle_core::state::GameState;
+    use vigilant_doodle_core::BalanceConfig;
+    use vigilant_doodle_gameplay::SpatialIndexPlugin;
+
+    fn lod_app(config: AiLodConfig) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                50,
+            )))
+            .insert_resource(Time::<Fixed>::from_duration(Duration::from_millis(50)))
+            .insert_state(GameState::Playing)
+            .add_plugins((
+                SpatialIndexPlugin,
+                super::super::behavior::BehaviorSystemPlugin,
+                AiLodPlugin,
+            ))
+            .insert_resource(config);
+        app.world_mut().spawn((
+            Transform::default(),
+            Player::from_balance(&BalanceConfig::default()),
+        ));
+        app
+    }
+
+    /// 运行若干帧，统计每个实体处于激活状态的帧数
+    fn count_updates(app: &mut App, entities: &[Entity], frames: usize) -> Vec<usize> {
+        let mut counts = vec![0; entities.len()];
+        for _ in 0..frames {
+            app.update();
+            for (count, entity) in counts.iter_mut().zip(entities) {
+                if app.world().get::<AiLod>(*entity).unwrap().active {
+                    *count += 1;
+                }
+            }
+        }
+        counts
+    }
+
+    #[test]
+    fn test_tiers_update_at_their_rates() {
+        let mut app = lod_app(AiLodConfig::default());
+        let entities: Vec<Entity> = [5.0, 40.0, 100.0]
+            .into_iter()
+            .map(|x| {
+                app.world_mut()
+                    .spawn((Transform::from_xyz(x, 0.0, 0.0), AiLod::default()))
+                    .id()
+            })
+            .collect();
+
+        // 首帧没有经过时间，不执行模拟步
+        app.update();
+
+        // 2 秒：近处每帧，远处每 0.25 秒，休眠每 1 秒
+        let counts = count_updates(&mut app, &entities, 40);
+        assert_eq!(counts[0], 40);
+        assert!((6..=9).contains(&counts[1]), "{counts:?}");
+        assert!((1..=2).contains(&counts[2]), "{counts:?}");
+
+        let world = app.world();
+        assert_eq!(world.get::<AiLod>(entities[1]).unwrap().tier, LodTier::Far);
+        assert_eq!(
+            world.get::<AiLod>(entities[2]).unwrap().tier,
+            LodTier::Dormant
+        );
+    }
+
+    #[test]
+    fn test_dormant_only_outside_camera_view() {
+        let mut app = lod_app(AiLodConfig::default());
+        // 相机位于玩家上方朝 -Z 看
+        let camera = Transform::from_xyz(0.0, 10.0, 0.0).looking_to(Vec3::NEG_Z, Vec3::Y);
+        let projection = PerspectiveProjection::default();
+        let clip_from_world = projection.get_clip_from_view() * camera.to_matrix().inverse();
+        app.world_mut().spawn((
+            Camera3d::default(),
+            Frustum::from_clip_from_world(&clip_from_world),
+        ));
+        let positions = [
+            // 近处但在相机身后
+            Vec3::new(0.0, 0.0, 10.0),
+            // 视野外
+            Vec3::new(0.0, 0.0, 40.0),
+            // 视野内，超出远处范围
+            Vec3::new(0.0, 0.0, -100.0),
+        ];
+        let entities: Vec<Entity> = positions
+            .into_iter()
+            .map(|position| {
+                app.world_mut()
+                    .spawn((Transform::from_translation(position), AiLod::default()))
+                    .id()
+            })
+            .collect();
+
+        app.update();
+        let counts = count_updates(&mut app, &entities, 40);
+        // 近处的敌人即使在视野外也每帧更新
+        assert_eq!(counts[0], 40);
+
+        let tier = |entity: Entity| app.world().get::<AiLod>(entity).unwrap().tier;
+        assert_eq!(tier(entities[0]), LodTier::Near);
+        assert_eq!(tier(entities[1]), LodTier::Dormant);
+        assert_eq!(tier(entities[2]), LodTier::Far);
+    }
+
+    #[test]
+    fn test_budget_limits_updates_and_is_fair() {
+        let config = AiLodConfig {
+            max_updates_per_frame: 2,
+            ..default()
+        };
+        let mut app = lod_app(config);
+        let entities: Vec<Entity> = (0..6)
+            .map(|i| {
+                app.world_mut()
+                    .spawn((Transform::from_xyz(40.0, 0.0, i as f32), AiLod::default()))
+                    .id()
+            })
+            .collect();
+
+        for _ in 0..20 {
+            app.update();
+            let active = entities
+                .iter()
+                .filter(|entity| app.world().get::<AiLod>(**entity).unwrap().active)
+                .count();
+            assert!(active <= 2);
+        }
+
+        // 预算不足时仍然轮流更新每个敌人
+        let counts = count_updates(&mut app, &entities, 20);
+        assert!(counts.iter().all(|&count| count >= 2), "{counts:?}");
+    }
+
+    #[test]
+    fn test_schedule_is_deterministic() {
+        let run = || {
+            let config = AiLodConfig {
+                max_updates_per_frame: 3,
+                ..default()
+            };
+            let mut app = lod_app(config);
+            let entities: Vec<Entity> = (0..10)
+                .map(|i| {
+                    app.world_mut()
+                        .spawn((
+                            Transform::from_xyz(20.0 + i as f32 * 8.0, 0.0, 0.0),
+                            AiLod::default(),
+                        ))
+                        .id()
+                })
+                .collect();
+            (0..30)
+                .map(|_| {
+                    app.update();
+                    entities
+                        .iter()
+                        .map(|entity| app.world().get::<AiLod>(*entity).unwrap().active)
+                        .collect::<Vec<_>>()
+                })
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(run(), run());
+    }
+}
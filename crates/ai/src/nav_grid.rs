@@ -0,0 +1,241 @@
+//! 导航网格
+//!
+//! 将地面范围与静态障碍物栅格化为可行走网格，供寻路使用：
+//! - 障碍物按敌人碰撞半径膨胀，保证路径与障碍物保持距离
+//! - 提供坐标转换、可行走判定与视线检测
+
+use bevy::prelude::*;
+
+/// 网格坐标（列 x，行 z）
+pub type Cell = IVec2;
+
+/// 导航网格资源
+#[derive(Resource, Debug, Clone)]
+pub struct NavGrid {
+    /// 网格左下角（最小 X/Z）的世界坐标
+    origin: Vec2,
+    /// 单元格边长
+    cell_size: f32,
+    /// 列数（X 方向）
+    width: i32,
+    /// 行数（Z 方向）
+    height: i32,
+    /// 每个单元格是否可行走（行优先存储）
+    walkable: Vec<bool>,
+}
+
+impl Default for NavGrid {
+    fn default() -> Self {
+        use vigilant_doodle_world::terrain::{FLOOR_HALF_LENGTH, FLOOR_HALF_WIDTH};
+
+        Self::new(
+            Rect::new(
+                -FLOOR_HALF_LENGTH,
+                -FLOOR_HALF_WIDTH,
+                FLOOR_HALF_LENGTH,
+                FLOOR_HALF_WIDTH,
+            ),
+            NavGrid::DEFAULT_CELL_SIZE,
+        )
+    }
+}
+
+impl NavGrid {
+    /// 默认单元格边长
+    pub const DEFAULT_CELL_SIZE: f32 = 1.0;
+
+    /// 创建全部可行走的网格
+    ///
+    /// `bounds` 为 XZ 平面上的可行走范围
+    pub fn new(bounds: Rect, cell_size: f32) -> Self {
+        let width = (bounds.width() / cell_size).ceil().max(1.0) as i32;
+        let height = (bounds.height() / cell_size).ceil().max(1.0) as i32;
+
+        Self {
+            origin: bounds.min,
+            cell_size,
+            width,
+            height,
+            walkable: vec![true; (width * height) as usize],
+        }
+    }
+
+    /// 从地面范围与障碍物占地生成网格
+    ///
+    /// 障碍物矩形会按 `agent_radius` 膨胀
+    pub fn from_obstacles(
+        bounds: Rect,
+        cell_size: f32,
+        obstacles: impl IntoIterator<Item = Rect>,
+        agent_radius: f32,
+    ) -> Self {
+        let mut grid = Self::new(bounds, cell_size);
+        for obstacle in obstacles {
+            grid.block_rect(obstacle.inflate(agent_radius));
+        }
+        grid
+    }
+
+    /// 将与矩形重叠的单元格标记为不可行走
+    pub fn block_rect(&mut self, rect: Rect) {
+        let min = self.world_to_cell_unclamped(rect.min);
+        let max = self.world_to_cell_unclamped(rect.max);
+
+        for z in min.y.max(0)..=max.y.min(self.height - 1) {
+            for x in min.x.max(0)..=max.x.min(self.width - 1) {
+                self.set_walkable(IVec2::new(x, z), false);
+            }
+        }
+    }
+
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    pub fn width(&self) -> i32 {
+        self.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.height
+    }
+
+    /// 单元格是否在网格范围内
+    pub fn in_bounds(&self, cell: Cell) -> bool {
+        cell.x >= 0 && cell.y >= 0 && cell.x < self.width && cell.y < self.height
+    }
+
+    /// 单元格是否可行走（越界视为不可行走）
+    pub fn is_walkable(&self, cell: Cell) -> bool {
+        self.in_bounds(cell) && self.walkable[self.index(cell)]
+    }
+
+    pub fn set_walkable(&mut self, cell: Cell, walkable: bool) {
+        if self.in_bounds(cell) {
+            let index = self.index(cell);
+            self.walkable[index] = walkable;
+        }
+    }
+
+    /// 世界坐标转换为单元格（限制在网格范围内）
+    pub fn world_to_cell(&self, position: Vec3) -> Cell {
+        self.world_to_cell_unclamped(Vec2::new(position.x, position.z))
+            .clamp(IVec2::ZERO, IVec2::new(self.width - 1, self.height - 1))
+    }
+
+    /// 单元格中心的世界坐标（Y 为 0）
+    pub fn cell_to_world(&self, cell: Cell) -> Vec3 {
+        let center = self.origin + (cell.as_vec2() + Vec2::splat(0.5)) * self.cell_size;
+        Vec3::new(center.x, 0.0, center.y)
+    }
+
+    /// 世界坐标是否可行走
+    pub fn is_walkable_world(&self, position: Vec3) -> bool {
+        let cell = self.world_to_cell_unclamped(Vec2::new(position.x, position.z));
+        self.is_walkable(cell)
+    }
+
+    /// 查找离给定单元格最近的可行走单元格（按环形向外搜索）
+    pub fn nearest_walkable(&self, cell: Cell) -> Option<Cell> {
+        let cell = cell.clamp(IVec2::ZERO, IVec2::new(self.width - 1, self.height - 1));
+        if self.is_walkable(cell) {
+            return Some(cell);
+        }
+
+        let max_radius = self.width.max(self.height);
+        for radius in 1..=max_radius {
+            let mut best: Option<(i32, Cell)> = None;
+            for dz in -radius..=radius {
+                for dx in -radius..=radius {
+                    if dx.abs() != radius && dz.abs() != radius {
+                        continue;
+                    }
+                    let candidate = cell + IVec2::new(dx, dz);
+                    if !self.is_walkable(candidate) {
+                        continue;
+                    }
+                    let distance = dx * dx + dz * dz;
+                    if best.is_none_or(|(best_distance, _)| distance < best_distance) {
+                        best = Some((distance, candidate));
+                    }
+                }
+            }
+            if let Some((_, found)) = best {
+                return Some(found);
+            }
+        }
+        None
+    }
+
+    /// 两点间是否存在可行走的直线视线
+    ///
+    /// 沿线段以半个单元格为步长采样
+    pub fn has_line_of_sight(&self, from: Vec3, to: Vec3) -> bool {
+        let from_2d = Vec2::new(from.x, from.z);
+        let to_2d = Vec2::new(to.x, to.z);
+        let distance = from_2d.distance(to_2d);
+        let steps = (distance / (self.cell_size * 0.5)).ceil().max(1.0) as i32;
+
+        (0..=steps).all(|step| {
+            let point = from_2d.lerp(to_2d, step as f32 / steps as f32);
+            self.is_walkable(self.world_to_cell_unclamped(point))
+        })
+    }
+
+    fn world_to_cell_unclamped(&self, point: Vec2) -> Cell {
+        ((point - self.origin) / self.cell_size).floor().as_ivec2()
+    }
+
+    fn index(&self, cell: Cell) -> usize {
+        (cell.y * self.width + cell.x) as usize
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_coordinate_round_trip() {
+        let grid = NavGrid::new(Rect::new(-10.0, -5.0, 10.0, 5.0), 1.0);
+        assert_eq!(grid.width(), 20);
+        assert_eq!(grid.height(), 10);
+
+        let cell = grid.world_to_cell(Vec3::new(0.2, 0.0, -0.7));
+        assert_eq!(cell, IVec2::new(10, 4));
+        assert_eq!(grid.cell_to_world(cell), Vec3::new(0.5, 0.0, -0.5));
+    }
+
+    #[test]
+    fn test_obstacles_are_inflated() {
+        let grid = NavGrid::from_obstacles(
+            Rect::new(-10.0, -10.0, 10.0, 10.0),
+            1.0,
+            [Rect::from_center_half_size(Vec2::ZERO, Vec2::splat(1.0))],
+            0.6,
+        );
+
+        assert!(!grid.is_walkable_world(Vec3::ZERO));
+        // 膨胀后障碍物边缘外 0.5 处仍不可行走
+        assert!(!grid.is_walkable_world(Vec3::new(1.5, 0.0, 0.0)));
+        assert!(grid.is_walkable_world(Vec3::new(3.0, 0.0, 0.0)));
+    }
+
+    #[test]
+    fn test_line_of_sight_and_nearest_walkable() {
+        let grid = NavGrid::from_obstacles(
+            Rect::new(-10.0, -10.0, 10.0, 10.0),
+            1.0,
+            [Rect::from_center_half_size(Vec2::ZERO, Vec2::new(0.5, 5.0))],
+            0.0,
+        );
+
+        assert!(!grid.has_line_of_sight(Vec3::new(-5.0, 0.0, 0.0), Vec3::new(5.0, 0.0, 0.0)));
+        assert!(grid.has_line_of_sight(Vec3::new(-5.0, 0.0, 8.0), Vec3::new(5.0, 0.0, 8.0)));
+
+        let blocked = grid.world_to_cell(Vec3::ZERO);
+        let nearest = grid.nearest_walkable(blocked).unwrap();
+        assert!(grid.is_walkable(nearest));
+        assert_eq!((nearest - blocked).abs().max_element(), 1);
+    }
+}
@@ -0,0 +1,537 @@
+//! 寻路系统
+//!
+//! 基于导航网格的 A* 寻路，包括：
+//! - 八方向 A*（禁止切角）
+//! - 视线拉直的路径平滑
+//! - 路径缓存与目标移动后的重新规划
+
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+
+use bevy::prelude::*;
+
+use super::behavior::BehaviorSystemSet;
+use super::blackboard::{horizontal_distance, Blackboard};
+use super::enemy_ai::{EnemyAIState, EnemyTarget};
+use super::lod::AiLod;
+use super::nav_grid::{Cell, NavGrid};
+use vigilant_doodle_world::terrain::{FLOOR_HALF_LENGTH, FLOOR_HALF_WIDTH};
+use vigilant_doodle_world::{LevelLayout, StaticObstacle};
+
+/// 导航代理半径（与敌人碰撞半径一致，用于膨胀障碍物）
+const NAV_AGENT_RADIUS: f32 = 0.6;
+/// 目标移动超过该距离时重新规划路径
+const REPLAN_DISTANCE: f32 = 1.5;
+/// 路径最长复用时间（秒），超时后强制重新规划
+const REPLAN_INTERVAL: f32 = 1.0;
+/// 到达路径点的判定距离
+const WAYPOINT_REACHED_DISTANCE: f32 = 0.5;
+
+/// 八方向邻居偏移与代价（直线 1，斜线 √2）
+const NEIGHBORS: [(IVec2, f32); 8] = [
+    (IVec2::new(1, 0), 1.0),
+    (IVec2::new(-1, 0), 1.0),
+    (IVec2::new(0, 1), 1.0),
+    (IVec2::new(0, -1), 1.0),
+    (IVec2::new(1, 1), std::f32::consts::SQRT_2),
+    (IVec2::new(1, -1), std::f32::consts::SQRT_2),
+    (IVec2::new(-1, 1), std::f32::consts::SQRT_2),
+    (IVec2::new(-1, -1), std::f32::consts::SQRT_2),
+];
+
+/// 导航插件（内部使用）
+///
+/// 维护导航网格，并在 Navigation 阶段为敌人规划路径
+pub struct NavigationPlugin;
+
+impl Plugin for NavigationPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<NavGrid>()
+            .add_systems(
+                FixedUpdate,
+                rebuild_nav_grid_system.before(BehaviorSystemSet::Detection),
+            )
+            .add_systems(
+                FixedUpdate,
+                update_nav_paths_system.in_set(BehaviorSystemSet::Navigation),
+            );
+    }
+}
+
+/// 开放列表节点
+#[derive(Clone, Copy)]
+struct OpenNode {
+    cell: Cell,
+    f_score: f32,
+    g_score: f32,
+}
+
+impl PartialEq for OpenNode {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl Eq for OpenNode {}
+
+impl PartialOrd for OpenNode {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for OpenNode {
+    fn cmp(&self, other: &Self) -> Ordering {
+        // BinaryHeap 是最大堆：f 越小优先级越高；同 f 时优先 g 更大（更接近终点），
+        // 再按坐标排序，保证结果确定
+        other
+            .f_score
+            .total_cmp(&self.f_score)
+            .then_with(|| self.g_score.total_cmp(&other.g_score))
+            .then_with(|| other.cell.y.cmp(&self.cell.y))
+            .then_with(|| other.cell.x.cmp(&self.cell.x))
+    }
+}
+
+/// 八方向距离启发函数（Octile）
+fn octile_distance(a: Cell, b: Cell) -> f32 {
+    let d = (a - b).abs();
+    let (min, max) = (d.x.min(d.y) as f32, d.x.max(d.y) as f32);
+    max + (std::f32::consts::SQRT_2 - 1.0) * min
+}
+
+/// 在网格上执行 A* 寻路，返回单元格序列（包含起点和终点）
+pub fn find_cell_path(grid: &NavGrid, start: Cell, goal: Cell) -> Option<Vec<Cell>> {
+    if !grid.is_walkable(start) || !grid.is_walkable(goal) {
+        return None;
+    }
+    if start == goal {
+        return Some(vec![start]);
+    }
+
+    let cell_count = (grid.width() * grid.height()) as usize;
+    let index = |cell: Cell| (cell.y * grid.width() + cell.x) as usize;
+
+    let mut g_scores = vec![f32::INFINITY; cell_count];
+    let mut came_from: Vec<Option<Cell>> = vec![None; cell_count];
+    let mut closed = vec![false; cell_count];
+    let mut open = BinaryHeap::new();
+
+    g_scores[index(start)] = 0.0;
+    open.push(OpenNode {
+        cell: start,
+        f_score: octile_distance(start, goal),
+        g_score: 0.0,
+    });
+
+    while let Some(current) = open.pop() {
+        if current.cell == goal {
+            let mut path = vec![goal];
+            let mut cell = goal;
+            while let Some(previous) = came_from[index(cell)] {
+                path.push(previous);
+                cell = previous;
+            }
+            path.reverse();
+            return Some(path);
+        }
+
+        if closed[index(current.cell)] {
+            continue;
+        }
+        closed[index(current.cell)] = true;
+
+        for (offset, cost) in NEIGHBORS {
+            let neighbor = current.cell + offset;
+            if !grid.is_walkable(neighbor) || closed[index(neighbor)] {
+                continue;
+            }
+            // 斜向移动时两侧都必须可行走，避免擦过障碍物拐角
+            if offset.x != 0
+                && offset.y != 0
+                && (!grid.is_walkable(current.cell + IVec2::new(offset.x, 0))
+                    || !grid.is_walkable(current.cell + IVec2::new(0, offset.y)))
+            {
+                continue;
+            }
+
+            let tentative = current.g_score + cost;
+            if tentative < g_scores[index(neighbor)] {
+                g_scores[index(neighbor)] = tentative;
+                came_from[index(neighbor)] = Some(current.cell);
+                open.push(OpenNode {
+                    cell: neighbor,
+                    f_score: tentative + octile_distance(neighbor, goal),
+                    g_score: tentative,
+                });
+            }
+        }
+    }
+
+    None
+}
+
+/// 路径平滑（视线拉直）
+///
+/// 从当前点出发，跳到仍有视线的最远路径点
+pub fn smooth_path(grid: &NavGrid, points: &[Vec3]) -> Vec<Vec3> {
+    if points.len() <= 2 {
+        return points.to_vec();
+    }
+
+    let mut smoothed = vec![points[0]];
+    let mut anchor = 0;
+    while anchor < points.len() - 1 {
+        let mut next = anchor + 1;
+        for candidate in (anchor + 2..points.len()).rev() {
+            if grid.has_line_of_sight(points[anchor], points[candidate]) {
+                next = candidate;
+                break;
+            }
+        }
+        smoothed.push(points[next]);
+        anchor = next;
+    }
+    smoothed
+}
+
+/// 计算从 `start` 到 `goal` 的平滑路径（世界坐标，不含起点）
+///
+/// 起点或终点落在障碍物内时吸附到最近的可行走单元格；无法到达时返回 None
+pub fn find_path(grid: &NavGrid, start: Vec3, goal: Vec3) -> Option<Vec<Vec3>> {
+    // 直接可见时无需搜索
+    if grid.has_line_of_sight(start, goal) {
+        return Some(vec![goal]);
+    }
+
+    let start_cell = grid.nearest_walkable(grid.world_to_cell(start))?;
+    let goal_cell = grid.nearest_walkable(grid.world_to_cell(goal))?;
+    let cells = find_cell_path(grid, start_cell, goal_cell)?;
+
+    let mut points: Vec<Vec3> = Vec::with_capacity(cells.len() + 1);
+    points.push(start);
+    points.extend(cells.iter().skip(1).map(|&cell| grid.cell_to_world(cell)));
+    // 终点可行走时使用精确位置替换终点单元格中心
+    if grid.is_walkable_world(goal)
+        && let Some(last) = points.last_mut()
+    {
+        *last = goal;
+    }
+
+    let mut smoothed = smooth_path(grid, &points);
+    smoothed.remove(0);
+    Some(smoothed)
+}
+
+/// 导航路径组件
+///
+/// 缓存当前路径，目标移动或超时后重新规划
+#[derive(Component, Debug, Clone, Default)]
+pub struct NavPath {
+    /// 路径点（世界坐标，不含起点）
+    waypoints: Vec<Vec3>,
+    /// 当前路径点索引
+    index: usize,
+    /// 规划时的目标位置
+    goal: Option<Vec3>,
+    /// 距离上次规划经过的时间（秒）
+    since_replan: f32,
+    /// 上次规划是否失败（无法到达）
+    unreachable: bool,
+}
+
+impl NavPath {
+    /// 是否需要重新规划
+    pub fn needs_replan(&self, goal: Vec3) -> bool {
+        match self.goal {
+            None => true,
+            Some(planned) => {
+                horizontal_distance(planned, goal) > REPLAN_DISTANCE
+                    || self.since_replan >= REPLAN_INTERVAL
+            }
+        }
+    }
+
+    /// 写入新规划的路径
+    pub fn set(&mut self, waypoints: Option<Vec<Vec3>>, goal: Vec3) {
+        self.unreachable = waypoints.is_none();
+        self.waypoints = waypoints.unwrap_or_default();
+        self.index = 0;
+        self.goal = Some(goal);
+        self.since_replan = 0.0;
+    }
+
+    /// 清除路径
+    pub fn clear(&mut self) {
+        *self = Self::default();
+    }
+
+    /// 目标是否无法到达
+    pub fn is_unreachable(&self) -> bool {
+        self.unreachable
+    }
+
+    /// 剩余路径点
+    pub fn remaining(&self) -> &[Vec3] {
+        self.waypoints.get(self.index..).unwrap_or_default()
+    }
+
+    /// 获取当前应前往的路径点（到达后自动前进）
+    ///
+    /// 返回 `(路径点, 是否为终点)`
+    pub fn next_waypoint(&mut self, position: Vec3) -> Option<(Vec3, bool)> {
+        while self.index + 1 < self.waypoints.len()
+            && horizontal_distance(position, self.waypoints[self.index])
+                <= WAYPOINT_REACHED_DISTANCE
+        {
+            self.index += 1;
+        }
+        let waypoint = *self.waypoints.get(self.index)?;
+        Some((waypoint, self.index + 1 == self.waypoints.len()))
+    }
+}
+
+/// 导航网格重建系统
+///
+/// 静态障碍物增删、移动或启用/禁用，或关卡变化时，根据关卡边界、关卡物体与启用的障碍物重新生成网格；
+/// 没有关卡布局时使用默认地面范围
+///
+/// 移除的障碍物由数量变化发现：移除事件只保留两帧，没有模拟步的帧里会丢失
+fn rebuild_nav_grid_system(
+    mut nav_grid: ResMut<NavGrid>,
+    level: Option<Res<LevelLayout>>,
+    obstacle_query: Query<(&Transform, &StaticObstacle)>,
+    changed_query: Query<
+        (),
+        (
+            With<StaticObstacle>,
+            Or<(Changed<StaticObstacle>, Changed<Transform>)>,
+        ),
+    >,
+    mut obstacle_count: Local<usize>,
+) {
+    let count = obstacle_query.iter().len();
+    let level_changed = level.as_ref().is_some_and(|level| level.is_changed());
+    if changed_query.is_empty() && count == *obstacle_count && !level_changed {
+        return;
+    }
+    *obstacle_count = count;
+    let obstacles: Vec<Rect> = obstacle_query
+        .iter()
+        .filter(|(_, obstacle)| obstacle.enabled)
+        .map(|(transform, obstacle)| obstacle.footprint(transform.translation))
+        .collect();
+
+    let bounds = level.as_ref().map_or(
+        Rect::new(
+            -FLOOR_HALF_LENGTH,
+            -FLOOR_HALF_WIDTH,
+            FLOOR_HALF_LENGTH,
+            FLOOR_HALF_WIDTH,
+        ),
+        |level| level.bounds,
+    );
+    // 关卡物体按区块加载，占地取自布局，与是否已加载无关
+    let features = level
+        .as_deref()
+        .map_or(&[][..], |level| &level.features[..]);
+    *nav_grid = NavGrid::from_obstacles(
+        bounds,
+        NavGrid::DEFAULT_CELL_SIZE,
+        features
+            .iter()
+            .map(|feature| feature.footprint())
+            .chain(obstacles.iter().copied()),
+        NAV_AGENT_RADIUS,
+    );
+
+    info!(
+        "[Nav] 导航网格已重建: {}x{}，障碍物 {} 个",
+        nav_grid.width(),
+        nav_grid.height(),
+        features.len() + obstacles.len()
+    );
+}
+
+/// 路径规划系统
+///
+/// 为需要移动的敌人维护导航路径：目标移动超过阈值或路径过期时重新规划
+pub(crate) fn update_nav_paths_system(
+    nav_grid: Res<NavGrid>,
+    mut enemy_query: Query<(
+        &Transform,
+        &EnemyAIState,
+        &EnemyTarget,
+        Option<&Blackboard>,
+        &mut NavPath,
+        Option<&AiLod>,
+    )>,
+    time: Res<Time>,
+) {
+    for (transform, state, target, blackboard, mut path, lod) in enemy_query.iter_mut() {
+        // 未轮到更新的敌人沿用旧路径（导航网格变化时仍立即重新规划）
+        let Some(delta) = AiLod::step_for(lod, time.delta_secs())
+            .or_else(|| nav_grid.is_changed().then_some(0.0))
+        else {
+            continue;
+        };
+
+        let goal = if state.should_move() {
+            match blackboard {
+                Some(blackboard) => blackboard.move_target,
+                None => target.last_known_position,
+            }
+        } else {
+            None
+        };
+
+        let Some(goal) = goal else {
+            if path.goal.is_some() {
+                path.clear();
+            }
+            continue;
+        };
+
+        path.since_replan += delta;
+        if nav_grid.is_changed() || path.needs_replan(goal) {
+            let waypoints = find_path(&nav_grid, transform.translation, goal);
+            if waypoints.is_none() {
+                debug!("[Nav] 无法规划到 {:?} 的路径", goal);
+            }
+            path.set(waypoints, goal);
+        }
+    }
+}
+
+/// 根据路径计算本帧的移动目标
+///
+/// 返回 `(移动目标, 停止距离)`：中间路径点需要精确到达，终点保留原停止距离；
+/// 没有可用路径时退回直线前往 `goal`
+pub(crate) fn steer_along_path(
+    path: Option<Mut<NavPath>>,
+    position: Vec3,
+    goal: Vec3,
+    stop_distance: f32,
+) -> (Vec3, f32) {
+    let Some(mut path) = path else {
+        return (goal, stop_distance);
+    };
+    if path.is_unreachable() {
+        return (goal, stop_distance);
+    }
+
+    match path.next_waypoint(position) {
+        Some((_, true)) | None => (goal, stop_distance),
+        Some((waypoint, false)) => (waypoint, 0.0),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 从字符画构造测试网格：`#` 为障碍，其余可行走；第一行对应 z = 0
+    fn grid_from_ascii(rows: &[&str]) -> NavGrid {
+        let height = rows.len() as f32;
+        let width = rows[0].len() as f32;
+        let mut grid = NavGrid::new(Rect::new(0.0, 0.0, width, height), 1.0);
+        for (z, row) in rows.iter().enumerate() {
+            for (x, ch) in row.chars().enumerate() {
+                if ch == '#' {
+                    grid.set_walkable(IVec2::new(x as i32, z as i32), false);
+                }
+            }
+        }
+        grid
+    }
+
+    fn center(x: i32, z: i32) -> Vec3 {
+        Vec3::new(x as f32 + 0.5, 0.0, z as f32 + 0.5)
+    }
+
+    #[test]
+    fn test_astar_open_field_is_straight() {
+        let grid = grid_from_ascii(&["......", "......", "......"]);
+        let path = find_cell_path(&grid, IVec2::new(0, 1), IVec2::new(5, 1)).unwrap();
+        assert_eq!(path.len(), 6);
+        assert!(path.iter().all(|cell| cell.y == 1));
+    }
+
+    #[test]
+    fn test_astar_goes_around_wall() {
+        let grid = grid_from_ascii(&[
+            ".......", //
+            "...#...", //
+            "...#...", //
+            "...#...", //
+            ".......", //
+        ]);
+        let path = find_cell_path(&grid, IVec2::new(0, 2), IVec2::new(6, 2)).unwrap();
+
+        assert!(path.iter().all(|&cell| grid.is_walkable(cell)));
+        assert!(path.iter().any(|cell| cell.y == 0 || cell.y == 4));
+        assert_eq!(path.first(), Some(&IVec2::new(0, 2)));
+        assert_eq!(path.last(), Some(&IVec2::new(6, 2)));
+    }
+
+    #[test]
+    fn test_astar_no_corner_cutting() {
+        let grid = grid_from_ascii(&[
+            ".#", //
+            "#.", //
+        ]);
+        assert!(find_cell_path(&grid, IVec2::new(0, 0), IVec2::new(1, 1)).is_none());
+    }
+
+    #[test]
+    fn test_unreachable_goal() {
+        let grid = grid_from_ascii(&[
+            "...#.", //
+            "...#.", //
+            "...#.", //
+        ]);
+        assert!(find_path(&grid, center(0, 1), center(4, 1)).is_none());
+    }
+
+    #[test]
+    fn test_smoothed_path_keeps_line_of_sight() {
+        let grid = grid_from_ascii(&[
+            "..........", //
+            "..........", //
+            "....#.....", //
+            "....#.....", //
+            "....#.....", //
+            "..........", //
+        ]);
+        let start = center(1, 3);
+        let goal = center(8, 3);
+        let path = find_path(&grid, start, goal).unwrap();
+
+        // 平滑后路径点应明显少于网格步数，且每一段都有视线
+        assert!(path.len() <= 3);
+        assert_eq!(*path.last().unwrap(), goal);
+        let mut previous = start;
+        for &point in &path {
+            assert!(grid.has_line_of_sight(previous, point));
+            previous = point;
+        }
+    }
+
+    #[test]
+    fn test_nav_path_replans_when_goal_moves() {
+        let mut path = NavPath::default();
+        assert!(path.needs_replan(Vec3::ZERO));
+
+        path.set(
+            Some(vec![Vec3::new(1.0, 0.0, 0.0), Vec3::new(2.0, 0.0, 0.0)]),
+            Vec3::ZERO,
+        );
+        assert!(!path.needs_replan(Vec3::new(1.0, 0.0, 0.0)));
+        assert!(path.needs_replan(Vec3::new(3.0, 0.0, 0.0)));
+
+        assert_eq!(
+            path.next_waypoint(Vec3::new(0.9, 0.0, 0.0)),
+            Some((Vec3::new(2.0, 0.0, 0.0), true))
+        );
+    }
+}
@@ -0,0 +1,389 @@
+//! 小队协同与警报
+//!
+//! 让附近的敌人作为一个整体行动：
+//! - 警报传播：发现玩家的敌人延迟一段时间后通知警报半径内的同伴，同伴可以继续转发
+//! - 小队黑板：同一小队共享玩家最后已知位置
+//! - 角色分配：交战中的小队成员按距离分配追击者 / 包抄者 / 警戒者
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use super::behavior::{sync_blackboard_system, tick_behavior_tree_system, BehaviorSystemSet};
+use super::blackboard::Blackboard;
+use super::enemy_ai::PlayerSpotted;
+
+/// 小队角色
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SquadRole {
+    /// 追击者：直接贴近目标
+    Chaser,
+    /// 包抄者：绕到目标侧面
+    Flanker,
+    /// 警戒者：保持距离监视目标
+    Watcher,
+}
+
+impl SquadRole {
+    /// 获取角色的显示名称（用于调试）
+    pub fn display_name(&self) -> &'static str {
+        match self {
+            Self::Chaser => "追击者",
+            Self::Flanker => "包抄者",
+            Self::Watcher => "警戒者",
+        }
+    }
+}
+
+/// 小队成员组件
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SquadMember {
+    /// 小队编号
+    pub squad: u32,
+}
+
+/// 警报参数组件
+///
+/// 未挂载此组件的敌人既不发出也不接收警报
+#[derive(Component, Debug, Clone)]
+pub struct AlertConfig {
+    /// 警报传播半径
+    pub alert_radius: f32,
+    /// 发现目标到通知同伴的延迟（秒）
+    pub alert_delay: f32,
+    /// 收到警报的同伴最多继续转发的次数
+    pub max_relay_hops: u32,
+}
+
+impl Default for AlertConfig {
+    fn default() -> Self {
+        Self {
+            alert_radius: 12.0,
+            alert_delay: 0.6,
+            max_relay_hops: 2,
+        }
+    }
+}
+
+/// 同伴收到警报消息
+#[derive(Message, Debug, Clone, Copy)]
+pub struct EnemyAlerted {
+    /// 收到警报的敌人
+    pub enemy: Entity,
+    /// 发出警报的敌人
+    pub source: Entity,
+    /// 警报中的玩家位置
+    pub position: Vec3,
+}
+
+/// 小队情报共享的时效（秒）
+///
+/// 超过该时间没有成员看到玩家时不再同步，各成员按自己的记忆搜索
+const KNOWLEDGE_SHARE_WINDOW: f32 = 1.0;
+
+/// 等待生效的警报
+#[derive(Debug, Clone)]
+struct PendingAlert {
+    source: Entity,
+    origin: Vec3,
+    position: Vec3,
+    radius: f32,
+    remaining: f32,
+    hops: u32,
+}
+
+/// 警报队列资源
+#[derive(Resource, Debug, Default)]
+pub struct AlertQueue {
+    pending: Vec<PendingAlert>,
+}
+
+impl AlertQueue {
+    /// 等待生效的警报数量
+    pub fn len(&self) -> usize {
+        self.pending.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.pending.is_empty()
+    }
+}
+
+/// 小队共享黑板
+#[derive(Debug, Clone, Default)]
+pub struct SquadBoard {
+    /// 小队所知的玩家最后位置
+    pub last_known_position: Option<Vec3>,
+    /// 小队最近一次看到玩家经过的时间（秒）
+    pub time_since_seen: f32,
+    /// 当前交战的成员数量
+    pub engaged: usize,
+}
+
+/// 小队资源：分配编号并保存每个小队的共享黑板
+#[derive(Resource, Debug, Default)]
+pub struct Squads {
+    next_id: u32,
+    boards: HashMap<u32, SquadBoard>,
+}
+
+impl Squads {
+    /// 分配新的小队编号
+    pub fn allocate(&mut self) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+        id
+    }
+
+    /// 获取小队共享黑板
+    pub fn board(&self, squad: u32) -> Option<&SquadBoard> {
+        self.boards.get(&squad)
+    }
+}
+
+/// 小队插件（内部使用）
+pub struct SquadPlugin;
+
+impl Plugin for SquadPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<EnemyAlerted>()
+            .init_resource::<AlertQueue>()
+            .init_resource::<Squads>()
+            .add_systems(
+                FixedUpdate,
+                (queue_alerts_system, propagate_alerts_system)
+                    .chain()
+                    .in_set(BehaviorSystemSet::Detection)
+                    .after(super::behavior::detect_player_system),
+            )
+            .add_systems(
+                FixedUpdate,
+                share_squad_knowledge_system
+                    .in_set(BehaviorSystemSet::Decision)
+                    .after(sync_blackboard_system)
+                    .before(tick_behavior_tree_system),
+            );
+    }
+}
+
+/// 将相互靠近的出生点分组为小队
+///
+/// 按顺序贪心分组：未分组的点作为队长，`radius` 内的其余未分组点加入该队；
+/// 返回每个点所在的组序号（从 0 开始）
+pub fn group_into_squads(positions: &[Vec3], radius: f32) -> Vec<usize> {
+    let mut groups = vec![usize::MAX; positions.len()];
+    let mut next = 0;
+
+    for leader in 0..positions.len() {
+        if groups[leader] != usize::MAX {
+            continue;
+        }
+        groups[leader] = next;
+        for member in leader + 1..positions.len() {
+            if groups[member] == usize::MAX
+                && positions[leader].distance(positions[member]) <= radius
+            {
+                groups[member] = next;
+            }
+        }
+        next += 1;
+    }
+    groups
+}
+
+/// 按交战人数分配角色
+///
+/// 返回的角色按离目标由近到远排列：近处为追击者，其次为包抄者，
+/// 每 4 人中最远的 1 人担任警戒者
+pub fn assign_roles(count: usize) -> Vec<SquadRole> {
+    let watchers = count / 4;
+    let flankers = (count - watchers) / 2;
+    let chasers = count - watchers - flankers;
+
+    std::iter::repeat_n(SquadRole::Chaser, chasers)
+        .chain(std::iter::repeat_n(SquadRole::Flanker, flankers))
+        .chain(std::iter::repeat_n(SquadRole::Watcher, watchers))
+        .collect()
+}
+
+/// 警报入队系统
+///
+/// 敌人发现玩家时，按其警报参数延迟通知同伴
+fn queue_alerts_system(
+    mut spotted: MessageReader<PlayerSpotted>,
+    enemy_query: Query<(&Transform, &AlertConfig)>,
+    mut queue: ResMut<AlertQueue>,
+) {
+    for message in spotted.read() {
+        let Ok((transform, config)) = enemy_query.get(message.enemy) else {
+            continue;
+        };
+        queue.pending.push(PendingAlert {
+            source: message.enemy,
+            origin: transform.translation,
+            position: message.position,
+            radius: config.alert_radius,
+            remaining: config.alert_delay,
+            hops: config.max_relay_hops,
+        });
+    }
+}
+
+/// 警报传播系统
+///
+/// 延迟结束后，通知警报半径内尚未察觉玩家的同伴；
+/// 被通知的同伴在剩余转发次数内继续向外传播
+fn propagate_alerts_system(
+    mut enemy_query: Query<(Entity, &Transform, &AlertConfig, &mut Blackboard)>,
+    mut queue: ResMut<AlertQueue>,
+    mut alerted: MessageWriter<EnemyAlerted>,
+    time: Res<Time>,
+) {
+    let delta = time.delta_secs();
+    for alert in queue.pending.iter_mut() {
+        alert.remaining -= delta;
+    }
+
+    let (ready, waiting): (Vec<_>, Vec<_>) = queue
+        .pending
+        .drain(..)
+        .partition(|alert| alert.remaining <= 0.0);
+    queue.pending = waiting;
+
+    for alert in ready {
+        for (entity, transform, config, mut blackboard) in enemy_query.iter_mut() {
+            if entity == alert.source
+                || blackboard.last_known_position.is_some()
+                || transform.translation.distance(alert.origin) > alert.radius
+            {
+                continue;
+            }
+
+            blackboard.hear_alert(alert.position);
+            alerted.write(EnemyAlerted {
+                enemy: entity,
+                source: alert.source,
+                position: alert.position,
+            });
+            debug!("[AI] 敌人收到同伴警报");
+
+            if alert.hops > 0 {
+                queue.pending.push(PendingAlert {
+                    source: entity,
+                    origin: transform.translation,
+                    position: alert.position,
+                    radius: config.alert_radius,
+                    remaining: config.alert_delay,
+                    hops: alert.hops - 1,
+                });
+            }
+        }
+    }
+}
+
+/// 小队共享黑板系统
+///
+/// 汇总每个小队最新的玩家情报并同步给所有成员，再为交战成员分配角色
+fn share_squad_knowledge_system(
+    mut enemy_query: Query<(Entity, &SquadMember, &mut Blackboard)>,
+    mut squads: ResMut<Squads>,
+) {
+    // 汇总：每个小队取最近一次看到玩家的成员情报
+    let mut boards: HashMap<u32, SquadBoard> = HashMap::new();
+    let mut engaged: HashMap<u32, Vec<(Entity, f32)>> = HashMap::new();
+
+    for (entity, member, blackboard) in enemy_query.iter() {
+        let board = boards.entry(member.squad).or_insert_with(|| SquadBoard {
+            time_since_seen: f32::INFINITY,
+            ..default()
+        });
+        if let Some(position) = blackboard.last_known_position
+            && blackboard.time_since_seen < board.time_since_seen
+        {
+            board.last_known_position = Some(position);
+            board.time_since_seen = blackboard.time_since_seen;
+        }
+        if blackboard.has_target {
+            board.engaged += 1;
+            engaged
+                .entry(member.squad)
+                .or_default()
+                .push((entity, blackboard.distance_to_target));
+        }
+    }
+
+    // 分配角色：按离目标由近到远，同距离按实体排序保证确定
+    let mut roles: HashMap<Entity, (SquadRole, f32)> = HashMap::new();
+    for members in engaged.values_mut() {
+        members.sort_by(|a, b| a.1.total_cmp(&b.1).then(a.0.cmp(&b.0)));
+        let mut flank_side = 1.0;
+        for ((entity, _), role) in members.iter().zip(assign_roles(members.len())) {
+            roles.insert(*entity, (role, flank_side));
+            if role == SquadRole::Flanker {
+                // 包抄者交替从两侧绕行
+                flank_side = -flank_side;
+            }
+        }
+    }
+
+    for (entity, member, mut blackboard) in enemy_query.iter_mut() {
+        if let Some(board) = boards.get(&member.squad)
+            && let Some(position) = board.last_known_position
+            && board.time_since_seen <= KNOWLEDGE_SHARE_WINDOW
+        {
+            blackboard.share_knowledge(position, board.time_since_seen);
+        }
+
+        match roles.get(&entity) {
+            Some(&(role, flank_side)) => {
+                if blackboard.squad_role != Some(role) {
+                    debug!("[AI] 小队角色分配: {}", role.display_name());
+                }
+                blackboard.squad_role = Some(role);
+                match role {
+                    SquadRole::Chaser => blackboard.flank_weight = 0.0,
+                    SquadRole::Flanker => {
+                        blackboard.flank_weight = 1.0;
+                        blackboard.flank_side = flank_side;
+                    }
+                    SquadRole::Watcher => {}
+                }
+            }
+            None => blackboard.squad_role = None,
+        }
+    }
+
+    squads.boards = boards;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_group_into_squads() {
+        let positions = [
+            Vec3::new(0.0, 0.0, 0.0),
+            Vec3::new(40.0, 0.0, 0.0),
+            Vec3::new(3.0, 0.0, 2.0),
+            Vec3::new(42.0, 0.0, -1.0),
+            Vec3::new(-30.0, 0.0, 0.0),
+        ];
+        assert_eq!(group_into_squads(&positions, 10.0), vec![0, 1, 0, 1, 2]);
+    }
+
+    #[test]
+    fn test_assign_roles() {
+        use SquadRole::*;
+
+        assert_eq!(assign_roles(1), vec![Chaser]);
+        assert_eq!(assign_roles(2), vec![Chaser, Flanker]);
+        assert_eq!(assign_roles(4), vec![Chaser, Chaser, Flanker, Watcher]);
+        assert_eq!(
+            assign_roles(8),
+            vec![
+                Chaser, Chaser, Chaser, Flanker, Flanker, Flanker, Watcher, Watcher
+            ]
+        );
+    }
+}
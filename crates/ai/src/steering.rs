@@ -0,0 +1,519 @@
+//! 局部避让与群体转向
+//!
+//! 在移动之前计算敌人的实际速度，替代事后的碰撞推开：
+//! - 预测式避让（基于碰撞时间，RVO 风格，相向时统一向右侧让行）
+//! - 分离 / 对齐 / 聚合（Boids）
+//! - 包围站位：同一目标的攻击者均匀分布在目标周围，而不是堆叠在一点；
+//!   内圈站满后其余攻击者在外侧等候圈排队
+//!
+//! 执行阶段的行为系统只写入期望速度（`SteeringAgent::preferred_velocity`），
+//! Steering 阶段统一计算最终速度：挂载角色控制器时交给控制器移动，否则直接积分位移。
+
+use std::collections::HashMap;
+
+use bevy::ecs::entity::EntityHashMap;
+use bevy::prelude::*;
+
+use super::behavior::BehaviorSystemSet;
+use super::blackboard::Blackboard;
+use super::enemy_ai::{EnemyAIConfig, EnemyAIState, EnemyTarget};
+use vigilant_doodle_gameplay::{CharacterMotion, CollisionRadius, Player, SpatialIndex};
+
+/// 转向参数组件
+#[derive(Component, Debug, Clone)]
+pub struct SteeringConfig {
+    /// 邻居感知半径
+    pub neighbor_radius: f32,
+    /// 避让预测时间窗口（秒）
+    pub time_horizon: f32,
+    /// 预测避让权重
+    pub avoidance_weight: f32,
+    /// 分离权重
+    pub separation_weight: f32,
+    /// 对齐权重
+    pub alignment_weight: f32,
+    /// 聚合权重
+    pub cohesion_weight: f32,
+    /// 最大加速度（速度变化率上限）
+    pub max_acceleration: f32,
+    /// 包围站位的相邻间距
+    pub slot_spacing: f32,
+    /// 距离目标多近时开始分配包围站位
+    pub slot_engage_distance: f32,
+}
+
+impl Default for SteeringConfig {
+    fn default() -> Self {
+        Self {
+            neighbor_radius: 4.0,
+            time_horizon: 1.5,
+            avoidance_weight: 1.0,
+            separation_weight: 1.5,
+            alignment_weight: 0.3,
+            cohesion_weight: 0.1,
+            max_acceleration: 25.0,
+            slot_spacing: 1.6,
+            slot_engage_distance: 8.0,
+        }
+    }
+}
+
+/// 转向代理组件
+///
+/// 挂载后，敌人的移动由 Steering 阶段统一积分
+#[derive(Component, Debug, Clone, Default)]
+pub struct SteeringAgent {
+    /// 本帧行为系统期望的速度（每帧积分后清零）
+    pub preferred_velocity: Vec3,
+    /// 当前实际速度
+    pub velocity: Vec3,
+    /// 避让半径
+    pub radius: f32,
+}
+
+impl SteeringAgent {
+    pub fn new(radius: f32) -> Self {
+        Self {
+            radius,
+            ..default()
+        }
+    }
+}
+
+/// 转向计算使用的代理快照（XZ 平面）
+#[derive(Debug, Clone, Copy)]
+pub struct AgentSnapshot {
+    pub entity: Entity,
+    pub position: Vec2,
+    pub velocity: Vec2,
+    pub radius: f32,
+    /// 是否为同伴代理（玩家等障碍物不参与对齐与聚合）
+    pub is_agent: bool,
+}
+
+/// 转向插件（内部使用）
+pub struct SteeringPlugin;
+
+impl Plugin for SteeringPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            assign_surround_slots_system
+                .in_set(BehaviorSystemSet::Decision)
+                .after(super::behavior::tick_behavior_tree_system),
+        )
+        .add_systems(
+            FixedUpdate,
+            apply_steering_system.in_set(BehaviorSystemSet::Steering),
+        );
+    }
+}
+
+/// 计算两个圆形代理的碰撞时间
+///
+/// `offset` 为自身相对邻居的位置，`relative_velocity` 为自身相对邻居的速度；
+/// 已重叠返回 0，不会碰撞返回 None
+pub fn time_to_collision(
+    offset: Vec2,
+    relative_velocity: Vec2,
+    combined_radius: f32,
+) -> Option<f32> {
+    let c = offset.length_squared() - combined_radius * combined_radius;
+    if c < 0.0 {
+        return Some(0.0);
+    }
+
+    let a = relative_velocity.length_squared();
+    let b = 2.0 * offset.dot(relative_velocity);
+    if a < f32::EPSILON || b >= 0.0 {
+        return None;
+    }
+
+    let discriminant = b * b - 4.0 * a * c;
+    if discriminant < 0.0 {
+        return None;
+    }
+    Some((-b - discriminant.sqrt()) / (2.0 * a))
+}
+
+/// 计算代理的目标速度
+///
+/// 在期望速度基础上叠加预测避让、分离、对齐、聚合，结果限制在 `max_speed` 内
+pub fn compute_steering<'a>(
+    agent: &AgentSnapshot,
+    preferred_velocity: Vec2,
+    neighbors: impl IntoIterator<Item = &'a AgentSnapshot>,
+    config: &SteeringConfig,
+    max_speed: f32,
+) -> Vec2 {
+    let mut avoidance = Vec2::ZERO;
+    let mut separation = Vec2::ZERO;
+    let mut velocity_sum = Vec2::ZERO;
+    let mut position_sum = Vec2::ZERO;
+    let mut flock_count = 0;
+
+    for neighbor in neighbors {
+        if neighbor.entity == agent.entity {
+            continue;
+        }
+
+        let mut offset = agent.position - neighbor.position;
+        let mut distance = offset.length();
+        if distance > config.neighbor_radius {
+            continue;
+        }
+        if distance < 1e-4 {
+            // 完全重合时按实体序号选择确定的分离方向
+            offset = if agent.entity < neighbor.entity {
+                Vec2::X
+            } else {
+                Vec2::NEG_X
+            } * 1e-4;
+            distance = 1e-4;
+        }
+        let away = offset / distance;
+        let combined_radius = agent.radius + neighbor.radius;
+
+        // 分离：进入个人空间后按侵入程度推开
+        let personal_space = combined_radius * 1.5;
+        if distance < personal_space {
+            separation += away * (personal_space - distance) / personal_space;
+        }
+
+        // 预测避让：在时间窗口内会相撞时，沿碰撞时刻的法线方向让开
+        let relative_velocity = preferred_velocity - neighbor.velocity;
+        if let Some(t) = time_to_collision(offset, relative_velocity, combined_radius)
+            && t < config.time_horizon
+        {
+            let contact = offset + relative_velocity * t;
+            let normal = contact.normalize_or(away);
+            // 相向而行时法线几乎与速度共线，统一向右侧让行打破对称
+            let right = -relative_velocity.normalize_or_zero().perp();
+            let strength = (config.time_horizon - t) / config.time_horizon;
+            avoidance += (normal + right * 0.5).normalize_or_zero() * strength;
+        }
+
+        if neighbor.is_agent {
+            velocity_sum += neighbor.velocity;
+            position_sum += neighbor.position;
+            flock_count += 1;
+        }
+    }
+
+    let mut desired = preferred_velocity
+        + avoidance * config.avoidance_weight * max_speed
+        + separation * config.separation_weight * max_speed;
+
+    // 对齐与聚合只作用于正在移动的代理，避免闲置敌人被同伴拖动
+    if flock_count > 0 && preferred_velocity.length_squared() > f32::EPSILON {
+        let count = flock_count as f32;
+        desired += (velocity_sum / count - agent.velocity) * config.alignment_weight;
+        desired += (position_sum / count - agent.position) * config.cohesion_weight;
+    }
+
+    desired.clamp_length_max(max_speed)
+}
+
+/// 计算围绕中心的包围站位
+///
+/// 内圈半径为 `radius`，按 `spacing` 能容纳的人数排满后，其余代理由近及远
+/// 分配到外侧每隔 `spacing` 一圈的等候圈，内圈不会超出攻击距离
+pub fn surround_slots(center: Vec3, positions: &[Vec3], radius: f32, spacing: f32) -> Vec<Vec3> {
+    // 离中心近的代理优先占据内圈，同距离按序号保证确定
+    let mut order: Vec<usize> = (0..positions.len()).collect();
+    order.sort_by(|&a, &b| {
+        center
+            .distance_squared(positions[a])
+            .total_cmp(&center.distance_squared(positions[b]))
+            .then(a.cmp(&b))
+    });
+
+    let mut slots = vec![center; positions.len()];
+    let mut remaining = order.as_slice();
+    let mut ring_radius = radius;
+    while !remaining.is_empty() {
+        let capacity = ((std::f32::consts::TAU * ring_radius / spacing) as usize).max(1);
+        let (ring, rest) = remaining.split_at(capacity.min(remaining.len()));
+        place_ring(center, positions, ring, ring_radius, &mut slots);
+        remaining = rest;
+        ring_radius += spacing;
+    }
+    slots
+}
+
+/// 把 `ring` 中的代理均匀分布到一圈上
+///
+/// 按当前方位角排序后依次分配槽位，并整体旋转使总偏转最小
+fn place_ring(center: Vec3, positions: &[Vec3], ring: &[usize], radius: f32, slots: &mut [Vec3]) {
+    let angle_of = |position: &Vec3| (position.z - center.z).atan2(position.x - center.x);
+    let mut order = ring.to_vec();
+    order.sort_by(|&a, &b| angle_of(&positions[a]).total_cmp(&angle_of(&positions[b])));
+
+    let step = std::f32::consts::TAU / order.len() as f32;
+
+    // 槽位整体旋转角：各代理方位角与其槽位偏移之差的圆周平均
+    let (sin_sum, cos_sum) =
+        order
+            .iter()
+            .enumerate()
+            .fold((0.0, 0.0), |(sin_sum, cos_sum), (slot, &index)| {
+                let base = angle_of(&positions[index]) - slot as f32 * step;
+                (sin_sum + base.sin(), cos_sum + base.cos())
+            });
+    let base_angle = sin_sum.atan2(cos_sum);
+
+    for (slot, &index) in order.iter().enumerate() {
+        let angle = base_angle + slot as f32 * step;
+        slots[index] = center + Vec3::new(angle.cos(), 0.0, angle.sin()) * radius;
+    }
+}
+
+/// 包围站位分配系统
+///
+/// 靠近目标的追击者与攻击者按目标分组，均匀分配到目标周围的槽位
+pub(crate) fn assign_surround_slots_system(
+    mut enemy_query: Query<(
+        Entity,
+        &Transform,
+        &EnemyTarget,
+        &EnemyAIState,
+        &EnemyAIConfig,
+        &SteeringConfig,
+        &mut Blackboard,
+    )>,
+) {
+    let mut groups: HashMap<Entity, Vec<(Entity, Vec3)>> = HashMap::new();
+
+    for (entity, transform, target, state, _, steering, mut blackboard) in enemy_query.iter_mut() {
+        blackboard.surround_slot = None;
+
+        let engaged = matches!(state, EnemyAIState::Chasing | EnemyAIState::Attacking)
+            && blackboard.distance_to_target <= steering.slot_engage_distance;
+        if let (true, Some(target_entity)) = (engaged, target.entity) {
+            groups
+                .entry(target_entity)
+                .or_default()
+                .push((entity, transform.translation));
+        }
+    }
+
+    for members in groups.values_mut() {
+        // 按实体排序，保证分组内顺序确定
+        members.sort_by_key(|(entity, _)| *entity);
+
+        let Ok((_, _, _, _, config, steering, blackboard)) = enemy_query.get(members[0].0) else {
+            continue;
+        };
+        let Some(center) = blackboard.target_position else {
+            continue;
+        };
+        let min_radius = config.attack_range * 0.75;
+        let spacing = steering.slot_spacing;
+
+        let positions: Vec<Vec3> = members.iter().map(|(_, position)| *position).collect();
+        let slots = surround_slots(center, &positions, min_radius, spacing);
+
+        for ((entity, _), slot) in members.iter().zip(slots) {
+            if let Ok((.., mut blackboard)) = enemy_query.get_mut(*entity) {
+                blackboard.surround_slot = Some(slot);
+                blackboard.move_target = Some(slot);
+            }
+        }
+    }
+}
+
+/// 转向积分系统
+///
+/// 基于上一帧速度快照计算所有代理的新速度，再统一积分位移，结果与遍历顺序无关
+fn apply_steering_system(
+    mut agent_query: Query<(
+        Entity,
+        &mut Transform,
+        &mut SteeringAgent,
+        &SteeringConfig,
+        &EnemyAIConfig,
+        &EnemyAIState,
+        Option<&mut CharacterMotion>,
+    )>,
+    player_query: Query<
+        (Entity, &Transform, &CollisionRadius),
+        (With<Player>, Without<SteeringAgent>),
+    >,
+    spatial_index: Res<SpatialIndex>,
+    time: Res<Time>,
+) {
+    // 索引重建后本帧内的移动余量
+    const QUERY_MARGIN: f32 = 1.0;
+
+    let delta = time.delta_secs();
+
+    let mut snapshots: EntityHashMap<AgentSnapshot> = agent_query
+        .iter()
+        .map(|(entity, transform, agent, ..)| {
+            let snapshot = AgentSnapshot {
+                entity,
+                position: transform.translation.xz(),
+                velocity: agent.velocity.xz(),
+                radius: agent.radius,
+                is_agent: true,
+            };
+            (entity, snapshot)
+        })
+        .collect();
+    snapshots.extend(player_query.iter().map(|(entity, transform, radius)| {
+        let snapshot = AgentSnapshot {
+            entity,
+            position: transform.translation.xz(),
+            velocity: Vec2::ZERO,
+            radius: radius.radius,
+            is_agent: false,
+        };
+        (entity, snapshot)
+    }));
+
+    let new_velocities: Vec<(Entity, Vec2)> = agent_query
+        .iter()
+        .map(|(entity, transform, agent, steering, config, ..)| {
+            let snapshot = AgentSnapshot {
+                entity,
+                position: transform.translation.xz(),
+                velocity: agent.velocity.xz(),
+                radius: agent.radius,
+                is_agent: true,
+            };
+            // 通过空间索引只取附近的邻居
+            let neighbors = spatial_index
+                .query_radius(
+                    transform.translation,
+                    steering.neighbor_radius + QUERY_MARGIN,
+                )
+                .filter_map(|entry| snapshots.get(&entry.entity));
+            let desired = compute_steering(
+                &snapshot,
+                agent.preferred_velocity.xz(),
+                neighbors,
+                steering,
+                config.move_speed,
+            );
+
+            // 限制加速度，避免速度突变造成抖动
+            let change =
+                (desired - snapshot.velocity).clamp_length_max(steering.max_acceleration * delta);
+            (entity, snapshot.velocity + change)
+        })
+        .collect();
+
+    for (entity, velocity) in new_velocities {
+        let Ok((_, mut transform, mut agent, _, _, state, motion)) = agent_query.get_mut(entity)
+        else {
+            continue;
+        };
+
+        agent.velocity = Vec3::new(velocity.x, 0.0, velocity.y);
+        agent.preferred_velocity = Vec3::ZERO;
+        match motion {
+            Some(mut motion) => motion.velocity = agent.velocity,
+            None => transform.translation += agent.velocity * delta,
+        }
+
+        // 攻击状态保持面向目标，其余状态面向移动方向
+        if *state != EnemyAIState::Attacking && velocity.length_squared() > 0.01 {
+            transform.look_to(agent.velocity, Vec3::Y);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn snapshot(index: u32, position: Vec2, velocity: Vec2) -> AgentSnapshot {
+        AgentSnapshot {
+            entity: Entity::from_raw_u32(index).unwrap(),
+            position,
+            velocity,
+            radius: 0.6,
+            is_agent: true,
+        }
+    }
+
+    #[test]
+    fn test_time_to_collision() {
+        // 相距 10，相对速度 2，合并半径 1 -> 4.5 秒后接触
+        let t = time_to_collision(Vec2::new(-10.0, 0.0), Vec2::new(2.0, 0.0), 1.0).unwrap();
+        assert!((t - 4.5).abs() < 1e-4);
+
+        // 背离而行不会碰撞
+        assert!(time_to_collision(Vec2::new(-10.0, 0.0), Vec2::new(-2.0, 0.0), 1.0).is_none());
+
+        // 已重叠
+        assert_eq!(
+            time_to_collision(Vec2::new(0.5, 0.0), Vec2::ZERO, 1.0),
+            Some(0.0)
+        );
+    }
+
+    #[test]
+    fn test_head_on_agents_sidestep() {
+        let config = SteeringConfig::default();
+        let a = snapshot(1, Vec2::new(-2.0, 0.0), Vec2::new(3.0, 0.0));
+        let b = snapshot(2, Vec2::new(2.0, 0.0), Vec2::new(-3.0, 0.0));
+        let all = [a, b];
+
+        let va = compute_steering(&a, Vec2::new(3.0, 0.0), &all, &config, 3.5);
+        let vb = compute_steering(&b, Vec2::new(-3.0, 0.0), &all, &config, 3.5);
+
+        // 双方各自向右侧让行，横向分量方向相反
+        assert!(va.y.abs() > 0.1);
+        assert!(va.y * vb.y < 0.0);
+        assert!(va.length() <= 3.5 + 1e-4);
+    }
+
+    #[test]
+    fn test_overlapping_agents_separate() {
+        let config = SteeringConfig::default();
+        let a = snapshot(1, Vec2::ZERO, Vec2::ZERO);
+        let b = snapshot(2, Vec2::new(0.3, 0.0), Vec2::ZERO);
+        let all = [a, b];
+
+        let va = compute_steering(&a, Vec2::ZERO, &all, &config, 3.5);
+        assert!(va.x < 0.0);
+    }
+
+    #[test]
+    fn test_surround_slots_spread_evenly() {
+        let center = Vec3::ZERO;
+        // 四个敌人都从右侧挤过来
+        let positions = [
+            Vec3::new(3.0, 0.0, 0.1),
+            Vec3::new(3.0, 0.0, -0.1),
+            Vec3::new(3.2, 0.0, 0.3),
+            Vec3::new(3.1, 0.0, -0.3),
+        ];
+        let slots = surround_slots(center, &positions, 1.5, 1.6);
+
+        for (i, a) in slots.iter().enumerate() {
+            assert!((a.distance(center) - 1.5).abs() < 1e-4);
+            for b in slots.iter().skip(i + 1) {
+                assert!(a.distance(*b) > 2.0);
+            }
+        }
+    }
+
+    #[test]
+    fn test_extra_agents_wait_on_outer_ring() {
+        // 越靠后的敌人离中心越远
+        let positions: Vec<Vec3> = (0..12)
+            .map(|i| Vec3::new(5.0 + i as f32 * 0.1, 0.0, 0.0))
+            .collect();
+        let slots = surround_slots(Vec3::ZERO, &positions, 1.5, 1.6);
+
+        // 半径 1.5 的内圈可容纳 5 人，其余在外侧一圈等候
+        let capacity = (std::f32::consts::TAU * 1.5 / 1.6) as usize;
+        assert_eq!(capacity, 5);
+        for (i, slot) in slots.iter().enumerate() {
+            let expected_radius = if i < capacity { 1.5 } else { 1.5 + 1.6 };
+            assert!((slot.length() - expected_radius).abs() < 1e-4);
+        }
+    }
+}
@@ -0,0 +1,46 @@
+//! 命令行参数
+//!
+//! 支持 `--name value` 与 `--name=value` 两种写法，供各插件读取启动参数。
+
+/// 读取命令行参数 `name` 的值（多次出现时取第一个）
+pub fn value(name: &str) -> Option<String> {
+    find(std::env::args().skip(1), name)
+}
+
+fn find(args: impl IntoIterator<Item = String>, name: &str) -> Option<String> {
+    let mut args = args.into_iter();
+    while let Some(arg) = args.next() {
+        if arg == name {
+            return args.next();
+        }
+        if let Some(value) = arg
+            .strip_prefix(name)
+            .and_then(|rest| rest.strip_prefix('='))
+        {
+            return Some(value.to_string());
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(list: &[&str]) -> Vec<String> {
+        list.iter().map(|arg| arg.to_string()).collect()
+    }
+
+    #[test]
+    fn test_find_value() {
+        assert_eq!(
+            find(args(&["--seed", "42", "--level=a.json"]), "--level"),
+            Some("a.json".to_string())
+        );
+        assert_eq!(
+            find(args(&["--seed", "42"]), "--seed"),
+            Some("42".to_string())
+        );
+        assert_eq!(find(args(&["--seeds=1", "--seed"]), "--seed"), None);
+    }
+}
@@ -1,13 +1,21 @@
 //! 游戏平衡性配置系统
 //!
 //! 从 JSON 配置文件加载游戏平衡参数，包括：
-//! - 玩家移动速度、跳跃力等
+//! - 玩家移动速度、生命值、跳跃力、冲刺、翻滚、体力、锁定目标、手电筒与背包等
+//! - 角色控制器参数（玩家与敌人共用）
 //! - 敌人 AI 参数（移动速度、检测范围等）
+//! - 敌人波次表与遭遇节奏
+//! - 敌人原型（关卡文件中引用）
+//! - 物品定义（背包、拾取物与关卡文件中按名称引用）
+//! - 程序化关卡生成与地形参数
+//! - 分块加载与昼夜循环参数
+//! - 固定随机种子（可选）
 //!
 //! 配置文件位置：`assets/balance.json`
 
 use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
 
 /// 游戏平衡配置
 ///
@@ -15,14 +23,26 @@ use serde::{Deserialize, Serialize};
 #[derive(Resource, Debug, Clone, Serialize, Deserialize)]
 pub struct BalanceConfig {
     pub player: PlayerBalance,
+    pub character: CharacterBalance,
     pub enemy: EnemyBalance,
+    pub level: LevelBalance,
+    /// 物品定义（按名称引用）
+    pub items: BTreeMap<String, ItemBalance>,
+    /// 平衡配置级的随机种子覆盖（随游戏数据发布，不是玩家设置），用于固定某一版数据的随机序列；
+    /// 为空时由命令行、环境变量或系统熵决定，命令行与环境变量优先（见 `rng`）
+    #[serde(default)]
+    pub seed_override: Option<u64>,
 }
 
 impl Default for BalanceConfig {
     fn default() -> Self {
         Self {
             player: PlayerBalance::default(),
+            character: CharacterBalance::default(),
             enemy: EnemyBalance::default(),
+            level: LevelBalance::default(),
+            items: default_items(),
+            seed_override: None,
         }
     }
 }
@@ -32,27 +52,327 @@ impl Default for BalanceConfig {
 pub struct PlayerBalance {
     /// 移动速度
     pub speed: f32,
+    /// 最大生命值
+    pub max_health: f32,
     /// 旋转速度（弧度/秒）
     pub rotation_speed: f32,
-    /// 跳跃力度
+    /// 跳跃力度（起跳速度）
     pub jump_force: f32,
-    /// 重力加速度
-    pub gravity: f32,
     /// 敌人检测范围（多少距离内会朝向敌人）
     pub detection_range: f32,
     /// 最大跳跃次数（1=单跳，2=二段跳）
     pub max_jump_count: u32,
+    /// 土狼时间：离开地面后仍可起跳的时长（秒）
+    pub coyote_time: f32,
+    /// 跳跃缓冲：落地前多久按下跳跃仍会在落地时起跳（秒）
+    pub jump_buffer_time: f32,
+    /// 上升中松开跳跃键时保留的上升速度比例（越小则短按跳得越低）
+    pub jump_release_multiplier: f32,
+    /// 空中控制（0=保持起跳时的水平速度，1=与地面相同）
+    pub air_control: f32,
+    /// 冲刺参数
+    pub sprint: SprintBalance,
+    /// 翻滚闪避参数
+    pub dodge: DodgeBalance,
+    /// 体力参数
+    pub stamina: StaminaBalance,
+    /// 锁定目标参数
+    pub targeting: TargetingBalance,
+    /// 手电筒参数
+    pub flashlight: FlashlightBalance,
+    /// 背包与拾取参数
+    pub inventory: InventoryBalance,
+    /// 与场景物体交互的参数
+    pub interaction: InteractionBalance,
+    /// 近战攻击参数
+    pub melee: MeleeBalance,
+    /// 经验值与升级参数
+    pub progression: ProgressionBalance,
 }
 
 impl Default for PlayerBalance {
     fn default() -> Self {
         Self {
             speed: 7.5,
+            max_health: 100.0,
             rotation_speed: 10.0,
             jump_force: 7.5,
-            gravity: -30.0,
             detection_range: 10.0,
             max_jump_count: 2, // 默认二段跳
+            coyote_time: 0.12,
+            jump_buffer_time: 0.12,
+            jump_release_multiplier: 0.5,
+            air_control: 0.6,
+            sprint: SprintBalance::default(),
+            dodge: DodgeBalance::default(),
+            stamina: StaminaBalance::default(),
+            targeting: TargetingBalance::default(),
+            flashlight: FlashlightBalance::default(),
+            inventory: InventoryBalance::default(),
+            interaction: InteractionBalance::default(),
+            melee: MeleeBalance::default(),
+            progression: ProgressionBalance::default(),
+        }
+    }
+}
+
+/// 冲刺参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SprintBalance {
+    /// 冲刺时的速度倍率
+    pub speed_multiplier: f32,
+    /// 冲刺每秒消耗的体力
+    pub stamina_per_second: f32,
+}
+
+impl Default for SprintBalance {
+    fn default() -> Self {
+        Self {
+            speed_multiplier: 1.6,
+            stamina_per_second: 20.0,
+        }
+    }
+}
+
+/// 翻滚闪避参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DodgeBalance {
+    /// 翻滚速度
+    pub speed: f32,
+    /// 翻滚持续时间（秒）
+    pub duration: f32,
+    /// 翻滚开始后的无敌时间（秒）
+    pub invulnerability: f32,
+    /// 翻滚结束后的冷却时间（秒）
+    pub cooldown: f32,
+    /// 每次翻滚消耗的体力
+    pub stamina_cost: f32,
+}
+
+impl Default for DodgeBalance {
+    fn default() -> Self {
+        Self {
+            speed: 14.0,
+            duration: 0.35,
+            invulnerability: 0.25,
+            cooldown: 0.8,
+            stamina_cost: 25.0,
+        }
+    }
+}
+
+/// 体力参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StaminaBalance {
+    /// 体力上限
+    pub max: f32,
+    /// 每秒恢复的体力
+    pub regen_per_second: f32,
+    /// 消耗体力后多久开始恢复（秒）
+    pub regen_delay: f32,
+}
+
+impl Default for StaminaBalance {
+    fn default() -> Self {
+        Self {
+            max: 100.0,
+            regen_per_second: 25.0,
+            regen_delay: 0.8,
+        }
+    }
+}
+
+/// 锁定目标参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TargetingBalance {
+    /// 可锁定目标的最大距离
+    pub lock_range: f32,
+    /// 锁定目标超出该距离时解除锁定
+    pub break_range: f32,
+}
+
+impl Default for TargetingBalance {
+    fn default() -> Self {
+        Self {
+            lock_range: 15.0,
+            break_range: 20.0,
+        }
+    }
+}
+
+/// 手电筒参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct FlashlightBalance {
+    /// 光照强度
+    pub intensity: f32,
+    /// 光照距离
+    pub range: f32,
+    /// 光锥半角（弧度）
+    pub outer_angle: f32,
+    /// 光束向下倾斜的角度（度）
+    pub pitch: f32,
+    /// 鼠标瞄准灵敏度（弧度/像素）
+    pub aim_sensitivity: f32,
+    /// 电量上限
+    pub battery_capacity: f32,
+    /// 开启时每秒消耗的电量
+    pub drain_per_second: f32,
+    /// 电量低于该比例时闪烁
+    pub low_battery: f32,
+}
+
+impl Default for FlashlightBalance {
+    fn default() -> Self {
+        Self {
+            intensity: 5000.0,
+            range: 50.0,
+            outer_angle: 0.8,
+            pitch: 30.0,
+            aim_sensitivity: 0.004,
+            battery_capacity: 100.0,
+            drain_per_second: 0.5,
+            low_battery: 0.2,
+        }
+    }
+}
+
+/// 背包与拾取参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InventoryBalance {
+    /// 背包格数
+    pub capacity: usize,
+    /// 拾取半径
+    pub pickup_radius: f32,
+    /// 随机放置的拾取物与玩家出生点的最小距离
+    pub pickup_min_distance: f32,
+    /// 每个关卡随机放置的拾取物（物品名称 → 数量）
+    pub level_pickups: BTreeMap<String, u32>,
+}
+
+impl Default for InventoryBalance {
+    fn default() -> Self {
+        Self {
+            capacity: 8,
+            pickup_radius: 1.2,
+            pickup_min_distance: 10.0,
+            level_pickups: BTreeMap::from([
+                ("battery".to_string(), 6),
+                ("medkit".to_string(), 3),
+                ("knife".to_string(), 1),
+            ]),
+        }
+    }
+}
+
+/// 交互参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct InteractionBalance {
+    /// 交互距离（到物体中心的水平距离）
+    pub range: f32,
+    /// 物体需位于玩家正前方的夹角范围内（度，全角）
+    pub facing_angle: f32,
+}
+
+impl Default for InteractionBalance {
+    fn default() -> Self {
+        Self {
+            range: 2.0,
+            facing_angle: 120.0,
+        }
+    }
+}
+
+/// 近战攻击参数
+///
+/// 装备武器时伤害取武器的 `damage`（见 `items`），否则为徒手伤害
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct MeleeBalance {
+    /// 徒手伤害
+    pub unarmed_damage: f32,
+    /// 攻击距离（到敌人中心的水平距离）
+    pub range: f32,
+    /// 敌人需位于玩家正前方的夹角范围内（度，全角）
+    pub attack_angle: f32,
+    /// 两次攻击的最小间隔（秒）
+    pub cooldown: f32,
+}
+
+impl Default for MeleeBalance {
+    fn default() -> Self {
+        Self {
+            unarmed_damage: 10.0,
+            range: 2.2,
+            attack_angle: 100.0,
+            cooldown: 0.45,
+        }
+    }
+}
+
+/// 经验值与升级参数
+///
+/// 击败敌人获得经验值（数量见 `enemy.archetypes.*.xp`）；每次升级选择一项属性提升，
+/// 属性在 `player` 的基础值上累加
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ProgressionBalance {
+    /// 从 1 级升到 2 级所需的经验值
+    pub xp_base: u32,
+    /// 之后每级所需经验值的倍率
+    pub xp_growth: f32,
+    /// 等级上限
+    pub max_level: u32,
+    /// 每次选择移动速度提升的速度
+    pub speed_bonus: f32,
+    /// 每次选择生命值提升的生命值上限（同时恢复同样多的生命值）
+    pub max_health_bonus: f32,
+    /// 跳跃次数上限（`max_jump_count` 提升到此为止）
+    pub max_jump_count: u32,
+}
+
+impl Default for ProgressionBalance {
+    fn default() -> Self {
+        Self {
+            xp_base: 50,
+            xp_growth: 1.4,
+            max_level: 20,
+            speed_bonus: 0.5,
+            max_health_bonus: 20.0,
+            max_jump_count: 4,
+        }
+    }
+}
+
+/// 角色控制器参数（玩家与敌人共用）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct CharacterBalance {
+    /// 胶囊半径
+    pub radius: f32,
+    /// 胶囊总高度
+    pub height: f32,
+    /// 脚底相对模型原点的高度
+    pub foot_offset: f32,
+    /// 可自动跨上的台阶高度
+    pub step_height: f32,
+    /// 可行走的最大坡度（度）
+    pub max_slope_angle: f32,
+    /// 与碰撞表面保持的间隙
+    pub skin_width: f32,
+    /// 贴地检测距离
+    pub snap_distance: f32,
+    /// 重力加速度
+    pub gravity: f32,
+}
+
+impl Default for CharacterBalance {
+    fn default() -> Self {
+        Self {
+            radius: 0.5,
+            height: 1.8,
+            foot_offset: -0.5,
+            step_height: 0.4,
+            max_slope_angle: 45.0,
+            skin_width: 0.02,
+            snap_distance: 0.3,
+            gravity: -9.8,
         }
     }
 }
@@ -64,6 +384,16 @@ pub struct EnemyBalance {
     pub ai: EnemyAIBalance,
     /// 检测参数
     pub detection: EnemyDetectionBalance,
+    /// 群体转向参数
+    pub steering: EnemySteeringBalance,
+    /// 小队与警报参数
+    pub squad: EnemySquadBalance,
+    /// AI 细节层级参数
+    pub lod: EnemyLodBalance,
+    /// 敌人波次参数
+    pub encounter: EnemyEncounterBalance,
+    /// 敌人原型（关卡文件按名称引用）
+    pub archetypes: BTreeMap<String, EnemyArchetypeBalance>,
 }
 
 impl Default for EnemyBalance {
@@ -71,6 +401,11 @@ impl Default for EnemyBalance {
         Self {
             ai: EnemyAIBalance::default(),
             detection: EnemyDetectionBalance::default(),
+            steering: EnemySteeringBalance::default(),
+            squad: EnemySquadBalance::default(),
+            lod: EnemyLodBalance::default(),
+            encounter: EnemyEncounterBalance::default(),
+            archetypes: default_archetypes(),
         }
     }
 }
@@ -84,6 +419,18 @@ pub struct EnemyAIBalance {
     pub stop_distance: f32,
     /// 旋转速度（朝向目标的速度）
     pub rotation_speed: f32,
+    /// 攻击范围
+    pub attack_range: f32,
+    /// 攻击间隔（秒）
+    pub attack_interval: f32,
+    /// 失去目标后的搜索时长（秒）
+    pub search_duration: f32,
+    /// 包抄时绕到目标侧面的距离
+    pub flank_distance: f32,
+    /// 巡逻半径（以出生点为中心）
+    pub patrol_radius: f32,
+    /// 警戒者与目标保持的距离
+    pub watch_distance: f32,
 }
 
 impl Default for EnemyAIBalance {
@@ -92,6 +439,12 @@ impl Default for EnemyAIBalance {
             move_speed: 3.5,
             stop_distance: 1.5,
             rotation_speed: 10.0,
+            attack_range: 2.0,
+            attack_interval: 1.2,
+            search_duration: 4.0,
+            flank_distance: 6.0,
+            patrol_radius: 5.0,
+            watch_distance: 9.0,
         }
     }
 }
@@ -105,6 +458,8 @@ pub struct EnemyDetectionBalance {
     pub lose_target_range: f32,
     /// 视野角度（度数，未来扩展用）
     pub field_of_view: f32,
+    /// 完全入夜时检测与失去目标范围的倍率（白天为 1，晨昏之间插值）
+    pub night_range_multiplier: f32,
 }
 
 impl Default for EnemyDetectionBalance {
@@ -113,6 +468,521 @@ impl Default for EnemyDetectionBalance {
             detection_range: 17.5,
             lose_target_range: 25.0,
             field_of_view: 360.0,
+            night_range_multiplier: 0.5,
+        }
+    }
+}
+
+/// 敌人群体转向参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnemySteeringBalance {
+    /// 邻居感知半径
+    pub neighbor_radius: f32,
+    /// 避让预测时间窗口（秒）
+    pub time_horizon: f32,
+    /// 预测避让权重
+    pub avoidance_weight: f32,
+    /// 分离权重
+    pub separation_weight: f32,
+    /// 对齐权重
+    pub alignment_weight: f32,
+    /// 聚合权重
+    pub cohesion_weight: f32,
+    /// 最大加速度
+    pub max_acceleration: f32,
+    /// 包围站位的相邻间距
+    pub slot_spacing: f32,
+    /// 距离目标多近时开始分配包围站位
+    pub slot_engage_distance: f32,
+}
+
+impl Default for EnemySteeringBalance {
+    fn default() -> Self {
+        Self {
+            neighbor_radius: 4.0,
+            time_horizon: 1.5,
+            avoidance_weight: 1.0,
+            separation_weight: 1.5,
+            alignment_weight: 0.3,
+            cohesion_weight: 0.1,
+            max_acceleration: 25.0,
+            slot_spacing: 1.6,
+            slot_engage_distance: 8.0,
+        }
+    }
+}
+
+/// 敌人小队与警报参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnemySquadBalance {
+    /// 出生时相距多近的敌人编为同一小队
+    pub squad_radius: f32,
+    /// 警报传播半径
+    pub alert_radius: f32,
+    /// 发现目标到通知同伴的延迟（秒）
+    pub alert_delay: f32,
+    /// 警报最多转发次数
+    pub max_relay_hops: u32,
+}
+
+impl Default for EnemySquadBalance {
+    fn default() -> Self {
+        Self {
+            squad_radius: 15.0,
+            alert_radius: 12.0,
+            alert_delay: 0.6,
+            max_relay_hops: 2,
+        }
+    }
+}
+
+/// 敌人 AI 细节层级参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnemyLodBalance {
+    /// 近处范围（此范围内每帧更新）
+    pub near_distance: f32,
+    /// 远处范围（没有相机时超出后进入休眠，有相机时按视野判定）
+    pub far_distance: f32,
+    /// 远处敌人的更新间隔（秒）
+    pub far_interval: f32,
+    /// 休眠敌人的更新间隔（秒）
+    pub dormant_interval: f32,
+    /// 每帧最多更新的非近处敌人数量
+    pub max_updates_per_frame: usize,
+}
+
+impl Default for EnemyLodBalance {
+    fn default() -> Self {
+        Self {
+            near_distance: 25.0,
+            far_distance: 60.0,
+            far_interval: 0.25,
+            dormant_interval: 1.0,
+            max_updates_per_frame: 64,
+        }
+    }
+}
+
+/// 敌人波次参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnemyEncounterBalance {
+    /// 开始游戏后第一波的延迟（秒）
+    pub initial_delay: f32,
+    /// 场上敌人清空后，下一波最多再等待多久（秒）
+    pub clear_delay: f32,
+    /// 玩家周围不生成敌人的半径
+    pub exclusion_radius: f32,
+    /// 生成点与地面边界的最小距离
+    pub spawn_margin: f32,
+    /// 同一小组敌人围绕生成点的散布半径
+    pub group_spread: f32,
+    /// 场上敌人数量上限
+    pub max_alive: u32,
+    /// 波次表，按顺序生成
+    pub waves: Vec<WaveBalance>,
+    /// 波次表用完后，每波在最后一波基础上增加的敌人数量
+    pub endless_growth: u32,
+    /// 强度系数下限（玩家吃力时降低敌人数量）
+    pub min_intensity: f32,
+    /// 强度系数上限（玩家轻松清场时增加敌人数量）
+    pub max_intensity: f32,
+    /// 每次调整强度系数的幅度
+    pub intensity_step: f32,
+    /// 每击败一个敌人的得分
+    pub score_per_kill: u32,
+    /// 每撑过一波的得分
+    pub score_per_wave: u32,
+}
+
+/// 单个波次
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct WaveBalance {
+    /// 上一波生成后多久生成本波（秒）
+    pub delay: f32,
+    /// 敌人数量（按强度系数缩放）
+    pub count: u32,
+    /// 分成几个小组从不同位置出现
+    pub groups: u32,
+}
+
+impl Default for EnemyEncounterBalance {
+    fn default() -> Self {
+        Self {
+            initial_delay: 3.0,
+            clear_delay: 5.0,
+            exclusion_radius: 20.0,
+            spawn_margin: 2.0,
+            group_spread: 4.0,
+            max_alive: 24,
+            waves: vec![
+                WaveBalance {
+                    delay: 0.0,
+                    count: 3,
+                    groups: 1,
+                },
+                WaveBalance {
+                    delay: 30.0,
+                    count: 5,
+                    groups: 2,
+                },
+                WaveBalance {
+                    delay: 30.0,
+                    count: 7,
+                    groups: 2,
+                },
+                WaveBalance {
+                    delay: 35.0,
+                    count: 9,
+                    groups: 3,
+                },
+                WaveBalance {
+                    delay: 40.0,
+                    count: 12,
+                    groups: 3,
+                },
+            ],
+            endless_growth: 2,
+            min_intensity: 0.6,
+            max_intensity: 1.6,
+            intensity_step: 0.15,
+            score_per_kill: 100,
+            score_per_wave: 250,
+        }
+    }
+}
+
+/// 敌人原型
+///
+/// 倍率作用于 `enemy.ai.move_speed` 与 `enemy.detection` 的距离参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct EnemyArchetypeBalance {
+    /// 移动速度倍率
+    pub speed_multiplier: f32,
+    /// 检测距离倍率
+    pub detection_multiplier: f32,
+    /// 最大生命值
+    pub health: f32,
+    /// 模型与碰撞半径缩放
+    pub scale: f32,
+    /// 击败后玩家获得的经验值
+    pub xp: u32,
+}
+
+impl Default for EnemyArchetypeBalance {
+    fn default() -> Self {
+        Self {
+            speed_multiplier: 1.0,
+            detection_multiplier: 1.0,
+            health: 100.0,
+            scale: 1.0,
+            xp: 10,
+        }
+    }
+}
+
+/// 默认原型：普通（grunt）、侦察（scout）、重型（brute）
+fn default_archetypes() -> BTreeMap<String, EnemyArchetypeBalance> {
+    BTreeMap::from([
+        ("grunt".to_string(), EnemyArchetypeBalance::default()),
+        (
+            "scout".to_string(),
+            EnemyArchetypeBalance {
+                speed_multiplier: 1.3,
+                detection_multiplier: 1.4,
+                health: 60.0,
+                scale: 0.9,
+                xp: 15,
+            },
+        ),
+        (
+            "brute".to_string(),
+            EnemyArchetypeBalance {
+                speed_multiplier: 0.75,
+                detection_multiplier: 0.9,
+                health: 200.0,
+                scale: 1.3,
+                xp: 30,
+            },
+        ),
+    ])
+}
+
+/// 物品定义
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ItemBalance {
+    /// 物品类型与效果
+    pub kind: ItemKind,
+    /// 每格最多堆叠的数量
+    pub max_stack: u32,
+    /// 拾取物颜色（sRGB）
+    pub color: [f32; 3],
+    /// 武器伤害（装备在武器槽时生效）
+    #[serde(default)]
+    pub damage: f32,
+}
+
+/// 物品类型
+#[derive(Debug, Clone, Copy, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ItemKind {
+    /// 消耗品：恢复生命值
+    Heal { amount: f32 },
+    /// 消耗品：恢复手电筒电量
+    Recharge { amount: f32 },
+    /// 装备：占用一个装备槽
+    Equipment { slot: EquipmentSlot },
+}
+
+/// 装备槽
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum EquipmentSlot {
+    /// 武器（手持）
+    Weapon,
+}
+
+/// 关卡目标
+///
+/// 程序化关卡使用 `level.objectives`，关卡文件可以给出自己的目标列表
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all = "snake_case")]
+pub enum ObjectiveKind {
+    /// 击败敌人（指定原型时只计该原型）
+    Kill {
+        count: u32,
+        #[serde(default)]
+        archetype: Option<String>,
+    },
+    /// 到达区域（XZ 圆心与半径）
+    Reach { position: [f32; 2], radius: f32 },
+    /// 收集物品（拾取或从宝箱取出，使用掉的也计入）
+    Collect { item: String, count: u32 },
+    /// 坚持一段时间（秒）
+    Survive { seconds: f32 },
+}
+
+/// 默认物品：医疗包（medkit）、电池（battery）、小刀（knife）
+fn default_items() -> BTreeMap<String, ItemBalance> {
+    BTreeMap::from([
+        (
+            "medkit".to_string(),
+            ItemBalance {
+                kind: ItemKind::Heal { amount: 40.0 },
+                max_stack: 5,
+                color: [0.85, 0.2, 0.2],
+                damage: 0.0,
+            },
+        ),
+        (
+            "battery".to_string(),
+            ItemBalance {
+                kind: ItemKind::Recharge { amount: 40.0 },
+                max_stack: 10,
+                color: [0.95, 0.8, 0.2],
+                damage: 0.0,
+            },
+        ),
+        (
+            "knife".to_string(),
+            ItemBalance {
+                kind: ItemKind::Equipment {
+                    slot: EquipmentSlot::Weapon,
+                },
+                max_stack: 1,
+                color: [0.75, 0.75, 0.8],
+                damage: 35.0,
+            },
+        ),
+    ])
+}
+
+/// 程序化关卡生成参数
+///
+/// 范围参数为 `[最小值, 最大值]`
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct LevelBalance {
+    /// 墙体数量（每道墙中间留有门洞）
+    pub wall_count: u32,
+    /// 墙体总长度范围
+    pub wall_length: [f32; 2],
+    /// 墙体厚度
+    pub wall_thickness: f32,
+    /// 墙体高度
+    pub wall_height: f32,
+    /// 墙体门洞宽度
+    pub doorway_width: f32,
+    /// 障碍物（柱子、岩石）数量
+    pub obstacle_count: u32,
+    /// 障碍物半尺寸范围
+    pub obstacle_size: [f32; 2],
+    /// 障碍物高度范围
+    pub obstacle_height: [f32; 2],
+    /// 障碍物为圆柱（柱子）的概率，其余为方块
+    pub pillar_chance: f64,
+    /// 道具（箱子等矮物体）数量
+    pub prop_count: u32,
+    /// 道具半尺寸范围
+    pub prop_size: [f32; 2],
+    /// 道具高度范围
+    pub prop_height: [f32; 2],
+    /// 物体之间、物体与边界之间保留的最小通道宽度
+    pub clearance: f32,
+    /// 玩家出生点周围保持空旷的半径
+    pub spawn_clear_radius: f32,
+    /// 可行走区域网格的格子尺寸
+    pub cell_size: f32,
+    /// 计算可行走区域时物体外扩的半径（角色半径）
+    pub agent_radius: f32,
+    /// 敌人生成点数量
+    pub enemy_spawn_count: u32,
+    /// 敌人生成点与玩家出生点的最小距离
+    pub enemy_spawn_min_distance: f32,
+    /// 敌人生成点之间的最小间距
+    pub enemy_spawn_spacing: f32,
+    /// 地形参数
+    pub terrain: TerrainBalance,
+    /// 分块加载参数
+    pub streaming: StreamingBalance,
+    /// 昼夜循环参数
+    pub day_night: DayNightBalance,
+    /// 程序化关卡的目标（全部完成即通关）
+    pub objectives: Vec<ObjectiveKind>,
+}
+
+/// 地形参数
+///
+/// 程序化关卡使用分形值噪声生成高度图；关卡文件指定噪声种子时也使用这些参数
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TerrainBalance {
+    /// 高度图格子尺寸
+    pub cell_size: f32,
+    /// 起伏幅度（高度在基准面上下该范围内）
+    pub amplitude: f32,
+    /// 最大一层起伏的波长
+    pub wavelength: f32,
+    /// 噪声层数（每层波长与幅度减半）
+    pub octaves: u32,
+    /// 计入可行走区域的最大坡度（度）
+    pub max_slope: f32,
+    /// 玩家出生点周围压平的半径
+    pub flat_radius: f32,
+}
+
+impl Default for TerrainBalance {
+    fn default() -> Self {
+        Self {
+            cell_size: 2.0,
+            amplitude: 2.5,
+            wavelength: 48.0,
+            octaves: 3,
+            max_slope: 35.0,
+            flat_radius: 6.0,
+        }
+    }
+}
+
+/// 分块加载参数
+///
+/// 地形与物体按正方形区块划分，玩家附近与相机视野内的区块加载，远离后卸载；
+/// 卸载半径大于加载半径，避免在边界处反复加载
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StreamingBalance {
+    /// 区块边长
+    pub chunk_size: f32,
+    /// 区块与玩家距离小于该值时加载
+    pub load_radius: f32,
+    /// 区块与玩家距离大于该值（且不在视野内）时卸载
+    pub unload_radius: f32,
+    /// 相机视野范围在地面上的外扩距离
+    pub frustum_margin: f32,
+}
+
+impl Default for StreamingBalance {
+    fn default() -> Self {
+        Self {
+            chunk_size: 32.0,
+            load_radius: 48.0,
+            unload_radius: 64.0,
+            frustum_margin: 8.0,
+        }
+    }
+}
+
+/// 昼夜循环参数
+///
+/// 太阳在 6 点从东侧升起、18 点从西侧落下，正午高度与方位取关卡的太阳光设置；
+/// 关卡太阳光与环境光为正午时的值，夜间插值到月光与夜间环境光
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct DayNightBalance {
+    /// 一整天（24 小时）的时长（秒）
+    pub day_length: f32,
+    /// 关卡开始时的时刻（小时，关卡文件可覆盖）
+    pub start_hour: f32,
+    /// 晨昏过渡的宽度（太阳高度的正弦值，地平线上下各该范围内插值）
+    pub twilight: f32,
+    /// 日出日落时太阳光的颜色（sRGB）
+    pub dusk_color: [f32; 3],
+    /// 月光颜色（sRGB）
+    pub moon_color: [f32; 3],
+    /// 月光照度
+    pub moon_illuminance: f32,
+    /// 夜间环境光颜色（sRGB）
+    pub night_ambient_color: [f32; 3],
+    /// 夜间环境光亮度
+    pub night_ambient_brightness: f32,
+}
+
+impl Default for DayNightBalance {
+    fn default() -> Self {
+        Self {
+            day_length: 600.0,
+            start_hour: 8.0,
+            twilight: 0.15,
+            dusk_color: [1.0, 0.55, 0.3],
+            moon_color: [0.55, 0.65, 1.0],
+            moon_illuminance: 150.0,
+            night_ambient_color: [0.4, 0.45, 0.8],
+            night_ambient_brightness: 15.0,
+        }
+    }
+}
+
+impl Default for LevelBalance {
+    fn default() -> Self {
+        Self {
+            wall_count: 6,
+            wall_length: [10.0, 26.0],
+            wall_thickness: 1.0,
+            wall_height: 3.0,
+            doorway_width: 4.0,
+            obstacle_count: 12,
+            obstacle_size: [1.0, 2.5],
+            obstacle_height: [2.0, 4.0],
+            pillar_chance: 0.5,
+            prop_count: 20,
+            prop_size: [0.5, 0.9],
+            prop_height: [0.6, 1.2],
+            clearance: 2.5,
+            spawn_clear_radius: 8.0,
+            cell_size: 1.0,
+            agent_radius: 0.6,
+            enemy_spawn_count: 24,
+            enemy_spawn_min_distance: 25.0,
+            enemy_spawn_spacing: 6.0,
+            terrain: TerrainBalance::default(),
+            streaming: StreamingBalance::default(),
+            day_night: DayNightBalance::default(),
+            objectives: vec![
+                ObjectiveKind::Collect {
+                    item: "battery".to_string(),
+                    count: 3,
+                },
+                ObjectiveKind::Kill {
+                    count: 5,
+                    archetype: None,
+                },
+                ObjectiveKind::Survive { seconds: 180.0 },
+            ],
         }
     }
 }
@@ -131,7 +1001,7 @@ impl Plugin for BalancePlugin {
 }
 
 /// 加载平衡配置系统
-fn load_balance_config(mut config: ResMut<BalanceConfig>) {
+pub(crate) fn load_balance_config(mut config: ResMut<BalanceConfig>) {
     // 尝试从文件加载配置
     match load_balance_from_file() {
         Ok(loaded_config) => {
@@ -188,6 +1058,20 @@ mod tests {
         assert_eq!(config.enemy.detection.detection_range, 17.5);
     }
 
+    #[test]
+    fn test_default_items() {
+        let config = BalanceConfig::default();
+        assert_eq!(
+            config.items["knife"].kind,
+            ItemKind::Equipment {
+                slot: EquipmentSlot::Weapon
+            }
+        );
+        for item in config.player.inventory.level_pickups.keys() {
+            assert!(config.items.contains_key(item), "{item}");
+        }
+    }
+
     #[test]
     fn test_serialization() {
         let config = BalanceConfig::default();
@@ -6,17 +6,22 @@
 //! - 存档系统（Save）
 //! - 加密系统（Crypto）
 //! - 平衡配置系统（Balance）
+//! - 可复现的随机数（Rng）
+//! - 命令行参数（Args）
 
 #![allow(clippy::type_complexity)]
 
+pub mod args;
 pub mod balance;
 pub mod crypto;
 pub mod localization;
+pub mod rng;
 pub mod save;
 pub mod state;
 
 // 重新导出常用类型
 pub use balance::{BalanceConfig, BalancePlugin};
 pub use localization::LocalizationPlugin;
+pub use rng::{GameRng, RngPlugin};
 pub use save::{SaveManager, SavePlugin};
 pub use state::{GameState, StatePlugin};
@@ -201,6 +201,8 @@ impl TranslationResources {
                 let lang_id = language.lang_id();
                 let mut bundle: fluent::bundle::FluentBundle<FluentResource, IntlLangMemoizer> =
                     fluent::bundle::FluentBundle::new_concurrent(vec![lang_id.clone()]);
+                // 参数直接嵌入界面文本，不需要双向文本隔离符
+                bundle.set_use_isolating(false);
 
                 // 添加所有资源到 bundle
                 for resource in resources {
@@ -473,6 +475,58 @@ pub mod keys {
     pub const GAME_RESUME: &str = "game-resume";
     pub const GAME_BACK_TO_MENU: &str = "game-back-to-menu";
 
+    // HUD
+    pub const HUD_WAVE: &str = "hud-wave";
+    pub const HUD_SCORE: &str = "hud-score";
+    pub const HUD_NEXT_WAVE: &str = "hud-next-wave";
+
+    // 背包
+    pub const INVENTORY_TITLE: &str = "inventory-title";
+    pub const INVENTORY_SLOT: &str = "inventory-slot";
+    pub const INVENTORY_EMPTY_SLOT: &str = "inventory-empty-slot";
+    pub const INVENTORY_WEAPON: &str = "inventory-weapon";
+    pub const INVENTORY_NOTHING: &str = "inventory-nothing";
+    /// 物品名称的键前缀（`item-<物品名称>`）
+    pub const ITEM_PREFIX: &str = "item-";
+
+    // 交互
+    pub const HUD_INTERACT: &str = "hud-interact";
+    pub const INTERACT_OPEN_DOOR: &str = "interact-open-door";
+    pub const INTERACT_CLOSE_DOOR: &str = "interact-close-door";
+    pub const INTERACT_DOOR_LOCKED: &str = "interact-door-locked";
+    pub const INTERACT_USE_SWITCH: &str = "interact-use-switch";
+    pub const INTERACT_OPEN_CHEST: &str = "interact-open-chest";
+    pub const INTERACT_CHEST_EMPTY: &str = "interact-chest-empty";
+    pub const INTERACT_READ_NOTE: &str = "interact-read-note";
+    pub const NOTE_CLOSE_HINT: &str = "note-close-hint";
+
+    // 关卡目标
+    pub const HUD_OBJECTIVES: &str = "hud-objectives";
+    pub const OBJECTIVE_DONE: &str = "objective-done";
+    pub const OBJECTIVE_PENDING: &str = "objective-pending";
+    pub const OBJECTIVE_KILL: &str = "objective-kill";
+    pub const OBJECTIVE_KILL_ARCHETYPE: &str = "objective-kill-archetype";
+    pub const OBJECTIVE_REACH: &str = "objective-reach";
+    pub const OBJECTIVE_COLLECT: &str = "objective-collect";
+    pub const OBJECTIVE_SURVIVE: &str = "objective-survive";
+    /// 敌人原型名称的键前缀（`enemy-<原型名称>`）
+    pub const ENEMY_PREFIX: &str = "enemy-";
+
+    // 通关
+    pub const LEVEL_COMPLETE_TITLE: &str = "level-complete-title";
+    pub const LEVEL_COMPLETE_SUMMARY: &str = "level-complete-summary";
+    pub const LEVEL_COMPLETE_NEXT: &str = "level-complete-next";
+
+    // 经验值与升级
+    pub const HUD_LEVEL: &str = "hud-level";
+    pub const HUD_LEVEL_MAX: &str = "hud-level-max";
+    pub const LEVEL_UP_TITLE: &str = "level-up-title";
+    pub const LEVEL_UP_CHOICES: &str = "level-up-choices";
+    pub const UPGRADE_SPEED: &str = "upgrade-speed";
+    pub const UPGRADE_MAX_HEALTH: &str = "upgrade-max-health";
+    pub const UPGRADE_JUMP_COUNT: &str = "upgrade-jump-count";
+    pub const UPGRADE_JUMP_COUNT_MAXED: &str = "upgrade-jump-count-maxed";
+
     // 设置
     pub const SETTINGS_TITLE: &str = "settings-title";
     pub const SETTINGS_APPEARANCE: &str = "settings-appearance";
@@ -492,6 +546,12 @@ pub mod keys {
     pub const SETTINGS_VSYNC: &str = "settings-vsync";
     pub const SETTINGS_DIFFICULTY: &str = "settings-difficulty";
     pub const SETTINGS_AUTO_SAVE: &str = "settings-auto-save";
+    pub const SETTINGS_AUTO_FACE: &str = "settings-auto-face";
+    pub const SETTINGS_LOCK_MODE: &str = "settings-lock-mode";
+    pub const SETTINGS_LOCK_MODE_SOFT: &str = "settings-lock-mode-soft";
+    pub const SETTINGS_LOCK_MODE_HARD: &str = "settings-lock-mode-hard";
+    pub const SETTINGS_ON: &str = "settings-on";
+    pub const SETTINGS_OFF: &str = "settings-off";
     pub const SETTINGS_MOUSE_SENSITIVITY: &str = "settings-mouse-sensitivity";
     pub const SETTINGS_INVERT_Y: &str = "settings-invert-y";
     pub const SETTINGS_KEY_BINDINGS: &str = "settings-key-bindings";
@@ -0,0 +1,217 @@
+//! 可复现的随机数
+//!
+//! 全局只有一个种子（`GameRng`），各系统按名称从中分出独立的随机流：
+//! - 每个流的种子由全局种子、流名称和该流的取用次数共同决定
+//! - 在一个系统里增加随机调用不会影响其他系统的序列
+//! - 存档保存种子和各流的取用次数，读档后可确定性地继续
+//!
+//! 种子来源（优先级从高到低）：
+//! - 命令行参数 `--seed <n>` 或 `--seed=<n>`
+//! - 环境变量 `VIGILANT_SEED`
+//! - 平衡配置中的 `seed_override`（`assets/balance.json`）：随游戏数据发布的覆盖，不是玩家设置
+//! - 系统熵（随机）
+//!
+//! 读档时以存档中的种子覆盖。
+
+use bevy::prelude::*;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use std::collections::BTreeMap;
+
+use crate::args;
+use crate::balance::{load_balance_config, BalanceConfig};
+
+/// 种子环境变量
+pub const SEED_ENV_VAR: &str = "VIGILANT_SEED";
+/// 种子命令行参数
+pub const SEED_ARG: &str = "--seed";
+
+/// 全局随机数资源
+#[derive(Resource, Debug, Clone, PartialEq, Eq)]
+pub struct GameRng {
+    /// 全局种子
+    seed: u64,
+    /// 各随机流已取用的次数
+    streams: BTreeMap<String, u64>,
+}
+
+impl GameRng {
+    /// 使用指定种子创建
+    pub fn from_seed(seed: u64) -> Self {
+        Self {
+            seed,
+            streams: BTreeMap::new(),
+        }
+    }
+
+    /// 从已保存的状态恢复
+    pub fn restore(seed: u64, streams: impl IntoIterator<Item = (String, u64)>) -> Self {
+        Self {
+            seed,
+            streams: streams.into_iter().collect(),
+        }
+    }
+
+    /// 全局种子
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// 各随机流已取用的次数（用于存档）
+    pub fn streams(&self) -> impl Iterator<Item = (&str, u64)> {
+        self.streams
+            .iter()
+            .map(|(name, count)| (name.as_str(), *count))
+    }
+
+    /// 从名为 `name` 的流中取出下一个随机数生成器
+    ///
+    /// 每次调用返回一个新的生成器，其序列只取决于全局种子、流名称和调用次数
+    pub fn stream(&mut self, name: &str) -> StdRng {
+        let count = self.streams.entry(name.to_string()).or_insert(0);
+        let seed = derive_seed(self.seed, name, *count);
+        *count += 1;
+        StdRng::seed_from_u64(seed)
+    }
+}
+
+/// 由全局种子、流名称和序号派生子种子（FNV-1a + SplitMix64，跨平台、跨版本稳定）
+fn derive_seed(seed: u64, name: &str, index: u64) -> u64 {
+    let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+    for byte in name.bytes() {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+    }
+    splitmix64(splitmix64(seed ^ hash) ^ index)
+}
+
+fn splitmix64(mut x: u64) -> u64 {
+    x = x.wrapping_add(0x9e37_79b9_7f4a_7c15);
+    x = (x ^ (x >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+    x = (x ^ (x >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+    x ^ (x >> 31)
+}
+
+/// 从命令行参数或环境变量读取种子
+fn seed_from_env() -> Option<u64> {
+    if let Some(value) = args::value(SEED_ARG) {
+        match value.parse() {
+            Ok(seed) => return Some(seed),
+            Err(e) => warn!("[Rng] 无效的种子参数 {:?}: {}", value, e),
+        }
+    }
+
+    let value = std::env::var(SEED_ENV_VAR).ok()?;
+    match value.parse() {
+        Ok(seed) => Some(seed),
+        Err(e) => {
+            warn!("[Rng] 无效的种子环境变量 {:?}: {}", value, e);
+            None
+        }
+    }
+}
+
+/// 随机数插件
+pub struct RngPlugin;
+
+impl Plugin for RngPlugin {
+    fn build(&self, app: &mut App) {
+        // 配置文件在 Startup 阶段才加载，先插入资源，加载后再按优先级确定种子
+        let env_seed = seed_from_env();
+        app.insert_resource(GameRng::from_seed(env_seed.unwrap_or_else(rand::random)))
+            .add_systems(
+                Startup,
+                (move |rng: ResMut<GameRng>, balance: Res<BalanceConfig>| {
+                    apply_configured_seed(env_seed, rng, balance)
+                })
+                .after(load_balance_config),
+            );
+    }
+}
+
+/// 命令行和环境变量都未指定种子时，使用平衡配置中的种子覆盖
+fn apply_configured_seed(
+    env_seed: Option<u64>,
+    mut rng: ResMut<GameRng>,
+    balance: Res<BalanceConfig>,
+) {
+    if let (None, Some(seed)) = (env_seed, balance.seed_override) {
+        *rng = GameRng::from_seed(seed);
+    }
+    info!("[Rng] 随机种子: {}", rng.seed());
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::Rng;
+
+    fn draw(rng: &mut GameRng, name: &str) -> u64 {
+        rng.stream(name).random()
+    }
+
+    #[test]
+    fn test_streams_are_independent() {
+        let mut a = GameRng::from_seed(42);
+        let mut b = GameRng::from_seed(42);
+
+        // b 在另一个流里多取一次，不影响 encounter 流
+        draw(&mut b, "ai");
+        let from_a: Vec<u64> = (0..3).map(|_| draw(&mut a, "encounter")).collect();
+        let from_b: Vec<u64> = (0..3).map(|_| draw(&mut b, "encounter")).collect();
+        assert_eq!(from_a, from_b);
+
+        // 同一流的后续取用、不同的种子都会得到不同的序列
+        assert_ne!(from_a[0], from_a[1]);
+        assert_ne!(
+            draw(&mut GameRng::from_seed(43), "encounter"),
+            draw(&mut GameRng::from_seed(42), "encounter")
+        );
+    }
+
+    #[test]
+    fn test_balance_seed_override_used_without_cli_or_env() {
+        let mut app = App::new();
+        app.insert_resource(BalanceConfig {
+            seed_override: Some(1234),
+            ..default()
+        })
+        .add_plugins(RngPlugin);
+        app.update();
+        assert_eq!(app.world().resource::<GameRng>().seed(), 1234);
+
+        // 命令行或环境变量指定的种子优先
+        let mut app = App::new();
+        app.insert_resource(GameRng::from_seed(7))
+            .insert_resource(BalanceConfig {
+                seed_override: Some(1234),
+                ..default()
+            })
+            .add_systems(
+                Update,
+                |rng: ResMut<GameRng>, balance: Res<BalanceConfig>| {
+                    apply_configured_seed(Some(7), rng, balance)
+                },
+            );
+        app.update();
+        assert_eq!(app.world().resource::<GameRng>().seed(), 7);
+    }
+
+    #[test]
+    fn test_restore_continues_sequence() {
+        let mut original = GameRng::from_seed(7);
+        draw(&mut original, "encounter");
+        draw(&mut original, "level");
+
+        let saved: Vec<(String, u64)> = original
+            .streams()
+            .map(|(name, count)| (name.to_string(), count))
+            .collect();
+        let mut restored = GameRng::restore(original.seed(), saved);
+        assert_eq!(restored, original);
+        assert_eq!(
+            draw(&mut restored, "encounter"),
+            draw(&mut original, "encounter")
+        );
+    }
+}
@@ -8,6 +8,8 @@ pub enum GameState {
     MainMenu, // 主菜单（游戏场景 + 模糊遮罩 + UI）
     Playing,  // 游戏进行
     Paused,   // 游戏暂停（显示暂停菜单）
+    LevelComplete, // 关卡目标全部完成（显示通关界面）
+    LevelUp,       // 玩家升级（显示属性选择界面）
 }
 
 /// 游戏进度追踪（用于区分首次启动和游戏中暂停）
@@ -15,6 +17,8 @@ pub enum GameState {
 pub struct GameProgress {
     /// 是否有游戏正在进行（从 MainMenu 进入过 Playing）
     pub has_active_game: bool,
+    /// 已通关的关卡数
+    pub levels_completed: u32,
 }
 
 /// 状态机插件
@@ -30,7 +34,9 @@ impl Plugin for StatePlugin {
                 OnEnter(GameState::Playing),
                 (log_enter_playing, mark_game_active),
             )
-            .add_systems(OnEnter(GameState::Paused), log_enter_paused);
+            .add_systems(OnEnter(GameState::Paused), log_enter_paused)
+            .add_systems(OnEnter(GameState::LevelComplete), log_enter_level_complete)
+            .add_systems(OnEnter(GameState::LevelUp), log_enter_level_up);
     }
 }
 
@@ -50,6 +56,14 @@ fn log_enter_paused() {
     info!("[State] → Paused");
 }
 
+fn log_enter_level_complete() {
+    info!("[State] → LevelComplete");
+}
+
+fn log_enter_level_up() {
+    info!("[State] → LevelUp");
+}
+
 /// 标记游戏为活跃状态（进入 Playing 时）
 fn mark_game_active(mut progress: ResMut<GameProgress>) {
     progress.has_active_game = true;
@@ -0,0 +1,275 @@
+//! AI 调试工具
+//!
+//! F3 开关敌人 AI 调试可视化：
+//! - Gizmos 绘制检测范围、视野、路径等（由 `AiDebugPlugin` 提供）
+//! - 敌人头顶显示当前状态标签
+//! - 启用 `inspector` 特性时，额外显示列出所有敌人黑板状态的 egui 面板
+
+use bevy::ecs::entity::EntityHashSet;
+use bevy::prelude::*;
+use vigilant_doodle_ai::{
+    AiDebugOverlay, AiDebugPlugin, AiLod, Blackboard, EnemyAIState, debug_label,
+};
+use vigilant_doodle_assets::GameAssets;
+use vigilant_doodle_gameplay::Enemy;
+
+/// 调试可视化开关键
+const TOGGLE_KEY: KeyCode = KeyCode::F3;
+/// 标签相对敌人位置的高度
+const LABEL_HEIGHT: f32 = 2.4;
+/// 标签字号
+const LABEL_FONT_SIZE: f32 = 14.0;
+/// 标签水平偏移（使文字大致居中于敌人头顶）
+const LABEL_OFFSET_X: f32 = 24.0;
+
+/// 状态标签（UI 节点，跟随对应敌人）
+#[derive(Component)]
+struct AiDebugLabel {
+    enemy: Entity,
+}
+
+pub struct AiDebugToolsPlugin;
+
+impl Plugin for AiDebugToolsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(AiDebugPlugin).add_systems(
+            Update,
+            (
+                toggle_ai_debug_overlay,
+                update_ai_debug_labels.run_if(resource_exists::<GameAssets>),
+            )
+                .chain(),
+        );
+
+        #[cfg(feature = "inspector")]
+        panel::build(app);
+    }
+}
+
+/// 按键切换调试可视化
+fn toggle_ai_debug_overlay(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<AiDebugOverlay>,
+) {
+    if keyboard.just_pressed(TOGGLE_KEY) {
+        overlay.toggle();
+    }
+}
+
+/// 更新状态标签
+///
+/// 开启时为每个敌人维护一个标签并投影到屏幕上，关闭或敌人消失时移除
+fn update_ai_debug_labels(
+    mut commands: Commands,
+    overlay: Res<AiDebugOverlay>,
+    assets: Res<GameAssets>,
+    enemy_query: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &EnemyAIState,
+            Option<&Blackboard>,
+            Option<&AiLod>,
+        ),
+        With<Enemy>,
+    >,
+    mut label_query: Query<(Entity, &AiDebugLabel, &mut Text, &mut Node, &mut Visibility)>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+) {
+    if !overlay.enabled {
+        for (label, ..) in label_query.iter() {
+            commands.entity(label).despawn();
+        }
+        return;
+    }
+
+    let camera = camera_query.single().ok();
+    let mut labeled = EntityHashSet::default();
+
+    for (label, tag, mut text, mut node, mut visibility) in label_query.iter_mut() {
+        let Ok((_, transform, state, blackboard, lod)) = enemy_query.get(tag.enemy) else {
+            commands.entity(label).despawn();
+            continue;
+        };
+        labeled.insert(tag.enemy);
+
+        let label_text = debug_label(*state, blackboard, lod);
+        if text.0 != label_text {
+            text.0 = label_text;
+        }
+
+        let screen_position = camera.and_then(|(camera, camera_transform)| {
+            camera
+                .world_to_viewport(
+                    camera_transform,
+                    transform.translation() + Vec3::Y * LABEL_HEIGHT,
+                )
+                .ok()
+        });
+        match screen_position {
+            Some(screen_position) => {
+                node.left = Val::Px(screen_position.x - LABEL_OFFSET_X);
+                node.top = Val::Px(screen_position.y);
+                *visibility = Visibility::Inherited;
+            }
+            None => *visibility = Visibility::Hidden,
+        }
+    }
+
+    for (enemy, _, state, blackboard, lod) in enemy_query.iter() {
+        if labeled.contains(&enemy) {
+            continue;
+        }
+        // 新标签先隐藏，下一帧定位后再显示
+        commands.spawn((
+            Text::new(debug_label(*state, blackboard, lod)),
+            TextFont {
+                font: assets.font.clone(),
+                font_size: LABEL_FONT_SIZE,
+                ..default()
+            },
+            TextColor(Color::srgb(0.95, 0.95, 0.95)),
+            Node {
+                position_type: PositionType::Absolute,
+                ..default()
+            },
+            Visibility::Hidden,
+            AiDebugLabel { enemy },
+        ));
+    }
+}
+
+/// 敌人黑板面板（egui）
+#[cfg(feature = "inspector")]
+mod panel {
+    use std::sync::Arc;
+
+    use bevy::prelude::*;
+    use bevy_inspector_egui::bevy_egui::{EguiContexts, EguiPlugin, EguiPrimaryContextPass, egui};
+    use vigilant_doodle_ai::{AiDebugOverlay, AiLod, Blackboard, EnemyAIState};
+    use vigilant_doodle_assets::GameAssets;
+    use vigilant_doodle_gameplay::Enemy;
+
+    /// egui 中注册的中文字体名称
+    const PANEL_FONT: &str = "game-font";
+
+    pub(super) fn build(app: &mut App) {
+        if !app.is_plugin_added::<EguiPlugin>() {
+            app.add_plugins(EguiPlugin::default());
+        }
+        app.add_systems(
+            EguiPrimaryContextPass,
+            (
+                install_panel_font.run_if(resource_exists::<GameAssets>),
+                ai_blackboard_panel.run_if(|overlay: Res<AiDebugOverlay>| overlay.enabled),
+            )
+                .chain(),
+        );
+    }
+
+    /// 将游戏字体注册为 egui 的后备字体（默认字体不含中文）
+    fn install_panel_font(
+        mut contexts: EguiContexts,
+        assets: Res<GameAssets>,
+        fonts: Res<Assets<Font>>,
+        mut installed: Local<bool>,
+    ) -> Result {
+        if *installed {
+            return Ok(());
+        }
+        let Some(font) = fonts.get(&assets.font) else {
+            return Ok(());
+        };
+
+        let mut definitions = egui::FontDefinitions::default();
+        definitions.font_data.insert(
+            PANEL_FONT.to_owned(),
+            Arc::new(egui::FontData::from_owned(font.data.as_ref().clone())),
+        );
+        for family in [egui::FontFamily::Proportional, egui::FontFamily::Monospace] {
+            definitions
+                .families
+                .entry(family)
+                .or_default()
+                .push(PANEL_FONT.to_owned());
+        }
+        contexts.ctx_mut()?.set_fonts(definitions);
+        *installed = true;
+        Ok(())
+    }
+
+    /// 列出所有敌人及其黑板状态
+    fn ai_blackboard_panel(
+        mut contexts: EguiContexts,
+        enemy_query: Query<(Entity, &EnemyAIState, &Blackboard, Option<&AiLod>), With<Enemy>>,
+    ) -> Result {
+        let ctx = contexts.ctx_mut()?;
+
+        let mut enemies: Vec<_> = enemy_query.iter().collect();
+        enemies.sort_by_key(|(entity, ..)| *entity);
+
+        egui::Window::new(format!("敌人 AI（{}）", enemies.len()))
+            .id(egui::Id::new("ai_blackboard_panel"))
+            .default_width(560.0)
+            .show(ctx, |ui| {
+                egui::ScrollArea::vertical().show(ui, |ui| {
+                    egui::Grid::new("ai_blackboard_grid")
+                        .striped(true)
+                        .show(ui, |ui| {
+                            for header in [
+                                "实体",
+                                "状态",
+                                "意图",
+                                "角色",
+                                "LOD",
+                                "目标",
+                                "距离",
+                                "未见(秒)",
+                                "最后位置",
+                                "冷却",
+                            ] {
+                                ui.strong(header);
+                            }
+                            ui.end_row();
+
+                            for (entity, state, blackboard, lod) in enemies {
+                                ui.label(format!("{entity}"));
+                                ui.label(state.display_name());
+                                ui.label(blackboard.intent.display_name());
+                                ui.label(
+                                    blackboard
+                                        .squad_role
+                                        .map_or("-", |role| role.display_name()),
+                                );
+                                ui.label(
+                                    lod.map_or("-".to_string(), |lod| format!("{:?}", lod.tier)),
+                                );
+                                ui.label(if blackboard.has_target { "是" } else { "否" });
+                                ui.label(format_finite(blackboard.distance_to_target));
+                                ui.label(format_finite(blackboard.time_since_seen));
+                                ui.label(
+                                    blackboard.last_known_position.map_or(
+                                        "-".to_string(),
+                                        |position| {
+                                            format!("({:.1}, {:.1})", position.x, position.z)
+                                        },
+                                    ),
+                                );
+                                ui.label(format!("{:.2}", blackboard.attack_cooldown));
+                                ui.end_row();
+                            }
+                        });
+                });
+            });
+        Ok(())
+    }
+
+    /// 格式化数值，无穷大（尚无目标）显示为 "-"
+    fn format_finite(value: f32) -> String {
+        if value.is_finite() {
+            format!("{value:.1}")
+        } else {
+            "-".to_string()
+        }
+    }
+}
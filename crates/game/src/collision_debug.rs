@@ -0,0 +1,28 @@
+//! 碰撞体调试工具
+//!
+//! F4 开关碰撞体调试可视化（Gizmos 绘制由 `CollisionDebugPlugin` 提供）
+
+use bevy::prelude::*;
+use vigilant_doodle_world::{CollisionDebugOverlay, CollisionDebugPlugin};
+
+/// 调试可视化开关键
+const TOGGLE_KEY: KeyCode = KeyCode::F4;
+
+pub struct CollisionDebugToolsPlugin;
+
+impl Plugin for CollisionDebugToolsPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_plugins(CollisionDebugPlugin)
+            .add_systems(Update, toggle_collision_debug_overlay);
+    }
+}
+
+/// 按键切换调试可视化
+fn toggle_collision_debug_overlay(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mut overlay: ResMut<CollisionDebugOverlay>,
+) {
+    if keyboard.just_pressed(TOGGLE_KEY) {
+        overlay.toggle();
+    }
+}
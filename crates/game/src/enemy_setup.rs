@@ -1,57 +1,145 @@
 //! 敌人 AI 组件设置
 //!
-//! 为敌人实体添加 AI 组件，使用平衡配置
+//! 为敌人实体添加 AI 组件，使用平衡配置与敌人原型倍率
 
 use bevy::prelude::*;
-use vigilant_doodle_ai::{DetectionConfig, EnemyAIConfig, EnemyAIState, EnemyTarget};
+use vigilant_doodle_ai::{
+    group_into_squads, AiLod, AiLodConfig, AlertConfig, BehaviorTree, Blackboard, DetectionConfig,
+    EnemyAIConfig, EnemyAIState, EnemyTarget, NavPath, SquadMember, Squads, SteeringAgent,
+    SteeringConfig,
+};
 use vigilant_doodle_core::{BalanceConfig, GameState};
-use vigilant_doodle_gameplay::Enemy;
+use vigilant_doodle_gameplay::{
+    CharacterController, CharacterMotion, CollisionRadius, Enemy, EnemyArchetype, PatrolRoute,
+};
 
 pub struct EnemySetupPlugin;
 
 impl Plugin for EnemySetupPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            setup_enemy_ai.run_if(in_state(GameState::AssetLoading)),
-        );
+        app.add_systems(OnEnter(GameState::AssetLoading), configure_ai_lod)
+            // 敌人按波次陆续生成，每帧为新敌人添加 AI 组件
+            .add_systems(Update, setup_enemy_ai);
     }
 }
 
+/// 从配置文件设置 AI 细节层级参数
+fn configure_ai_lod(mut commands: Commands, balance: Res<BalanceConfig>) {
+    let lod = &balance.enemy.lod;
+    commands.insert_resource(AiLodConfig {
+        near_distance: lod.near_distance,
+        far_distance: lod.far_distance,
+        far_interval: lod.far_interval,
+        dormant_interval: lod.dormant_interval,
+        max_updates_per_frame: lod.max_updates_per_frame,
+    });
+}
+
 /// 为新生成的敌人添加 AI 组件
 fn setup_enemy_ai(
     mut commands: Commands,
-    enemy_query: Query<Entity, (With<Enemy>, Without<EnemyAIState>)>,
+    enemy_query: Query<
+        (
+            Entity,
+            &Transform,
+            &CollisionRadius,
+            Option<&EnemyArchetype>,
+            Option<&PatrolRoute>,
+        ),
+        (With<Enemy>, Without<EnemyAIState>),
+    >,
     balance: Res<BalanceConfig>,
+    mut squads: ResMut<Squads>,
 ) {
-    for entity in enemy_query.iter() {
-        // 从配置文件创建 AI 组件
+    // 本批新敌人按出生点分组为小队
+    let positions: Vec<Vec3> = enemy_query
+        .iter()
+        .map(|(_, transform, ..)| transform.translation)
+        .collect();
+    let groups = group_into_squads(&positions, balance.enemy.squad.squad_radius);
+    let group_count = groups.iter().max().map_or(0, |max| max + 1);
+    let squad_ids: Vec<u32> = (0..group_count).map(|_| squads.allocate()).collect();
+
+    for ((entity, transform, collision, archetype, patrol), group) in enemy_query.iter().zip(groups)
+    {
+        let archetype = archetype
+            .map(|archetype| archetype.config(&balance))
+            .unwrap_or_default();
+
+        // 从配置文件创建 AI 组件（按原型倍率调整速度与检测距离）
         let ai_config = EnemyAIConfig {
-            move_speed: balance.enemy.ai.move_speed,
+            move_speed: balance.enemy.ai.move_speed * archetype.speed_multiplier,
             stop_distance: balance.enemy.ai.stop_distance,
             rotation_speed: balance.enemy.ai.rotation_speed,
+            attack_range: balance.enemy.ai.attack_range,
+            attack_interval: balance.enemy.ai.attack_interval,
+            search_duration: balance.enemy.ai.search_duration,
+            flank_distance: balance.enemy.ai.flank_distance,
+            watch_distance: balance.enemy.ai.watch_distance,
         };
 
         let detection_config = DetectionConfig {
-            detection_range: balance.enemy.detection.detection_range,
-            lose_target_range: balance.enemy.detection.lose_target_range,
+            detection_range: balance.enemy.detection.detection_range
+                * archetype.detection_multiplier,
+            lose_target_range: balance.enemy.detection.lose_target_range
+                * archetype.detection_multiplier,
             field_of_view: balance.enemy.detection.field_of_view,
+            night_range_multiplier: balance.enemy.detection.night_range_multiplier,
+        };
+
+        let steering = &balance.enemy.steering;
+        let steering_config = SteeringConfig {
+            neighbor_radius: steering.neighbor_radius,
+            time_horizon: steering.time_horizon,
+            avoidance_weight: steering.avoidance_weight,
+            separation_weight: steering.separation_weight,
+            alignment_weight: steering.alignment_weight,
+            cohesion_weight: steering.cohesion_weight,
+            max_acceleration: steering.max_acceleration,
+            slot_spacing: steering.slot_spacing,
+            slot_engage_distance: steering.slot_engage_distance,
+        };
+
+        let alert_config = AlertConfig {
+            alert_radius: balance.enemy.squad.alert_radius,
+            alert_delay: balance.enemy.squad.alert_delay,
+            max_relay_hops: balance.enemy.squad.max_relay_hops,
         };
 
         // 保存值用于日志输出
         let move_speed = ai_config.move_speed;
         let detection_range = detection_config.detection_range;
 
+        // 黑板：沿关卡指定的路线巡逻，否则以出生点为中心巡逻
+        let home = transform.translation;
+        let route = match patrol {
+            Some(route) => route.0.clone(),
+            None => Blackboard::square_patrol_route(home, balance.enemy.ai.patrol_radius),
+        };
+        let blackboard = Blackboard::with_home(home).with_patrol_route(route);
+
         commands.entity(entity).insert((
             EnemyAIState::default(),
             ai_config,
             detection_config,
             EnemyTarget::default(),
+            blackboard,
+            BehaviorTree::default_enemy(),
+            NavPath::default(),
+            steering_config,
+            SteeringAgent::new(collision.radius),
+            alert_config,
+            SquadMember {
+                squad: squad_ids[group],
+            },
+            AiLod::default(),
+            CharacterController::from_balance(&balance),
+            CharacterMotion::default(),
         ));
 
         debug!(
-            "[EnemySetup] 敌人 AI 配置: 速度={}, 检测范围={}",
-            move_speed, detection_range
+            "[EnemySetup] 敌人 AI 配置: 速度={}, 检测范围={}, 小队={}",
+            move_speed, detection_range, squad_ids[group]
         );
     }
 }
@@ -9,6 +9,10 @@
 // 本地模块
 // ============================================================================
 
+#[cfg(debug_assertions)]
+mod ai_debug;
+#[cfg(debug_assertions)]
+mod collision_debug;
 mod enemy_setup;
 mod save;
 
@@ -19,11 +23,15 @@ mod save;
 use bevy::prelude::*;
 
 // 核心系统
-use vigilant_doodle_core::{BalancePlugin, LocalizationPlugin, SavePlugin, StatePlugin};
+use vigilant_doodle_core::{BalancePlugin, LocalizationPlugin, RngPlugin, SavePlugin, StatePlugin};
 
 // 本地模块插件
 use enemy_setup::EnemySetupPlugin;
 use save::GameSavePlugin;
+#[cfg(debug_assertions)]
+use ai_debug::AiDebugToolsPlugin;
+#[cfg(debug_assertions)]
+use collision_debug::CollisionDebugToolsPlugin;
 
 // 资源加载
 use vigilant_doodle_assets::AssetLoaderPlugin;
@@ -32,19 +40,28 @@ use vigilant_doodle_assets::AssetLoaderPlugin;
 use vigilant_doodle_camera::IsometricCameraPlugin;
 
 // 世界生成
-use vigilant_doodle_world::SpawningPlugin;
+use vigilant_doodle_world::{
+    CollisionLayerPlugin, DayNightPlugin, SpawningPlugin, StreamingPlugin,
+};
 
 // 输入系统
 use vigilant_doodle_input::{CursorPlugin, InputPlugin};
 
 // 游戏玩法
-use vigilant_doodle_gameplay::{EnemyPlugin, MovementPlugin, PlayerPlugin};
+use vigilant_doodle_gameplay::{
+    CombatPlugin, EncounterPlugin, EnemyPlugin, FlashlightPlugin, InteractionPlugin,
+    InventoryPlugin, MovementPlugin, ObjectivePlugin, PickupPlugin, PlayerPlugin,
+    ProgressionPlugin, SimulationPlugin, SpatialIndexPlugin, TargetingPlugin,
+};
 
 // AI 系统
 use vigilant_doodle_ai::EnemyAIPlugin;
 
 // UI 系统
-use vigilant_doodle_ui::{MenuPlugin, SettingsMenuPlugin};
+use vigilant_doodle_ui::{
+    HudPlugin, InventoryPanelPlugin, LevelCompletePlugin, LevelUpPlugin, MenuPlugin,
+    NotePanelPlugin, SettingsMenuPlugin,
+};
 
 // ============================================================================
 // 游戏主插件
@@ -60,30 +77,59 @@ impl Plugin for GamePlugin {
         info!("[Game] 加载游戏插件...");
 
         app
-            // 1. 核心系统（状态机、本地化、存档管理器、平衡配置）
+            // 1. 核心系统（状态机、本地化、存档管理器、平衡配置、随机数）
             .add_plugins((
                 StatePlugin,
                 LocalizationPlugin,
                 SavePlugin,
                 GameSavePlugin,
                 BalancePlugin,
+                RngPlugin,
             ))
             // 2. 资源加载
             .add_plugins(AssetLoaderPlugin)
             // 3. 相机系统（斜向俯视）
             .add_plugins(IsometricCameraPlugin)
-            // 4. 世界生成（地形、光照）
-            .add_plugins(SpawningPlugin)
-            // 5. 游戏玩法（玩家、敌人、移动）
-            .add_plugins((PlayerPlugin, EnemyPlugin, MovementPlugin))
+            // 4. 世界生成（地形、光照、碰撞层、分块加载、昼夜循环）
+            .add_plugins((
+                SpawningPlugin,
+                CollisionLayerPlugin,
+                StreamingPlugin,
+                DayNightPlugin,
+            ))
+            // 5. 游戏玩法（固定步长模拟、玩家、手电筒、背包与拾取物、可交互物体、关卡目标、经验值与升级、锁定目标、敌人波次、移动、空间索引）
+            .add_plugins((
+                SimulationPlugin,
+                PlayerPlugin,
+                FlashlightPlugin,
+                InventoryPlugin,
+                PickupPlugin,
+                CombatPlugin,
+                InteractionPlugin,
+                ObjectivePlugin,
+                ProgressionPlugin,
+                TargetingPlugin,
+                EncounterPlugin,
+                EnemyPlugin,
+                MovementPlugin,
+                SpatialIndexPlugin,
+            ))
             // 6. 敌人 AI 设置（添加 AI 组件）
             .add_plugins(EnemySetupPlugin)
             // 7. AI 系统（敌人行为）
             .add_plugins(EnemyAIPlugin)
             // 8. 输入系统（键盘、鼠标、光标）
             .add_plugins((InputPlugin, CursorPlugin))
-            // 9. UI系统（主菜单、设置菜单）
-            .add_plugins((MenuPlugin, SettingsMenuPlugin));
+            // 9. UI系统（主菜单、设置菜单、HUD、背包界面、纸条界面、通关界面、升级界面）
+            .add_plugins((
+                MenuPlugin,
+                SettingsMenuPlugin,
+                HudPlugin,
+                InventoryPanelPlugin,
+                NotePanelPlugin,
+                LevelCompletePlugin,
+                LevelUpPlugin,
+            ));
 
         // Inspector 工具（可选启用）
         #[cfg(feature = "inspector")]
@@ -99,6 +145,10 @@ impl Plugin for GamePlugin {
                 bevy::diagnostic::FrameTimeDiagnosticsPlugin::default(),
                 bevy::diagnostic::LogDiagnosticsPlugin::default(),
             ));
+            // AI 调试可视化（F3 开关）
+            app.add_plugins(AiDebugToolsPlugin);
+            // 碰撞体调试可视化（F4 开关）
+            app.add_plugins(CollisionDebugToolsPlugin);
         }
 
         info!("[Game] 游戏插件加载完成");
@@ -6,12 +6,24 @@ use bevy::prelude::*;
 use serde::{Deserialize, Serialize};
 use std::fs;
 
+use vigilant_doodle_assets::GameAssets;
+use vigilant_doodle_core::rng::GameRng;
 use vigilant_doodle_core::save::SaveManager;
+use vigilant_doodle_core::BalanceConfig;
+use vigilant_doodle_gameplay::{
+    enemy_bundle, Chest, Door, EncounterState, Enemy, EnemyArchetype, EnemyStats,
+    InteractableRestore, InteractableState, ItemStack, LevelInteractable, LevelPickups, Objectives,
+    PatrolRoute, Switch,
+};
+use vigilant_doodle_world::{LevelLayout, LevelSource, LoadLevel, TimeOfDay};
 
 // ============================================================================
 // 存档数据结构
 // ============================================================================
 
+/// 当前存档版本
+const SAVE_VERSION: u32 = 12;
+
 /// 存档数据
 #[derive(Debug, Clone, Serialize, Deserialize, bincode::Encode, bincode::Decode)]
 pub struct SaveData {
@@ -21,10 +33,32 @@ pub struct SaveData {
     pub timestamp: u64,
     /// 玩家数据
     pub player: PlayerSaveData,
+    /// 背包数据
+    pub inventory: InventorySaveData,
+    /// 等级与经验值
+    pub progression: ProgressionSaveData,
     /// 敌人数据列表
     pub enemies: Vec<EnemySaveData>,
+    /// 遭遇导演（波次）状态
+    pub encounter: EncounterSaveData,
+    /// 随机数状态
+    pub rng: RngSaveData,
+    /// 关卡文件路径（为空表示程序化关卡）
+    pub level_file: String,
+    /// 程序化关卡种子
+    pub level_seed: u64,
+    /// 当前时刻（小时）
+    pub time_of_day: f32,
+    /// 关卡目标进度
+    pub objectives: ObjectiveSaveData,
+    /// 关卡拾取物状态
+    pub pickups: PickupSaveData,
+    /// 门、开关与宝箱的状态
+    pub interactables: Vec<InteractableSaveData>,
     /// 游戏进度标记
     pub has_active_game: bool,
+    /// 已通关的关卡数
+    pub levels_completed: u32,
 }
 
 /// 玩家存档数据
@@ -33,33 +67,152 @@ pub struct PlayerSaveData {
     pub position: [f32; 3],
     pub rotation: [f32; 4], // 四元数
     pub speed: f32,
+    /// 生命值
+    pub health: f32,
+    /// 手电筒电量
+    pub flashlight_charge: f32,
+    /// 手电筒是否开启
+    pub flashlight_on: bool,
+}
+
+/// 背包存档数据
+#[derive(Debug, Clone, Default, Serialize, Deserialize, bincode::Encode, bincode::Decode)]
+pub struct InventorySaveData {
+    /// 物品格（物品名称, 数量）
+    pub slots: Vec<(String, u32)>,
+    /// 已装备的物品名称
+    pub equipped: Vec<String>,
+}
+
+/// 等级与经验值存档数据
+#[derive(Debug, Clone, Serialize, Deserialize, bincode::Encode, bincode::Decode)]
+pub struct ProgressionSaveData {
+    pub level: u32,
+    /// 当前等级内已获得的经验值
+    pub xp: u32,
+    /// 未使用的属性选择次数
+    pub pending_choices: u32,
+    /// 各属性已提升的次数（移动速度、生命值上限、跳跃次数）
+    pub upgrades: [u32; 3],
+}
+
+impl Default for ProgressionSaveData {
+    fn default() -> Self {
+        Self {
+            level: 1,
+            xp: 0,
+            pending_choices: 0,
+            upgrades: [0; 3],
+        }
+    }
+}
+
+/// 关卡目标存档数据
+#[derive(Debug, Clone, Default, Serialize, Deserialize, bincode::Encode, bincode::Decode)]
+pub struct ObjectiveSaveData {
+    /// 各目标的进度
+    pub progress: Vec<f32>,
+    /// 关卡是否已完成
+    pub level_complete: bool,
+}
+
+/// 关卡拾取物存档数据
+#[derive(Debug, Clone, Default, Serialize, Deserialize, bincode::Encode, bincode::Decode)]
+pub struct PickupSaveData {
+    /// 随机放置使用的种子
+    pub seed: u64,
+    /// 各拾取物已被拾取的数量（拾取物编号, 数量）
+    pub collected: Vec<(u32, u32)>,
+}
+
+/// 可交互物体存档数据
+#[derive(Debug, Clone, Serialize, Deserialize, bincode::Encode, bincode::Decode)]
+pub struct InteractableSaveData {
+    /// 在关卡可交互物体列表中的序号
+    pub index: u32,
+    pub state: InteractableStateSaveData,
+}
+
+/// 可交互物体状态
+#[derive(Debug, Clone, Serialize, Deserialize, bincode::Encode, bincode::Decode)]
+pub enum InteractableStateSaveData {
+    Door {
+        open: bool,
+    },
+    Switch {
+        on: bool,
+    },
+    /// 箱中剩余的物品（物品名称, 数量）
+    Chest {
+        items: Vec<(String, u32)>,
+    },
 }
 
 /// 敌人存档数据
+///
+/// 读档时按原型重新生成敌人，AI 组件由敌人设置系统按原型添加
 #[derive(Debug, Clone, Serialize, Deserialize, bincode::Encode, bincode::Decode)]
 pub struct EnemySaveData {
+    /// 敌人原型名称
+    pub archetype: String,
     pub position: [f32; 3],
     pub rotation: [f32; 4],
-    // AI 配置
-    pub move_speed: f32,
-    pub stop_distance: f32,
-    // 检测配置
-    pub detection_range: f32,
-    pub lose_target_range: f32,
+    pub max_health: f32,
+    pub health: f32,
+    /// 巡逻路线（关卡放置的敌人）
+    pub patrol: Vec<[f32; 3]>,
+}
+
+/// 遭遇导演存档数据
+#[derive(Debug, Clone, Default, Serialize, Deserialize, bincode::Encode, bincode::Decode)]
+pub struct EncounterSaveData {
+    /// 已开始的波次数
+    pub wave: u32,
+    pub score: u32,
+    pub kills: u32,
+    /// 强度系数
+    pub intensity: f32,
+    /// 距离下一波的时间（秒）
+    pub next_wave_in: f32,
+    /// 当前波是否已被清场
+    pub wave_cleared: bool,
+}
+
+/// 随机数存档数据
+#[derive(Debug, Clone, Default, Serialize, Deserialize, bincode::Encode, bincode::Decode)]
+pub struct RngSaveData {
+    /// 全局种子
+    pub seed: u64,
+    /// 各随机流已取用的次数
+    pub streams: Vec<(String, u64)>,
 }
 
 impl Default for SaveData {
     fn default() -> Self {
         Self {
-            version: 1,
+            version: SAVE_VERSION,
             timestamp: 0,
             player: PlayerSaveData {
                 position: [0.0, 0.5, 0.0],
                 rotation: [0.0, 0.0, 0.0, 1.0],
                 speed: 10.0,
+                health: 100.0,
+                flashlight_charge: 100.0,
+                flashlight_on: true,
             },
+            inventory: InventorySaveData::default(),
+            progression: ProgressionSaveData::default(),
             enemies: Vec::new(),
+            encounter: EncounterSaveData::default(),
+            rng: RngSaveData::default(),
+            level_file: String::new(),
+            level_seed: 0,
+            time_of_day: 8.0,
+            objectives: ObjectiveSaveData::default(),
+            pickups: PickupSaveData::default(),
+            interactables: Vec::new(),
             has_active_game: false,
+            levels_completed: 0,
         }
     }
 }
@@ -90,17 +243,41 @@ impl Plugin for GameSavePlugin {
 // ============================================================================
 
 /// 处理保存请求
+#[allow(clippy::too_many_arguments)]
 fn handle_save_request(
     mut save_manager: ResMut<SaveManager>,
-    player_query: Query<(&Transform, &vigilant_doodle_gameplay::Player), Without<vigilant_doodle_gameplay::Enemy>>,
+    player_query: Query<
+        (
+            &Transform,
+            &vigilant_doodle_gameplay::Player,
+            &vigilant_doodle_gameplay::Health,
+            &vigilant_doodle_gameplay::Flashlight,
+            &vigilant_doodle_gameplay::Inventory,
+            &vigilant_doodle_gameplay::Progression,
+        ),
+        Without<vigilant_doodle_gameplay::Enemy>,
+    >,
     enemy_query: Query<
         (
             &Transform,
-            &vigilant_doodle_ai::EnemyAIConfig,
-            &vigilant_doodle_ai::DetectionConfig,
+            &EnemyArchetype,
+            &EnemyStats,
+            Option<&PatrolRoute>,
         ),
-        With<vigilant_doodle_gameplay::Enemy>,
+        With<Enemy>,
     >,
+    encounter: Option<Res<EncounterState>>,
+    game_rng: Res<GameRng>,
+    level: Res<LevelLayout>,
+    time_of_day: Res<TimeOfDay>,
+    objectives: Res<Objectives>,
+    pickups: Res<LevelPickups>,
+    interactable_query: Query<(
+        &LevelInteractable,
+        Option<&Door>,
+        Option<&Switch>,
+        Option<&Chest>,
+    )>,
     game_progress: Res<vigilant_doodle_core::state::GameProgress>,
 ) {
     if !save_manager.pending_save {
@@ -109,40 +286,129 @@ fn handle_save_request(
     save_manager.pending_save = false;
 
     // 收集玩家数据
-    let player_data = if let Ok((transform, player)) = player_query.single() {
-        PlayerSaveData {
-            position: transform.translation.to_array(),
-            rotation: transform.rotation.to_array(),
-            speed: player.speed,
-        }
-    } else {
+    let Ok((transform, player, health, flashlight, inventory, progression)) = player_query.single()
+    else {
         warn!("[SaveManager] 无法找到玩家实体");
         return;
     };
+    let player_data = PlayerSaveData {
+        position: transform.translation.to_array(),
+        rotation: transform.rotation.to_array(),
+        speed: player.speed,
+        health: health.current(),
+        flashlight_charge: flashlight.charge(),
+        flashlight_on: flashlight.is_on(),
+    };
+
+    // 收集背包数据
+    let inventory_data = InventorySaveData {
+        slots: inventory
+            .slots()
+            .iter()
+            .map(|stack| (stack.item.clone(), stack.count))
+            .collect(),
+        equipped: inventory
+            .equipment()
+            .map(|(_, item)| item.to_string())
+            .collect(),
+    };
+
+    // 收集等级数据
+    let progression_data = ProgressionSaveData {
+        level: progression.level(),
+        xp: progression.xp(),
+        pending_choices: progression.pending_choices(),
+        upgrades: progression.upgrade_counts(),
+    };
 
     // 收集敌人数据
     let enemies_data: Vec<EnemySaveData> = enemy_query
         .iter()
-        .map(|(transform, ai_config, detection_config)| EnemySaveData {
+        .map(|(transform, archetype, stats, patrol)| EnemySaveData {
+            archetype: archetype.0.clone(),
             position: transform.translation.to_array(),
             rotation: transform.rotation.to_array(),
-            move_speed: ai_config.move_speed,
-            stop_distance: ai_config.stop_distance,
-            detection_range: detection_config.detection_range,
-            lose_target_range: detection_config.lose_target_range,
+            max_health: stats.max_health,
+            health: stats.current_health,
+            patrol: patrol.map_or_else(Vec::new, |patrol| {
+                patrol.0.iter().map(|point| point.to_array()).collect()
+            }),
         })
         .collect();
 
+    // 收集可交互物体数据（纸条没有状态）
+    let interactables_data: Vec<InteractableSaveData> = interactable_query
+        .iter()
+        .filter_map(|(index, door, switch, chest)| {
+            let state = match (door, switch, chest) {
+                (Some(door), ..) => InteractableStateSaveData::Door { open: door.open },
+                (_, Some(switch), _) => InteractableStateSaveData::Switch { on: switch.on },
+                (.., Some(chest)) => InteractableStateSaveData::Chest {
+                    items: chest
+                        .items
+                        .iter()
+                        .map(|stack| (stack.item.clone(), stack.count))
+                        .collect(),
+                },
+                _ => return None,
+            };
+            Some(InteractableSaveData {
+                index: index.0,
+                state,
+            })
+        })
+        .collect();
+
+    // 收集遭遇导演数据
+    let encounter_data =
+        encounter.map_or_else(EncounterSaveData::default, |encounter| EncounterSaveData {
+            wave: encounter.wave(),
+            score: encounter.score(),
+            kills: encounter.kills(),
+            intensity: encounter.intensity(),
+            next_wave_in: encounter.next_wave_in(),
+            wave_cleared: encounter.wave_cleared(),
+        });
+
     // 创建存档数据
     let save_data = SaveData {
-        version: 1,
+        version: SAVE_VERSION,
         timestamp: std::time::SystemTime::now()
             .duration_since(std::time::UNIX_EPOCH)
             .unwrap()
             .as_secs(),
         player: player_data,
+        inventory: inventory_data,
+        progression: progression_data,
         enemies: enemies_data,
+        encounter: encounter_data,
+        rng: RngSaveData {
+            seed: game_rng.seed(),
+            streams: game_rng
+                .streams()
+                .map(|(name, count)| (name.to_string(), count))
+                .collect(),
+        },
+        level_file: match &level.source {
+            LevelSource::File { path } => path.clone(),
+            LevelSource::Procedural { .. } => String::new(),
+        },
+        level_seed: match level.source {
+            LevelSource::Procedural { seed } => seed,
+            LevelSource::File { .. } => 0,
+        },
+        time_of_day: time_of_day.hour,
+        objectives: ObjectiveSaveData {
+            progress: objectives.progress(),
+            level_complete: objectives.is_level_complete(),
+        },
+        pickups: PickupSaveData {
+            seed: pickups.seed(),
+            collected: pickups.collected().collect(),
+        },
+        interactables: interactables_data,
         has_active_game: game_progress.has_active_game,
+        levels_completed: game_progress.levels_completed,
     };
 
     // 保存到文件（二进制加密格式）
@@ -175,18 +441,33 @@ fn handle_save_request(
 }
 
 /// 处理加载请求
+#[allow(clippy::too_many_arguments)]
 fn handle_load_request(
+    mut commands: Commands,
     mut save_manager: ResMut<SaveManager>,
-    mut player_query: Query<(&mut Transform, &mut vigilant_doodle_gameplay::Player), Without<vigilant_doodle_gameplay::Enemy>>,
-    mut enemy_query: Query<
+    mut player_query: Query<
         (
             &mut Transform,
-            &mut vigilant_doodle_ai::EnemyAIConfig,
-            &mut vigilant_doodle_ai::DetectionConfig,
+            &mut vigilant_doodle_gameplay::Player,
+            &mut vigilant_doodle_gameplay::Health,
+            &mut vigilant_doodle_gameplay::Flashlight,
+            &mut vigilant_doodle_gameplay::Inventory,
+            &mut vigilant_doodle_gameplay::Progression,
+            &mut vigilant_doodle_gameplay::JumpController,
         ),
-        With<vigilant_doodle_gameplay::Enemy>,
+        Without<vigilant_doodle_gameplay::Enemy>,
     >,
+    enemy_query: Query<Entity, With<Enemy>>,
+    mut encounter: Option<ResMut<EncounterState>>,
+    mut game_rng: ResMut<GameRng>,
+    mut load_level: MessageWriter<LoadLevel>,
+    mut time_of_day: ResMut<TimeOfDay>,
+    mut objectives: ResMut<Objectives>,
+    mut pickups: ResMut<LevelPickups>,
+    mut interactables: ResMut<InteractableRestore>,
     mut game_progress: ResMut<vigilant_doodle_core::state::GameProgress>,
+    balance: Res<BalanceConfig>,
+    assets: Res<GameAssets>,
 ) {
     if !save_manager.pending_load {
         return;
@@ -231,35 +512,157 @@ fn handle_load_request(
     info!("[SaveManager] 正在加载存档（版本: {}, 时间戳: {}）",
           save_data.version, save_data.timestamp);
 
+    if save_data.version != SAVE_VERSION {
+        warn!("[SaveManager] 存档版本不匹配（期望: {}）", SAVE_VERSION);
+        return;
+    }
+
     // 恢复玩家数据
-    if let Ok((mut transform, mut player)) = player_query.single_mut() {
+    if let Ok((
+        mut transform,
+        mut player,
+        mut health,
+        mut flashlight,
+        mut inventory,
+        mut progression,
+        mut jump,
+    )) = player_query.single_mut()
+    {
+        // 先恢复等级，生命值按提升后的上限恢复
+        let saved = &save_data.progression;
+        progression.restore(saved.level, saved.xp, saved.pending_choices, saved.upgrades);
+        progression.apply(&balance.player, &mut player, &mut health, &mut jump);
+
         transform.translation = Vec3::from_array(save_data.player.position);
         transform.rotation = Quat::from_array(save_data.player.rotation);
         player.speed = save_data.player.speed;
+        health.restore(save_data.player.health);
+        flashlight.restore(
+            save_data.player.flashlight_charge,
+            save_data.player.flashlight_on,
+        );
+        let slots: Vec<ItemStack> = save_data
+            .inventory
+            .slots
+            .iter()
+            .map(|(item, count)| ItemStack {
+                item: item.clone(),
+                count: *count,
+            })
+            .collect();
+        inventory.restore(&slots, &save_data.inventory.equipped, &balance.items);
         info!("[SaveManager] 玩家数据已恢复");
     } else {
         warn!("[SaveManager] 无法找到玩家实体");
     }
 
-    // 恢复敌人数据
-    let mut enemy_iter = enemy_query.iter_mut();
-    for enemy_save in &save_data.enemies {
-        if let Some((mut transform, mut ai_config, mut detection_config)) = enemy_iter.next() {
-            transform.translation = Vec3::from_array(enemy_save.position);
-            transform.rotation = Quat::from_array(enemy_save.rotation);
-            ai_config.move_speed = enemy_save.move_speed;
-            ai_config.stop_distance = enemy_save.stop_distance;
-            detection_config.detection_range = enemy_save.detection_range;
-            detection_config.lose_target_range = enemy_save.lose_target_range;
-        } else {
-            warn!("[SaveManager] 敌人数量不匹配");
-            break;
+    // 恢复敌人：移除现有敌人，按存档重新生成
+    for entity in enemy_query.iter() {
+        commands.entity(entity).despawn();
+    }
+    for (i, enemy_save) in save_data.enemies.iter().enumerate() {
+        let position = Vec3::from_array(enemy_save.position);
+        let archetype = EnemyArchetype(enemy_save.archetype.clone());
+        let mut enemy = commands.spawn(enemy_bundle(
+            &assets,
+            &balance,
+            position,
+            &archetype.0,
+            format!("Enemy_{}_{}", archetype.0, i),
+        ));
+        enemy.insert((
+            Transform::from_translation(position)
+                .with_rotation(Quat::from_array(enemy_save.rotation))
+                .with_scale(Vec3::splat(archetype.config(&balance).scale)),
+            EnemyStats {
+                max_health: enemy_save.max_health,
+                current_health: enemy_save.health,
+                ..default()
+            },
+        ));
+        if !enemy_save.patrol.is_empty() {
+            enemy.insert(PatrolRoute(
+                enemy_save
+                    .patrol
+                    .iter()
+                    .copied()
+                    .map(Vec3::from_array)
+                    .collect(),
+            ));
         }
     }
     info!("[SaveManager] 敌人数据已恢复（{}个）", save_data.enemies.len());
 
+    // 恢复遭遇导演状态（之后的波次与保存时一致）
+    if let Some(encounter) = encounter.as_mut() {
+        let saved = &save_data.encounter;
+        encounter.restore(
+            saved.wave,
+            saved.score,
+            saved.kills,
+            saved.intensity,
+            saved.next_wave_in,
+            saved.wave_cleared,
+        );
+    }
+
+    // 恢复关卡布局（关卡不同时重新加载，保留已恢复的玩家与敌人）
+    let source = if save_data.level_file.is_empty() {
+        LevelSource::Procedural {
+            seed: save_data.level_seed,
+        }
+    } else {
+        LevelSource::File {
+            path: save_data.level_file,
+        }
+    };
+    // 目标进度在存档关卡加载后应用（关卡相同时立即应用）
+    objectives.restore(
+        source.clone(),
+        save_data.objectives.progress,
+        save_data.objectives.level_complete,
+    );
+    // 拾取物在存档关卡加载后按存档重新放置（已拾取的不再出现）
+    pickups.restore(
+        source.clone(),
+        save_data.pickups.seed,
+        save_data.pickups.collected,
+    );
+    // 门、开关与宝箱同样在存档关卡加载后按存档状态重新放置，与目标进度保持一致
+    interactables.restore(
+        source.clone(),
+        save_data.interactables.into_iter().map(|saved| {
+            let state = match saved.state {
+                InteractableStateSaveData::Door { open } => InteractableState::Door { open },
+                InteractableStateSaveData::Switch { on } => InteractableState::Switch { on },
+                InteractableStateSaveData::Chest { items } => InteractableState::Chest {
+                    items: items
+                        .into_iter()
+                        .map(|(item, count)| ItemStack { item, count })
+                        .collect(),
+                },
+            };
+            (saved.index, state)
+        }),
+    );
+    load_level.write(LoadLevel {
+        source,
+        respawn: false,
+    });
+
+    // 恢复时刻（重新加载关卡时不会重置时刻）
+    time_of_day.set_hour(save_data.time_of_day);
+
+    // 恢复随机数状态（之后的随机序列与保存时一致）
+    *game_rng = GameRng::restore(save_data.rng.seed, save_data.rng.streams);
+    info!(
+        "[SaveManager] 随机数状态已恢复（种子: {}）",
+        game_rng.seed()
+    );
+
     // 恢复游戏进度
     game_progress.has_active_game = save_data.has_active_game;
+    game_progress.levels_completed = save_data.levels_completed;
 
     info!("[SaveManager] 存档加载完成");
 }
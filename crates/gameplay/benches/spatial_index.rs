@@ -0,0 +1,166 @@
+//! 空间索引基准测试
+//!
+//! 运行：`cargo bench -p vigilant-doodle-gameplay --bench spatial_index`
+//!
+//! 在无窗口 App 中生成数千个敌人，测量每帧「重建索引 + 碰撞分离」的耗时，
+//! 以及检测 / 最近敌人 / 邻居查询的耗时，并与逐对遍历（O(n²)）对比，
+//! 检查是否仍在 60 FPS 的帧预算内。
+
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+use bevy::prelude::*;
+use bevy::state::app::StatesPlugin;
+use vigilant_doodle_core::state::GameState;
+use vigilant_doodle_gameplay::{
+    CollisionRadius, Enemy, MovementPlugin, Player, SpatialEntry, SpatialIndex, SpatialIndexPlugin,
+    SpatialKind,
+};
+use vigilant_doodle_world::terrain::{FLOOR_HALF_LENGTH, FLOOR_HALF_WIDTH};
+use vigilant_doodle_world::CollisionLayerPlugin;
+
+/// 60 FPS 帧预算
+const FRAME_BUDGET: Duration = Duration::from_micros(16_667);
+/// 每组测量的帧数
+const FRAMES: u32 = 60;
+/// 敌人数量
+const ENEMY_COUNTS: [u32; 3] = [1000, 2000, 5000];
+/// 检测范围（与平衡配置一致）
+const DETECTION_RANGE: f32 = 17.5;
+/// 邻居查询半径（与转向配置一致）
+const NEIGHBOR_RADIUS: f32 = 4.0;
+
+/// 确定性地散布在地面范围内的位置
+fn scattered(index: u32) -> Vec3 {
+    let x = ((index.wrapping_mul(7919)) % 10_007) as f32 / 10_007.0;
+    let z = ((index.wrapping_mul(104_729)) % 10_009) as f32 / 10_009.0;
+    Vec3::new(
+        (x * 2.0 - 1.0) * FLOOR_HALF_LENGTH,
+        0.0,
+        (z * 2.0 - 1.0) * FLOOR_HALF_WIDTH,
+    )
+}
+
+fn build_app(count: u32) -> App {
+    let mut app = App::new();
+    app.add_plugins((MinimalPlugins, StatesPlugin))
+        .insert_state(GameState::Playing)
+        // 角色控制器需要碰撞层（场景中没有碰撞体）
+        .add_plugins((SpatialIndexPlugin, MovementPlugin, CollisionLayerPlugin));
+
+    app.world_mut().spawn((
+        Transform::default(),
+        Player::from_balance(&default()),
+        CollisionRadius::new(0.6),
+    ));
+    for index in 0..count {
+        app.world_mut().spawn((
+            Transform::from_translation(scattered(index)),
+            Enemy,
+            CollisionRadius::new(0.6),
+        ));
+    }
+    app
+}
+
+/// 每个模拟步的重建索引 + 碰撞分离耗时
+///
+/// 直接运行 `FixedUpdate`，不受测量时实际帧间隔影响
+fn bench_frame(count: u32) -> Duration {
+    let mut app = build_app(count);
+    // 预热（状态切换、分配内存）
+    app.update();
+    for _ in 0..5 {
+        app.world_mut().run_schedule(FixedUpdate);
+    }
+
+    let start = Instant::now();
+    for _ in 0..FRAMES {
+        app.world_mut().run_schedule(FixedUpdate);
+    }
+    start.elapsed() / FRAMES
+}
+
+/// 每帧的查询耗时：检测范围查询 + 最近敌人 + 每个敌人一次邻居查询
+fn bench_queries(count: u32) -> (Duration, Duration) {
+    let positions: Vec<Vec3> = (0..count).map(scattered).collect();
+    let mut index = SpatialIndex::default();
+    let mut world = World::new();
+    let entities: Vec<Entity> = positions.iter().map(|_| world.spawn_empty().id()).collect();
+    let player = Vec3::ZERO;
+
+    let indexed = {
+        let start = Instant::now();
+        for _ in 0..FRAMES {
+            index.clear();
+            for (entity, position) in entities.iter().zip(&positions) {
+                index.insert(SpatialEntry {
+                    entity: *entity,
+                    position: *position,
+                    radius: 0.6,
+                    kind: SpatialKind::Enemy,
+                });
+            }
+
+            black_box(index.query_radius(player, DETECTION_RANGE).count());
+            black_box(index.nearest(player, DETECTION_RANGE, |_| true));
+            for position in &positions {
+                black_box(index.query_radius(*position, NEIGHBOR_RADIUS).count());
+            }
+        }
+        start.elapsed() / FRAMES
+    };
+
+    let brute_force = {
+        let start = Instant::now();
+        for _ in 0..FRAMES {
+            black_box(
+                positions
+                    .iter()
+                    .filter(|position| position.distance(player) <= DETECTION_RANGE)
+                    .count(),
+            );
+            black_box(
+                positions
+                    .iter()
+                    .map(|position| position.distance(player))
+                    .filter(|distance| *distance <= DETECTION_RANGE)
+                    .min_by(|a, b| a.total_cmp(b)),
+            );
+            for a in &positions {
+                black_box(
+                    positions
+                        .iter()
+                        .filter(|b| a.distance(**b) <= NEIGHBOR_RADIUS)
+                        .count(),
+                );
+            }
+        }
+        start.elapsed() / FRAMES
+    };
+
+    (indexed, brute_force)
+}
+
+fn main() {
+    println!("帧预算: {:.2} ms", FRAME_BUDGET.as_secs_f64() * 1000.0);
+    println!(
+        "{:>8} | {:>14} | {:>14} | {:>14} | 预算内",
+        "敌人数", "索引+分离/帧", "索引查询/帧", "逐对查询/帧"
+    );
+
+    for count in ENEMY_COUNTS {
+        let frame = bench_frame(count);
+        let (indexed, brute_force) = bench_queries(count);
+        let within_budget = frame + indexed <= FRAME_BUDGET;
+
+        println!(
+            "{:>8} | {:>11.3} ms | {:>11.3} ms | {:>11.3} ms | {}",
+            count,
+            frame.as_secs_f64() * 1000.0,
+            indexed.as_secs_f64() * 1000.0,
+            brute_force.as_secs_f64() * 1000.0,
+            if within_budget { "是" } else { "否" }
+        );
+    }
+}
@@ -0,0 +1,332 @@
+//! 玩家移动能力：冲刺、翻滚闪避与体力
+//!
+//! - 冲刺：按住冲刺键移动时提高速度，持续消耗体力
+//! - 翻滚：在地面上向移动方向（无输入时向后）快速位移，开始后短时间内无敌，结束后进入冷却
+//! - 体力：冲刺与翻滚共用，消耗后经过一段延迟才开始恢复；
+//!   耗尽后进入力竭状态，恢复到一定比例前不能再次冲刺
+//!
+//! 状态的推进由玩家移动系统在固定步长中调用，这里只提供组件与规则。
+
+use bevy::prelude::*;
+use vigilant_doodle_core::BalanceConfig;
+
+/// 力竭后恢复到该比例才能再次冲刺
+const EXHAUSTION_RECOVERY: f32 = 0.3;
+
+/// 体力组件
+#[derive(Component, Debug, Clone)]
+pub struct Stamina {
+    /// 当前体力
+    pub current: f32,
+    /// 体力上限
+    pub max: f32,
+    /// 每秒恢复的体力
+    pub regen_per_second: f32,
+    /// 消耗体力后多久开始恢复（秒）
+    pub regen_delay: f32,
+    /// 距离开始恢复的剩余时间
+    regen_cooldown: f32,
+    /// 是否力竭
+    exhausted: bool,
+}
+
+impl Stamina {
+    /// 从平衡配置创建体力（初始为满）
+    pub fn from_balance(balance: &BalanceConfig) -> Self {
+        let stamina = &balance.player.stamina;
+        Self {
+            current: stamina.max,
+            max: stamina.max,
+            regen_per_second: stamina.regen_per_second,
+            regen_delay: stamina.regen_delay,
+            regen_cooldown: 0.0,
+            exhausted: false,
+        }
+    }
+
+    /// 当前体力占上限的比例（0.0 ~ 1.0）
+    pub fn fraction(&self) -> f32 {
+        if self.max > 0.0 {
+            (self.current / self.max).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// 是否力竭
+    pub fn is_exhausted(&self) -> bool {
+        self.exhausted
+    }
+
+    /// 一次性消耗：体力足够时扣除并返回 true，否则不扣除
+    pub fn try_spend(&mut self, amount: f32) -> bool {
+        if self.current < amount {
+            return false;
+        }
+        self.current -= amount;
+        self.on_spent();
+        true
+    }
+
+    /// 持续消耗：扣除至多 `amount`，体力耗尽时进入力竭
+    ///
+    /// 力竭或已无体力时返回 false
+    pub fn drain(&mut self, amount: f32) -> bool {
+        if self.exhausted || self.current <= 0.0 {
+            return false;
+        }
+        self.current = (self.current - amount).max(0.0);
+        self.on_spent();
+        true
+    }
+
+    /// 推进恢复
+    pub fn tick(&mut self, mut delta: f32) {
+        if self.regen_cooldown > 0.0 {
+            let wait = self.regen_cooldown.min(delta);
+            self.regen_cooldown -= wait;
+            delta -= wait;
+        }
+        self.current = (self.current + self.regen_per_second * delta).min(self.max);
+        if self.exhausted && self.fraction() >= EXHAUSTION_RECOVERY {
+            self.exhausted = false;
+        }
+    }
+
+    fn on_spent(&mut self) {
+        self.regen_cooldown = self.regen_delay;
+        if self.current <= 0.0 {
+            self.exhausted = true;
+        }
+    }
+}
+
+/// 进行中的翻滚
+#[derive(Debug, Clone, Copy)]
+struct ActiveDodge {
+    /// 水平单位方向
+    direction: Vec3,
+    /// 已持续时间
+    elapsed: f32,
+}
+
+/// 移动能力组件（冲刺与翻滚的参数和状态）
+#[derive(Component, Debug, Clone)]
+pub struct MovementAbilities {
+    /// 冲刺时的速度倍率
+    pub sprint_multiplier: f32,
+    /// 冲刺每秒消耗的体力
+    pub sprint_stamina_per_second: f32,
+    /// 翻滚速度
+    pub dodge_speed: f32,
+    /// 翻滚持续时间（秒）
+    pub dodge_duration: f32,
+    /// 翻滚开始后的无敌时间（秒）
+    pub dodge_invulnerability: f32,
+    /// 翻滚结束后的冷却时间（秒）
+    pub dodge_cooldown: f32,
+    /// 每次翻滚消耗的体力
+    pub dodge_stamina_cost: f32,
+    /// 本步是否在冲刺
+    sprinting: bool,
+    /// 进行中的翻滚
+    dodge: Option<ActiveDodge>,
+    /// 翻滚冷却剩余时间
+    cooldown_remaining: f32,
+}
+
+impl MovementAbilities {
+    /// 从平衡配置创建移动能力
+    pub fn from_balance(balance: &BalanceConfig) -> Self {
+        let sprint = &balance.player.sprint;
+        let dodge = &balance.player.dodge;
+        Self {
+            sprint_multiplier: sprint.speed_multiplier,
+            sprint_stamina_per_second: sprint.stamina_per_second,
+            dodge_speed: dodge.speed,
+            dodge_duration: dodge.duration,
+            dodge_invulnerability: dodge.invulnerability,
+            dodge_cooldown: dodge.cooldown,
+            dodge_stamina_cost: dodge.stamina_cost,
+            sprinting: false,
+            dodge: None,
+            cooldown_remaining: 0.0,
+        }
+    }
+
+    /// 是否在冲刺
+    pub fn is_sprinting(&self) -> bool {
+        self.sprinting
+    }
+
+    /// 是否在翻滚
+    pub fn is_dodging(&self) -> bool {
+        self.dodge.is_some()
+    }
+
+    /// 是否处于翻滚无敌时间内（此时 `combat` 忽略敌人的攻击）
+    pub fn is_invulnerable(&self) -> bool {
+        self.dodge
+            .is_some_and(|dodge| dodge.elapsed < self.dodge_invulnerability)
+    }
+
+    /// 翻滚冷却剩余时间（秒）
+    pub fn dodge_cooldown_remaining(&self) -> f32 {
+        self.cooldown_remaining
+    }
+
+    /// 推进翻滚计时与冷却
+    pub fn tick(&mut self, delta: f32) {
+        if let Some(dodge) = &mut self.dodge {
+            dodge.elapsed += delta;
+            if dodge.elapsed >= self.dodge_duration {
+                self.dodge = None;
+                self.cooldown_remaining = self.dodge_cooldown;
+            }
+        } else {
+            self.cooldown_remaining = (self.cooldown_remaining - delta).max(0.0);
+        }
+    }
+
+    /// 尝试向 `direction` 翻滚，翻滚中、冷却中或体力不足时失败
+    pub fn try_dodge(&mut self, direction: Vec3, stamina: &mut Stamina) -> bool {
+        let direction = direction.with_y(0.0).normalize_or_zero();
+        if self.dodge.is_some()
+            || self.cooldown_remaining > 0.0
+            || direction == Vec3::ZERO
+            || !stamina.try_spend(self.dodge_stamina_cost)
+        {
+            return false;
+        }
+        self.dodge = Some(ActiveDodge {
+            direction,
+            elapsed: 0.0,
+        });
+        self.sprinting = false;
+        true
+    }
+
+    /// 翻滚中的水平速度
+    pub fn dodge_velocity(&self) -> Option<Vec3> {
+        self.dodge.map(|dodge| dodge.direction * self.dodge_speed)
+    }
+
+    /// 更新冲刺状态，返回本步的速度倍率
+    ///
+    /// 需要按住冲刺键、有移动输入且未在翻滚，并按步长消耗体力
+    pub fn update_sprint(
+        &mut self,
+        wants_sprint: bool,
+        moving: bool,
+        stamina: &mut Stamina,
+        delta: f32,
+    ) -> f32 {
+        self.sprinting = wants_sprint
+            && moving
+            && self.dodge.is_none()
+            && stamina.drain(self.sprint_stamina_per_second * delta);
+        if self.sprinting {
+            self.sprint_multiplier
+        } else {
+            1.0
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const STEP: f32 = 0.1;
+
+    fn balance() -> BalanceConfig {
+        BalanceConfig::default()
+    }
+
+    #[test]
+    fn test_stamina_regen_waits_for_delay() {
+        let mut stamina = Stamina::from_balance(&balance());
+        assert!(stamina.try_spend(50.0));
+        assert!(!stamina.try_spend(60.0));
+        assert_eq!(stamina.current, 50.0);
+
+        // 延迟 0.8 秒内不恢复
+        for _ in 0..7 {
+            stamina.tick(STEP);
+        }
+        assert_eq!(stamina.current, 50.0);
+
+        // 之后每秒恢复 25
+        for _ in 0..11 {
+            stamina.tick(STEP);
+        }
+        assert!((stamina.current - 75.0).abs() < 1e-3, "{}", stamina.current);
+    }
+
+    #[test]
+    fn test_sprint_drains_until_exhausted() {
+        let balance = balance();
+        let mut stamina = Stamina::from_balance(&balance);
+        let mut abilities = MovementAbilities::from_balance(&balance);
+
+        // 没有移动输入时不冲刺也不消耗
+        assert_eq!(
+            abilities.update_sprint(true, false, &mut stamina, STEP),
+            1.0
+        );
+        assert_eq!(stamina.current, stamina.max);
+
+        // 每秒 20 体力，5 秒耗尽
+        let mut sprinting_steps = 0;
+        while abilities.update_sprint(true, true, &mut stamina, STEP) > 1.0 {
+            sprinting_steps += 1;
+            stamina.tick(STEP);
+        }
+        assert!((49..=51).contains(&sprinting_steps), "{sprinting_steps}");
+        assert!(stamina.is_exhausted());
+        assert!(!abilities.is_sprinting());
+
+        // 恢复到 30% 前不能再次冲刺
+        while stamina.is_exhausted() {
+            assert_eq!(abilities.update_sprint(true, true, &mut stamina, STEP), 1.0);
+            stamina.tick(STEP);
+        }
+        assert!(stamina.fraction() >= EXHAUSTION_RECOVERY);
+        assert!(abilities.update_sprint(true, true, &mut stamina, STEP) > 1.0);
+    }
+
+    #[test]
+    fn test_dodge_invulnerability_and_cooldown() {
+        let balance = balance();
+        let mut stamina = Stamina::from_balance(&balance);
+        let mut abilities = MovementAbilities::from_balance(&balance);
+
+        assert!(abilities.try_dodge(Vec3::X, &mut stamina));
+        assert_eq!(stamina.current, 75.0);
+        assert_eq!(abilities.dodge_velocity(), Some(Vec3::X * 14.0));
+        assert!(abilities.is_invulnerable());
+        // 翻滚中不能再次翻滚
+        assert!(!abilities.try_dodge(Vec3::X, &mut stamina));
+
+        // 0.3 秒：无敌已结束（0.25），翻滚仍在进行（0.35）
+        for _ in 0..3 {
+            abilities.tick(STEP);
+        }
+        assert!(abilities.is_dodging());
+        assert!(!abilities.is_invulnerable());
+
+        // 翻滚结束后进入冷却
+        abilities.tick(STEP);
+        assert!(!abilities.is_dodging());
+        assert!(!abilities.try_dodge(Vec3::X, &mut stamina));
+        for _ in 0..9 {
+            abilities.tick(STEP);
+        }
+        assert!(abilities.try_dodge(Vec3::X, &mut stamina));
+
+        // 体力不足时不能翻滚
+        stamina.current = 10.0;
+        let mut rested = MovementAbilities::from_balance(&balance);
+        assert!(!rested.try_dodge(Vec3::Z, &mut stamina));
+    }
+}
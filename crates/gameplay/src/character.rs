@@ -0,0 +1,640 @@
+//! 运动学角色控制器
+//!
+//! 玩家与敌人共用的角色移动：
+//! - 角色形状为竖直胶囊，用沿轴排列的球体近似
+//! - 水平移动采用“碰撞并滑动”（collide and slide），沿墙面滑动、沿可行走斜面爬坡
+//! - 遇到不高于台阶高度的障碍时自动跨上
+//! - 通过向下的球体投射检测地面，行走时贴地（下坡、下台阶不会腾空）
+//! - 重力与跳跃在控制器中积分，可落在任意高度的平台上
+//! - 地面为关卡的高度图地形，与静态碰撞体一起参与投射与穿透修正，角色可沿坡面上下
+//! - 静态碰撞体从世界碰撞层（`CollisionLayer`）按角色附近范围取得，移动后限制在世界边界内
+//! - 位于未加载区块的角色（`Frozen`）不移动
+//!
+//! 输入由玩家移动系统 / AI 转向系统写入 `CharacterMotion::velocity`，
+//! 控制器在 `MovementSystemSet::Character` 中统一积分。
+
+use bevy::prelude::*;
+use vigilant_doodle_core::BalanceConfig;
+use vigilant_doodle_world::terrain::Heightfield;
+use vigilant_doodle_world::{CollisionLayer, Frozen, LevelLayout, ShapeHit, StaticCollider};
+
+/// 单次移动最多的滑动迭代次数
+const MAX_SLIDE_ITERATIONS: usize = 4;
+/// 穿透修正的最多迭代次数
+const MAX_DEPENETRATION_ITERATIONS: usize = 4;
+/// 跨台阶时至少需要前进的距离
+const MIN_STEP_ADVANCE: f32 = 0.001;
+
+/// 角色控制器参数组件
+#[derive(Component, Debug, Clone)]
+pub struct CharacterController {
+    /// 胶囊半径
+    pub radius: f32,
+    /// 胶囊总高度
+    pub height: f32,
+    /// 脚底相对 `Transform` 原点的高度（模型原点高于脚底时为负）
+    pub foot_offset: f32,
+    /// 可自动跨上的台阶高度
+    pub step_height: f32,
+    /// 可行走的最大坡度（度）
+    pub max_slope_angle: f32,
+    /// 与碰撞表面保持的间隙
+    pub skin_width: f32,
+    /// 贴地检测距离
+    pub snap_distance: f32,
+    /// 重力加速度（负数向下）
+    pub gravity: f32,
+}
+
+impl Default for CharacterController {
+    fn default() -> Self {
+        Self {
+            radius: 0.5,
+            height: 1.8,
+            foot_offset: -0.5,
+            step_height: 0.4,
+            max_slope_angle: 45.0,
+            skin_width: 0.02,
+            snap_distance: 0.3,
+            gravity: -9.8,
+        }
+    }
+}
+
+impl CharacterController {
+    /// 从平衡配置创建角色控制器
+    pub fn from_balance(balance: &BalanceConfig) -> Self {
+        let character = &balance.character;
+        Self {
+            radius: character.radius,
+            height: character.height,
+            foot_offset: character.foot_offset,
+            step_height: character.step_height,
+            max_slope_angle: character.max_slope_angle,
+            skin_width: character.skin_width,
+            snap_distance: character.snap_distance,
+            gravity: character.gravity,
+        }
+    }
+
+    /// 表面法线是否可行走
+    pub fn is_walkable(&self, normal: Vec3) -> bool {
+        normal.y >= self.max_slope_angle.to_radians().cos() - f32::EPSILON
+    }
+
+    /// 近似胶囊的球心高度（相对 `Transform` 原点，自下而上）
+    fn sphere_heights(&self) -> [f32; 3] {
+        let bottom = self.foot_offset + self.radius;
+        let top = self.foot_offset + (self.height - self.radius).max(self.radius);
+        [bottom, (bottom + top) * 0.5, top]
+    }
+}
+
+/// 角色运动状态组件
+#[derive(Component, Debug, Clone)]
+pub struct CharacterMotion {
+    /// 期望水平速度（每帧由玩家输入或 AI 转向写入）
+    pub velocity: Vec3,
+    /// 垂直速度
+    pub vertical_velocity: f32,
+    /// 是否站在可行走的地面上
+    pub grounded: bool,
+    /// 地面法线（离地时为 `Vec3::Y`）
+    pub ground_normal: Vec3,
+}
+
+impl Default for CharacterMotion {
+    fn default() -> Self {
+        Self {
+            velocity: Vec3::ZERO,
+            vertical_velocity: 0.0,
+            grounded: false,
+            ground_normal: Vec3::Y,
+        }
+    }
+}
+
+impl CharacterMotion {
+    /// 以给定初速度起跳
+    pub fn jump(&mut self, speed: f32) {
+        self.vertical_velocity = speed;
+        self.grounded = false;
+        self.ground_normal = Vec3::Y;
+    }
+}
+
+/// 滑动结果
+struct SlideResult {
+    position: Vec3,
+    /// 接触到的可行走表面法线
+    ground: Option<Vec3>,
+    /// 是否撞到天花板
+    ceiling: bool,
+}
+
+/// 单个角色的碰撞查询
+struct CharacterBody<'a> {
+    controller: &'a CharacterController,
+    colliders: &'a [(Transform, StaticCollider)],
+    terrain: Option<&'a Heightfield>,
+}
+
+impl CharacterBody<'_> {
+    /// 胶囊投射：返回所有近似球体中最先接触的表面
+    fn cast(&self, position: Vec3, direction: Vec3, max_distance: f32) -> Option<ShapeHit> {
+        let mut nearest: Option<ShapeHit> = None;
+        for height in self.controller.sphere_heights() {
+            let origin = position + Vec3::Y * height;
+            for (transform, collider) in self.colliders {
+                if let Some(hit) = collider.sphere_cast(
+                    transform,
+                    origin,
+                    direction,
+                    max_distance,
+                    self.controller.radius,
+                ) && nearest.is_none_or(|nearest| hit.distance < nearest.distance)
+                {
+                    nearest = Some(hit);
+                }
+            }
+            if let Some(hit) = self.terrain.and_then(|terrain| {
+                terrain.sphere_cast(origin, direction, max_distance, self.controller.radius)
+            }) && nearest.is_none_or(|nearest| hit.distance < nearest.distance)
+            {
+                nearest = Some(hit);
+            }
+        }
+        nearest
+    }
+
+    /// 将角色推出与碰撞体重叠的位置
+    fn depenetrate(&self, mut position: Vec3) -> Vec3 {
+        for _ in 0..MAX_DEPENETRATION_ITERATIONS {
+            let mut resolved = true;
+            for height in self.controller.sphere_heights() {
+                for (transform, collider) in self.colliders {
+                    let center = position + Vec3::Y * height;
+                    if let Some((normal, depth)) =
+                        collider.sphere_penetration(transform, center, self.controller.radius)
+                    {
+                        position += normal * (depth + self.controller.skin_width);
+                        resolved = false;
+                    }
+                }
+                if let Some((normal, depth)) = self.terrain.and_then(|terrain| {
+                    terrain.sphere_penetration(position + Vec3::Y * height, self.controller.radius)
+                }) {
+                    position += normal * (depth + self.controller.skin_width);
+                    resolved = false;
+                }
+            }
+            if resolved {
+                break;
+            }
+        }
+        position
+    }
+
+    /// 碰撞并滑动
+    ///
+    /// `allow_step` 为真时，被陡峭表面阻挡会尝试跨上台阶
+    fn slide(&self, mut position: Vec3, displacement: Vec3, allow_step: bool) -> SlideResult {
+        let skin = self.controller.skin_width;
+        let mut remaining = displacement;
+        let mut ground = None;
+        let mut ceiling = false;
+
+        for _ in 0..MAX_SLIDE_ITERATIONS {
+            let length = remaining.length();
+            if length < f32::EPSILON {
+                break;
+            }
+            let direction = remaining / length;
+            let Some(hit) = self.cast(position, direction, length + skin) else {
+                position += remaining;
+                break;
+            };
+
+            let travel = (hit.distance - skin).max(0.0);
+            position += direction * travel;
+            remaining = direction * (length - travel);
+
+            let walkable = self.controller.is_walkable(hit.normal);
+            if walkable {
+                ground = Some(hit.normal);
+            } else if hit.normal.y < -0.5 {
+                ceiling = true;
+            }
+
+            if allow_step
+                && !walkable
+                && let Some(stepped) = self.try_step(position, remaining)
+            {
+                position = stepped;
+                break;
+            }
+
+            // 陡峭表面按竖直墙处理，水平移动时不会被推上去
+            let plane = if !walkable && displacement.y == 0.0 {
+                hit.normal.with_y(0.0).normalize_or(hit.normal)
+            } else {
+                hit.normal
+            };
+            remaining -= plane * remaining.dot(plane);
+        }
+
+        SlideResult {
+            position,
+            ground,
+            ceiling,
+        }
+    }
+
+    /// 尝试跨上台阶：抬升、前进、再落到台阶顶面
+    fn try_step(&self, position: Vec3, remaining: Vec3) -> Option<Vec3> {
+        let skin = self.controller.skin_width;
+        let horizontal = remaining.with_y(0.0);
+        let length = horizontal.length();
+        if length < MIN_STEP_ADVANCE {
+            return None;
+        }
+        let direction = horizontal / length;
+
+        let clearance = self
+            .cast(position, Vec3::Y, self.controller.step_height + skin)
+            .map_or(self.controller.step_height, |hit| {
+                (hit.distance - skin).max(0.0)
+            });
+        let raised = position + Vec3::Y * clearance;
+
+        let advance = self
+            .cast(raised, direction, length + skin)
+            .map_or(length, |hit| (hit.distance - skin).max(0.0));
+        if advance < MIN_STEP_ADVANCE {
+            return None;
+        }
+        let advanced = raised + direction * advance;
+
+        let hit = self.cast(advanced, Vec3::NEG_Y, clearance + skin)?;
+        if !self.controller.is_walkable(hit.normal) {
+            return None;
+        }
+        Some(advanced - Vec3::Y * (hit.distance - skin))
+    }
+}
+
+/// 移动角色一帧，返回新位置
+///
+/// 依次执行：穿透修正 → 重力 → 水平滑动（含跨台阶） → 垂直滑动（落地 / 撞顶） → 贴地检测；
+/// `terrain` 为关卡地形（没有时只与碰撞体交互）
+pub fn move_character(
+    position: Vec3,
+    controller: &CharacterController,
+    motion: &mut CharacterMotion,
+    colliders: &[(Transform, StaticCollider)],
+    terrain: Option<&Heightfield>,
+    delta: f32,
+) -> Vec3 {
+    let body = CharacterBody {
+        controller,
+        colliders,
+        terrain,
+    };
+    let skin = controller.skin_width;
+    let mut position = body.depenetrate(position);
+
+    // 重力
+    if motion.grounded && motion.vertical_velocity <= 0.0 {
+        motion.vertical_velocity = 0.0;
+    } else {
+        motion.vertical_velocity += controller.gravity * delta;
+    }
+
+    // 水平移动：站在地面上时沿地面切向移动，保持水平速度不变
+    let horizontal = motion.velocity.with_y(0.0) * delta;
+    if horizontal.length_squared() > f32::EPSILON {
+        let mut displacement = horizontal;
+        if motion.grounded {
+            let normal = motion.ground_normal;
+            displacement = (displacement - normal * displacement.dot(normal)).normalize_or_zero()
+                * horizontal.length();
+        }
+        position = body.slide(position, displacement, motion.grounded).position;
+    }
+
+    // 垂直移动
+    let vertical = motion.vertical_velocity * delta;
+    let mut landed = None;
+    if vertical != 0.0 {
+        let result = body.slide(position, Vec3::Y * vertical, false);
+        position = result.position;
+        if vertical < 0.0 {
+            landed = result.ground;
+        } else if result.ceiling {
+            motion.vertical_velocity = 0.0;
+        }
+    }
+
+    // 贴地检测
+    if motion.vertical_velocity <= 0.0 {
+        let probe_distance = if motion.grounded || landed.is_some() {
+            controller.snap_distance + skin
+        } else {
+            skin * 2.0
+        };
+        match body.cast(position, Vec3::NEG_Y, probe_distance) {
+            Some(hit) if controller.is_walkable(hit.normal) => {
+                position.y -= hit.distance - skin;
+                motion.grounded = true;
+                motion.ground_normal = hit.normal;
+                motion.vertical_velocity = 0.0;
+            }
+            _ => match landed {
+                Some(normal) => {
+                    motion.grounded = true;
+                    motion.ground_normal = normal;
+                    motion.vertical_velocity = 0.0;
+                }
+                None => {
+                    motion.grounded = false;
+                    motion.ground_normal = Vec3::Y;
+                }
+            },
+        }
+    } else {
+        motion.grounded = false;
+        motion.ground_normal = Vec3::Y;
+    }
+
+    position
+}
+
+/// 角色控制器系统
+///
+/// 只把碰撞层中角色附近的碰撞体交给控制器，避免每个角色遍历全部碰撞体
+pub(crate) fn character_controller_system(
+    mut character_query: Query<
+        (&mut Transform, &CharacterController, &mut CharacterMotion),
+        Without<Frozen>,
+    >,
+    collision_layer: Res<CollisionLayer>,
+    level: Option<Res<LevelLayout>>,
+    time: Res<Time>,
+    mut nearby: Local<Vec<(Transform, StaticCollider)>>,
+) {
+    let delta = time.delta_secs();
+    if delta <= 0.0 {
+        return;
+    }
+
+    let terrain = level.as_deref().map(|level| &level.terrain);
+    for (mut transform, controller, mut motion) in character_query.iter_mut() {
+        let reach = controller.height
+            + controller.snap_distance
+            + (motion.velocity.length() + motion.vertical_velocity.abs()) * delta;
+        collision_layer.query(transform.translation, reach, &mut nearby);
+
+        let position = move_character(
+            transform.translation,
+            controller,
+            &mut motion,
+            &nearby,
+            terrain,
+            delta,
+        );
+        // 高速移动穿过边界墙时拉回世界边界内
+        let inside = collision_layer.clamp_to_bounds(position.xz(), controller.radius);
+        transform.translation = Vec3::new(inside.x, position.y, inside.y);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const DELTA: f32 = 1.0 / 60.0;
+
+    /// 顶面位于 y = 0 的大地面
+    fn floor() -> (Transform, StaticCollider) {
+        (
+            Transform::from_xyz(0.0, -0.5, 0.0),
+            StaticCollider::cuboid(Vec3::new(50.0, 0.5, 50.0)),
+        )
+    }
+
+    /// 底面位于 y = 0、顶面高 `height` 的方块
+    fn block(center: Vec2, half_extents: Vec2, height: f32) -> (Transform, StaticCollider) {
+        (
+            Transform::from_xyz(center.x, height * 0.5, center.y),
+            StaticCollider::cuboid(Vec3::new(half_extents.x, height * 0.5, half_extents.y)),
+        )
+    }
+
+    /// 沿 +X 方向上升、坡度为 `angle` 度的斜坡（底边位于 x = 0）
+    fn ramp(angle: f32) -> (Transform, StaticCollider) {
+        let angle = angle.to_radians();
+        let half_length = 6.0;
+        let rotation = Quat::from_rotation_z(angle);
+        // 顶面左端落在原点
+        let top_left = rotation * Vec3::new(-half_length, 0.5, 0.0);
+        (
+            Transform::from_translation(-top_left).with_rotation(rotation),
+            StaticCollider::cuboid(Vec3::new(half_length, 0.5, 3.0)),
+        )
+    }
+
+    fn controller() -> CharacterController {
+        CharacterController {
+            foot_offset: 0.0,
+            ..default()
+        }
+    }
+
+    fn simulate(
+        colliders: &[(Transform, StaticCollider)],
+        start: Vec3,
+        velocity: Vec3,
+        frames: usize,
+        motion: &mut CharacterMotion,
+    ) -> Vec3 {
+        let controller = controller();
+        motion.velocity = velocity;
+        let mut position = start;
+        for _ in 0..frames {
+            position = move_character(position, &controller, motion, colliders, None, DELTA);
+        }
+        position
+    }
+
+    #[test]
+    fn test_walks_over_terrain_hill() {
+        // 沿 X 方向先升后降的山丘（最大坡度约 20°）
+        let terrain = Heightfield::from_fn(Vec2::splat(60.0), 1.0, |p| {
+            3.0 * (-(p.x * p.x) / 50.0).exp()
+        });
+        let controller = controller();
+        let mut motion = CharacterMotion {
+            velocity: Vec3::new(5.0, 0.0, 0.0),
+            ..default()
+        };
+        let mut position = Vec3::new(-15.0, 1.0, 0.0);
+        let mut peak = f32::NEG_INFINITY;
+        for _ in 0..360 {
+            position = move_character(
+                position,
+                &controller,
+                &mut motion,
+                &[],
+                Some(&terrain),
+                DELTA,
+            );
+            peak = peak.max(position.y);
+            assert!(position.y >= terrain.height_at(position.xz()) - 0.05);
+        }
+
+        // 越过山顶并贴着另一侧的坡面下来
+        assert!(position.x > 10.0);
+        assert!(peak > 2.5);
+        assert!(motion.grounded);
+        assert!((position.y - terrain.height_at(position.xz())).abs() < 0.1);
+    }
+
+    #[test]
+    fn test_falls_and_lands_on_floor() {
+        let mut motion = CharacterMotion::default();
+        let position = simulate(
+            &[floor()],
+            Vec3::new(0.0, 3.0, 0.0),
+            Vec3::ZERO,
+            120,
+            &mut motion,
+        );
+
+        assert!(motion.grounded);
+        assert!(position.y.abs() < 0.05, "{position}");
+        assert_eq!(motion.vertical_velocity, 0.0);
+    }
+
+    #[test]
+    fn test_slides_along_wall() {
+        let colliders = [
+            floor(),
+            block(Vec2::new(3.0, 0.0), Vec2::new(0.5, 10.0), 3.0),
+        ];
+        let mut motion = CharacterMotion::default();
+        // 斜向撞墙：X 方向被挡住，Z 方向继续滑动
+        let position = simulate(
+            &colliders,
+            Vec3::ZERO,
+            Vec3::new(4.0, 0.0, 2.0),
+            60,
+            &mut motion,
+        );
+
+        assert!(position.x < 2.5 - 0.5 + 0.05, "{position}");
+        assert!(position.z > 1.5, "{position}");
+        assert!(motion.grounded);
+    }
+
+    #[test]
+    fn test_slides_around_pillar() {
+        // 底面位于 y = 0 的圆柱，略偏离移动路线
+        let pillar = (
+            Transform::from_xyz(3.0, 1.5, 0.3),
+            StaticCollider::cylinder(1.0, 1.5),
+        );
+        let mut motion = CharacterMotion::default();
+        let mut position = Vec3::ZERO;
+        motion.velocity = Vec3::new(4.0, 0.0, 0.0);
+        let controller = controller();
+        let colliders = [floor(), pillar];
+        for _ in 0..120 {
+            position = move_character(position, &controller, &mut motion, &colliders, None, DELTA);
+            let offset = (position - Vec3::new(3.0, position.y, 0.3)).length();
+            assert!(offset >= 1.5 - 0.05, "{position}");
+        }
+
+        // 沿圆柱侧面绕过，而不是卡在正面
+        assert!(position.x > 5.0, "{position}");
+        assert!(position.z < -0.5, "{position}");
+    }
+
+    #[test]
+    fn test_walks_up_gentle_slope_but_not_steep() {
+        let mut motion = CharacterMotion::default();
+        let gentle = simulate(
+            &[floor(), ramp(25.0)],
+            Vec3::new(-2.0, 0.0, 0.0),
+            Vec3::new(4.0, 0.0, 0.0),
+            90,
+            &mut motion,
+        );
+        assert!(gentle.x > 2.0 && gentle.y > 1.0, "{gentle}");
+        assert!(motion.grounded);
+
+        let mut motion = CharacterMotion::default();
+        let steep = simulate(
+            &[floor(), ramp(60.0)],
+            Vec3::new(-2.0, 0.0, 0.0),
+            Vec3::new(4.0, 0.0, 0.0),
+            90,
+            &mut motion,
+        );
+        assert!(steep.y < 0.5, "{steep}");
+    }
+
+    #[test]
+    fn test_steps_up_low_ledge_only() {
+        let colliders = [
+            floor(),
+            block(Vec2::new(3.0, 0.0), Vec2::new(1.0, 5.0), 0.3),
+            block(Vec2::new(3.0, 8.0), Vec2::new(1.0, 2.0), 1.0),
+        ];
+
+        let mut motion = CharacterMotion::default();
+        let low = simulate(
+            &colliders,
+            Vec3::ZERO,
+            Vec3::new(3.0, 0.0, 0.0),
+            60,
+            &mut motion,
+        );
+        assert!(low.x > 2.5 && (low.y - 0.3).abs() < 0.05, "{low}");
+
+        let mut motion = CharacterMotion::default();
+        let high = simulate(
+            &colliders,
+            Vec3::new(0.0, 0.0, 8.0),
+            Vec3::new(3.0, 0.0, 0.0),
+            60,
+            &mut motion,
+        );
+        assert!(high.x < 1.6 && high.y < 0.05, "{high}");
+    }
+
+    #[test]
+    fn test_jump_lands_on_platform() {
+        let colliders = [
+            floor(),
+            block(Vec2::new(3.0, 0.0), Vec2::new(1.5, 1.5), 1.5),
+        ];
+        let controller = controller();
+        let mut motion = CharacterMotion::default();
+
+        let mut position = simulate(&colliders, Vec3::ZERO, Vec3::ZERO, 5, &mut motion);
+        assert!(motion.grounded);
+
+        motion.jump(7.5);
+        motion.velocity = Vec3::new(3.0, 0.0, 0.0);
+        for _ in 0..90 {
+            position = move_character(position, &controller, &mut motion, &colliders, None, DELTA);
+        }
+        motion.velocity = Vec3::ZERO;
+        for _ in 0..30 {
+            position = move_character(position, &controller, &mut motion, &colliders, None, DELTA);
+        }
+
+        assert!(motion.grounded);
+        assert!((position.y - 1.5).abs() < 0.05, "{position}");
+    }
+}
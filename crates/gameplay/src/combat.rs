@@ -0,0 +1,358 @@
+//! 近战攻击
+//!
+//! - 按攻击键（鼠标左键）向正前方挥击，冷却期间的输入被忽略
+//! - 攻击距离与夹角范围内最近的敌人受到伤害：装备武器时为武器的 `damage`，否则为徒手伤害
+//! - 生命值归零的敌人在同一模拟步由敌人系统移除，并发出 `EnemyDefeated`
+//! - 敌人的攻击（`EnemyAttack`，由 AI 发出）按攻击者的 `attack_power` 扣除玩家生命值；
+//!   翻滚无敌时间内受到的攻击被忽略
+
+use bevy::prelude::*;
+use vigilant_doodle_core::balance::EquipmentSlot;
+use vigilant_doodle_core::{state::GameState, BalanceConfig};
+use vigilant_doodle_input::actions::InputActions;
+
+use crate::abilities::MovementAbilities;
+use crate::enemy::{defeat_enemies, Enemy, EnemyAttack, EnemyStats};
+use crate::health::Health;
+use crate::interaction::interaction_target;
+use crate::inventory::Inventory;
+use crate::player::Player;
+
+/// 玩家的近战攻击状态
+#[derive(Component, Debug, Clone, Default)]
+pub struct MeleeAttack {
+    /// 距离下次可以攻击的剩余时间（秒）
+    pub cooldown: f32,
+}
+
+/// 当前攻击伤害：装备武器时取武器伤害，否则为徒手伤害
+pub fn attack_damage(inventory: Option<&Inventory>, balance: &BalanceConfig) -> f32 {
+    inventory
+        .and_then(|inventory| inventory.equipped(EquipmentSlot::Weapon))
+        .and_then(|weapon| balance.items.get(weapon))
+        .map(|weapon| weapon.damage)
+        .unwrap_or(balance.player.melee.unarmed_damage)
+}
+
+/// 近战攻击插件
+pub struct CombatPlugin;
+
+impl Plugin for CombatPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(
+            FixedUpdate,
+            (player_attack.before(defeat_enemies), apply_enemy_attacks)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// 处理攻击输入，对面前最近的敌人造成伤害
+fn player_attack(
+    mut actions: ResMut<InputActions>,
+    mut player_query: Query<(&Transform, &mut MeleeAttack, Option<&Inventory>), With<Player>>,
+    mut enemy_query: Query<(Entity, &Transform, &mut EnemyStats), With<Enemy>>,
+    balance: Res<BalanceConfig>,
+    time: Res<Time>,
+) {
+    let attack = std::mem::take(&mut actions.attack);
+    let Ok((transform, mut melee, inventory)) = player_query.single_mut() else {
+        return;
+    };
+    melee.cooldown = (melee.cooldown - time.delta_secs()).max(0.0);
+    if !attack || melee.cooldown > 0.0 {
+        return;
+    }
+
+    let config = &balance.player.melee;
+    melee.cooldown = config.cooldown;
+    let target = interaction_target(
+        transform.translation,
+        transform.rotation * Vec3::NEG_Z,
+        config.range,
+        config.attack_angle,
+        enemy_query
+            .iter()
+            .filter(|(_, _, stats)| stats.current_health > 0.0)
+            .map(|(entity, transform, _)| (entity, transform.translation)),
+    );
+    let Some(target) = target else {
+        return;
+    };
+    let Ok((_, _, mut stats)) = enemy_query.get_mut(target) else {
+        return;
+    };
+
+    let damage = attack_damage(inventory, &balance);
+    stats.current_health -= damage;
+    info!(
+        "[Combat] 命中敌人，伤害 {}，剩余生命值 {}",
+        damage,
+        stats.current_health.max(0.0)
+    );
+}
+
+/// 结算敌人对玩家的攻击，翻滚无敌时间内不受伤害
+fn apply_enemy_attacks(
+    mut attacks: MessageReader<EnemyAttack>,
+    mut player_query: Query<(&mut Health, Option<&MovementAbilities>), With<Player>>,
+    enemy_query: Query<&EnemyStats, With<Enemy>>,
+) {
+    for attack in attacks.read() {
+        let (Ok((mut health, abilities)), Ok(stats)) = (
+            player_query.get_mut(attack.target),
+            enemy_query.get(attack.attacker),
+        ) else {
+            continue;
+        };
+        if abilities.is_some_and(MovementAbilities::is_invulnerable) {
+            debug!("[Combat] 翻滚无敌，忽略攻击");
+            continue;
+        }
+        health.damage(stats.attack_power);
+        info!(
+            "[Combat] 玩家受到攻击，伤害 {}，剩余生命值 {}",
+            stats.attack_power,
+            health.current()
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::abilities::Stamina;
+    use crate::enemy::{EnemyArchetype, EnemyDefeated};
+    use crate::progression::{Progression, ProgressionPlugin};
+    use crate::spatial::SpatialIndexSet;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+
+    /// 每帧一个模拟步
+    const STEP: Duration = Duration::from_millis(10);
+
+    fn spawn_enemy(app: &mut App, position: Vec3, health: f32) -> Entity {
+        app.world_mut()
+            .spawn((
+                Transform::from_translation(position),
+                Enemy,
+                EnemyArchetype("grunt".to_string()),
+                EnemyStats {
+                    max_health: health,
+                    current_health: health,
+                    ..default()
+                },
+            ))
+            .id()
+    }
+
+    fn combat_app(balance: &BalanceConfig) -> App {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(STEP))
+            .insert_resource(Time::<Fixed>::from_duration(STEP))
+            .insert_state(GameState::Playing)
+            .insert_resource(balance.clone())
+            .init_resource::<InputActions>()
+            .add_message::<EnemyDefeated>()
+            .add_message::<EnemyAttack>()
+            .add_systems(FixedUpdate, defeat_enemies.before(SpatialIndexSet))
+            .add_plugins((CombatPlugin, ProgressionPlugin));
+        app
+    }
+
+    #[test]
+    fn test_attack_defeats_enemy_and_awards_xp() {
+        let balance = BalanceConfig::default();
+        let mut app = combat_app(&balance);
+
+        let mut inventory = Inventory::from_balance(&balance);
+        inventory.restore(&[], &["knife".to_string()], &balance.items);
+        // 玩家朝向 -Z
+        let player = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                Player::from_balance(&balance),
+                MeleeAttack::default(),
+                inventory,
+                Progression::default(),
+            ))
+            .id();
+        let knife = balance.items["knife"].damage;
+        let target = spawn_enemy(&mut app, Vec3::new(0.0, 0.0, -1.5), knife * 1.5);
+        let behind = spawn_enemy(&mut app, Vec3::new(0.0, 0.0, 1.0), knife);
+        // 首帧没有经过时间
+        app.update();
+
+        let attack = |app: &mut App| {
+            app.world_mut().resource_mut::<InputActions>().attack = true;
+            app.update();
+        };
+        let health =
+            |app: &App, enemy: Entity| app.world().get::<EnemyStats>(enemy).unwrap().current_health;
+        attack(&mut app);
+        assert_eq!(health(&app, target), knife * 0.5);
+        assert_eq!(health(&app, behind), knife);
+
+        // 冷却中的攻击无效
+        attack(&mut app);
+        assert_eq!(health(&app, target), knife * 0.5);
+
+        let cooldown_steps = (balance.player.melee.cooldown / STEP.as_secs_f32()).ceil() as usize;
+        for _ in 0..cooldown_steps {
+            app.update();
+        }
+        attack(&mut app);
+
+        // 敌人被击败，玩家获得该原型的经验值
+        assert!(app.world().get_entity(target).is_err());
+        assert!(app.world().get_entity(behind).is_ok());
+        let progression = app.world().get::<Progression>(player).unwrap();
+        assert_eq!(progression.xp(), balance.enemy.archetypes["grunt"].xp);
+    }
+
+    fn spawn_player(app: &mut App, balance: &BalanceConfig) -> Entity {
+        // 玩家朝向 -Z，徒手
+        app.world_mut()
+            .spawn((
+                Transform::default(),
+                Player::from_balance(balance),
+                MeleeAttack::default(),
+                Inventory::from_balance(balance),
+            ))
+            .id()
+    }
+
+    #[test]
+    fn test_attack_damage_uses_equipped_weapon() {
+        let balance = BalanceConfig::default();
+        let unarmed = balance.player.melee.unarmed_damage;
+        assert_eq!(attack_damage(None, &balance), unarmed);
+
+        let mut inventory = Inventory::from_balance(&balance);
+        assert_eq!(attack_damage(Some(&inventory), &balance), unarmed);
+        inventory.restore(&[], &["knife".to_string()], &balance.items);
+        assert_eq!(
+            attack_damage(Some(&inventory), &balance),
+            balance.items["knife"].damage
+        );
+    }
+
+    #[test]
+    fn test_attack_hits_only_nearest_enemy_in_range_and_angle() {
+        let balance = BalanceConfig::default();
+        let config = &balance.player.melee;
+        let mut app = combat_app(&balance);
+        spawn_player(&mut app, &balance);
+
+        // 正前方最近的敌人、正前方更远的敌人、超出攻击距离、超出攻击夹角
+        let half_angle = (config.attack_angle * 0.5 + 10.0).to_radians();
+        let positions = [
+            Vec3::new(0.0, 0.0, -1.0),
+            Vec3::new(0.0, 0.0, -config.range * 0.9),
+            Vec3::new(0.0, 0.0, -config.range - 0.5),
+            Vec3::new(half_angle.sin(), 0.0, -half_angle.cos()),
+        ];
+        let enemies = positions.map(|position| spawn_enemy(&mut app, position, 100.0));
+        app.update();
+
+        app.world_mut().resource_mut::<InputActions>().attack = true;
+        app.update();
+        let health =
+            enemies.map(|enemy| app.world().get::<EnemyStats>(enemy).unwrap().current_health);
+        assert_eq!(health, [100.0 - config.unarmed_damage, 100.0, 100.0, 100.0]);
+    }
+
+    #[test]
+    fn test_attack_cooldown_limits_hits() {
+        let balance = BalanceConfig::default();
+        let config = &balance.player.melee;
+        let mut app = combat_app(&balance);
+        spawn_player(&mut app, &balance);
+        let enemy = spawn_enemy(&mut app, Vec3::new(0.0, 0.0, -1.0), 1000.0);
+        app.update();
+
+        // 持续按住攻击键 1 秒：每个冷却时间只命中一次
+        let steps = (1.0 / STEP.as_secs_f32()).round() as usize;
+        for _ in 0..steps {
+            app.world_mut().resource_mut::<InputActions>().attack = true;
+            app.update();
+        }
+        let hits = (1.0 / config.cooldown).ceil();
+        let health = app.world().get::<EnemyStats>(enemy).unwrap().current_health;
+        assert_eq!(health, 1000.0 - hits * config.unarmed_damage);
+    }
+
+    #[test]
+    fn test_enemy_attack_damages_player() {
+        let balance = BalanceConfig::default();
+        let mut app = combat_app(&balance);
+        let player = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                Player::from_balance(&balance),
+                Health::from_balance(&balance),
+            ))
+            .id();
+        let enemy = spawn_enemy(&mut app, Vec3::new(0.0, 0.0, -1.0), 100.0);
+        let attack_power = app.world().get::<EnemyStats>(enemy).unwrap().attack_power;
+        app.update();
+
+        app.world_mut().write_message(EnemyAttack {
+            attacker: enemy,
+            target: player,
+        });
+        app.update();
+        let health = app.world().get::<Health>(player).unwrap();
+        assert_eq!(health.current(), balance.player.max_health - attack_power);
+    }
+
+    #[test]
+    fn test_dodge_invulnerability_ignores_enemy_attacks() {
+        let balance = BalanceConfig::default();
+        let mut app = combat_app(&balance);
+        let player = app
+            .world_mut()
+            .spawn((
+                Transform::default(),
+                Player::from_balance(&balance),
+                Health::from_balance(&balance),
+                MovementAbilities::from_balance(&balance),
+            ))
+            .id();
+        let enemy = spawn_enemy(&mut app, Vec3::new(0.0, 0.0, -1.0), 100.0);
+        let attack_power = app.world().get::<EnemyStats>(enemy).unwrap().attack_power;
+        app.update();
+
+        let hit = |app: &mut App| {
+            app.world_mut().write_message(EnemyAttack {
+                attacker: enemy,
+                target: player,
+            });
+            app.update();
+            app.world().get::<Health>(player).unwrap().current()
+        };
+
+        // 翻滚开始：处于无敌时间内，攻击无效
+        let mut stamina = Stamina::from_balance(&balance);
+        let mut abilities = app
+            .world_mut()
+            .get_mut::<MovementAbilities>(player)
+            .unwrap();
+        assert!(abilities.try_dodge(Vec3::X, &mut stamina));
+        assert!(abilities.is_invulnerable());
+        assert_eq!(hit(&mut app), balance.player.max_health);
+
+        // 无敌时间结束后受到伤害
+        let mut abilities = app
+            .world_mut()
+            .get_mut::<MovementAbilities>(player)
+            .unwrap();
+        abilities.tick(balance.player.dodge.invulnerability);
+        assert!(!abilities.is_invulnerable());
+        assert_eq!(hit(&mut app), balance.player.max_health - attack_power);
+    }
+}
@@ -0,0 +1,503 @@
+//! 遭遇导演（敌人波次）
+//!
+//! 按平衡配置中的波次表随时间生成敌人：
+//! - 第一波在开始游戏后 `initial_delay` 秒出现，之后每波按表中的延迟出现；
+//!   波次表用完后重复最后一波并逐波增加数量
+//! - 场上敌人被清空时提前进入下一波（最多再等 `clear_delay` 秒）
+//! - 根据玩家表现调整强度系数：清场则提高，下一波到来时场上敌人仍过多则降低；
+//!   每波数量按强度系数缩放，且不超过场上上限
+//! - 生成点取自关卡的敌人生成点，并避开玩家周围的禁区；同一小组的敌人聚在可行走区域内一起出现；
+//!   位置取自 `GameRng` 的 `encounter` 流，同一种子下可复现
+//!
+//! 击败数只统计 `EnemyDefeated`（关卡重置或区块卸载移除的敌人不算）。
+//! 关卡重置（切换关卡）时遭遇重新开始。波次、分数等状态通过 `EncounterState` 资源提供给 HUD。
+
+use bevy::prelude::*;
+use rand::Rng;
+use vigilant_doodle_assets::GameAssets;
+use vigilant_doodle_core::balance::{EnemyEncounterBalance, WaveBalance};
+use vigilant_doodle_core::{rng::GameRng, state::GameState, BalanceConfig};
+use vigilant_doodle_world::level::{LevelChanged, LevelLayout, LevelSet};
+
+use crate::enemy::{enemy_bundle, Enemy, EnemyDefeated, DEFAULT_ARCHETYPE};
+use crate::player::Player;
+use crate::spatial::SpatialIndexSet;
+
+/// 寻找小组生成点的最多尝试次数
+const MAX_SPAWN_ATTEMPTS: usize = 32;
+/// 场上已满时推迟下一波的时间（秒）
+const FULL_RETRY_DELAY: f32 = 1.0;
+/// 生成点使用的随机流
+const RNG_STREAM: &str = "encounter";
+
+/// 一次波次生成计划
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct WavePlan {
+    /// 波次编号（从 1 开始）
+    pub wave: u32,
+    /// 生成数量
+    pub count: u32,
+    /// 小组数量
+    pub groups: u32,
+}
+
+/// 遭遇状态（供 HUD 显示）
+#[derive(Resource, Debug, Clone)]
+pub struct EncounterState {
+    /// 已开始的波次数
+    wave: u32,
+    /// 分数
+    score: u32,
+    /// 击败的敌人数
+    kills: u32,
+    /// 场上敌人数
+    enemies_alive: u32,
+    /// 强度系数
+    intensity: f32,
+    /// 距离下一波的时间
+    next_wave_in: f32,
+    /// 当前波是否已被清场
+    wave_cleared: bool,
+}
+
+impl EncounterState {
+    /// 新的遭遇，第一波在 `initial_delay` 秒后出现
+    pub fn new(config: &EnemyEncounterBalance) -> Self {
+        Self {
+            wave: 0,
+            score: 0,
+            kills: 0,
+            enemies_alive: 0,
+            intensity: 1.0,
+            next_wave_in: config.initial_delay,
+            wave_cleared: false,
+        }
+    }
+
+    pub fn wave(&self) -> u32 {
+        self.wave
+    }
+
+    pub fn score(&self) -> u32 {
+        self.score
+    }
+
+    pub fn kills(&self) -> u32 {
+        self.kills
+    }
+
+    pub fn enemies_alive(&self) -> u32 {
+        self.enemies_alive
+    }
+
+    pub fn intensity(&self) -> f32 {
+        self.intensity
+    }
+
+    /// 距离下一波的时间（秒）
+    pub fn next_wave_in(&self) -> f32 {
+        self.next_wave_in.max(0.0)
+    }
+
+    /// 当前波是否已被清场
+    pub fn wave_cleared(&self) -> bool {
+        self.wave_cleared
+    }
+
+    /// 恢复状态（读档，场上敌人数在下一步重新统计）
+    pub fn restore(
+        &mut self,
+        wave: u32,
+        score: u32,
+        kills: u32,
+        intensity: f32,
+        next_wave_in: f32,
+        wave_cleared: bool,
+    ) {
+        self.wave = wave;
+        self.score = score;
+        self.kills = kills;
+        self.intensity = intensity;
+        self.next_wave_in = next_wave_in;
+        self.wave_cleared = wave_cleared;
+    }
+
+    /// 推进导演：记录击败数与场上敌人数，到时返回下一波的生成计划
+    pub fn advance(
+        &mut self,
+        config: &EnemyEncounterBalance,
+        alive: u32,
+        new_kills: u32,
+        delta: f32,
+    ) -> Option<WavePlan> {
+        self.kills += new_kills;
+        self.score += new_kills * config.score_per_kill;
+        self.enemies_alive = alive;
+
+        // 清场：玩家表现良好，提高强度并提前下一波
+        if self.wave > 0 && alive == 0 && !self.wave_cleared {
+            self.wave_cleared = true;
+            self.score += config.score_per_wave;
+            self.adjust_intensity(config, config.intensity_step);
+            self.next_wave_in = self.next_wave_in.min(config.clear_delay);
+        }
+
+        self.next_wave_in -= delta;
+        if self.next_wave_in > 0.0 {
+            return None;
+        }
+
+        let capacity = config.max_alive.saturating_sub(alive);
+        let wave = wave_definition(config, self.wave + 1)?;
+        if capacity == 0 {
+            self.next_wave_in = FULL_RETRY_DELAY;
+            return None;
+        }
+
+        // 撑到下一波：计分；场上敌人仍过多说明玩家吃力，降低强度
+        if self.wave > 0 && !self.wave_cleared {
+            self.score += config.score_per_wave;
+            if alive * 2 > config.max_alive {
+                self.adjust_intensity(config, -config.intensity_step);
+            }
+        }
+
+        self.wave += 1;
+        self.wave_cleared = false;
+        self.next_wave_in =
+            wave_definition(config, self.wave + 1).map_or(f32::INFINITY, |next| next.delay);
+
+        let count = ((wave.count as f32 * self.intensity).round() as u32)
+            .max(1)
+            .min(capacity);
+        Some(WavePlan {
+            wave: self.wave,
+            count,
+            groups: wave.groups.clamp(1, count),
+        })
+    }
+
+    fn adjust_intensity(&mut self, config: &EnemyEncounterBalance, step: f32) {
+        self.intensity = (self.intensity + step).clamp(config.min_intensity, config.max_intensity);
+    }
+}
+
+/// 第 `wave` 波（从 1 开始）的定义，波次表用完后重复最后一波并增加数量
+fn wave_definition(config: &EnemyEncounterBalance, wave: u32) -> Option<WaveBalance> {
+    let index = wave.checked_sub(1)? as usize;
+    if let Some(definition) = config.waves.get(index) {
+        return Some(definition.clone());
+    }
+    let mut last = config.waves.last()?.clone();
+    let extra = (index + 1 - config.waves.len()) as u32;
+    last.count += config.endless_growth * extra;
+    Some(last)
+}
+
+/// 为一波敌人选择生成点
+///
+/// 先为每个小组从关卡的敌人生成点中选一个远离玩家的中心，再在中心周围的可行走格子上散布组员；
+/// 关卡没有生成点时在边界内随机取中心；找不到满足禁区的中心时使用尝试中离玩家最远的点；
+/// 生成点放在地表上
+pub fn spawn_points(
+    rng: &mut impl Rng,
+    player: Vec3,
+    count: u32,
+    groups: u32,
+    config: &EnemyEncounterBalance,
+    level: &LevelLayout,
+) -> Vec<Vec3> {
+    let half = (level.bounds.half_size() - config.spawn_margin).max(Vec2::ZERO);
+    let center_of_bounds = level.bounds.center();
+    let spread = config.group_spread.min(half.x).min(half.y);
+    let player = player.xz();
+
+    let groups = groups.clamp(1, count.max(1));
+    let mut points = Vec::with_capacity(count as usize);
+    for group in 0..groups {
+        let members = count / groups + u32::from(group < count % groups);
+
+        // 小组中心：离玩家至少“禁区 + 散布半径”
+        let mut center = center_of_bounds;
+        let mut best_distance = f32::NEG_INFINITY;
+        for _ in 0..MAX_SPAWN_ATTEMPTS {
+            let candidate = if level.enemy_spawns.is_empty() {
+                center_of_bounds
+                    + Vec2::new(
+                        rng.random_range(-(half.x - spread)..=half.x - spread),
+                        rng.random_range(-(half.y - spread)..=half.y - spread),
+                    )
+            } else {
+                level.enemy_spawns[rng.random_range(0..level.enemy_spawns.len())]
+            };
+            let distance = candidate.distance(player);
+            if distance > best_distance {
+                best_distance = distance;
+                center = candidate;
+            }
+            if distance >= config.exclusion_radius + spread {
+                break;
+            }
+        }
+
+        for _ in 0..members {
+            // 组员落在可行走格子上，多次尝试失败时与中心重合
+            let mut point = center;
+            for _ in 0..MAX_SPAWN_ATTEMPTS {
+                let angle = rng.random_range(0.0..std::f32::consts::TAU);
+                let radius = spread * rng.random_range(0.0f32..=1.0).sqrt();
+                let candidate = center + Vec2::from_angle(angle) * radius;
+                if level.walkable.is_walkable(candidate) {
+                    point = candidate;
+                    break;
+                }
+            }
+            points.push(
+                level
+                    .terrain
+                    .clamp_to_terrain(Vec3::new(point.x, 0.0, point.y)),
+            );
+        }
+    }
+    points
+}
+
+/// 遭遇导演插件
+pub struct EncounterPlugin;
+
+impl Plugin for EncounterPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnExit(GameState::AssetLoading), init_encounter)
+            .add_systems(
+                Update,
+                reset_encounter
+                    .after(LevelSet)
+                    .run_if(resource_exists::<EncounterState>),
+            )
+            .add_systems(
+                FixedUpdate,
+                run_encounter_director
+                    .after(SpatialIndexSet)
+                    .run_if(in_state(GameState::Playing))
+                    .run_if(resource_exists::<EncounterState>),
+            );
+    }
+}
+
+fn init_encounter(mut commands: Commands, balance: Res<BalanceConfig>) {
+    commands.insert_resource(EncounterState::new(&balance.enemy.encounter));
+}
+
+/// 关卡重置时重新开始遭遇
+fn reset_encounter(
+    mut changed: MessageReader<LevelChanged>,
+    mut state: ResMut<EncounterState>,
+    balance: Res<BalanceConfig>,
+) {
+    if changed.read().any(|event| event.respawn) {
+        *state = EncounterState::new(&balance.enemy.encounter);
+    }
+}
+
+/// 遭遇导演系统
+#[allow(clippy::too_many_arguments)]
+fn run_encounter_director(
+    mut commands: Commands,
+    mut state: ResMut<EncounterState>,
+    mut game_rng: ResMut<GameRng>,
+    mut defeated: MessageReader<EnemyDefeated>,
+    enemy_query: Query<(), With<Enemy>>,
+    player_query: Query<&Transform, With<Player>>,
+    assets: Res<GameAssets>,
+    balance: Res<BalanceConfig>,
+    level: Res<LevelLayout>,
+    time: Res<Time>,
+) {
+    let config = &balance.enemy.encounter;
+    let kills = defeated.read().count() as u32;
+    let alive = enemy_query.iter().count() as u32;
+
+    let Some(plan) = state.advance(config, alive, kills, time.delta_secs()) else {
+        return;
+    };
+
+    let player = player_query
+        .single()
+        .map_or(Vec3::ZERO, |transform| transform.translation);
+    let mut rng = game_rng.stream(RNG_STREAM);
+    let points = spawn_points(&mut rng, player, plan.count, plan.groups, config, &level);
+
+    for (i, position) in points.into_iter().enumerate() {
+        commands.spawn(enemy_bundle(
+            &assets,
+            &balance,
+            position,
+            DEFAULT_ARCHETYPE,
+            format!("Enemy_{}_{}", plan.wave, i),
+        ));
+    }
+
+    info!(
+        "[Encounter] 第 {} 波：{} 个敌人（{} 组），强度 {:.2}",
+        plan.wave,
+        plan.count,
+        plan.groups,
+        state.intensity()
+    );
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use rand::SeedableRng;
+    use rand::rngs::StdRng;
+    use vigilant_doodle_core::balance::LevelBalance;
+    use vigilant_doodle_world::generate_level;
+
+    fn config() -> EnemyEncounterBalance {
+        EnemyEncounterBalance {
+            initial_delay: 2.0,
+            clear_delay: 3.0,
+            waves: vec![
+                WaveBalance {
+                    delay: 0.0,
+                    count: 4,
+                    groups: 1,
+                },
+                WaveBalance {
+                    delay: 10.0,
+                    count: 6,
+                    groups: 2,
+                },
+            ],
+            endless_growth: 2,
+            ..default()
+        }
+    }
+
+    /// 以固定步长推进直到生成下一波，返回计划与经过的时间
+    fn run_until_wave(
+        state: &mut EncounterState,
+        config: &EnemyEncounterBalance,
+        alive: u32,
+    ) -> (WavePlan, f32) {
+        let mut elapsed = 0.0;
+        for _ in 0..10_000 {
+            elapsed += 0.1;
+            if let Some(plan) = state.advance(config, alive, 0, 0.1) {
+                return (plan, elapsed);
+            }
+        }
+        panic!("没有生成波次");
+    }
+
+    #[test]
+    fn test_waves_follow_schedule() {
+        let config = config();
+        let mut state = EncounterState::new(&config);
+
+        let (first, elapsed) = run_until_wave(&mut state, &config, 0);
+        assert_eq!((first.wave, first.count, first.groups), (1, 4, 1));
+        assert!((elapsed - 2.0).abs() < 0.15);
+
+        // 场上仍有敌人：按表中的延迟生成第二波
+        let (second, elapsed) = run_until_wave(&mut state, &config, 4);
+        assert_eq!((second.wave, second.count, second.groups), (2, 6, 2));
+        assert!((elapsed - 10.0).abs() < 0.15);
+        assert_eq!(state.score(), config.score_per_wave);
+
+        // 波次表用完后重复最后一波并增加数量
+        let (third, _) = run_until_wave(&mut state, &config, 4);
+        assert_eq!((third.wave, third.count), (3, 8));
+    }
+
+    #[test]
+    fn test_clearing_wave_raises_intensity() {
+        let config = config();
+        let mut state = EncounterState::new(&config);
+        run_until_wave(&mut state, &config, 0);
+
+        // 击败全部敌人：计分、提高强度，下一波在清场延迟后到来
+        assert!(state.advance(&config, 0, 4, 0.1).is_none());
+        assert_eq!(state.kills(), 4);
+        assert_eq!(
+            state.score(),
+            4 * config.score_per_kill + config.score_per_wave
+        );
+        assert!((state.intensity() - 1.15).abs() < 1e-5);
+        assert!(state.next_wave_in() <= config.clear_delay);
+
+        let (second, elapsed) = run_until_wave(&mut state, &config, 0);
+        assert!(elapsed <= config.clear_delay);
+        assert_eq!(second.count, 7); // 6 × 1.15 ≈ 7
+    }
+
+    #[test]
+    fn test_restored_state_continues_schedule() {
+        let config = config();
+        let mut state = EncounterState::new(&config);
+        run_until_wave(&mut state, &config, 0);
+        state.advance(&config, 0, 4, 0.1);
+
+        let mut restored = EncounterState::new(&config);
+        restored.restore(
+            state.wave(),
+            state.score(),
+            state.kills(),
+            state.intensity(),
+            state.next_wave_in(),
+            state.wave_cleared(),
+        );
+        assert_eq!(
+            run_until_wave(&mut restored, &config, 0),
+            run_until_wave(&mut state, &config, 0)
+        );
+        assert_eq!(restored.score(), state.score());
+    }
+
+    #[test]
+    fn test_overwhelmed_player_lowers_intensity_and_cap_holds() {
+        let config = EnemyEncounterBalance {
+            max_alive: 8,
+            ..config()
+        };
+        let mut state = EncounterState::new(&config);
+        run_until_wave(&mut state, &config, 0);
+
+        // 下一波到来时场上仍有 5 个（超过上限一半）：降低强度，数量受上限限制
+        let (second, _) = run_until_wave(&mut state, &config, 5);
+        assert!((state.intensity() - 0.85).abs() < 1e-5);
+        assert_eq!(second.count, 3);
+
+        // 场上已满：推迟
+        let mut full = state.clone();
+        for _ in 0..1000 {
+            assert!(full.advance(&config, 8, 0, 0.1).is_none());
+        }
+    }
+
+    #[test]
+    fn test_spawn_points_respect_level_and_exclusion() {
+        let config = EnemyEncounterBalance::default();
+        let mut rng = StdRng::seed_from_u64(7);
+
+        // 空旷地面与生成的关卡
+        let levels = [
+            LevelLayout::default(),
+            generate_level(3, &LevelBalance::default()),
+        ];
+        for level in &levels {
+            for player in [Vec3::ZERO, Vec3::new(60.0, 0.0, 25.0)] {
+                let points = spawn_points(&mut rng, player, 12, 3, &config, level);
+                assert_eq!(points.len(), 12);
+                for point in points {
+                    assert!(level.walkable.is_walkable(point.xz()), "{point}");
+                    assert!(
+                        point.distance(player.with_y(0.0)) >= config.exclusion_radius,
+                        "{point}"
+                    );
+                }
+            }
+        }
+    }
+}
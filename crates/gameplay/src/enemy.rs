@@ -1,13 +1,25 @@
 //! 敌人基础组件
 //!
-//! 定义敌人实体的基础属性和生成逻辑
-//! AI 组件由 game crate 统一添加
+//! 定义敌人实体的基础属性与组件组合
+//! 敌人由遭遇导演（`encounter`）按波次生成，或由关卡文件预先放置；AI 组件由 game crate 统一添加
+//! 玩家的近战攻击（`combat`）降低敌人生命值；生命值归零的敌人被移除，并发出 `EnemyDefeated`（供目标与经验值统计）
+//! 敌人的攻击由 AI 发出 `EnemyAttack`，在 `combat` 中结算为玩家伤害
 
 use bevy::prelude::*;
 
 use vigilant_doodle_assets::GameAssets;
-use vigilant_doodle_core::state::GameState;
+use vigilant_doodle_core::balance::EnemyArchetypeBalance;
+use vigilant_doodle_core::{state::GameState, BalanceConfig};
+use vigilant_doodle_world::level::{LevelChanged, LevelLayout, LevelSet};
+use vigilant_doodle_world::streaming::Streamed;
 use crate::movement::CollisionRadius;
+use crate::simulation::TransformInterpolation;
+use crate::spatial::SpatialIndexSet;
+
+/// 默认敌人原型（波次生成的敌人）
+pub const DEFAULT_ARCHETYPE: &str = "grunt";
+/// 敌人碰撞半径（原型缩放前）
+const BASE_COLLISION_RADIUS: f32 = 0.6;
 
 /// 敌人标记组件
 ///
@@ -19,13 +31,12 @@ pub struct Enemy;
 ///
 /// 存储敌人的基础属性（生命值、攻击力等）
 #[derive(Component)]
-#[allow(dead_code)] // TODO: 未来实现战斗系统时会使用
 pub struct EnemyStats {
     /// 最大生命值
     pub max_health: f32,
     /// 当前生命值
     pub current_health: f32,
-    /// 攻击力（未来扩展）
+    /// 攻击力（每次命中对玩家造成的伤害）
     pub attack_power: f32,
 }
 
@@ -39,54 +50,175 @@ impl Default for EnemyStats {
     }
 }
 
-/// 敌人插件
+/// 敌人原型
+///
+/// 名称对应平衡配置中的 `enemy.archetypes`
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct EnemyArchetype(pub String);
+
+impl EnemyArchetype {
+    /// 原型参数（未知原型使用默认参数）
+    pub fn config(&self, balance: &BalanceConfig) -> EnemyArchetypeBalance {
+        balance
+            .enemy
+            .archetypes
+            .get(&self.0)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+/// 敌人被击败
+#[derive(Message, Debug, Clone)]
+pub struct EnemyDefeated {
+    pub entity: Entity,
+    /// 敌人原型名称
+    pub archetype: String,
+    pub position: Vec3,
+}
+
+/// 敌人攻击
+///
+/// AI 的攻击行为按攻击间隔发出，由 `combat` 结算伤害
+#[derive(Message, Debug, Clone, Copy)]
+pub struct EnemyAttack {
+    /// 发起攻击的敌人
+    pub attacker: Entity,
+    /// 被攻击的目标
+    pub target: Entity,
+}
+
+/// 关卡文件指定的巡逻路线
+#[derive(Component, Debug, Clone, Default)]
+pub struct PatrolRoute(pub Vec<Vec3>);
+
+/// 单个敌人的实体组件
 ///
-/// 负责敌人实体的生成
+/// 由遭遇导演、关卡放置或读档生成；AI 组件由 game crate 的系统添加
+pub fn enemy_bundle(
+    assets: &GameAssets,
+    balance: &BalanceConfig,
+    position: Vec3,
+    archetype: &str,
+    name: String,
+) -> impl Bundle {
+    let archetype = EnemyArchetype(archetype.to_string());
+    let config = archetype.config(balance);
+    (
+        // 使用 glb 模型
+        SceneRoot(assets.enemy_model.clone()),
+        Transform::from_translation(position).with_scale(Vec3::splat(config.scale)),
+        // 敌人标记
+        Enemy,
+        archetype,
+        // 敌人属性
+        EnemyStats {
+            max_health: config.health,
+            current_health: config.health,
+            ..default()
+        },
+        // 碰撞检测
+        CollisionRadius::new(BASE_COLLISION_RADIUS * config.scale),
+        // 渲染插值（模拟在固定步长中进行）
+        TransformInterpolation::default(),
+        // 所在区块卸载时冻结
+        Streamed,
+        // 调试名称
+        Name::new(name),
+    )
+}
+
+/// 敌人插件（关卡预置敌人）
 pub struct EnemyPlugin;
 
 impl Plugin for EnemyPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            OnEnter(GameState::AssetLoading),
-            spawn_enemies.after(vigilant_doodle_assets::load_assets),
-        );
+        app.add_message::<EnemyDefeated>()
+            .add_message::<EnemyAttack>()
+            .add_systems(Update, spawn_level_enemies.after(LevelSet))
+            .add_systems(
+                FixedUpdate,
+                defeat_enemies
+                    .before(SpatialIndexSet)
+                    .run_if(in_state(GameState::Playing)),
+            );
     }
 }
 
-/// 生成敌人系统
-fn spawn_enemies(
+/// 移除生命值归零的敌人
+pub(crate) fn defeat_enemies(
     mut commands: Commands,
+    enemy_query: Query<(Entity, &EnemyStats, &EnemyArchetype, &Transform), With<Enemy>>,
+    mut defeated: MessageWriter<EnemyDefeated>,
+) {
+    for (entity, stats, archetype, transform) in enemy_query.iter() {
+        if stats.current_health > 0.0 {
+            continue;
+        }
+        defeated.write(EnemyDefeated {
+            entity,
+            archetype: archetype.0.clone(),
+            position: transform.translation,
+        });
+        commands.entity(entity).despawn();
+        info!("[Enemy] {} 被击败", archetype.0);
+    }
+}
+
+/// 关卡重置时移除现有敌人并生成关卡中预先放置的敌人
+fn spawn_level_enemies(
+    mut commands: Commands,
+    mut changed: MessageReader<LevelChanged>,
+    enemy_query: Query<Entity, With<Enemy>>,
     assets: Res<GameAssets>,
+    balance: Res<BalanceConfig>,
+    level: Res<LevelLayout>,
 ) {
-    use rand::Rng;
-    let mut rng = rand::rng();
-
-    info!("[Enemy] 开始生成敌人...");
-
-    // 生成 3 个敌人（适配扩大后的地图范围）
-    for i in 0..3 {
-        let x = rng.random_range(-60.0..60.0);
-        let z = rng.random_range(-30.0..30.0);
-
-        commands.spawn((
-            // 使用 glb 模型
-            SceneRoot(assets.enemy_model.clone()),
-            Transform::from_xyz(x, 0.0, z),
-            // 敌人标记
-            Enemy,
-            // 敌人属性
-            EnemyStats::default(),
-            // 碰撞检测
-            CollisionRadius::new(0.6),
-            // AI 组件将由 game crate 的系统添加
-            // 调试名称
-            Name::new(format!("Enemy_{}", i)),
-        ));
+    if !changed.read().any(|event| event.respawn) {
+        return;
+    }
 
-        debug!("[Enemy] 生成敌人 {} at ({:.1}, {:.1})", i, x, z);
+    for entity in enemy_query.iter() {
+        commands.entity(entity).despawn();
     }
 
-    info!("[Enemy] 敌人生成完成（共 3 个，使用模型）");
+    for (i, placement) in level.enemy_placements.iter().enumerate() {
+        if !balance.enemy.archetypes.contains_key(&placement.archetype) {
+            warn!(
+                "[Enemy] 未知的敌人原型 {:?}，使用默认参数",
+                placement.archetype
+            );
+        }
+        let position = level.terrain.clamp_to_terrain(Vec3::new(
+            placement.position.x,
+            0.0,
+            placement.position.y,
+        ));
+        let mut enemy = commands.spawn(enemy_bundle(
+            &assets,
+            &balance,
+            position,
+            &placement.archetype,
+            format!("Enemy_{}_{}", placement.archetype, i),
+        ));
+        if !placement.patrol.is_empty() {
+            enemy.insert(PatrolRoute(
+                placement
+                    .patrol
+                    .iter()
+                    .map(|point| Vec3::new(point.x, 0.0, point.y))
+                    .collect(),
+            ));
+        }
+    }
+
+    if !level.enemy_placements.is_empty() {
+        info!(
+            "[Enemy] 关卡 '{}' 放置了 {} 个敌人",
+            level.name,
+            level.enemy_placements.len()
+        );
+    }
 }
 
 #[cfg(test)]
@@ -100,4 +232,15 @@ mod tests {
         assert_eq!(stats.current_health, 100.0);
         assert_eq!(stats.attack_power, 10.0);
     }
+
+    #[test]
+    fn test_unknown_archetype_uses_default() {
+        let balance = BalanceConfig::default();
+        let scout = EnemyArchetype("scout".to_string()).config(&balance);
+        assert!(scout.speed_multiplier > 1.0);
+
+        let unknown = EnemyArchetype("dragon".to_string()).config(&balance);
+        assert_eq!(unknown.speed_multiplier, 1.0);
+        assert_eq!(unknown.health, 100.0);
+    }
 }
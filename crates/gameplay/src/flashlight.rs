@@ -0,0 +1,342 @@
+//! 手电筒
+//!
+//! 玩家携带的手电筒：
+//! - 开关键切换开启，开启时持续消耗电量，电量耗尽后自动关闭
+//! - 电量低于阈值时闪烁，电量越低熄灭的时间越长
+//! - 光束朝向与身体朝向无关，由鼠标左右移动转动（世界空间方位角）
+//! - 电池作为消耗品放在背包中，使用后恢复电量（见 `inventory`）
+//!
+//! 敌人通过 `Flashlight::illuminates` 判断自己是否处在光束中。
+//! 电量与瞄准在固定步长中更新，光源的亮度与朝向在渲染帧中同步。
+
+use bevy::color::palettes::css::YELLOW_GREEN;
+use bevy::prelude::*;
+use vigilant_doodle_core::{state::GameState, BalanceConfig};
+use vigilant_doodle_input::actions::InputActions;
+
+use crate::player::Player;
+use crate::spatial::SpatialIndexSet;
+
+/// 光源相对玩家的位置
+const BEAM_OFFSET: Vec3 = Vec3::new(0.0, 2.0, 0.0);
+/// 闪烁熄灭时保留的亮度比例
+const FLICKER_DIM: f32 = 0.1;
+
+/// 手电筒组件（挂在玩家上）
+#[derive(Component, Debug, Clone)]
+pub struct Flashlight {
+    /// 光照强度
+    pub intensity: f32,
+    /// 光照距离
+    pub range: f32,
+    /// 光锥半角（弧度）
+    pub outer_angle: f32,
+    /// 光束向下倾斜的角度（弧度）
+    pub pitch: f32,
+    /// 鼠标瞄准灵敏度（弧度/像素）
+    pub aim_sensitivity: f32,
+    /// 电量上限
+    pub capacity: f32,
+    /// 开启时每秒消耗的电量
+    pub drain_per_second: f32,
+    /// 电量低于该比例时闪烁
+    pub low_battery: f32,
+    /// 当前电量
+    charge: f32,
+    /// 是否开启
+    on: bool,
+    /// 光束方位角（绕 Y 轴，0 朝向 -Z）
+    yaw: f32,
+}
+
+impl Flashlight {
+    /// 从平衡配置创建手电筒（满电、开启）
+    pub fn from_balance(balance: &BalanceConfig) -> Self {
+        let flashlight = &balance.player.flashlight;
+        Self {
+            intensity: flashlight.intensity,
+            range: flashlight.range,
+            outer_angle: flashlight.outer_angle,
+            pitch: flashlight.pitch.to_radians(),
+            aim_sensitivity: flashlight.aim_sensitivity,
+            capacity: flashlight.battery_capacity,
+            drain_per_second: flashlight.drain_per_second,
+            low_battery: flashlight.low_battery,
+            charge: flashlight.battery_capacity,
+            on: true,
+            yaw: 0.0,
+        }
+    }
+
+    /// 当前电量
+    pub fn charge(&self) -> f32 {
+        self.charge
+    }
+
+    /// 当前电量占上限的比例（0.0 ~ 1.0）
+    pub fn fraction(&self) -> f32 {
+        if self.capacity > 0.0 {
+            (self.charge / self.capacity).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// 是否开启
+    pub fn is_on(&self) -> bool {
+        self.on
+    }
+
+    /// 是否正在发光（开启且有电）
+    pub fn is_lit(&self) -> bool {
+        self.on && self.charge > 0.0
+    }
+
+    /// 是否电量不足（会闪烁）
+    pub fn is_low(&self) -> bool {
+        self.fraction() < self.low_battery
+    }
+
+    /// 切换开关，返回切换后是否开启（没电时无法开启）
+    pub fn toggle(&mut self) -> bool {
+        self.on = !self.on && self.charge > 0.0;
+        self.on
+    }
+
+    /// 恢复状态（读档）
+    pub fn restore(&mut self, charge: f32, on: bool) {
+        self.charge = charge.clamp(0.0, self.capacity);
+        self.on = on && self.charge > 0.0;
+    }
+
+    /// 开启时消耗电量，耗尽后关闭
+    pub fn drain(&mut self, dt: f32) {
+        if !self.on {
+            return;
+        }
+        self.charge = (self.charge - self.drain_per_second * dt).max(0.0);
+        if self.charge <= 0.0 {
+            self.on = false;
+        }
+    }
+
+    /// 恢复电量（不超过上限）
+    pub fn recharge(&mut self, amount: f32) {
+        self.charge = (self.charge + amount).min(self.capacity);
+    }
+
+    /// 按鼠标移动量转动光束（右移为顺时针）
+    pub fn aim(&mut self, mouse_delta: Vec2) {
+        self.yaw =
+            (self.yaw - mouse_delta.x * self.aim_sensitivity).rem_euclid(std::f32::consts::TAU);
+    }
+
+    /// 将光束转向某个水平方向
+    pub fn aim_at(&mut self, direction: Vec3) {
+        if let Some(direction) = direction.with_y(0.0).try_normalize() {
+            self.yaw = (-direction.x).atan2(-direction.z);
+        }
+    }
+
+    /// 光束的水平方向
+    pub fn aim_direction(&self) -> Vec3 {
+        Quat::from_rotation_y(self.yaw) * Vec3::NEG_Z
+    }
+
+    /// 光束的世界空间朝向（含向下倾斜）
+    pub fn beam_rotation(&self) -> Quat {
+        Quat::from_rotation_y(self.yaw) * Quat::from_rotation_x(-self.pitch)
+    }
+
+    /// 位于 `holder` 的手电筒是否照到 `point`
+    ///
+    /// 在水平面上判断：距离不超过光照距离，且与光束方向的夹角不超过光锥半角
+    pub fn illuminates(&self, holder: Vec3, point: Vec3) -> bool {
+        if !self.is_lit() {
+            return false;
+        }
+        let offset = (point - holder).with_y(0.0);
+        let distance = offset.length();
+        if distance > self.range {
+            return false;
+        }
+        distance <= f32::EPSILON || self.aim_direction().angle_between(offset) <= self.outer_angle
+    }
+
+    /// 当前亮度（`elapsed` 为经过的时间，用于低电量闪烁）
+    pub fn brightness(&self, elapsed: f32) -> f32 {
+        if !self.is_lit() {
+            return 0.0;
+        }
+        if !self.is_low() || self.low_battery <= 0.0 {
+            return self.intensity;
+        }
+        let severity = 1.0 - self.fraction() / self.low_battery;
+        self.intensity * flicker(elapsed, severity)
+    }
+}
+
+/// 闪烁系数：几个不同频率的正弦叠加超过阈值时熄灭，`severity` 越大阈值越低
+fn flicker(elapsed: f32, severity: f32) -> f32 {
+    let noise = (elapsed * 11.0).sin() + (elapsed * 17.3 + 1.7).sin() + (elapsed * 3.1).sin();
+    if noise > 3.0 - 3.0 * severity.clamp(0.0, 1.0) {
+        FLICKER_DIM
+    } else {
+        1.0
+    }
+}
+
+/// 标记：手电筒光源（玩家的子实体）
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct FlashlightBeam;
+
+/// 手电筒光源
+pub(crate) fn flashlight_beam(flashlight: &Flashlight) -> impl Bundle {
+    (
+        SpotLight {
+            color: YELLOW_GREEN.into(),
+            range: flashlight.range,
+            intensity: flashlight.intensity,
+            outer_angle: flashlight.outer_angle,
+            inner_angle: flashlight.outer_angle * 0.5,
+            shadows_enabled: true,
+            ..default()
+        },
+        Transform::from_translation(BEAM_OFFSET).with_rotation(flashlight.beam_rotation()),
+        FlashlightBeam,
+        Name::new("PlayerFlashlight"),
+    )
+}
+
+/// 手电筒插件
+pub struct FlashlightPlugin;
+
+impl Plugin for FlashlightPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(Update, update_flashlight_beam).add_systems(
+            FixedUpdate,
+            update_flashlight
+                // 在 AI 检测之前更新，敌人使用本步的光束方向
+                .before(SpatialIndexSet)
+                .run_if(in_state(GameState::Playing)),
+        );
+    }
+}
+
+/// 处理开关与瞄准输入并消耗电量
+fn update_flashlight(
+    mut player_query: Query<&mut Flashlight, With<Player>>,
+    mut actions: ResMut<InputActions>,
+    time: Res<Time>,
+) {
+    let aim = std::mem::take(&mut actions.aim);
+    let toggle = std::mem::take(&mut actions.toggle_flashlight);
+    let Ok(mut flashlight) = player_query.single_mut() else {
+        return;
+    };
+
+    if toggle {
+        let on = flashlight.toggle();
+        info!("[Flashlight] 手电筒已{}", if on { "开启" } else { "关闭" });
+    }
+    if aim != Vec2::ZERO {
+        flashlight.aim(aim);
+    }
+
+    let was_on = flashlight.is_on();
+    flashlight.drain(time.delta_secs());
+    if was_on && !flashlight.is_on() {
+        info!("[Flashlight] 电量耗尽");
+    }
+}
+
+/// 同步光源的亮度与朝向（光束朝向独立于玩家朝向）
+fn update_flashlight_beam(
+    player_query: Query<(&Transform, &Flashlight), Without<FlashlightBeam>>,
+    mut beam_query: Query<(&ChildOf, &mut SpotLight, &mut Transform), With<FlashlightBeam>>,
+    time: Res<Time>,
+) {
+    for (child_of, mut light, mut transform) in beam_query.iter_mut() {
+        let Ok((player_transform, flashlight)) = player_query.get(child_of.parent()) else {
+            continue;
+        };
+        light.intensity = flashlight.brightness(time.elapsed_secs());
+        transform.rotation = player_transform.rotation.inverse() * flashlight.beam_rotation();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn flashlight() -> Flashlight {
+        Flashlight::from_balance(&BalanceConfig::default())
+    }
+
+    #[test]
+    fn test_battery_drain_and_toggle() {
+        let mut flashlight = flashlight();
+        assert!(flashlight.is_lit());
+
+        // 关闭时不耗电
+        assert!(!flashlight.toggle());
+        flashlight.drain(10.0);
+        assert_eq!(flashlight.fraction(), 1.0);
+
+        // 耗尽后自动关闭且无法开启
+        assert!(flashlight.toggle());
+        flashlight.drain(flashlight.capacity / flashlight.drain_per_second * 0.9);
+        assert!(flashlight.is_low());
+        flashlight.drain(1000.0);
+        assert_eq!(flashlight.charge(), 0.0);
+        assert!(!flashlight.is_on());
+        assert!(!flashlight.toggle());
+        assert_eq!(flashlight.brightness(0.0), 0.0);
+
+        // 充电后恢复，不超过上限
+        flashlight.recharge(1000.0);
+        assert_eq!(flashlight.charge(), flashlight.capacity);
+        assert!(flashlight.toggle());
+        assert_eq!(flashlight.brightness(0.0), flashlight.intensity);
+    }
+
+    #[test]
+    fn test_low_battery_flickers() {
+        let mut flashlight = flashlight();
+        flashlight.restore(flashlight.capacity * 0.02, true);
+        let samples: Vec<f32> = (0..200)
+            .map(|i| flashlight.brightness(i as f32 * 0.05))
+            .collect();
+        assert!(samples.contains(&flashlight.intensity));
+        assert!(samples.iter().any(|&b| b < flashlight.intensity));
+    }
+
+    #[test]
+    fn test_illuminates_beam_cone() {
+        let mut flashlight = flashlight();
+        let holder = Vec3::new(5.0, 0.0, 5.0);
+        flashlight.aim_at(Vec3::X);
+        assert!(flashlight.aim_direction().distance(Vec3::X) < 1e-5);
+
+        assert!(flashlight.illuminates(holder, holder + Vec3::new(10.0, 1.0, 2.0)));
+        // 身后、光锥外、超出距离
+        assert!(!flashlight.illuminates(holder, holder + Vec3::new(-10.0, 0.0, 0.0)));
+        assert!(!flashlight.illuminates(holder, holder + Vec3::new(2.0, 0.0, 10.0)));
+        assert!(!flashlight.illuminates(holder, holder + Vec3::new(60.0, 0.0, 0.0)));
+
+        // 鼠标右移使光束顺时针（俯视）转动：+X 转向 +Z
+        flashlight.aim(Vec2::new(
+            std::f32::consts::FRAC_PI_2 / flashlight.aim_sensitivity,
+            0.0,
+        ));
+        assert!(flashlight.aim_direction().distance(Vec3::Z) < 1e-4);
+        assert!(flashlight.illuminates(holder, holder + Vec3::new(0.0, 0.0, 10.0)));
+
+        // 光束向下倾斜
+        assert!((flashlight.beam_rotation() * Vec3::NEG_Z).y < 0.0);
+
+        // 关闭后照不到
+        flashlight.toggle();
+        assert!(!flashlight.illuminates(holder, holder + Vec3::new(0.0, 0.0, 10.0)));
+    }
+}
@@ -0,0 +1,86 @@
+//! 玩家生命值
+//!
+//! 上限取自平衡配置的 `player.max_health`（升级可提高，见 `progression`）；医疗包等消耗品通过 `Health::heal` 恢复。
+
+use bevy::prelude::*;
+use vigilant_doodle_core::BalanceConfig;
+
+/// 生命值组件（挂在玩家上）
+#[derive(Component, Debug, Clone)]
+pub struct Health {
+    /// 生命值上限
+    pub max: f32,
+    /// 当前生命值
+    current: f32,
+}
+
+impl Health {
+    /// 从平衡配置创建（满血）
+    pub fn from_balance(balance: &BalanceConfig) -> Self {
+        Self {
+            max: balance.player.max_health,
+            current: balance.player.max_health,
+        }
+    }
+
+    /// 当前生命值
+    pub fn current(&self) -> f32 {
+        self.current
+    }
+
+    /// 当前生命值占上限的比例（0.0 ~ 1.0）
+    pub fn fraction(&self) -> f32 {
+        if self.max > 0.0 {
+            (self.current / self.max).clamp(0.0, 1.0)
+        } else {
+            0.0
+        }
+    }
+
+    /// 是否满血
+    pub fn is_full(&self) -> bool {
+        self.current >= self.max
+    }
+
+    /// 恢复生命值（不超过上限）
+    pub fn heal(&mut self, amount: f32) {
+        self.current = (self.current + amount).min(self.max);
+    }
+
+    /// 受到伤害（不低于 0）
+    pub fn damage(&mut self, amount: f32) {
+        self.current = (self.current - amount).max(0.0);
+    }
+
+    /// 调整生命值上限：提高的部分同时恢复，降低时当前生命值不超过新上限
+    pub fn set_max(&mut self, max: f32) {
+        let increase = (max - self.max).max(0.0);
+        self.max = max;
+        self.current = (self.current + increase).min(max);
+    }
+
+    /// 恢复状态（读档）
+    pub fn restore(&mut self, current: f32) {
+        self.current = current.clamp(0.0, self.max);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_heal_and_damage_are_clamped() {
+        let mut health = Health::from_balance(&BalanceConfig::default());
+        assert!(health.is_full());
+
+        health.damage(health.max * 2.0);
+        assert_eq!(health.current(), 0.0);
+
+        health.heal(30.0);
+        assert_eq!(health.current(), 30.0);
+        health.heal(health.max);
+        assert_eq!(health.current(), health.max);
+        assert_eq!(health.fraction(), 1.0);
+    }
+}
@@ -0,0 +1,868 @@
+//! 场景中的可交互物体
+//!
+//! - 交互距离内、位于玩家正前方夹角范围内的可交互物体中，最近的一个成为交互目标（`InteractionFocus`），
+//!   HUD 显示其提示文本；按交互键（E）发出 `Interacted` 消息，由各类物体的处理系统响应
+//! - 门：打开/关闭，关闭时启用碰撞体与导航障碍；指定频道的门只能由同频道的开关控制
+//! - 开关：发出 `SwitchToggled`，切换同频道的所有门
+//! - 宝箱：物品放入背包，放不下的留在箱中，取空后不再可交互
+//! - 纸条：阅读时显示文本（`ReadingNote`），再次交互或离开后关闭
+//!
+//! 关卡重置时按 `LevelLayout::interactables` 重新放置。门、开关与宝箱的状态随存档保存（按在关卡中的序号），
+//! 读档时在存档关卡加载后按存档状态重新放置（见 `InteractableRestore`）。
+
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use vigilant_doodle_core::balance::ItemBalance;
+use vigilant_doodle_core::localization::{keys, LocalizedText};
+use vigilant_doodle_core::{state::GameState, BalanceConfig};
+use vigilant_doodle_input::actions::InputActions;
+use vigilant_doodle_world::level::{
+    InteractableKind, LevelChanged, LevelLayout, LevelSet, LevelSource,
+};
+use vigilant_doodle_world::{StaticCollider, StaticObstacle};
+
+use crate::inventory::{Inventory, ItemCollected, ItemStack};
+use crate::movement::CollisionRadius;
+use crate::player::Player;
+use crate::spatial::SpatialIndexSet;
+
+/// 门板开关的速度（米/秒）
+const DOOR_SLIDE_SPEED: f32 = 4.0;
+/// 宝箱半尺寸
+const CHEST_HALF_EXTENTS: Vec3 = Vec3::new(0.6, 0.4, 0.4);
+/// 开关立柱尺寸
+const SWITCH_SIZE: Vec3 = Vec3::new(0.2, 1.2, 0.2);
+/// 纸条尺寸
+const NOTE_SIZE: Vec3 = Vec3::new(0.4, 0.02, 0.3);
+
+/// 可交互物体
+#[derive(Component, Debug, Clone)]
+pub struct Interactable {
+    /// 提示文本（HUD 中显示为“[E] 提示”）
+    pub prompt: LocalizedText,
+    /// 是否可交互（禁用时不会成为交互目标）
+    pub enabled: bool,
+}
+
+impl Interactable {
+    pub fn new(prompt: &str) -> Self {
+        Self {
+            prompt: LocalizedText::new(prompt),
+            enabled: true,
+        }
+    }
+}
+
+/// 当前交互目标
+#[derive(Resource, Debug, Default, PartialEq)]
+pub struct InteractionFocus {
+    pub target: Option<Entity>,
+}
+
+/// 正在阅读的纸条
+#[derive(Resource, Debug, Default)]
+pub struct ReadingNote {
+    pub note: Option<Entity>,
+}
+
+/// 玩家与物体交互
+#[derive(Message, Debug, Clone, Copy)]
+pub struct Interacted {
+    pub entity: Entity,
+}
+
+/// 开关被拨动
+#[derive(Message, Debug, Clone)]
+pub struct SwitchToggled {
+    pub channel: String,
+    /// 拨动后的状态
+    pub on: bool,
+}
+
+/// 门
+#[derive(Component, Debug, Clone)]
+pub struct Door {
+    pub open: bool,
+    /// 控制频道（为空时玩家可直接开关）
+    pub channel: Option<String>,
+    /// 门板半尺寸
+    half_extents: Vec3,
+}
+
+impl Door {
+    /// 打开时门板的位移：沿门板走向滑开一个门宽
+    fn slide(&self) -> Vec3 {
+        if self.half_extents.x >= self.half_extents.z {
+            Vec3::X * self.half_extents.x * 2.0
+        } else {
+            Vec3::Z * self.half_extents.z * 2.0
+        }
+    }
+
+    /// 当前状态对应的提示文本
+    fn prompt(&self) -> &'static str {
+        match (self.channel.is_some(), self.open) {
+            (true, _) => keys::INTERACT_DOOR_LOCKED,
+            (false, true) => keys::INTERACT_CLOSE_DOOR,
+            (false, false) => keys::INTERACT_OPEN_DOOR,
+        }
+    }
+
+    /// 角色（XZ 位置与半径）是否挡在门洞中
+    fn blocked_by(&self, door_position: Vec3, position: Vec3, radius: f32) -> bool {
+        let offset = (position - door_position).abs();
+        offset.x < self.half_extents.x + radius && offset.z < self.half_extents.z + radius
+    }
+}
+
+/// 标记：门板（门的子实体，打开时滑开）
+#[derive(Component)]
+struct DoorPanel;
+
+/// 开关
+#[derive(Component, Debug, Clone)]
+pub struct Switch {
+    pub channel: String,
+    pub on: bool,
+}
+
+/// 宝箱
+#[derive(Component, Debug, Clone, Default)]
+pub struct Chest {
+    /// 箱中剩余的物品
+    pub items: Vec<ItemStack>,
+}
+
+impl Chest {
+    /// 把物品放入背包，返回放入的物品与数量；放不下的留在箱中
+    pub fn take_into(
+        &mut self,
+        inventory: &mut Inventory,
+        items: &BTreeMap<String, ItemBalance>,
+    ) -> Vec<ItemStack> {
+        let mut taken = Vec::new();
+        self.items.retain_mut(|stack| {
+            let added = inventory.add(&stack.item, stack.count, items);
+            stack.count -= added;
+            if added > 0 {
+                taken.push(ItemStack {
+                    item: stack.item.clone(),
+                    count: added,
+                });
+            }
+            stack.count > 0
+        });
+        taken
+    }
+}
+
+/// 纸条
+#[derive(Component, Debug, Clone)]
+pub struct Note {
+    /// 文本的翻译键
+    pub text: String,
+}
+
+/// 标记：按关卡放置的可交互物体（关卡重置时移除），值为在 `LevelLayout::interactables` 中的序号
+#[derive(Component, Debug, Clone, Copy, PartialEq, Eq)]
+pub struct LevelInteractable(pub u32);
+
+/// 可交互物体的可变状态（用于存档）
+#[derive(Debug, Clone, PartialEq)]
+pub enum InteractableState {
+    Door {
+        open: bool,
+    },
+    Switch {
+        on: bool,
+    },
+    /// 箱中剩余的物品
+    Chest {
+        items: Vec<ItemStack>,
+    },
+}
+
+/// 读档时等待应用的可交互物体状态
+#[derive(Resource, Debug, Default)]
+pub struct InteractableRestore {
+    pending: Option<(LevelSource, BTreeMap<u32, InteractableState>)>,
+}
+
+impl InteractableRestore {
+    /// 恢复存档中的状态（按关卡中的序号）
+    ///
+    /// 存档关卡加载后（当前就是存档关卡时在下一次更新）按此重新放置可交互物体
+    pub fn restore(
+        &mut self,
+        source: LevelSource,
+        states: impl IntoIterator<Item = (u32, InteractableState)>,
+    ) {
+        self.pending = Some((source, states.into_iter().collect()));
+    }
+
+    /// 关卡加载后取出需要应用的状态，返回 None 表示无需重新放置
+    ///
+    /// 关卡重置时返回空状态（按关卡初始状态放置）；存档关卡已加载时返回存档中的状态；
+    /// 其他关卡加载后丢弃等待中的状态
+    fn sync(
+        &mut self,
+        level: &LevelLayout,
+        respawn: Option<bool>,
+    ) -> Option<BTreeMap<u32, InteractableState>> {
+        if respawn == Some(true) {
+            self.pending = None;
+            return Some(BTreeMap::new());
+        }
+        let ready = self.pending.take_if(|(source, _)| *source == level.source);
+        if ready.is_none() && respawn.is_some() {
+            self.pending = None;
+        }
+        ready.map(|(_, states)| states)
+    }
+}
+
+/// 可交互物体的网格与材质
+#[derive(Resource)]
+struct InteractableAssets {
+    chest_mesh: Handle<Mesh>,
+    switch_mesh: Handle<Mesh>,
+    note_mesh: Handle<Mesh>,
+    door_material: Handle<StandardMaterial>,
+    chest_material: Handle<StandardMaterial>,
+    switch_off: Handle<StandardMaterial>,
+    switch_on: Handle<StandardMaterial>,
+    note_material: Handle<StandardMaterial>,
+}
+
+/// 在候选物体中选择交互目标
+///
+/// 只考虑水平距离不超过 `range`、且方向与 `forward` 的夹角不超过 `facing_angle` 一半（度）的物体，取最近的一个
+pub fn interaction_target(
+    origin: Vec3,
+    forward: Vec3,
+    range: f32,
+    facing_angle: f32,
+    candidates: impl IntoIterator<Item = (Entity, Vec3)>,
+) -> Option<Entity> {
+    let forward = forward.xz().normalize_or_zero();
+    let min_dot = (facing_angle.to_radians() * 0.5).cos();
+    candidates
+        .into_iter()
+        .filter_map(|(entity, position)| {
+            let offset = (position - origin).xz();
+            let distance = offset.length();
+            if distance > range {
+                return None;
+            }
+            // 站在物体上方时不要求朝向
+            let facing = distance < f32::EPSILON || offset.dot(forward) / distance >= min_dot;
+            facing.then_some((entity, distance))
+        })
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .map(|(entity, _)| entity)
+}
+
+/// 交互插件
+pub struct InteractionPlugin;
+
+impl Plugin for InteractionPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<InteractionFocus>()
+            .init_resource::<ReadingNote>()
+            .init_resource::<InteractableRestore>()
+            .add_message::<Interacted>()
+            .add_message::<SwitchToggled>()
+            .add_systems(OnExit(GameState::AssetLoading), setup_interactable_assets)
+            .add_systems(
+                Update,
+                (
+                    spawn_level_interactables
+                        .after(LevelSet)
+                        .run_if(resource_exists::<InteractableAssets>),
+                    animate_doors,
+                ),
+            )
+            .add_systems(
+                FixedUpdate,
+                (
+                    update_interaction_focus,
+                    interact,
+                    (use_switches, open_chests, read_notes),
+                    toggle_doors,
+                )
+                    .chain()
+                    // 与背包系统一样在 AI 检测之前，门的碰撞体在下一步生效
+                    .before(SpatialIndexSet)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+fn setup_interactable_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let mut material = |color: Color, emissive: f32| {
+        materials.add(StandardMaterial {
+            base_color: color,
+            emissive: color.to_linear() * emissive,
+            perceptual_roughness: 0.8,
+            ..default()
+        })
+    };
+    commands.insert_resource(InteractableAssets {
+        chest_mesh: meshes.add(Cuboid::from_size(CHEST_HALF_EXTENTS * 2.0)),
+        switch_mesh: meshes.add(Cuboid::from_size(SWITCH_SIZE)),
+        note_mesh: meshes.add(Cuboid::from_size(NOTE_SIZE)),
+        door_material: material(Color::srgb(0.35, 0.25, 0.18), 0.0),
+        chest_material: material(Color::srgb(0.55, 0.4, 0.2), 0.0),
+        switch_off: material(Color::srgb(0.8, 0.2, 0.15), 1.5),
+        switch_on: material(Color::srgb(0.3, 0.85, 0.3), 1.5),
+        note_material: material(Color::srgb(0.95, 0.93, 0.85), 0.5),
+    });
+}
+
+/// 关卡重置或读档后重新放置可交互物体
+///
+/// 存档中的状态与关卡中该序号的物体类型不一致时（关卡已修改），按关卡初始状态放置
+#[allow(clippy::too_many_arguments)]
+fn spawn_level_interactables(
+    mut commands: Commands,
+    mut changed: MessageReader<LevelChanged>,
+    existing: Query<Entity, With<LevelInteractable>>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    assets: Res<InteractableAssets>,
+    level: Res<LevelLayout>,
+    mut restore: ResMut<InteractableRestore>,
+    mut reading: ResMut<ReadingNote>,
+) {
+    let respawn = changed
+        .read()
+        .map(|event| event.respawn)
+        .reduce(|a, b| a || b);
+    let Some(mut states) = restore.sync(&level, respawn) else {
+        return;
+    };
+
+    for entity in existing.iter() {
+        commands.entity(entity).despawn();
+    }
+    reading.note = None;
+
+    for (index, placement) in (0..).zip(&level.interactables) {
+        let state = states.remove(&index);
+        let ground = level.terrain.clamp_to_terrain(Vec3::new(
+            placement.position.x,
+            0.0,
+            placement.position.y,
+        ));
+        match &placement.kind {
+            InteractableKind::Door {
+                half_extents,
+                height,
+                channel,
+                open,
+            } => {
+                let open = match state {
+                    Some(InteractableState::Door { open }) => open,
+                    _ => *open,
+                };
+                let door = Door {
+                    open,
+                    channel: channel.clone(),
+                    half_extents: Vec3::new(half_extents.x, height * 0.5, half_extents.y),
+                };
+                let panel = if door.open { door.slide() } else { Vec3::ZERO };
+                let mut entity = commands.spawn((
+                    Transform::from_translation(ground + Vec3::Y * door.half_extents.y),
+                    Visibility::default(),
+                    Interactable::new(door.prompt()),
+                    LevelInteractable(index),
+                    Name::new(format!("Door_{}", index)),
+                ));
+                entity.with_child((
+                    Mesh3d(meshes.add(Cuboid::from_size(door.half_extents * 2.0))),
+                    MeshMaterial3d(assets.door_material.clone()),
+                    Transform::from_translation(panel),
+                    DoorPanel,
+                ));
+                entity.insert((door_blockers(&door), door));
+            }
+            InteractableKind::Switch { channel } => {
+                let on = matches!(state, Some(InteractableState::Switch { on: true }));
+                let material = if on {
+                    assets.switch_on.clone()
+                } else {
+                    assets.switch_off.clone()
+                };
+                commands.spawn((
+                    Mesh3d(assets.switch_mesh.clone()),
+                    MeshMaterial3d(material),
+                    Transform::from_translation(ground + Vec3::Y * SWITCH_SIZE.y * 0.5),
+                    Switch {
+                        channel: channel.clone(),
+                        on,
+                    },
+                    Interactable::new(keys::INTERACT_USE_SWITCH),
+                    LevelInteractable(index),
+                    Name::new(format!("Switch_{}", index)),
+                ));
+            }
+            InteractableKind::Chest { items } => {
+                let items = match state {
+                    Some(InteractableState::Chest { items }) => items,
+                    _ => items
+                        .iter()
+                        .map(|(item, count)| ItemStack {
+                            item: item.clone(),
+                            count: *count,
+                        })
+                        .collect(),
+                };
+                let chest = Chest { items };
+                commands.spawn((
+                    Mesh3d(assets.chest_mesh.clone()),
+                    MeshMaterial3d(assets.chest_material.clone()),
+                    Transform::from_translation(ground + Vec3::Y * CHEST_HALF_EXTENTS.y),
+                    StaticCollider::cuboid(CHEST_HALF_EXTENTS),
+                    StaticObstacle::new(CHEST_HALF_EXTENTS.xz(), CHEST_HALF_EXTENTS.y * 2.0),
+                    chest_interactable(&chest),
+                    chest,
+                    LevelInteractable(index),
+                    Name::new(format!("Chest_{}", index)),
+                ));
+            }
+            InteractableKind::Note { text } => {
+                commands.spawn((
+                    Mesh3d(assets.note_mesh.clone()),
+                    MeshMaterial3d(assets.note_material.clone()),
+                    Transform::from_translation(ground + Vec3::Y * NOTE_SIZE.y),
+                    Note { text: text.clone() },
+                    Interactable::new(keys::INTERACT_READ_NOTE),
+                    LevelInteractable(index),
+                    Name::new(format!("Note_{}", index)),
+                ));
+            }
+        }
+    }
+    info!(
+        "[Interaction] 放置了 {} 个可交互物体",
+        level.interactables.len()
+    );
+}
+
+/// 门的碰撞体与导航障碍：只有关闭的门阻挡角色与导航
+///
+/// 开关门只切换 `enabled`，碰撞层与导航网格由变更检测重建
+fn door_blockers(door: &Door) -> (StaticCollider, StaticObstacle) {
+    (
+        StaticCollider::cuboid(door.half_extents).with_enabled(!door.open),
+        StaticObstacle::new(door.half_extents.xz(), door.half_extents.y * 2.0)
+            .with_enabled(!door.open),
+    )
+}
+
+/// 宝箱当前状态对应的交互组件（取空后禁用）
+fn chest_interactable(chest: &Chest) -> Interactable {
+    if chest.items.is_empty() {
+        Interactable {
+            prompt: LocalizedText::new(keys::INTERACT_CHEST_EMPTY),
+            enabled: false,
+        }
+    } else {
+        Interactable::new(keys::INTERACT_OPEN_CHEST)
+    }
+}
+
+/// 更新交互目标；离开正在阅读的纸条时关闭纸条
+fn update_interaction_focus(
+    player_query: Query<&Transform, With<Player>>,
+    interactable_query: Query<(Entity, &GlobalTransform, &Interactable)>,
+    balance: Res<BalanceConfig>,
+    mut focus: ResMut<InteractionFocus>,
+    mut reading: ResMut<ReadingNote>,
+) {
+    let target = player_query.single().ok().and_then(|transform| {
+        let config = &balance.player.interaction;
+        interaction_target(
+            transform.translation,
+            transform.rotation * Vec3::NEG_Z,
+            config.range,
+            config.facing_angle,
+            interactable_query
+                .iter()
+                .filter(|(_, _, interactable)| interactable.enabled)
+                .map(|(entity, transform, _)| (entity, transform.translation())),
+        )
+    });
+
+    focus.set_if_neq(InteractionFocus { target });
+    if reading.note.is_some() && reading.note != target {
+        reading.note = None;
+    }
+}
+
+/// 处理交互输入
+fn interact(
+    mut actions: ResMut<InputActions>,
+    focus: Res<InteractionFocus>,
+    mut interacted: MessageWriter<Interacted>,
+) {
+    if !std::mem::take(&mut actions.interact) {
+        return;
+    }
+    if let Some(entity) = focus.target {
+        interacted.write(Interacted { entity });
+    }
+}
+
+/// 拨动开关
+fn use_switches(
+    mut interacted: MessageReader<Interacted>,
+    mut switch_query: Query<(&mut Switch, &mut MeshMaterial3d<StandardMaterial>)>,
+    mut toggled: MessageWriter<SwitchToggled>,
+    assets: Option<Res<InteractableAssets>>,
+) {
+    for event in interacted.read() {
+        let Ok((mut switch, mut material)) = switch_query.get_mut(event.entity) else {
+            continue;
+        };
+        switch.on = !switch.on;
+        if let Some(assets) = &assets {
+            material.0 = if switch.on {
+                assets.switch_on.clone()
+            } else {
+                assets.switch_off.clone()
+            };
+        }
+        info!("[Interaction] 开关 {} -> {}", switch.channel, switch.on);
+        toggled.write(SwitchToggled {
+            channel: switch.channel.clone(),
+            on: switch.on,
+        });
+    }
+}
+
+/// 打开宝箱
+fn open_chests(
+    mut interacted: MessageReader<Interacted>,
+    mut chest_query: Query<(&mut Chest, &mut Interactable)>,
+    mut player_query: Query<&mut Inventory, With<Player>>,
+    mut collected: MessageWriter<ItemCollected>,
+    balance: Res<BalanceConfig>,
+) {
+    for event in interacted.read() {
+        let Ok((mut chest, mut interactable)) = chest_query.get_mut(event.entity) else {
+            continue;
+        };
+        let Ok(mut inventory) = player_query.single_mut() else {
+            return;
+        };
+        let taken = chest.take_into(&mut inventory, &balance.items);
+        if taken.is_empty() {
+            info!("[Interaction] 背包已满，无法取出宝箱中的物品");
+            continue;
+        }
+        *interactable = chest_interactable(&chest);
+        for stack in taken {
+            info!("[Interaction] 从宝箱中取出 {} x{}", stack.item, stack.count);
+            collected.write(ItemCollected {
+                item: stack.item,
+                count: stack.count,
+            });
+        }
+    }
+}
+
+/// 阅读纸条（再次交互时关闭）
+fn read_notes(
+    mut interacted: MessageReader<Interacted>,
+    note_query: Query<(), With<Note>>,
+    mut reading: ResMut<ReadingNote>,
+) {
+    for event in interacted.read() {
+        if !note_query.contains(event.entity) {
+            continue;
+        }
+        reading.note = if reading.note == Some(event.entity) {
+            None
+        } else {
+            Some(event.entity)
+        };
+    }
+}
+
+/// 开关门：直接交互（无频道的门）或同频道的开关被拨动
+///
+/// 玩家站在门洞中时不会关门
+fn toggle_doors(
+    mut interacted: MessageReader<Interacted>,
+    mut toggled: MessageReader<SwitchToggled>,
+    mut door_query: Query<(
+        Entity,
+        &Transform,
+        &mut Door,
+        &mut Interactable,
+        &mut StaticCollider,
+        &mut StaticObstacle,
+    )>,
+    player_query: Query<(&Transform, &CollisionRadius), With<Player>>,
+) {
+    let direct: Vec<Entity> = interacted.read().map(|event| event.entity).collect();
+    let channels: Vec<String> = toggled.read().map(|event| event.channel.clone()).collect();
+    if direct.is_empty() && channels.is_empty() {
+        return;
+    }
+
+    for (entity, transform, mut door, mut interactable, mut collider, mut obstacle) in
+        door_query.iter_mut()
+    {
+        let triggered = match &door.channel {
+            Some(channel) => channels.contains(channel),
+            None => direct.contains(&entity),
+        };
+        if !triggered {
+            continue;
+        }
+        if door.open
+            && player_query.iter().any(|(player, radius)| {
+                door.blocked_by(transform.translation, player.translation, radius.radius)
+            })
+        {
+            info!("[Interaction] 门洞被挡住，无法关门");
+            continue;
+        }
+
+        door.open = !door.open;
+        collider.enabled = !door.open;
+        obstacle.enabled = !door.open;
+        interactable.prompt = LocalizedText::new(door.prompt());
+        info!(
+            "[Interaction] {} {}",
+            entity,
+            if door.open { "开门" } else { "关门" }
+        );
+    }
+}
+
+/// 门板滑向当前状态对应的位置
+fn animate_doors(
+    door_query: Query<&Door>,
+    mut panel_query: Query<(&ChildOf, &mut Transform), With<DoorPanel>>,
+    time: Res<Time>,
+) {
+    for (child_of, mut transform) in panel_query.iter_mut() {
+        let Ok(door) = door_query.get(child_of.parent()) else {
+            continue;
+        };
+        let target = if door.open { door.slide() } else { Vec3::ZERO };
+        let offset = target - transform.translation;
+        let step = DOOR_SLIDE_SPEED * time.delta_secs();
+        transform.translation = if offset.length() <= step {
+            target
+        } else {
+            transform.translation + offset.normalize() * step
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vigilant_doodle_world::level::InteractablePlacement;
+
+    fn interactables_app() -> App {
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .add_message::<LevelChanged>()
+            .init_resource::<ReadingNote>()
+            .init_resource::<InteractableRestore>()
+            .insert_resource(Assets::<Mesh>::default())
+            .insert_resource(InteractableAssets {
+                chest_mesh: default(),
+                switch_mesh: default(),
+                note_mesh: default(),
+                door_material: default(),
+                chest_material: default(),
+                switch_off: default(),
+                switch_on: default(),
+                note_material: default(),
+            })
+            .insert_resource(LevelLayout {
+                interactables: vec![
+                    InteractablePlacement {
+                        kind: InteractableKind::Door {
+                            half_extents: Vec2::new(1.0, 0.1),
+                            height: 2.0,
+                            channel: Some("gate".to_string()),
+                            open: false,
+                        },
+                        position: Vec2::new(0.0, 5.0),
+                    },
+                    InteractablePlacement {
+                        kind: InteractableKind::Switch {
+                            channel: "gate".to_string(),
+                        },
+                        position: Vec2::new(3.0, 3.0),
+                    },
+                    InteractablePlacement {
+                        kind: InteractableKind::Chest {
+                            items: vec![("battery".to_string(), 2)],
+                        },
+                        position: Vec2::new(-3.0, 3.0),
+                    },
+                ],
+                ..default()
+            })
+            .add_systems(Update, spawn_level_interactables);
+        app
+    }
+
+    fn states(app: &mut App) -> Vec<(u32, bool, Option<InteractableState>)> {
+        let mut query = app.world_mut().query::<(
+            &LevelInteractable,
+            &Interactable,
+            Option<&Door>,
+            Option<&Switch>,
+            Option<&Chest>,
+        )>();
+        let mut states: Vec<_> = query
+            .iter(app.world())
+            .map(|(index, interactable, door, switch, chest)| {
+                let state = match (door, switch, chest) {
+                    (Some(door), ..) => Some(InteractableState::Door { open: door.open }),
+                    (_, Some(switch), _) => Some(InteractableState::Switch { on: switch.on }),
+                    (.., Some(chest)) => Some(InteractableState::Chest {
+                        items: chest.items.clone(),
+                    }),
+                    _ => None,
+                };
+                (index.0, interactable.enabled, state)
+            })
+            .collect();
+        states.sort_by_key(|(index, ..)| *index);
+        states
+    }
+
+    #[test]
+    fn test_restore_reapplies_saved_states() {
+        let mut app = interactables_app();
+        app.world_mut()
+            .write_message(LevelChanged { respawn: true });
+        app.update();
+        let battery = vec![ItemStack {
+            item: "battery".to_string(),
+            count: 2,
+        }];
+        assert_eq!(
+            states(&mut app),
+            vec![
+                (0, true, Some(InteractableState::Door { open: false })),
+                (1, true, Some(InteractableState::Switch { on: false })),
+                (2, true, Some(InteractableState::Chest { items: battery })),
+            ]
+        );
+
+        // 读档：当前就是存档关卡，下一次更新时按存档状态重新放置
+        let source = app.world().resource::<LevelLayout>().source.clone();
+        app.world_mut()
+            .resource_mut::<InteractableRestore>()
+            .restore(
+                source,
+                [
+                    (0, InteractableState::Door { open: true }),
+                    (1, InteractableState::Switch { on: true }),
+                    (2, InteractableState::Chest { items: Vec::new() }),
+                ],
+            );
+        app.update();
+        assert_eq!(
+            states(&mut app),
+            vec![
+                (0, true, Some(InteractableState::Door { open: true })),
+                (1, true, Some(InteractableState::Switch { on: true })),
+                // 已取空的宝箱不可再交互
+                (
+                    2,
+                    false,
+                    Some(InteractableState::Chest { items: Vec::new() })
+                ),
+            ]
+        );
+        // 打开的门不再阻挡
+        let mut doors = app.world_mut().query::<(&Door, &StaticCollider)>();
+        let (_, collider) = doors.single(app.world()).unwrap();
+        assert!(!collider.enabled);
+    }
+
+    #[test]
+    fn test_interaction_target_prefers_nearest_in_front() {
+        let mut world = World::new();
+        let [near, far, behind, out_of_range] = [(); 4].map(|_| world.spawn_empty().id());
+        let candidates = [
+            (far, Vec3::new(0.0, 0.0, -1.8)),
+            (near, Vec3::new(0.5, 0.0, -1.0)),
+            (behind, Vec3::new(0.0, 0.0, 0.5)),
+            (out_of_range, Vec3::new(0.0, 0.0, -3.0)),
+        ];
+
+        let target = |candidates: &[(Entity, Vec3)]| {
+            interaction_target(
+                Vec3::ZERO,
+                Vec3::NEG_Z,
+                2.0,
+                120.0,
+                candidates.iter().copied(),
+            )
+        };
+        assert_eq!(target(&candidates), Some(near));
+        // 身后更近的物体不算
+        assert_eq!(target(&candidates[2..]), None);
+        assert_eq!(target(&candidates[..1]), Some(far));
+    }
+
+    #[test]
+    fn test_chest_keeps_items_that_do_not_fit() {
+        let balance = BalanceConfig::default();
+        let mut inventory = Inventory::from_balance(&balance);
+        inventory.capacity = 1;
+        let mut chest = Chest {
+            items: vec![
+                ItemStack {
+                    item: "battery".to_string(),
+                    count: 3,
+                },
+                ItemStack {
+                    item: "medkit".to_string(),
+                    count: 1,
+                },
+            ],
+        };
+
+        let taken = chest.take_into(&mut inventory, &balance.items);
+        assert_eq!(taken.len(), 1);
+        assert_eq!(taken[0].count, 3);
+        assert_eq!(inventory.count("battery"), 3);
+        assert_eq!(chest.items.len(), 1);
+        assert_eq!(chest.items[0].item, "medkit");
+
+        inventory.capacity = 2;
+        let taken = chest.take_into(&mut inventory, &balance.items);
+        assert_eq!(taken[0].item, "medkit");
+        assert!(chest.items.is_empty());
+    }
+
+    #[test]
+    fn test_door_blocking_and_slide() {
+        let door = Door {
+            open: true,
+            channel: None,
+            half_extents: Vec3::new(4.0, 1.5, 0.3),
+        };
+        assert_eq!(door.slide(), Vec3::new(8.0, 0.0, 0.0));
+        assert_eq!(door.prompt(), keys::INTERACT_CLOSE_DOOR);
+        assert!(door.blocked_by(Vec3::ZERO, Vec3::new(2.0, 0.0, 0.5), 0.6));
+        assert!(!door.blocked_by(Vec3::ZERO, Vec3::new(2.0, 0.0, 1.0), 0.6));
+    }
+}
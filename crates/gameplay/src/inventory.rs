@@ -0,0 +1,426 @@
+//! 玩家背包
+//!
+//! - 背包有固定格数（`player.inventory.capacity`），同种物品按 `max_stack` 堆叠，装满后无法拾取
+//! - 数字键 1~9 使用对应格的物品：
+//!   消耗品（医疗包、电池）立即生效，效果无意义时（满血、满电）不消耗；
+//!   装备（小刀等武器）放入对应装备槽，原有装备放回背包
+//! - 装备的武器模型挂在玩家身上
+//!
+//! 物品定义来自平衡配置的 `items`，背包只记录物品名称与数量。
+
+use bevy::prelude::*;
+use std::collections::BTreeMap;
+use vigilant_doodle_assets::GameAssets;
+use vigilant_doodle_core::balance::{EquipmentSlot, ItemBalance, ItemKind};
+use vigilant_doodle_core::{state::GameState, BalanceConfig};
+use vigilant_doodle_input::actions::InputActions;
+
+use crate::flashlight::Flashlight;
+use crate::health::Health;
+use crate::player::Player;
+use crate::spatial::SpatialIndexSet;
+
+/// 手持武器相对玩家的位置
+const WEAPON_OFFSET: Vec3 = Vec3::new(0.45, 0.9, -0.3);
+
+/// 一格物品
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ItemStack {
+    /// 物品名称
+    pub item: String,
+    pub count: u32,
+}
+
+/// 背包组件（挂在玩家上）
+#[derive(Component, Debug, Clone, Default)]
+pub struct Inventory {
+    /// 格数
+    pub capacity: usize,
+    /// 物品格（不含空格）
+    slots: Vec<ItemStack>,
+    /// 装备槽
+    equipped: BTreeMap<EquipmentSlot, String>,
+}
+
+impl Inventory {
+    /// 从平衡配置创建空背包
+    pub fn from_balance(balance: &BalanceConfig) -> Self {
+        Self {
+            capacity: balance.player.inventory.capacity,
+            ..default()
+        }
+    }
+
+    /// 物品格
+    pub fn slots(&self) -> &[ItemStack] {
+        &self.slots
+    }
+
+    /// 某种物品的总数
+    pub fn count(&self, item: &str) -> u32 {
+        self.slots
+            .iter()
+            .filter(|stack| stack.item == item)
+            .map(|stack| stack.count)
+            .sum()
+    }
+
+    /// 装备槽中的物品
+    pub fn equipped(&self, slot: EquipmentSlot) -> Option<&str> {
+        self.equipped.get(&slot).map(String::as_str)
+    }
+
+    /// 所有装备
+    pub fn equipment(&self) -> impl Iterator<Item = (EquipmentSlot, &str)> {
+        self.equipped
+            .iter()
+            .map(|(slot, item)| (*slot, item.as_str()))
+    }
+
+    /// 放入物品，先补满已有的堆叠再占用空格，返回实际放入的数量（未知物品放不进）
+    pub fn add(&mut self, item: &str, count: u32, items: &BTreeMap<String, ItemBalance>) -> u32 {
+        let Some(definition) = items.get(item) else {
+            return 0;
+        };
+        let max_stack = definition.max_stack.max(1);
+
+        let mut remaining = count;
+        for stack in self.slots.iter_mut().filter(|stack| stack.item == item) {
+            let added = remaining.min(max_stack.saturating_sub(stack.count));
+            stack.count += added;
+            remaining -= added;
+        }
+        while remaining > 0 && self.slots.len() < self.capacity {
+            let added = remaining.min(max_stack);
+            self.slots.push(ItemStack {
+                item: item.to_string(),
+                count: added,
+            });
+            remaining -= added;
+        }
+        count - remaining
+    }
+
+    /// 从第 `index` 格取出一个物品
+    pub fn take(&mut self, index: usize) -> Option<String> {
+        let stack = self.slots.get_mut(index)?;
+        stack.count -= 1;
+        let item = stack.item.clone();
+        if stack.count == 0 {
+            self.slots.remove(index);
+        }
+        Some(item)
+    }
+
+    /// 将第 `index` 格的物品放入装备槽，原有装备放回背包；背包放不下时不交换
+    pub fn equip(
+        &mut self,
+        index: usize,
+        slot: EquipmentSlot,
+        items: &BTreeMap<String, ItemBalance>,
+    ) -> bool {
+        let Some(item) = self.take(index) else {
+            return false;
+        };
+        let Some(previous) = self.equipped.insert(slot, item.clone()) else {
+            return true;
+        };
+        if self.add(&previous, 1, items) == 1 {
+            return true;
+        }
+        // 放不回去：恢复原状（刚取出一个，原格一定放得下）
+        self.equipped.insert(slot, previous);
+        self.add(&item, 1, items);
+        false
+    }
+
+    /// 恢复状态（读档），放不下或未知的物品会被丢弃
+    pub fn restore(
+        &mut self,
+        slots: &[ItemStack],
+        equipped: &[String],
+        items: &BTreeMap<String, ItemBalance>,
+    ) {
+        self.slots.clear();
+        self.equipped.clear();
+        for stack in slots {
+            self.add(&stack.item, stack.count, items);
+        }
+        for item in equipped {
+            if let Some(ItemKind::Equipment { slot }) = items.get(item).map(|item| item.kind) {
+                self.equipped.insert(slot, item.clone());
+            }
+        }
+    }
+}
+
+/// 使用第 `index` 格的物品，返回是否生效（未生效时物品保留在背包中）
+pub fn use_item(
+    inventory: &mut Inventory,
+    index: usize,
+    items: &BTreeMap<String, ItemBalance>,
+    health: &mut Health,
+    flashlight: &mut Flashlight,
+) -> bool {
+    let Some(kind) = inventory
+        .slots()
+        .get(index)
+        .and_then(|stack| items.get(&stack.item))
+        .map(|item| item.kind)
+    else {
+        return false;
+    };
+
+    match kind {
+        ItemKind::Heal { amount } => {
+            if health.is_full() {
+                return false;
+            }
+            health.heal(amount);
+        }
+        ItemKind::Recharge { amount } => {
+            if flashlight.fraction() >= 1.0 {
+                return false;
+            }
+            flashlight.recharge(amount);
+        }
+        ItemKind::Equipment { slot } => return inventory.equip(index, slot, items),
+    }
+    inventory.take(index);
+    true
+}
+
+/// 物品放入背包（拾取或从宝箱取出）
+#[derive(Message, Debug, Clone)]
+pub struct ItemCollected {
+    pub item: String,
+    pub count: u32,
+}
+
+/// 标记：玩家手持的武器模型（玩家的子实体）
+#[derive(Component, Debug, Clone)]
+pub struct EquippedWeapon(pub String);
+
+/// 背包插件
+pub struct InventoryPlugin;
+
+impl Plugin for InventoryPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<ItemCollected>()
+            .add_systems(
+                Update,
+                sync_equipped_weapon.run_if(resource_exists::<GameAssets>),
+            )
+            .add_systems(
+                FixedUpdate,
+                use_inventory_items
+                    // 与手电筒系统一样在 AI 检测之前，本步即可使用恢复的电量
+                    .before(SpatialIndexSet)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+/// 处理物品使用输入
+fn use_inventory_items(
+    mut player_query: Query<(&mut Inventory, &mut Health, &mut Flashlight), With<Player>>,
+    mut actions: ResMut<InputActions>,
+    balance: Res<BalanceConfig>,
+) {
+    let Some(index) = actions.use_slot.take() else {
+        return;
+    };
+    let Ok((mut inventory, mut health, mut flashlight)) = player_query.single_mut() else {
+        return;
+    };
+    let Some(item) = inventory.slots().get(index).map(|stack| stack.item.clone()) else {
+        return;
+    };
+
+    if use_item(
+        &mut inventory,
+        index,
+        &balance.items,
+        &mut health,
+        &mut flashlight,
+    ) {
+        info!("[Inventory] 使用了 {}", item);
+    } else {
+        info!("[Inventory] 现在无法使用 {}", item);
+    }
+}
+
+/// 按装备槽更新玩家手持的武器模型
+fn sync_equipped_weapon(
+    mut commands: Commands,
+    player_query: Query<(Entity, &Inventory), (With<Player>, Changed<Inventory>)>,
+    weapon_query: Query<(Entity, &ChildOf, &EquippedWeapon)>,
+    assets: Res<GameAssets>,
+) {
+    for (player, inventory) in player_query.iter() {
+        let wanted = inventory.equipped(EquipmentSlot::Weapon);
+        let mut current = None;
+        for (entity, child_of, weapon) in weapon_query.iter() {
+            if child_of.parent() != player {
+                continue;
+            }
+            if Some(weapon.0.as_str()) == wanted {
+                current = Some(entity);
+            } else {
+                commands.entity(entity).despawn();
+            }
+        }
+
+        if let (Some(item), None) = (wanted, current) {
+            // 目前只有小刀一种武器模型
+            let weapon = commands
+                .spawn((
+                    SceneRoot(assets.knife_model.clone()),
+                    Transform::from_translation(WEAPON_OFFSET),
+                    EquippedWeapon(item.to_string()),
+                    Name::new("EquippedWeapon"),
+                ))
+                .id();
+            commands.entity(player).add_child(weapon);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup() -> (BalanceConfig, Inventory, Health, Flashlight) {
+        let balance = BalanceConfig::default();
+        let inventory = Inventory::from_balance(&balance);
+        let health = Health::from_balance(&balance);
+        let flashlight = Flashlight::from_balance(&balance);
+        (balance, inventory, health, flashlight)
+    }
+
+    #[test]
+    fn test_stacking_and_capacity() {
+        let (balance, mut inventory, ..) = setup();
+        let items = &balance.items;
+        inventory.capacity = 2;
+
+        // 电池每格最多 10 个：12 个占两格
+        assert_eq!(inventory.add("battery", 12, items), 12);
+        assert_eq!(inventory.slots().len(), 2);
+        assert_eq!(inventory.count("battery"), 12);
+
+        // 补满第二格后装不下
+        assert_eq!(inventory.add("battery", 10, items), 8);
+        assert_eq!(inventory.add("medkit", 1, items), 0);
+        assert_eq!(inventory.add("unknown", 1, items), 0);
+
+        // 取空一格后腾出位置
+        for _ in 0..10 {
+            assert_eq!(inventory.take(0).as_deref(), Some("battery"));
+        }
+        assert_eq!(inventory.slots().len(), 1);
+        assert_eq!(inventory.add("medkit", 1, items), 1);
+    }
+
+    #[test]
+    fn test_consumables_only_used_when_effective() {
+        let (balance, mut inventory, mut health, mut flashlight) = setup();
+        let items = &balance.items;
+        inventory.add("medkit", 2, items);
+        inventory.add("battery", 1, items);
+
+        // 满血、满电时不消耗
+        assert!(!use_item(
+            &mut inventory,
+            0,
+            items,
+            &mut health,
+            &mut flashlight
+        ));
+        assert!(!use_item(
+            &mut inventory,
+            1,
+            items,
+            &mut health,
+            &mut flashlight
+        ));
+        assert_eq!(inventory.count("medkit"), 2);
+
+        health.damage(50.0);
+        assert!(use_item(
+            &mut inventory,
+            0,
+            items,
+            &mut health,
+            &mut flashlight
+        ));
+        assert_eq!(health.current(), 90.0);
+        assert_eq!(inventory.count("medkit"), 1);
+
+        flashlight.restore(10.0, true);
+        assert!(use_item(
+            &mut inventory,
+            1,
+            items,
+            &mut health,
+            &mut flashlight
+        ));
+        assert_eq!(flashlight.charge(), 50.0);
+        assert_eq!(inventory.count("battery"), 0);
+        assert!(!use_item(
+            &mut inventory,
+            5,
+            items,
+            &mut health,
+            &mut flashlight
+        ));
+    }
+
+    #[test]
+    fn test_equip_swaps_weapon() {
+        let (mut balance, mut inventory, mut health, mut flashlight) = setup();
+        let machete = balance.items["knife"].clone();
+        balance.items.insert("machete".to_string(), machete);
+        let items = &balance.items;
+
+        inventory.add("knife", 1, items);
+        assert!(use_item(
+            &mut inventory,
+            0,
+            items,
+            &mut health,
+            &mut flashlight
+        ));
+        assert_eq!(inventory.equipped(EquipmentSlot::Weapon), Some("knife"));
+        assert!(inventory.slots().is_empty());
+
+        // 换上新武器，旧武器放回背包
+        inventory.add("machete", 1, items);
+        assert!(inventory.equip(0, EquipmentSlot::Weapon, items));
+        assert_eq!(inventory.equipped(EquipmentSlot::Weapon), Some("machete"));
+        assert_eq!(inventory.count("knife"), 1);
+    }
+
+    #[test]
+    fn test_restore_drops_unknown_items() {
+        let (balance, mut inventory, ..) = setup();
+        inventory.restore(
+            &[
+                ItemStack {
+                    item: "medkit".to_string(),
+                    count: 3,
+                },
+                ItemStack {
+                    item: "removed".to_string(),
+                    count: 1,
+                },
+            ],
+            &["knife".to_string(), "medkit".to_string()],
+            &balance.items,
+        );
+        assert_eq!(inventory.slots().len(), 1);
+        assert_eq!(inventory.count("medkit"), 3);
+        assert_eq!(inventory.equipped(EquipmentSlot::Weapon), Some("knife"));
+        assert_eq!(inventory.equipment().count(), 1);
+    }
+}
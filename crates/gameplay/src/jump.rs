@@ -0,0 +1,102 @@
+//! 玩家跳跃
+//!
+//! 在角色控制器的起跳之上提供手感辅助：
+//! - 土狼时间：走下平台边缘后短时间内仍可按地面跳起跳
+//! - 跳跃缓冲：落地前稍早按下跳跃，落地时自动起跳
+//! - 可变跳跃高度：上升中松开跳跃键会削减上升速度
+//! - 多段跳：地面跳之外的空中跳跃次数；未起跳离开地面且土狼时间耗尽后，地面跳作废
+//!
+//! 由玩家移动系统在每个模拟步中调用。
+
+use bevy::prelude::*;
+use vigilant_doodle_core::BalanceConfig;
+
+use crate::character::CharacterMotion;
+
+/// 跳跃组件（参数与状态）
+#[derive(Component, Debug, Clone)]
+pub struct JumpController {
+    /// 起跳速度
+    pub jump_force: f32,
+    /// 最大跳跃次数（1=单跳，2=二段跳）
+    pub max_jump_count: u32,
+    /// 土狼时间（秒）
+    pub coyote_time: f32,
+    /// 跳跃缓冲时间（秒）
+    pub buffer_time: f32,
+    /// 上升中松开跳跃键时保留的上升速度比例
+    pub release_multiplier: f32,
+    /// 当前已跳跃次数
+    pub jump_count: u32,
+    /// 土狼时间剩余
+    coyote_remaining: f32,
+    /// 跳跃缓冲剩余
+    buffer_remaining: f32,
+    /// 是否处于可被松键削减的上升阶段
+    rising: bool,
+}
+
+impl JumpController {
+    /// 从平衡配置创建跳跃组件
+    pub fn from_balance(balance: &BalanceConfig) -> Self {
+        let player = &balance.player;
+        Self {
+            jump_force: player.jump_force,
+            max_jump_count: player.max_jump_count,
+            coyote_time: player.coyote_time,
+            buffer_time: player.jump_buffer_time,
+            release_multiplier: player.jump_release_multiplier,
+            jump_count: 0,
+            coyote_remaining: 0.0,
+            buffer_remaining: 0.0,
+            rising: false,
+        }
+    }
+
+    /// 推进计时：记录跳跃输入，在地面时重置次数与土狼时间
+    pub fn tick(&mut self, motion: &CharacterMotion, pressed: bool, delta: f32) {
+        if pressed {
+            self.buffer_remaining = self.buffer_time;
+        } else {
+            self.buffer_remaining = (self.buffer_remaining - delta).max(0.0);
+        }
+
+        if motion.grounded && motion.vertical_velocity <= 0.0 {
+            self.jump_count = 0;
+            self.coyote_remaining = self.coyote_time;
+            self.rising = false;
+        } else if self.coyote_remaining > 0.0 {
+            self.coyote_remaining -= delta;
+            if self.coyote_remaining <= 0.0 && self.jump_count == 0 {
+                // 未起跳就离开地面：地面跳作废，只剩空中跳跃
+                self.jump_count = 1;
+            }
+        }
+    }
+
+    /// 有缓冲的跳跃输入且还有跳跃次数时起跳
+    pub fn try_jump(&mut self, motion: &mut CharacterMotion) -> bool {
+        if self.buffer_remaining <= 0.0 || self.jump_count >= self.max_jump_count {
+            return false;
+        }
+        motion.jump(self.jump_force);
+        self.jump_count += 1;
+        self.buffer_remaining = 0.0;
+        self.coyote_remaining = 0.0;
+        self.rising = true;
+        true
+    }
+
+    /// 可变跳跃高度：上升中松开跳跃键时削减上升速度（每次跳跃只削减一次）
+    pub fn apply_release(&mut self, motion: &mut CharacterMotion, held: bool) {
+        if !self.rising {
+            return;
+        }
+        if motion.vertical_velocity <= 0.0 {
+            self.rising = false;
+        } else if !held {
+            motion.vertical_velocity *= self.release_multiplier;
+            self.rising = false;
+        }
+    }
+}
@@ -1,13 +1,69 @@
 //! Vigilant Doodle Gameplay - 游戏玩法系统
 //!
-//! 包含玩家、敌人和移动系统。
+//! 提供游戏玩法的核心逻辑，包括：
+//! - 玩家与生命值（Player）
+//! - 移动能力：跳跃、冲刺、翻滚与体力（Abilities）
+//! - 手电筒（Flashlight）
+//! - 背包与拾取物（Inventory / Pickup）
+//! - 近战攻击（Combat）
+//! - 可交互物体：门、开关、宝箱、纸条（Interaction）
+//! - 关卡目标（Objective）
+//! - 经验值与升级（Progression）
+//! - 锁定目标（Targeting）
+//! - 敌人与波次（Enemy / Encounter）
+//! - 移动与角色控制器（Movement / Character）
+//! - 空间索引（Spatial）
+//! - 固定步长模拟（Simulation）
 
 #![allow(clippy::type_complexity)]
 
+mod abilities;
+mod character;
+mod combat;
+mod encounter;
 mod enemy;
+mod flashlight;
+mod health;
+mod interaction;
+mod inventory;
+mod jump;
 mod movement;
+mod objective;
+mod pickup;
 mod player;
+mod progression;
+mod simulation;
+mod spatial;
+mod targeting;
 
-pub use enemy::{Enemy, EnemyPlugin};
-pub use movement::MovementPlugin;
+pub use abilities::{MovementAbilities, Stamina};
+pub use character::{move_character, CharacterController, CharacterMotion};
+pub use combat::{attack_damage, CombatPlugin, MeleeAttack};
+pub use encounter::{spawn_points, EncounterPlugin, EncounterState, WavePlan};
+pub use enemy::{
+    enemy_bundle, Enemy, EnemyArchetype, EnemyAttack, EnemyDefeated, EnemyPlugin, EnemyStats, PatrolRoute,
+    DEFAULT_ARCHETYPE,
+};
+pub use flashlight::{Flashlight, FlashlightBeam, FlashlightPlugin};
+pub use health::Health;
+pub use interaction::{
+    interaction_target, Chest, Door, Interactable, InteractableRestore, InteractableState,
+    Interacted, InteractionFocus, InteractionPlugin, LevelInteractable, Note, ReadingNote, Switch,
+    SwitchToggled,
+};
+pub use inventory::{
+    use_item, EquippedWeapon, Inventory, InventoryPlugin, ItemCollected, ItemStack,
+};
+pub use jump::JumpController;
+pub use movement::{CollisionRadius, MovementPlugin, MovementSystemSet};
+pub use objective::{
+    AdvanceLevel, Objective, ObjectiveCompleted, ObjectivePlugin, Objectives,
+};
+pub use pickup::{pickup_points, ItemPickup, LevelPickups, PickupPlugin};
 pub use player::{Player, PlayerPlugin};
+pub use progression::{Progression, ProgressionPlugin, Upgrade, UpgradeChosen};
+pub use simulation::{SimulationPlugin, TransformInterpolation, SIMULATION_HZ};
+pub use spatial::{
+    SpatialEntry, SpatialIndex, SpatialIndexPlugin, SpatialIndexSet, SpatialKind,
+};
+pub use targeting::{LockOn, TargetingMode, TargetingPlugin, TargetingSettings};
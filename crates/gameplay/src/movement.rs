@@ -1,15 +1,19 @@
 use vigilant_doodle_core::state::GameState;
+use crate::character::character_controller_system;
+use crate::spatial::{SpatialIndex, SpatialIndexSet};
 use crate::{Enemy, Player};
-use vigilant_doodle_world::terrain::{FLOOR_HALF_LENGTH, FLOOR_HALF_WIDTH};
 use bevy::prelude::*;
+use vigilant_doodle_world::streaming::Frozen;
 
 /// 移动系统集合（定义执行顺序）
+///
+/// 写入角色期望速度的系统（玩家输入、AI 转向）应排在 `Separation` 之前
 #[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
 pub enum MovementSystemSet {
     /// 碰撞分离（先执行）
     Separation,
-    /// 地形限制（后执行）
-    TerrainClamping,
+    /// 角色控制器（碰撞移动，后执行）
+    Character,
 }
 
 /// 碰撞体积组件（圆形碰撞）
@@ -31,76 +35,74 @@ impl Plugin for MovementPlugin {
         app
             // 配置系统集合的执行顺序
             .configure_sets(
-                Update,
-                (
-                    MovementSystemSet::Separation,
-                    MovementSystemSet::TerrainClamping,
-                )
-                    .chain() // 先碰撞分离，再地形限制
+                FixedUpdate,
+                (MovementSystemSet::Separation, MovementSystemSet::Character)
+                    .chain() // 先碰撞分离，再由角色控制器移动并修正穿透
+                    .after(SpatialIndexSet)
                     .run_if(in_state(GameState::Playing)),
             )
             // 添加系统到对应的集合
             .add_systems(
-                Update,
+                FixedUpdate,
                 separate_entities.in_set(MovementSystemSet::Separation),
             )
             .add_systems(
-                Update,
-                clamp_to_terrain.in_set(MovementSystemSet::TerrainClamping),
+                FixedUpdate,
+                character_controller_system.in_set(MovementSystemSet::Character),
             );
     }
 }
 
-fn clamp_to_terrain(mut query: Query<&mut Transform, Or<(With<Player>, With<Enemy>)>>) {
-    for mut transform in query.iter_mut() {
-        transform.translation.x = transform
-            .translation
-            .x
-            .clamp(-FLOOR_HALF_LENGTH, FLOOR_HALF_LENGTH);
-        transform.translation.z = transform
-            .translation
-            .z
-            .clamp(-FLOOR_HALF_WIDTH, FLOOR_HALF_WIDTH);
-    }
-}
-
 /// 分离重叠的实体（防止敌人和玩家重叠）
+///
+/// 通过空间索引只检查附近的实体对
 fn separate_entities(
-    mut query: Query<(Entity, &mut Transform, &CollisionRadius), Or<(With<Player>, With<Enemy>)>>,
+    mut query: Query<
+        (&mut Transform, &CollisionRadius),
+        (Or<(With<Player>, With<Enemy>)>, Without<Frozen>),
+    >,
+    index: Res<SpatialIndex>,
 ) {
     // 碰撞分离参数
     const SEPARATION_DAMPING: f32 = 0.3; // 阻尼系数（降低推力强度）
     const MIN_OVERLAP_THRESHOLD: f32 = 0.05; // 最小重叠阈值（小于此值不推开）
+    const QUERY_MARGIN: f32 = 0.5; // 索引重建后本帧内的移动余量
 
-    let mut combinations = query.iter_combinations_mut();
+    for entry in index.entries() {
+        let query_radius = entry.radius + index.max_radius() + QUERY_MARGIN;
 
-    while let Some(
-        [
-            (_entity_a, mut transform_a, radius_a),
-            (_entity_b, mut transform_b, radius_b),
-        ],
-    ) = combinations.fetch_next()
-    {
-        let delta = transform_a.translation - transform_b.translation;
-        let distance = delta.length();
-        let min_distance = radius_a.radius + radius_b.radius;
-        let overlap = min_distance - distance;
+        for other in index.query_radius(entry.position, query_radius) {
+            // 每对只处理一次
+            if other.entity <= entry.entity {
+                continue;
+            }
+            let Ok([(mut transform_a, radius_a), (mut transform_b, radius_b)]) =
+                query.get_many_mut([entry.entity, other.entity])
+            else {
+                continue;
+            };
 
-        // 如果重叠超过阈值，则推开
-        if overlap > MIN_OVERLAP_THRESHOLD && distance > 0.001 {
-            let push_direction = delta.normalize();
-            // 应用阻尼系数，使推力更平滑
-            let push_amount = overlap * 0.5 * SEPARATION_DAMPING;
+            let delta = transform_a.translation - transform_b.translation;
+            let distance = delta.length();
+            let min_distance = radius_a.radius + radius_b.radius;
+            let overlap = min_distance - distance;
 
-            // 只在水平面推开（保持 Y 轴不变）
-            let push = Vec3::new(
-                push_direction.x * push_amount,
-                0.0,
-                push_direction.z * push_amount,
-            );
+            // 如果重叠超过阈值，则推开
+            if overlap > MIN_OVERLAP_THRESHOLD && distance > 0.001 {
+                let push_direction = delta.normalize();
+                // 应用阻尼系数，使推力更平滑
+                let push_amount = overlap * 0.5 * SEPARATION_DAMPING;
+
+                // 只在水平面推开（保持 Y 轴不变）
+                let push = Vec3::new(
+                    push_direction.x * push_amount,
+                    0.0,
+                    push_direction.z * push_amount,
+                );
 
-            transform_a.translation += push;
-            transform_b.translation -= push;
+                transform_a.translation += push;
+                transform_b.translation -= push;
+            }
         }
     }
 }
@@ -0,0 +1,565 @@
+//! 关卡目标
+//!
+//! 目标来自关卡布局（关卡文件的 `objectives`，否则平衡配置 `level.objectives`）：
+//! - 击败敌人：统计 `EnemyDefeated`，可限定敌人原型
+//! - 到达区域：玩家进入圆形区域（区域在地面上显示为发光圆盘，完成后移除）
+//! - 收集物品：统计 `ItemCollected`（拾取物与宝箱）
+//! - 坚持：游戏进行中累计时间
+//!
+//! 每完成一个目标发出 `ObjectiveCompleted`；全部完成后进入 `GameState::LevelComplete`。
+//! 离开通关界面时发出 `AdvanceLevel` 加载下一关：关卡文件指定的 `next_level`，否则新的程序化关卡。
+//! 关卡重置时目标重新开始；进度随存档保存（见 `Objectives::progress` / `Objectives::restore`）。
+
+use bevy::prelude::*;
+use rand::Rng;
+use vigilant_doodle_core::balance::ObjectiveKind;
+use vigilant_doodle_core::{
+    rng::GameRng,
+    state::{GameProgress, GameState},
+};
+use vigilant_doodle_world::level::{
+    LevelChanged, LevelLayout, LevelSet, LevelSource, LoadLevel, LEVEL_RNG_STREAM,
+};
+
+use crate::enemy::EnemyDefeated;
+use crate::inventory::ItemCollected;
+use crate::player::Player;
+use crate::spatial::SpatialIndexSet;
+
+/// 到达区域标记的厚度
+const MARKER_HEIGHT: f32 = 0.05;
+/// 到达区域标记的颜色
+const MARKER_COLOR: Color = Color::srgba(0.3, 0.8, 1.0, 0.35);
+
+/// 单个目标及其进度
+#[derive(Debug, Clone, PartialEq)]
+pub struct Objective {
+    pub kind: ObjectiveKind,
+    /// 当前进度（击败数、收集数、坚持秒数；到达区域为 0 或 1）
+    progress: f32,
+    completed: bool,
+}
+
+impl Objective {
+    pub fn new(kind: ObjectiveKind) -> Self {
+        Self {
+            kind,
+            progress: 0.0,
+            completed: false,
+        }
+    }
+
+    /// 完成所需的进度
+    pub fn target(&self) -> f32 {
+        match &self.kind {
+            ObjectiveKind::Kill { count, .. } | ObjectiveKind::Collect { count, .. } => {
+                *count as f32
+            }
+            ObjectiveKind::Reach { .. } => 1.0,
+            ObjectiveKind::Survive { seconds } => *seconds,
+        }
+    }
+
+    pub fn progress(&self) -> f32 {
+        self.progress
+    }
+
+    pub fn is_completed(&self) -> bool {
+        self.completed
+    }
+
+    fn add(&mut self, amount: f32) {
+        self.progress = (self.progress + amount).min(self.target());
+    }
+}
+
+/// 当前关卡的目标
+#[derive(Resource, Debug, Clone, Default)]
+pub struct Objectives {
+    /// 目标所属的关卡
+    source: LevelSource,
+    objectives: Vec<Objective>,
+    /// 是否已通关（只通关一次）
+    level_complete: bool,
+    /// 读档时存档关卡尚未加载，等该关卡加载后再应用的进度
+    pending: Option<(LevelSource, Vec<f32>, bool)>,
+}
+
+impl Objectives {
+    pub fn new(source: LevelSource, kinds: &[ObjectiveKind]) -> Self {
+        Self {
+            source,
+            objectives: kinds.iter().cloned().map(Objective::new).collect(),
+            level_complete: false,
+            pending: None,
+        }
+    }
+
+    /// 目标所属的关卡
+    pub fn source(&self) -> &LevelSource {
+        &self.source
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Objective> {
+        self.objectives.iter()
+    }
+
+    pub fn is_level_complete(&self) -> bool {
+        self.level_complete
+    }
+
+    /// 记录击败一个敌人
+    pub fn record_kill(&mut self, archetype: &str) {
+        for objective in self.objectives.iter_mut() {
+            let matched = match &objective.kind {
+                ObjectiveKind::Kill {
+                    archetype: wanted, ..
+                } => wanted.as_deref().is_none_or(|wanted| wanted == archetype),
+                _ => false,
+            };
+            if matched {
+                objective.add(1.0);
+            }
+        }
+    }
+
+    /// 记录收集物品
+    pub fn record_item(&mut self, item: &str, count: u32) {
+        for objective in self.objectives.iter_mut() {
+            if matches!(
+                &objective.kind,
+                ObjectiveKind::Collect { item: wanted, .. } if wanted == item
+            ) {
+                objective.add(count as f32);
+            }
+        }
+    }
+
+    /// 记录玩家位置（XZ 平面）
+    pub fn record_position(&mut self, position: Vec2) {
+        for objective in self.objectives.iter_mut() {
+            let inside = match &objective.kind {
+                ObjectiveKind::Reach {
+                    position: zone,
+                    radius,
+                } => position.distance(Vec2::from_array(*zone)) <= *radius,
+                _ => false,
+            };
+            if inside {
+                objective.add(1.0);
+            }
+        }
+    }
+
+    /// 记录经过的时间
+    pub fn record_time(&mut self, delta: f32) {
+        for objective in self.objectives.iter_mut() {
+            if matches!(objective.kind, ObjectiveKind::Survive { .. }) {
+                objective.add(delta);
+            }
+        }
+    }
+
+    /// 标记达到进度的目标为已完成，返回新完成的目标下标
+    pub fn update_completion(&mut self) -> Vec<usize> {
+        let mut completed = Vec::new();
+        for (index, objective) in self.objectives.iter_mut().enumerate() {
+            if !objective.completed && objective.progress >= objective.target() {
+                objective.completed = true;
+                completed.push(index);
+            }
+        }
+        completed
+    }
+
+    /// 全部目标完成且尚未通关时标记通关并返回 true；没有目标的关卡不会通关
+    pub fn try_complete_level(&mut self) -> bool {
+        if self.level_complete
+            || self.objectives.is_empty()
+            || !self.objectives.iter().all(Objective::is_completed)
+        {
+            return false;
+        }
+        self.level_complete = true;
+        true
+    }
+
+    /// 各目标的进度（用于存档）
+    pub fn progress(&self) -> Vec<f32> {
+        self.objectives.iter().map(Objective::progress).collect()
+    }
+
+    /// 恢复存档中的进度
+    ///
+    /// 存档关卡与当前目标的关卡相同时立即应用，否则等该关卡加载后应用
+    pub fn restore(&mut self, source: LevelSource, progress: Vec<f32>, level_complete: bool) {
+        if source == self.source {
+            self.apply(&progress, level_complete);
+        } else {
+            self.pending = Some((source, progress, level_complete));
+        }
+    }
+
+    /// 关卡加载后同步目标：关卡重置、切换或目标变化时重新开始，并应用等待中的进度
+    fn sync(&mut self, level: &LevelLayout, respawn: bool) {
+        let unchanged = !respawn
+            && self.source == level.source
+            && self
+                .objectives
+                .iter()
+                .map(|objective| &objective.kind)
+                .eq(level.objectives.iter());
+        if unchanged {
+            return;
+        }
+
+        let pending = self.pending.take();
+        *self = Self::new(level.source.clone(), &level.objectives);
+        if let Some((_, progress, level_complete)) =
+            pending.filter(|(source, ..)| !respawn && *source == level.source)
+        {
+            self.apply(&progress, level_complete);
+        }
+    }
+
+    /// 应用进度，已达到的目标直接标记为完成（不发出完成消息）
+    fn apply(&mut self, progress: &[f32], level_complete: bool) {
+        if progress.len() != self.objectives.len() {
+            warn!("[Objective] 存档中的目标数量与关卡不一致，进度未恢复");
+            return;
+        }
+        for (objective, &value) in self.objectives.iter_mut().zip(progress) {
+            objective.progress = value.clamp(0.0, objective.target());
+            objective.completed = objective.progress >= objective.target();
+        }
+        self.level_complete = level_complete;
+    }
+}
+
+/// 某个目标已完成
+#[derive(Message, Debug, Clone, Copy)]
+pub struct ObjectiveCompleted {
+    /// 目标下标
+    pub index: usize,
+}
+
+/// 请求加载下一关（由通关界面发出，状态切换由通关界面负责）
+#[derive(Message, Debug, Clone, Copy, Default)]
+pub struct AdvanceLevel;
+
+/// 标记：到达区域的地面标记
+#[derive(Component)]
+struct ObjectiveMarker(usize);
+
+/// 到达区域标记的网格与材质
+#[derive(Resource)]
+struct MarkerAssets {
+    mesh: Handle<Mesh>,
+    material: Handle<StandardMaterial>,
+}
+
+/// 关卡目标插件
+pub struct ObjectivePlugin;
+
+impl Plugin for ObjectivePlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Objectives>()
+            .add_message::<ObjectiveCompleted>()
+            .add_message::<AdvanceLevel>()
+            .add_systems(OnExit(GameState::AssetLoading), setup_marker_assets)
+            .add_systems(
+                Update,
+                (
+                    (sync_objectives, update_objective_markers)
+                        .chain()
+                        .after(LevelSet)
+                        .run_if(resource_exists::<MarkerAssets>),
+                    advance_level.before(LevelSet),
+                ),
+            )
+            .add_systems(
+                FixedUpdate,
+                track_objectives
+                    .after(SpatialIndexSet)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+fn setup_marker_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    commands.insert_resource(MarkerAssets {
+        mesh: meshes.add(Cylinder::new(1.0, MARKER_HEIGHT)),
+        material: materials.add(StandardMaterial {
+            base_color: MARKER_COLOR,
+            emissive: MARKER_COLOR.to_linear() * 2.0,
+            alpha_mode: AlphaMode::Blend,
+            unlit: true,
+            ..default()
+        }),
+    });
+}
+
+/// 关卡加载后同步目标
+fn sync_objectives(
+    mut changed: MessageReader<LevelChanged>,
+    mut objectives: ResMut<Objectives>,
+    level: Res<LevelLayout>,
+) {
+    let Some(respawn) = changed
+        .read()
+        .map(|event| event.respawn)
+        .reduce(|a, b| a || b)
+    else {
+        return;
+    };
+    let before = objectives.source.clone();
+    objectives.sync(&level, respawn);
+    if respawn || before != objectives.source {
+        info!("[Objective] 关卡目标：{} 个", objectives.objectives.len());
+    }
+}
+
+/// 按目标状态放置或移除到达区域标记
+fn update_objective_markers(
+    mut commands: Commands,
+    objectives: Res<Objectives>,
+    marker_query: Query<(Entity, &ObjectiveMarker)>,
+    marker_assets: Res<MarkerAssets>,
+    level: Res<LevelLayout>,
+) {
+    if !objectives.is_changed() {
+        return;
+    }
+
+    let mut present = vec![false; objectives.objectives.len()];
+    for (entity, marker) in marker_query.iter() {
+        match objectives.objectives.get(marker.0) {
+            Some(objective)
+                if !objective.completed
+                    && matches!(objective.kind, ObjectiveKind::Reach { .. }) =>
+            {
+                present[marker.0] = true;
+            }
+            _ => commands.entity(entity).despawn(),
+        }
+    }
+
+    for (index, objective) in objectives.objectives.iter().enumerate() {
+        let ObjectiveKind::Reach { position, radius } = &objective.kind else {
+            continue;
+        };
+        if objective.completed || present[index] {
+            continue;
+        }
+        let center = level
+            .terrain
+            .clamp_to_terrain(Vec3::new(position[0], 0.0, position[1]));
+        commands.spawn((
+            Mesh3d(marker_assets.mesh.clone()),
+            MeshMaterial3d(marker_assets.material.clone()),
+            Transform::from_translation(center + Vec3::Y * MARKER_HEIGHT)
+                .with_scale(Vec3::new(*radius, 1.0, *radius)),
+            ObjectiveMarker(index),
+            Name::new(format!("ObjectiveMarker_{}", index)),
+        ));
+    }
+}
+
+/// 统计目标进度，全部完成后进入通关状态
+#[allow(clippy::too_many_arguments)]
+fn track_objectives(
+    mut objectives: ResMut<Objectives>,
+    mut defeated: MessageReader<EnemyDefeated>,
+    mut collected: MessageReader<ItemCollected>,
+    mut completed: MessageWriter<ObjectiveCompleted>,
+    mut next_state: ResMut<NextState<GameState>>,
+    mut progress: ResMut<GameProgress>,
+    player_query: Query<&Transform, With<Player>>,
+    time: Res<Time>,
+) {
+    for event in defeated.read() {
+        objectives.record_kill(&event.archetype);
+    }
+    for event in collected.read() {
+        objectives.record_item(&event.item, event.count);
+    }
+    if let Ok(transform) = player_query.single() {
+        objectives.record_position(transform.translation.xz());
+    }
+    objectives.record_time(time.delta_secs());
+
+    for index in objectives.update_completion() {
+        info!("[Objective] 目标 {} 已完成", index + 1);
+        completed.write(ObjectiveCompleted { index });
+    }
+
+    if objectives.try_complete_level() {
+        progress.levels_completed += 1;
+        info!(
+            "[Objective] 关卡完成（累计 {} 关）",
+            progress.levels_completed
+        );
+        next_state.set(GameState::LevelComplete);
+    }
+}
+
+/// 加载下一关
+fn advance_level(
+    mut requests: MessageReader<AdvanceLevel>,
+    mut load_level: MessageWriter<LoadLevel>,
+    mut game_rng: ResMut<GameRng>,
+    level: Res<LevelLayout>,
+) {
+    if requests.read().count() == 0 {
+        return;
+    }
+
+    let source = match &level.next_level {
+        Some(path) => LevelSource::File { path: path.clone() },
+        None => LevelSource::Procedural {
+            seed: game_rng.stream(LEVEL_RNG_STREAM).random(),
+        },
+    };
+    info!("[Objective] 进入下一关：{:?}", source);
+    load_level.write(LoadLevel {
+        source,
+        respawn: true,
+    });
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use bevy::state::app::StatesPlugin;
+
+    fn kinds() -> Vec<ObjectiveKind> {
+        vec![
+            ObjectiveKind::Kill {
+                count: 2,
+                archetype: Some("scout".into()),
+            },
+            ObjectiveKind::Reach {
+                position: [10.0, 0.0],
+                radius: 2.0,
+            },
+            ObjectiveKind::Collect {
+                item: "battery".into(),
+                count: 3,
+            },
+            ObjectiveKind::Survive { seconds: 5.0 },
+        ]
+    }
+
+    #[test]
+    fn test_objectives_track_progress() {
+        let mut objectives = Objectives::new(LevelSource::default(), &kinds());
+
+        objectives.record_kill("grunt");
+        objectives.record_kill("scout");
+        objectives.record_position(Vec2::new(5.0, 0.0));
+        objectives.record_item("medkit", 2);
+        objectives.record_item("battery", 2);
+        objectives.record_time(2.0);
+        assert_eq!(objectives.progress(), vec![1.0, 0.0, 2.0, 2.0]);
+        assert!(objectives.update_completion().is_empty());
+
+        objectives.record_kill("scout");
+        objectives.record_position(Vec2::new(9.0, 1.0));
+        objectives.record_item("battery", 5);
+        assert_eq!(objectives.update_completion(), vec![0, 1, 2]);
+        // 进度不超过目标
+        assert_eq!(objectives.progress(), vec![2.0, 1.0, 3.0, 2.0]);
+        assert!(!objectives.try_complete_level());
+
+        objectives.record_time(3.0);
+        assert_eq!(objectives.update_completion(), vec![3]);
+        assert!(objectives.try_complete_level());
+        // 只通关一次
+        assert!(!objectives.try_complete_level());
+        assert!(objectives.is_level_complete());
+    }
+
+    #[test]
+    fn test_defeated_enemies_complete_kill_objective() {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_state(GameState::Playing)
+            .init_resource::<GameProgress>()
+            .insert_resource(Objectives::new(
+                LevelSource::default(),
+                &[ObjectiveKind::Kill {
+                    count: 2,
+                    archetype: Some("grunt".into()),
+                }],
+            ))
+            .add_message::<EnemyDefeated>()
+            .add_message::<ItemCollected>()
+            .add_message::<ObjectiveCompleted>()
+            .add_systems(Update, track_objectives);
+
+        let defeat = |app: &mut App, archetype: &str| {
+            app.world_mut().write_message(EnemyDefeated {
+                entity: Entity::PLACEHOLDER,
+                archetype: archetype.into(),
+                position: Vec3::ZERO,
+            });
+            app.update();
+        };
+        defeat(&mut app, "grunt");
+        defeat(&mut app, "scout");
+        assert_eq!(app.world().resource::<Objectives>().progress(), vec![1.0]);
+
+        defeat(&mut app, "grunt");
+        app.update();
+        assert!(app.world().resource::<Objectives>().is_level_complete());
+        assert_eq!(app.world().resource::<GameProgress>().levels_completed, 1);
+        assert_eq!(
+            *app.world().resource::<State<GameState>>().get(),
+            GameState::LevelComplete
+        );
+    }
+
+    #[test]
+    fn test_level_without_objectives_never_completes() {
+        let mut objectives = Objectives::new(LevelSource::default(), &[]);
+        objectives.record_time(1000.0);
+        assert!(!objectives.try_complete_level());
+    }
+
+    #[test]
+    fn test_restore_waits_for_level() {
+        let file = LevelSource::File {
+            path: "levels/test.level.json".into(),
+        };
+        let mut level = LevelLayout {
+            objectives: kinds(),
+            ..default()
+        };
+        let mut objectives = Objectives::new(LevelSource::default(), &[]);
+
+        // 存档关卡尚未加载：暂不应用
+        objectives.restore(file.clone(), vec![2.0, 0.0, 1.0, 4.0], false);
+        assert!(objectives.iter().next().is_none());
+
+        // 其他关卡加载时丢弃
+        objectives.sync(&level, false);
+        assert_eq!(objectives.progress(), vec![0.0; 4]);
+
+        objectives.restore(file.clone(), vec![2.0, 0.0, 1.0, 4.0], false);
+        level.source = file;
+        objectives.sync(&level, false);
+        assert_eq!(objectives.progress(), vec![2.0, 0.0, 1.0, 4.0]);
+        let completed: Vec<bool> = objectives.iter().map(Objective::is_completed).collect();
+        assert_eq!(completed, vec![true, false, false, false]);
+
+        // 同一关卡热重载保留进度，重置则重新开始
+        objectives.sync(&level, false);
+        assert_eq!(objectives.progress(), vec![2.0, 0.0, 1.0, 4.0]);
+        objectives.sync(&level, true);
+        assert_eq!(objectives.progress(), vec![0.0; 4]);
+    }
+}
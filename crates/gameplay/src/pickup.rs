@@ -0,0 +1,388 @@
+//! 场景中的拾取物
+//!
+//! - 关卡重置时放置拾取物：关卡文件中预先放置的物品，加上平衡配置 `player.inventory.level_pickups`
+//!   中每种物品在可行走区域内随机放置的若干个（位置由关卡重置时从 `GameRng` 的 `pickups` 流取得的种子决定，
+//!   与玩家出生点保持距离）
+//! - 玩家进入拾取半径时放入背包；背包只放得下一部分时，剩余的留在原地
+//! - 已拾取的数量随存档保存，读档时按此重新放置（见 `LevelPickups::collected` / `LevelPickups::restore`）
+//!
+//! 武器使用武器模型，其他物品显示为物品颜色的发光小方块。
+
+use bevy::prelude::*;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+use std::collections::BTreeMap;
+use vigilant_doodle_assets::GameAssets;
+use vigilant_doodle_core::balance::{EquipmentSlot, ItemKind};
+use vigilant_doodle_core::{rng::GameRng, state::GameState, BalanceConfig};
+use vigilant_doodle_world::level::{LevelChanged, LevelLayout, LevelSet, LevelSource};
+
+use crate::inventory::{Inventory, ItemCollected};
+use crate::player::Player;
+use crate::spatial::SpatialIndexSet;
+
+/// 拾取物位置使用的随机数流
+const RNG_STREAM: &str = "pickups";
+/// 拾取物离地高度
+const PICKUP_HEIGHT: f32 = 0.4;
+/// 拾取物方块边长
+const PICKUP_SIZE: f32 = 0.35;
+
+/// 拾取物
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct ItemPickup {
+    /// 编号（关卡内按放置顺序编号，存档按编号记录已拾取的数量）
+    pub id: u32,
+    /// 物品名称
+    pub item: String,
+    /// 剩余数量
+    pub count: u32,
+}
+
+/// 当前关卡的拾取物状态
+#[derive(Resource, Debug, Clone, Default)]
+pub struct LevelPickups {
+    /// 随机放置使用的种子
+    seed: u64,
+    /// 各拾取物已被拾取的数量（按编号）
+    collected: BTreeMap<u32, u32>,
+    /// 读档时存档关卡尚未加载，等该关卡加载后再应用的状态
+    pending: Option<(LevelSource, u64, BTreeMap<u32, u32>)>,
+}
+
+impl LevelPickups {
+    /// 随机放置使用的种子（用于存档）
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// 各拾取物已被拾取的数量（编号, 数量），用于存档
+    pub fn collected(&self) -> impl Iterator<Item = (u32, u32)> + '_ {
+        self.collected.iter().map(|(&id, &count)| (id, count))
+    }
+
+    /// 恢复存档中的拾取状态
+    ///
+    /// 存档关卡加载后（当前就是存档关卡时在下一次更新）按此重新放置拾取物
+    pub fn restore(
+        &mut self,
+        source: LevelSource,
+        seed: u64,
+        collected: impl IntoIterator<Item = (u32, u32)>,
+    ) {
+        self.pending = Some((source, seed, collected.into_iter().collect()));
+    }
+
+    /// 记录拾取
+    fn record(&mut self, id: u32, count: u32) {
+        *self.collected.entry(id).or_default() += count;
+    }
+
+    /// 同步关卡状态，返回是否需要重新放置拾取物
+    ///
+    /// `respawn` 为本次更新中关卡变化的重置标记（None 表示关卡没有变化）：
+    /// 关卡重置时取新种子重新开始；存档关卡已加载时应用等待中的状态；其他关卡加载后丢弃等待中的状态
+    fn sync(&mut self, level: &LevelLayout, respawn: Option<bool>, rng: &mut GameRng) -> bool {
+        if respawn == Some(true) {
+            *self = Self {
+                seed: rng.stream(RNG_STREAM).random(),
+                ..default()
+            };
+            return true;
+        }
+        match self.pending.take_if(|(source, ..)| *source == level.source) {
+            Some((_, seed, collected)) => {
+                *self = Self {
+                    seed,
+                    collected,
+                    pending: None,
+                };
+                true
+            }
+            None => {
+                if respawn.is_some() {
+                    self.pending = None;
+                }
+                false
+            }
+        }
+    }
+
+    /// 本关尚未拾取完的拾取物及其位置（编号按关卡放置、随机放置的顺序）
+    fn placements(&self, level: &LevelLayout, balance: &BalanceConfig) -> Vec<(ItemPickup, Vec3)> {
+        let config = &balance.player.inventory;
+        let mut rng = StdRng::seed_from_u64(self.seed);
+        let placed = level.item_placements.iter().map(|placement| {
+            let position = level.terrain.clamp_to_terrain(Vec3::new(
+                placement.position.x,
+                0.0,
+                placement.position.y,
+            ));
+            (placement.item.clone(), placement.count, position)
+        });
+        let scattered: Vec<(String, u32, Vec3)> = config
+            .level_pickups
+            .iter()
+            .flat_map(|(item, &count)| {
+                pickup_points(&mut rng, count, config.pickup_min_distance, level)
+                    .into_iter()
+                    .map(|position| (item.clone(), 1, position))
+                    .collect::<Vec<_>>()
+            })
+            .collect();
+
+        placed
+            .chain(scattered)
+            .zip(0..)
+            .filter_map(|((item, count, position), id)| {
+                let taken = self.collected.get(&id).copied().unwrap_or(0);
+                let count = count.saturating_sub(taken);
+                (count > 0).then_some((ItemPickup { id, item, count }, position))
+            })
+            .collect()
+    }
+}
+
+/// 拾取物的网格与各物品的材质
+#[derive(Resource)]
+struct PickupAssets {
+    mesh: Handle<Mesh>,
+    materials: BTreeMap<String, Handle<StandardMaterial>>,
+}
+
+/// 为随机放置的拾取物选择位置
+///
+/// 从关卡可行走区域中随机选取，与玩家出生点保持 `min_distance`；位置放在地表上
+pub fn pickup_points(
+    rng: &mut impl Rng,
+    count: u32,
+    min_distance: f32,
+    level: &LevelLayout,
+) -> Vec<Vec3> {
+    let mut candidates: Vec<Vec2> = level
+        .walkable
+        .walkable_cells()
+        .filter(|cell| cell.distance(level.player_spawn) >= min_distance)
+        .collect();
+
+    let count = (count as usize).min(candidates.len());
+    (0..count)
+        .map(|i| {
+            let chosen = rng.random_range(i..candidates.len());
+            candidates.swap(i, chosen);
+            level
+                .terrain
+                .clamp_to_terrain(Vec3::new(candidates[i].x, 0.0, candidates[i].y))
+        })
+        .collect()
+}
+
+/// 拾取物插件
+pub struct PickupPlugin;
+
+impl Plugin for PickupPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<LevelPickups>()
+            .add_systems(OnExit(GameState::AssetLoading), setup_pickup_assets)
+            .add_systems(
+                Update,
+                spawn_level_pickups
+                    .after(LevelSet)
+                    .run_if(resource_exists::<PickupAssets>),
+            )
+            .add_systems(
+                FixedUpdate,
+                collect_pickups
+                    .before(SpatialIndexSet)
+                    .run_if(in_state(GameState::Playing)),
+            );
+    }
+}
+
+fn setup_pickup_assets(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+    balance: Res<BalanceConfig>,
+) {
+    let materials = balance
+        .items
+        .iter()
+        .map(|(name, item)| {
+            let color = Color::srgb_from_array(item.color);
+            let material = materials.add(StandardMaterial {
+                base_color: color,
+                emissive: color.to_linear() * 2.0,
+                ..default()
+            });
+            (name.clone(), material)
+        })
+        .collect();
+    commands.insert_resource(PickupAssets {
+        mesh: meshes.add(Cuboid::from_length(PICKUP_SIZE)),
+        materials,
+    });
+}
+
+/// 关卡重置或读档后重新放置拾取物
+#[allow(clippy::too_many_arguments)]
+fn spawn_level_pickups(
+    mut commands: Commands,
+    mut changed: MessageReader<LevelChanged>,
+    pickup_query: Query<Entity, With<ItemPickup>>,
+    mut pickups: ResMut<LevelPickups>,
+    mut game_rng: ResMut<GameRng>,
+    pickup_assets: Res<PickupAssets>,
+    assets: Res<GameAssets>,
+    balance: Res<BalanceConfig>,
+    level: Res<LevelLayout>,
+) {
+    let respawn = changed
+        .read()
+        .map(|event| event.respawn)
+        .reduce(|a, b| a || b);
+    if !pickups.sync(&level, respawn, &mut game_rng) {
+        return;
+    }
+
+    for entity in pickup_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let mut spawned = 0;
+    for (pickup, position) in pickups.placements(&level, &balance) {
+        let Some(definition) = balance.items.get(&pickup.item) else {
+            warn!("[Pickup] 未知的物品 {:?}，已忽略", pickup.item);
+            continue;
+        };
+        let transform = Transform::from_translation(position + Vec3::Y * PICKUP_HEIGHT);
+        let name = Name::new(format!("Pickup_{}_{}", pickup.item, pickup.id));
+        if matches!(
+            definition.kind,
+            ItemKind::Equipment {
+                slot: EquipmentSlot::Weapon
+            }
+        ) {
+            commands.spawn((
+                SceneRoot(assets.knife_model.clone()),
+                transform,
+                pickup,
+                name,
+            ));
+        } else {
+            commands.spawn((
+                Mesh3d(pickup_assets.mesh.clone()),
+                MeshMaterial3d(pickup_assets.materials[&pickup.item].clone()),
+                transform,
+                pickup,
+                name,
+            ));
+        }
+        spawned += 1;
+    }
+    info!("[Pickup] 放置了 {} 个拾取物", spawned);
+}
+
+/// 拾取范围内的物品
+fn collect_pickups(
+    mut commands: Commands,
+    mut player_query: Query<(&Transform, &mut Inventory), With<Player>>,
+    mut pickup_query: Query<(Entity, &Transform, &mut ItemPickup)>,
+    mut collected: MessageWriter<ItemCollected>,
+    mut pickups: ResMut<LevelPickups>,
+    balance: Res<BalanceConfig>,
+) {
+    let Ok((player_transform, mut inventory)) = player_query.single_mut() else {
+        return;
+    };
+    let radius = balance.player.inventory.pickup_radius;
+
+    for (entity, transform, mut pickup) in pickup_query.iter_mut() {
+        let offset = transform.translation - player_transform.translation;
+        if offset.with_y(0.0).length() > radius || offset.y.abs() > radius + PICKUP_HEIGHT {
+            continue;
+        }
+        let added = inventory.add(&pickup.item, pickup.count, &balance.items);
+        if added == 0 {
+            continue;
+        }
+        pickup.count -= added;
+        pickups.record(pickup.id, added);
+        if pickup.count == 0 {
+            commands.entity(entity).despawn();
+        }
+        info!("[Pickup] 拾取 {} x{}", pickup.item, added);
+        collected.write(ItemCollected {
+            item: pickup.item.clone(),
+            count: added,
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use vigilant_doodle_world::level::ItemPlacement;
+
+    #[test]
+    fn test_pickup_points() {
+        let level = LevelLayout::default();
+        let mut rng = StdRng::seed_from_u64(3);
+        let points = pickup_points(&mut rng, 6, 10.0, &level);
+        assert_eq!(points.len(), 6);
+        for point in &points {
+            assert!(point.xz().distance(level.player_spawn) >= 10.0);
+            assert!(level.walkable.is_walkable(point.xz()));
+        }
+
+        // 同一种子下可复现
+        let mut rng = StdRng::seed_from_u64(3);
+        assert_eq!(pickup_points(&mut rng, 6, 10.0, &level), points);
+    }
+
+    #[test]
+    fn test_save_and_restore_skip_collected_pickups() {
+        let balance = BalanceConfig::default();
+        let mut level = LevelLayout::default();
+        level.item_placements.push(ItemPlacement {
+            item: "battery".to_string(),
+            count: 3,
+            position: Vec2::new(4.0, 4.0),
+        });
+        let mut rng = GameRng::from_seed(7);
+        let mut pickups = LevelPickups::default();
+        assert!(pickups.sync(&level, Some(true), &mut rng));
+        let all = pickups.placements(&level, &balance);
+        assert_eq!(all.len(), 11);
+
+        // 关卡放置的电池拾取了一部分，第一个随机放置的拾取物拾取完
+        pickups.record(all[0].0.id, 2);
+        pickups.record(all[1].0.id, 1);
+        let seed = pickups.seed();
+        let collected: Vec<(u32, u32)> = pickups.collected().collect();
+
+        // 读档：存档关卡加载前不放置，其他关卡加载时丢弃
+        let mut loaded = LevelPickups::default();
+        let other = LevelLayout {
+            source: LevelSource::Procedural { seed: 99 },
+            ..default()
+        };
+        loaded.restore(level.source.clone(), seed, collected.clone());
+        assert!(!loaded.sync(&other, None, &mut rng));
+        assert!(!loaded.sync(&other, Some(false), &mut rng));
+        assert!(!loaded.sync(&level, None, &mut rng));
+
+        loaded.restore(level.source.clone(), seed, collected);
+        assert!(loaded.sync(&level, Some(false), &mut rng));
+        let restored = loaded.placements(&level, &balance);
+        assert_eq!(restored.len(), all.len() - 1);
+        assert_eq!(restored[0].0.count, 1);
+        assert_eq!(restored[0].1, all[0].1);
+        assert!(restored.iter().all(|(pickup, _)| pickup.id != all[1].0.id));
+        assert_eq!(&restored[1..], &all[2..]);
+
+        // 关卡重置后重新开始
+        assert!(loaded.sync(&level, Some(true), &mut rng));
+        assert_eq!(loaded.collected().count(), 0);
+    }
+}
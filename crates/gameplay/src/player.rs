@@ -1,22 +1,28 @@
-use crate::movement::CollisionRadius;
-use bevy::color::palettes::css::YELLOW_GREEN;
+use crate::abilities::{MovementAbilities, Stamina};
+use crate::character::{CharacterController, CharacterMotion};
+use crate::combat::MeleeAttack;
+use crate::flashlight::{flashlight_beam, Flashlight};
+use crate::health::Health;
+use crate::inventory::Inventory;
+use crate::jump::JumpController;
+use crate::movement::{CollisionRadius, MovementSystemSet};
+use crate::progression::Progression;
+use crate::simulation::TransformInterpolation;
+use crate::spatial::{SpatialIndex, SpatialIndexSet, SpatialKind};
+use crate::targeting::{LockOn, TargetingMode, TargetingSettings};
 use bevy::prelude::*;
 use vigilant_doodle_assets::GameAssets;
 use vigilant_doodle_camera::components::CameraTarget;
 use vigilant_doodle_core::{state::GameState, BalanceConfig};
+use vigilant_doodle_world::level::{LevelChanged, LevelLayout, LevelSet};
+use vigilant_doodle_world::streaming::StreamingFocus;
 
 #[derive(Component)]
 pub struct Player {
     pub speed: f32,
-    pub rotation_speed: f32,    // 旋转速度（弧度/秒）
-    pub jump_force: f32,         // 跳跃力度
-    pub gravity: f32,            // 重力加速度
-    pub vertical_velocity: f32,  // 垂直速度（Y 轴）
-    pub is_grounded: bool,       // 是否在地面
-    pub ground_level: f32,       // 地面高度
-    pub detection_range: f32,    // 敌人检测范围
-    pub max_jump_count: u32,     // 最大跳跃次数（1=单跳，2=二段跳）
-    pub jump_count: u32,         // 当前已跳跃次数
+    pub rotation_speed: f32,  // 旋转速度（弧度/秒）
+    pub detection_range: f32, // 敌人检测范围
+    pub air_control: f32,     // 空中控制（0=保持起跳时的水平速度，1=与地面相同）
 }
 
 impl Player {
@@ -25,27 +31,31 @@ impl Player {
         Self {
             speed: balance.player.speed,
             rotation_speed: balance.player.rotation_speed,
-            jump_force: balance.player.jump_force,
-            gravity: balance.player.gravity,
             detection_range: balance.player.detection_range,
-            max_jump_count: balance.player.max_jump_count,
-            vertical_velocity: 0.0,
-            is_grounded: true,
-            ground_level: 0.0,
-            jump_count: 0,
+            air_control: balance.player.air_control,
         }
     }
 }
 
+/// 空中控制为 1 时，水平速度趋近期望速度的速率（每秒可改变的速度为移动速度的倍数）
+const AIR_CONTROL_RESPONSE: f32 = 10.0;
+
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
     fn build(&self, app: &mut App) {
         app.add_systems(
-                OnEnter(GameState::AssetLoading),
-                spawn_player.after(vigilant_doodle_assets::load_assets),
-            )
-            .add_systems(Update, player_movement.run_if(in_state(GameState::Playing)));
+            OnEnter(GameState::AssetLoading),
+            spawn_player.after(vigilant_doodle_assets::load_assets),
+        )
+        .add_systems(Update, move_player_to_level_spawn.after(LevelSet))
+        .add_systems(
+            FixedUpdate,
+            player_movement
+                .after(SpatialIndexSet)
+                .before(MovementSystemSet::Separation)
+                .run_if(in_state(GameState::Playing)),
+        );
     }
 }
 
@@ -56,10 +66,13 @@ fn spawn_player(
 ) {
     // 从配置文件创建玩家
     let player = Player::from_balance(&balance);
+    let controller = CharacterController::from_balance(&balance);
+    let jump = JumpController::from_balance(&balance);
+    let flashlight = Flashlight::from_balance(&balance);
 
     info!(
         "[Player] 玩家配置: 速度={}, 跳跃力={}, 最大跳跃次数={}",
-        player.speed, player.jump_force, player.max_jump_count
+        player.speed, jump.jump_force, jump.max_jump_count
     );
 
     // 使用 glb 模型作为玩家
@@ -68,38 +81,75 @@ fn spawn_player(
             SceneRoot(assets.player_model.clone()),
             Transform::from_xyz(0.0, 0.0, 0.0),
             player,
+            controller,
+            CharacterMotion::default(),
+            jump,
+            LockOn::from_balance(&balance),
+            MovementAbilities::from_balance(&balance),
+            // 元组组件数量有上限，玩家状态放在嵌套元组中
+            (
+                Stamina::from_balance(&balance),
+                Health::from_balance(&balance),
+                Inventory::from_balance(&balance),
+                Progression::default(),
+                MeleeAttack::default(),
+                flashlight.clone(),
+            ),
+            TransformInterpolation::default(),
             CameraTarget,              // 标记为相机跟随目标
+            StreamingFocus,            // 周围区块保持加载
             CollisionRadius::new(0.6), // 碰撞半径
             Name::new("Player"),
         ))
         .with_children(|parent| {
-            // 玩家子实体：探照灯
-            parent.spawn((
-                SpotLight {
-                    color: YELLOW_GREEN.into(),
-                    range: 50.0,
-                    intensity: 5000.0,
-                    outer_angle: 0.8,
-                    inner_angle: 0.4,
-                    shadows_enabled: true,
-                    ..default()
-                },
-                Transform::from_xyz(0.0, 2.0, 0.0).looking_at(Vec3::new(0.0, 0.0, 1.0), Vec3::Y),
-                Name::new("PlayerFlashlight"),
-            ));
+            // 玩家子实体：手电筒光源（朝向由手电筒系统控制）
+            parent.spawn(flashlight_beam(&flashlight));
         });
 
     info!("[Player] Player spawned with model");
 }
 
-fn player_movement(
-    mut player_query: Query<(&mut Transform, &mut Player)>,
-    enemy_query: Query<&Transform, (With<crate::Enemy>, Without<Player>)>,
-    actions: Res<vigilant_doodle_input::actions::InputActions>,
+/// 关卡重置后将玩家移到关卡出生点
+fn move_player_to_level_spawn(
+    mut changed: MessageReader<LevelChanged>,
+    level: Res<LevelLayout>,
+    mut player_query: Query<&mut Transform, With<Player>>,
+) {
+    if !changed.read().any(|event| event.respawn) {
+        return;
+    }
+    for mut transform in player_query.iter_mut() {
+        transform.translation = level.terrain.clamp_to_terrain(Vec3::new(
+            level.player_spawn.x,
+            0.0,
+            level.player_spawn.y,
+        ));
+    }
+}
+
+/// 玩家移动系统
+///
+/// 将输入转换为角色控制器的期望速度、跳跃、冲刺与翻滚，并按锁定目标与设置处理朝向；
+/// 碰撞、重力与落地由角色控制器负责
+pub(crate) fn player_movement(
+    mut player_query: Query<(
+        &mut Transform,
+        &Player,
+        &mut CharacterMotion,
+        &mut JumpController,
+        &mut MovementAbilities,
+        &mut Stamina,
+        Option<&LockOn>,
+    )>,
+    spatial_index: Res<SpatialIndex>,
+    settings: Res<TargetingSettings>,
+    mut actions: ResMut<vigilant_doodle_input::actions::InputActions>,
     camera_query: Query<&Transform, (With<Camera3d>, Without<Player>, Without<crate::Enemy>)>,
     time: Res<Time>,
 ) {
-    let Ok((mut transform, mut player)) = player_query.single_mut() else {
+    let Ok((mut transform, player, mut motion, mut jump, mut abilities, mut stamina, lock_on)) =
+        player_query.single_mut()
+    else {
         return;
     };
 
@@ -130,66 +180,89 @@ fn player_movement(
     let move_direction = (forward_normalized * actions.movement.y)
         + (right_normalized * actions.movement.x);
 
-    // 移动玩家（如果有移动输入）
-    if move_direction.length_squared() > 0.001 {
-        let move_direction = move_direction.normalize();
-        transform.translation += move_direction * player.speed * time.delta_secs();
-    }
-
-    // === 跳跃和重力系统 ===
+    let delta = time.delta_secs();
+    let moving = move_direction.length_squared() > 0.001;
 
-    // 地面检测（简单检测：Y 轴是否接近地面高度）
-    const GROUND_THRESHOLD: f32 = 0.01; // 地面检测阈值
-    player.is_grounded = transform.translation.y <= player.ground_level + GROUND_THRESHOLD;
+    // === 翻滚 ===
 
-    // 在地面时重置跳跃次数
-    if player.is_grounded {
-        player.jump_count = 0;
+    // 翻滚输入与跳跃一样只在一个模拟步中生效，只能在地面上翻滚
+    // 无移动输入时向后翻滚（远离当前朝向）
+    abilities.tick(delta);
+    let dodge_pressed = std::mem::take(&mut actions.dodge);
+    if dodge_pressed && motion.grounded {
+        let direction = if moving {
+            move_direction
+        } else {
+            transform.back().as_vec3()
+        };
+        if abilities.try_dodge(direction, &mut stamina) {
+            info!("[Player] 翻滚！");
+        }
     }
 
-    // 按下跳跃键且还有跳跃次数（支持多段跳）
-    if actions.jump && player.jump_count < player.max_jump_count {
-        player.vertical_velocity = player.jump_force;
-        player.jump_count += 1;
-        info!(
-            "[Player] 跳跃！({}/{})",
-            player.jump_count, player.max_jump_count
-        );
-    }
+    // === 冲刺 ===
 
-    // 应用重力（只要不在地面）
-    if !player.is_grounded || player.vertical_velocity > 0.0 {
-        player.vertical_velocity += player.gravity * time.delta_secs();
+    // 期望水平速度（由角色控制器积分），翻滚中使用翻滚速度
+    // 空中按空中控制逐渐趋近期望速度
+    let speed_multiplier = abilities.update_sprint(actions.sprint, moving, &mut stamina, delta);
+    stamina.tick(delta);
+    let desired_velocity = if moving {
+        move_direction.normalize() * player.speed * speed_multiplier
     } else {
-        // 在地面时重置垂直速度
-        player.vertical_velocity = 0.0;
-    }
+        Vec3::ZERO
+    };
+    motion.velocity = if let Some(dodge_velocity) = abilities.dodge_velocity() {
+        dodge_velocity
+    } else if motion.grounded {
+        desired_velocity
+    } else {
+        let max_change = player.speed * player.air_control * AIR_CONTROL_RESPONSE * delta;
+        motion.velocity.move_towards(desired_velocity, max_change)
+    };
 
-    // 更新垂直位置
-    transform.translation.y += player.vertical_velocity * time.delta_secs();
+    // === 跳跃 ===
 
-    // 防止穿透地面
-    if transform.translation.y < player.ground_level {
-        transform.translation.y = player.ground_level;
-        player.vertical_velocity = 0.0;
+    // 跳跃输入只在一个模拟步中生效，由跳跃缓冲保留一小段时间
+    // 翻滚中不能跳跃（缓冲仍在计时，翻滚结束时可能立即起跳）
+    let jump_pressed = std::mem::take(&mut actions.jump);
+    jump.tick(&motion, jump_pressed, delta);
+    if !abilities.is_dodging() && jump.try_jump(&mut motion) {
+        info!(
+            "[Player] 跳跃！({}/{})",
+            jump.jump_count, jump.max_jump_count
+        );
     }
+    jump.apply_release(&mut motion, actions.jump_held);
 
-    // 确定玩家朝向方向（使用配置的检测范围）
-    let detection_range = player.detection_range;
+    // === 朝向 ===
 
-    // 查找检测范围内最近的敌人
-    let nearest_enemy = enemy_query
-        .iter()
-        .map(|enemy_transform| {
-            let distance = transform.translation.distance(enemy_transform.translation);
-            (distance, enemy_transform.translation)
+    // 锁定目标：硬锁定始终朝向目标，软锁定只在停下时朝向目标
+    // 未锁定：按设置自动朝向检测范围内最近的敌人
+    let locked_position = lock_on
+        .and_then(LockOn::target)
+        .filter(|_| settings.mode == TargetingMode::Hard || !moving)
+        .and_then(|target| {
+            spatial_index
+                .entries()
+                .iter()
+                .find(|entry| entry.entity == target)
         })
-        .filter(|(distance, _)| *distance <= detection_range)
-        .min_by(|a, b| a.0.partial_cmp(&b.0).unwrap());
+        .map(|entry| entry.position);
+    let facing_enemy = if lock_on.is_some_and(|lock_on| lock_on.target().is_some()) {
+        locked_position
+    } else if settings.auto_face {
+        spatial_index
+            .nearest(transform.translation, player.detection_range, |entry| {
+                entry.kind == SpatialKind::Enemy
+            })
+            .map(|entry| entry.position)
+    } else {
+        None
+    };
 
     // 计算目标朝向
-    let target_forward = if let Some((_, enemy_pos)) = nearest_enemy {
-        // 有敌人：朝向最近的敌人
+    let target_forward = if let Some(enemy_pos) = facing_enemy {
+        // 有敌人：朝向该敌人
         let direction = enemy_pos - transform.translation;
         let direction_2d = Vec3::new(direction.x, 0.0, direction.z);
 
@@ -199,7 +272,7 @@ fn player_movement(
         }
 
         direction_2d.normalize()
-    } else if move_direction.length_squared() > 0.001 {
+    } else if moving {
         // 无敌人且有移动：朝向移动方向
         move_direction.normalize()
     } else {
@@ -212,6 +285,228 @@ fn player_movement(
 
     // 使用球面线性插值（slerp）平滑旋转
     // rotation_factor 限制在 [0, 1] 范围内
-    let rotation_factor = (player.rotation_speed * time.delta_secs()).clamp(0.0, 1.0);
+    let rotation_factor = (player.rotation_speed * delta).clamp(0.0, 1.0);
     transform.rotation = transform.rotation.slerp(target_rotation, rotation_factor);
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::movement::MovementPlugin;
+    use crate::spatial::SpatialIndexPlugin;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use vigilant_doodle_input::actions::InputActions;
+    use vigilant_doodle_world::terrain::Heightfield;
+    use vigilant_doodle_world::{CollisionLayerPlugin, StaticCollider};
+
+    /// 每帧一个模拟步
+    const STEP: Duration = Duration::from_millis(10);
+
+    /// 无渲染的玩家模拟：平台顶面 y=0，右边缘 x=0
+    struct Harness {
+        app: App,
+        player: Entity,
+    }
+
+    impl Harness {
+        fn new(balance: BalanceConfig, x: f32) -> Self {
+            let mut app = App::new();
+            app.add_plugins((MinimalPlugins, StatesPlugin))
+                .insert_resource(TimeUpdateStrategy::ManualDuration(STEP))
+                .insert_resource(Time::<Fixed>::from_duration(STEP))
+                .insert_state(GameState::Playing)
+                .init_resource::<InputActions>()
+                .init_resource::<TargetingSettings>()
+                // 地形远低于平台，不影响从平台边缘下落
+                .insert_resource(LevelLayout {
+                    terrain: Heightfield::flat(Vec2::splat(100.0), -50.0),
+                    ..default()
+                })
+                .add_message::<LevelChanged>()
+                .add_plugins((
+                    PlayerPlugin,
+                    SpatialIndexPlugin,
+                    MovementPlugin,
+                    CollisionLayerPlugin,
+                ));
+
+            app.world_mut().spawn((
+                Transform::from_xyz(-10.0, -0.5, 0.0),
+                StaticCollider::cuboid(Vec3::new(10.0, 0.5, 10.0)),
+            ));
+            // 相机朝向 -Z，输入 x 为 +X 方向
+            app.world_mut()
+                .spawn((Camera3d::default(), Transform::default()));
+            let player = app
+                .world_mut()
+                .spawn((
+                    Transform::from_xyz(x, 0.5, 0.0),
+                    Player::from_balance(&balance),
+                    CharacterController::from_balance(&balance),
+                    CharacterMotion::default(),
+                    JumpController::from_balance(&balance),
+                    MovementAbilities::from_balance(&balance),
+                    Stamina::from_balance(&balance),
+                ))
+                .id();
+
+            let mut harness = Self { app, player };
+            // 首帧没有经过时间，随后落稳
+            harness.run(10, |_| {});
+            assert!(harness.motion().grounded);
+            harness
+        }
+
+        /// 设置输入后推进若干模拟步
+        fn run(&mut self, steps: usize, input: impl Fn(&mut InputActions)) {
+            for _ in 0..steps {
+                input(&mut self.app.world_mut().resource_mut::<InputActions>());
+                self.app.update();
+            }
+        }
+
+        fn press_jump(&mut self) {
+            self.app.world_mut().resource_mut::<InputActions>().jump = true;
+        }
+
+        fn motion(&self) -> &CharacterMotion {
+            self.app
+                .world()
+                .get::<CharacterMotion>(self.player)
+                .unwrap()
+        }
+
+        fn position(&self) -> Vec3 {
+            self.app
+                .world()
+                .get::<Transform>(self.player)
+                .unwrap()
+                .translation
+        }
+
+        /// 推进直到离开地面，返回经过的步数
+        fn run_until_airborne(&mut self, input: impl Fn(&mut InputActions)) -> usize {
+            for steps in 1..=200 {
+                self.run(1, &input);
+                if !self.motion().grounded {
+                    return steps;
+                }
+            }
+            panic!("未离开地面");
+        }
+
+        /// 推进直到落地，返回经过的步数
+        fn run_until_landed(&mut self, input: impl Fn(&mut InputActions)) -> usize {
+            for steps in 1..=300 {
+                self.run(1, &input);
+                if self.motion().grounded {
+                    return steps;
+                }
+            }
+            panic!("未落地");
+        }
+    }
+
+    fn single_jump_balance() -> BalanceConfig {
+        let mut balance = BalanceConfig::default();
+        balance.player.max_jump_count = 1;
+        balance
+    }
+
+    fn walk_right(actions: &mut InputActions) {
+        actions.movement = Vec2::X;
+        actions.jump_held = true;
+    }
+
+    fn hold_jump(actions: &mut InputActions) {
+        actions.jump_held = true;
+    }
+
+    #[test]
+    fn test_coyote_time_allows_late_jump() {
+        // 走下平台边缘后 50ms 内按跳跃仍可起跳
+        let mut harness = Harness::new(single_jump_balance(), -1.0);
+        harness.run_until_airborne(walk_right);
+        harness.run(5, walk_right);
+        harness.press_jump();
+        harness.run(1, walk_right);
+        assert!(harness.motion().vertical_velocity > 5.0);
+
+        // 超出土狼时间后不再起跳
+        let mut harness = Harness::new(single_jump_balance(), -1.0);
+        harness.run_until_airborne(walk_right);
+        harness.run(20, walk_right);
+        harness.press_jump();
+        harness.run(1, walk_right);
+        assert!(harness.motion().vertical_velocity < 0.0);
+    }
+
+    #[test]
+    fn test_jump_buffer_triggers_on_landing() {
+        // 先测出一次完整跳跃的滞空步数
+        let mut harness = Harness::new(single_jump_balance(), -10.0);
+        harness.press_jump();
+        harness.run_until_airborne(hold_jump);
+        let airborne_steps = harness.run_until_landed(hold_jump);
+
+        // 落地前 50ms 按下跳跃：落地后立即再次起跳
+        let mut harness = Harness::new(single_jump_balance(), -10.0);
+        harness.press_jump();
+        harness.run_until_airborne(hold_jump);
+        harness.run(airborne_steps - 5, hold_jump);
+        harness.press_jump();
+        harness.run(6, hold_jump);
+        assert!(harness.motion().vertical_velocity > 5.0);
+
+        // 落地前 300ms 按下跳跃：缓冲已过期，不会再次起跳
+        let mut harness = Harness::new(single_jump_balance(), -10.0);
+        harness.press_jump();
+        harness.run_until_airborne(hold_jump);
+        harness.run(airborne_steps - 30, hold_jump);
+        harness.press_jump();
+        harness.run(40, hold_jump);
+        assert!(harness.motion().grounded);
+    }
+
+    #[test]
+    fn test_releasing_jump_lowers_apex() {
+        let peak = |hold_steps: usize| {
+            let mut harness = Harness::new(BalanceConfig::default(), -10.0);
+            harness.press_jump();
+            let mut peak = harness.position().y;
+            for step in 0..200 {
+                harness.run(1, |actions| actions.jump_held = step < hold_steps);
+                peak = peak.max(harness.position().y);
+            }
+            peak - harness.position().y
+        };
+
+        // 按住到顶点：v²/2g ≈ 2.87
+        let full = peak(200);
+        assert!((full - 2.87).abs() < 0.1, "{full}");
+        // 短按：上升速度减半后跳得明显更低
+        let short = peak(5);
+        assert!(short < full * 0.5, "{short} / {full}");
+    }
+
+    #[test]
+    fn test_air_control() {
+        let airborne_velocity = |air_control: f32| {
+            let mut balance = single_jump_balance();
+            balance.player.air_control = air_control;
+            let mut harness = Harness::new(balance, -10.0);
+            harness.press_jump();
+            harness.run(1, walk_right);
+            // 空中松开方向键
+            harness.run(20, |actions| actions.movement = Vec2::ZERO);
+            assert!(!harness.motion().grounded);
+            harness.motion().velocity
+        };
+
+        // 无空中控制时保持起跳时的水平速度，完全控制时很快停下
+        assert!(airborne_velocity(0.0).abs_diff_eq(Vec3::X * 7.5, 1e-4));
+        assert_eq!(airborne_velocity(1.0), Vec3::ZERO);
+    }
+}
@@ -0,0 +1,332 @@
+//! 经验值与升级
+//!
+//! - 击败敌人（`EnemyDefeated`）获得经验值，数量取自平衡配置中该原型的 `xp`
+//! - 经验值达到当前等级所需时升级：1 级升 2 级需要 `xp_base`，之后每级乘以 `xp_growth`，
+//!   到达 `max_level` 后不再获得经验值
+//! - 每次升级获得一次属性选择：移动速度、生命值上限或跳跃次数（不超过 `max_jump_count`）；
+//!   有未使用的选择时进入 `GameState::LevelUp`，选择界面发出 `UpgradeChosen`，用完后回到游戏
+//! - 属性在 `player` 的基础值上累加，写入 `Player`、`Health` 与 `JumpController`
+//!
+//! 进度挂在玩家上，切换关卡时保留，并随存档保存。
+
+use bevy::prelude::*;
+use vigilant_doodle_core::balance::{PlayerBalance, ProgressionBalance};
+use vigilant_doodle_core::{state::GameState, BalanceConfig};
+
+use crate::enemy::EnemyDefeated;
+use crate::health::Health;
+use crate::jump::JumpController;
+use crate::player::Player;
+use crate::spatial::SpatialIndexSet;
+
+/// 升级时可选的属性提升
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Upgrade {
+    /// 移动速度
+    Speed,
+    /// 生命值上限
+    MaxHealth,
+    /// 跳跃次数
+    JumpCount,
+}
+
+impl Upgrade {
+    pub const ALL: [Upgrade; 3] = [Upgrade::Speed, Upgrade::MaxHealth, Upgrade::JumpCount];
+
+    fn index(self) -> usize {
+        self as usize
+    }
+}
+
+/// 玩家的等级、经验值与已选择的属性提升
+#[derive(Component, Debug, Clone, PartialEq, Eq)]
+pub struct Progression {
+    /// 当前等级（从 1 开始）
+    level: u32,
+    /// 当前等级内已获得的经验值
+    xp: u32,
+    /// 未使用的属性选择次数
+    pending_choices: u32,
+    /// 各属性已提升的次数（按 `Upgrade` 顺序）
+    upgrades: [u32; 3],
+}
+
+impl Default for Progression {
+    fn default() -> Self {
+        Self {
+            level: 1,
+            xp: 0,
+            pending_choices: 0,
+            upgrades: [0; 3],
+        }
+    }
+}
+
+impl Progression {
+    pub fn level(&self) -> u32 {
+        self.level
+    }
+
+    pub fn xp(&self) -> u32 {
+        self.xp
+    }
+
+    pub fn pending_choices(&self) -> u32 {
+        self.pending_choices
+    }
+
+    /// 某项属性已提升的次数
+    pub fn upgrades(&self, upgrade: Upgrade) -> u32 {
+        self.upgrades[upgrade.index()]
+    }
+
+    /// 升到下一级所需的经验值，已到等级上限时为 None
+    pub fn xp_to_next(&self, config: &ProgressionBalance) -> Option<u32> {
+        (self.level < config.max_level).then(|| {
+            (config.xp_base as f32 * config.xp_growth.powi(self.level as i32 - 1)).round() as u32
+        })
+    }
+
+    /// 获得经验值，返回升级的次数
+    pub fn add_xp(&mut self, amount: u32, config: &ProgressionBalance) -> u32 {
+        self.xp += amount;
+        let mut gained = 0;
+        while let Some(required) = self.xp_to_next(config) {
+            if self.xp < required.max(1) {
+                break;
+            }
+            self.xp -= required.max(1);
+            self.level += 1;
+            self.pending_choices += 1;
+            gained += 1;
+        }
+        if self.xp_to_next(config).is_none() {
+            self.xp = 0;
+        }
+        gained
+    }
+
+    /// 当前是否可以选择该属性
+    pub fn can_choose(&self, upgrade: Upgrade, balance: &PlayerBalance) -> bool {
+        self.pending_choices > 0
+            && (upgrade != Upgrade::JumpCount
+                || self.max_jump_count(balance) < balance.progression.max_jump_count)
+    }
+
+    /// 使用一次属性选择，不可选择时返回 false
+    pub fn choose(&mut self, upgrade: Upgrade, balance: &PlayerBalance) -> bool {
+        if !self.can_choose(upgrade, balance) {
+            return false;
+        }
+        self.pending_choices -= 1;
+        self.upgrades[upgrade.index()] += 1;
+        true
+    }
+
+    /// 提升后的移动速度
+    pub fn speed(&self, balance: &PlayerBalance) -> f32 {
+        balance.speed + balance.progression.speed_bonus * self.upgrades(Upgrade::Speed) as f32
+    }
+
+    /// 提升后的生命值上限
+    pub fn max_health(&self, balance: &PlayerBalance) -> f32 {
+        balance.max_health
+            + balance.progression.max_health_bonus * self.upgrades(Upgrade::MaxHealth) as f32
+    }
+
+    /// 提升后的最大跳跃次数
+    pub fn max_jump_count(&self, balance: &PlayerBalance) -> u32 {
+        balance.max_jump_count + self.upgrades(Upgrade::JumpCount)
+    }
+
+    /// 把属性写入玩家组件；生命值上限提高的部分同时恢复
+    pub fn apply(
+        &self,
+        balance: &PlayerBalance,
+        player: &mut Player,
+        health: &mut Health,
+        jump: &mut JumpController,
+    ) {
+        player.speed = self.speed(balance);
+        health.set_max(self.max_health(balance));
+        jump.max_jump_count = self.max_jump_count(balance);
+    }
+
+    /// 各属性已提升的次数（用于存档）
+    pub fn upgrade_counts(&self) -> [u32; 3] {
+        self.upgrades
+    }
+
+    /// 恢复状态（读档）
+    pub fn restore(&mut self, level: u32, xp: u32, pending_choices: u32, upgrades: [u32; 3]) {
+        self.level = level.max(1);
+        self.xp = xp;
+        self.pending_choices = pending_choices;
+        self.upgrades = upgrades;
+    }
+}
+
+/// 选择了一项属性提升（由升级界面发出）
+#[derive(Message, Debug, Clone, Copy)]
+pub struct UpgradeChosen {
+    pub upgrade: Upgrade,
+}
+
+/// 经验值与升级插件
+pub struct ProgressionPlugin;
+
+impl Plugin for ProgressionPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_message::<UpgradeChosen>()
+            .add_systems(
+                FixedUpdate,
+                award_xp
+                    .after(SpatialIndexSet)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                (
+                    enter_level_up.run_if(in_state(GameState::Playing)),
+                    choose_upgrades.run_if(in_state(GameState::LevelUp)),
+                    apply_progression,
+                )
+                    .chain(),
+            );
+    }
+}
+
+/// 击败敌人获得经验值
+fn award_xp(
+    mut defeated: MessageReader<EnemyDefeated>,
+    mut player_query: Query<&mut Progression, With<Player>>,
+    balance: Res<BalanceConfig>,
+) {
+    let Ok(mut progression) = player_query.single_mut() else {
+        return;
+    };
+    let config = &balance.player.progression;
+    for event in defeated.read() {
+        let xp = balance
+            .enemy
+            .archetypes
+            .get(&event.archetype)
+            .cloned()
+            .unwrap_or_default()
+            .xp;
+        if progression.add_xp(xp, config) > 0 {
+            info!("[Progression] 升级到 {} 级", progression.level());
+        }
+    }
+}
+
+/// 有未使用的属性选择时进入升级界面
+///
+/// 本帧已有其他状态切换（如通关）时先让其生效，回到游戏后再进入
+fn enter_level_up(
+    player_query: Query<&Progression, With<Player>>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    let Ok(progression) = player_query.single() else {
+        return;
+    };
+    if progression.pending_choices() > 0 && matches!(*next_state, NextState::Unchanged) {
+        next_state.set(GameState::LevelUp);
+    }
+}
+
+/// 处理升级界面的选择，用完后回到游戏
+fn choose_upgrades(
+    mut chosen: MessageReader<UpgradeChosen>,
+    mut player_query: Query<&mut Progression, With<Player>>,
+    mut next_state: ResMut<NextState<GameState>>,
+    balance: Res<BalanceConfig>,
+) {
+    let Ok(mut progression) = player_query.single_mut() else {
+        return;
+    };
+    for event in chosen.read() {
+        if progression.choose(event.upgrade, &balance.player) {
+            info!("[Progression] 提升 {:?}", event.upgrade);
+        }
+    }
+    if progression.pending_choices() == 0 {
+        next_state.set(GameState::Playing);
+    }
+}
+
+/// 进度变化时更新玩家属性
+fn apply_progression(
+    mut player_query: Query<
+        (&Progression, &mut Player, &mut Health, &mut JumpController),
+        Changed<Progression>,
+    >,
+    balance: Res<BalanceConfig>,
+) {
+    for (progression, mut player, mut health, mut jump) in player_query.iter_mut() {
+        progression.apply(&balance.player, &mut player, &mut health, &mut jump);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_xp_levels_up() {
+        let config = ProgressionBalance {
+            xp_base: 50,
+            xp_growth: 2.0,
+            max_level: 4,
+            ..default()
+        };
+        let mut progression = Progression::default();
+        assert_eq!(progression.xp_to_next(&config), Some(50));
+
+        assert_eq!(progression.add_xp(40, &config), 0);
+        // 多出的经验值计入下一级，一次可升多级
+        assert_eq!(progression.add_xp(70, &config), 1);
+        assert_eq!((progression.level(), progression.xp()), (2, 60));
+        assert_eq!(progression.add_xp(240, &config), 2);
+        assert_eq!(progression.level(), 4);
+        assert_eq!(progression.pending_choices(), 3);
+
+        // 等级上限：不再积累经验值
+        assert_eq!(progression.xp_to_next(&config), None);
+        assert_eq!(progression.add_xp(1000, &config), 0);
+        assert_eq!(progression.xp(), 0);
+    }
+
+    #[test]
+    fn test_upgrades_raise_stats() {
+        let balance = PlayerBalance {
+            max_jump_count: 2,
+            progression: ProgressionBalance {
+                max_jump_count: 3,
+                ..default()
+            },
+            ..default()
+        };
+        let mut progression = Progression::default();
+        // 没有可用的选择
+        assert!(!progression.choose(Upgrade::Speed, &balance));
+
+        progression.restore(4, 0, 3, [0; 3]);
+        assert!(progression.choose(Upgrade::Speed, &balance));
+        assert!(progression.choose(Upgrade::JumpCount, &balance));
+        // 跳跃次数已到上限
+        assert!(!progression.can_choose(Upgrade::JumpCount, &balance));
+        assert!(progression.choose(Upgrade::MaxHealth, &balance));
+        assert_eq!(progression.pending_choices(), 0);
+
+        assert_eq!(
+            progression.speed(&balance),
+            balance.speed + balance.progression.speed_bonus
+        );
+        assert_eq!(
+            progression.max_health(&balance),
+            balance.max_health + balance.progression.max_health_bonus
+        );
+        assert_eq!(progression.max_jump_count(&balance), 3);
+    }
+}
@@ -0,0 +1,214 @@
+//! 固定步长模拟
+//!
+//! 影响玩法的系统（空间索引、移动、角色控制器、AI）都运行在 `FixedUpdate` 中，
+//! 以固定步长推进，结果与帧率无关，可在测试、回放中复现。
+//!
+//! 渲染帧与模拟步不对齐，挂载 `TransformInterpolation` 的实体在渲染前
+//! 按 `Time<Fixed>` 的超出比例在上一步与当前步之间插值：
+//! - 固定循环前：恢复为模拟结果（外部在 Update 中移动过的实体视为瞬移，直接采用新位置）
+//! - 每个模拟步开始 / 结束时：记录起止变换
+//! - 固定循环后：写入插值后的变换供渲染与相机使用
+
+use bevy::app::RunFixedMainLoopSystems;
+use bevy::prelude::*;
+
+/// 模拟频率（Hz）
+pub const SIMULATION_HZ: f64 = 60.0;
+
+/// 变换插值组件
+#[derive(Component, Debug, Clone, Default)]
+pub struct TransformInterpolation {
+    /// 当前模拟步开始时的变换
+    start: Option<(Vec3, Quat)>,
+    /// 当前模拟步结束时的变换（模拟结果）
+    end: Option<(Vec3, Quat)>,
+    /// 上次写入的插值结果
+    rendered: Option<(Vec3, Quat)>,
+}
+
+/// 固定步长模拟插件
+pub struct SimulationPlugin;
+
+impl Plugin for SimulationPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Time::<Fixed>::from_hz(SIMULATION_HZ))
+            .add_systems(
+                RunFixedMainLoop,
+                restore_simulated_transforms.in_set(RunFixedMainLoopSystems::BeforeFixedMainLoop),
+            )
+            .add_systems(FixedFirst, record_step_start)
+            .add_systems(FixedLast, record_step_end)
+            .add_systems(
+                RunFixedMainLoop,
+                interpolate_transforms.in_set(RunFixedMainLoopSystems::AfterFixedMainLoop),
+            );
+    }
+}
+
+/// 恢复模拟结果，使模拟从真实状态继续
+fn restore_simulated_transforms(mut query: Query<(&mut Transform, &mut TransformInterpolation)>) {
+    for (mut transform, mut interpolation) in query.iter_mut() {
+        let current = (transform.translation, transform.rotation);
+        match (interpolation.rendered, interpolation.end) {
+            (Some(rendered), Some((translation, rotation))) if rendered == current => {
+                transform.translation = translation;
+                transform.rotation = rotation;
+            }
+            (None, _) => {}
+            _ => {
+                // 插值后被外部修改（瞬移、读档），丢弃旧的插值区间
+                *interpolation = TransformInterpolation::default();
+            }
+        }
+    }
+}
+
+fn record_step_start(mut query: Query<(&Transform, &mut TransformInterpolation)>) {
+    for (transform, mut interpolation) in query.iter_mut() {
+        interpolation.start = Some((transform.translation, transform.rotation));
+    }
+}
+
+fn record_step_end(mut query: Query<(&Transform, &mut TransformInterpolation)>) {
+    for (transform, mut interpolation) in query.iter_mut() {
+        interpolation.end = Some((transform.translation, transform.rotation));
+    }
+}
+
+/// 按超出比例插值渲染变换
+fn interpolate_transforms(
+    mut query: Query<(&mut Transform, &mut TransformInterpolation)>,
+    time: Res<Time<Fixed>>,
+) {
+    let alpha = time.overstep_fraction();
+    for (mut transform, mut interpolation) in query.iter_mut() {
+        let (Some((start_translation, start_rotation)), Some((end_translation, end_rotation))) =
+            (interpolation.start, interpolation.end)
+        else {
+            continue;
+        };
+        transform.translation = start_translation.lerp(end_translation, alpha);
+        transform.rotation = start_rotation.slerp(end_rotation, alpha);
+        interpolation.rendered = Some((transform.translation, transform.rotation));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::character::{CharacterController, CharacterMotion};
+    use crate::movement::MovementPlugin;
+    use crate::spatial::SpatialIndexPlugin;
+    use bevy::state::app::StatesPlugin;
+    use bevy::time::TimeUpdateStrategy;
+    use std::time::Duration;
+    use vigilant_doodle_core::state::GameState;
+    use vigilant_doodle_world::{CollisionLayerPlugin, StaticCollider};
+
+    /// 每个模拟步结束时记录的位置
+    #[derive(Resource, Default)]
+    struct Trajectory(Vec<Vec3>);
+
+    fn record_trajectory(
+        query: Query<&Transform, With<CharacterMotion>>,
+        mut trajectory: ResMut<Trajectory>,
+    ) {
+        trajectory
+            .0
+            .extend(query.iter().map(|transform| transform.translation));
+    }
+
+    /// 以给定帧时长运行角色模拟，返回每个模拟步的位置
+    fn run_character(frame: Duration, frames: usize) -> Vec<Vec3> {
+        let mut app = App::new();
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(frame))
+            .insert_state(GameState::Playing)
+            .add_plugins((
+                SimulationPlugin,
+                SpatialIndexPlugin,
+                MovementPlugin,
+                CollisionLayerPlugin,
+            ))
+            .init_resource::<Trajectory>()
+            .add_systems(FixedLast, record_trajectory.before(record_step_end));
+
+        app.world_mut().spawn((
+            Transform::from_xyz(0.0, -0.5, 0.0),
+            StaticCollider::cuboid(Vec3::new(50.0, 0.5, 50.0)),
+        ));
+        app.world_mut().spawn((
+            Transform::from_xyz(0.0, 2.0, 0.0),
+            CharacterController::default(),
+            CharacterMotion {
+                velocity: Vec3::new(2.0, 0.0, 1.0),
+                ..default()
+            },
+            TransformInterpolation::default(),
+        ));
+
+        for _ in 0..frames {
+            app.update();
+        }
+        std::mem::take(&mut app.world_mut().resource_mut::<Trajectory>().0)
+    }
+
+    #[test]
+    fn test_simulation_is_frame_rate_independent() {
+        let fast = run_character(Duration::from_millis(7), 150);
+        let slow = run_character(Duration::from_millis(33), 32);
+
+        let steps = fast.len().min(slow.len());
+        assert!(steps >= 55, "{steps}");
+        // 逐步完全一致（包括落地前的下落过程）
+        assert_eq!(fast[..steps], slow[..steps]);
+    }
+
+    #[test]
+    fn test_rendered_transform_is_interpolated() {
+        let step = Duration::from_millis(50);
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(
+                20,
+            )))
+            .add_plugins(SimulationPlugin)
+            .insert_resource(Time::<Fixed>::from_duration(step))
+            .add_systems(
+                FixedUpdate,
+                |mut query: Query<&mut Transform>, time: Res<Time>| {
+                    for mut transform in query.iter_mut() {
+                        transform.translation.x += time.delta_secs();
+                    }
+                },
+            );
+        let entity = app
+            .world_mut()
+            .spawn((Transform::default(), TransformInterpolation::default()))
+            .id();
+
+        let mut last_rendered = 0.0;
+        for _ in 0..20 {
+            app.update();
+            let rendered = app.world().get::<Transform>(entity).unwrap().translation.x;
+            let interpolation = app.world().get::<TransformInterpolation>(entity).unwrap();
+            let Some(((start, _), (end, _))) = interpolation.start.zip(interpolation.end) else {
+                continue;
+            };
+            // 渲染位置位于上一步与当前步之间，且单调前进
+            assert!(rendered >= start.x - 1e-6 && rendered <= end.x + 1e-6);
+            assert!(rendered >= last_rendered - 1e-6);
+            last_rendered = rendered;
+        }
+
+        // 模拟本身不受插值影响：20 帧 × 20ms = 380ms（首帧无时间）-> 7 步
+        let end = app
+            .world()
+            .get::<TransformInterpolation>(entity)
+            .unwrap()
+            .end
+            .unwrap()
+            .0;
+        assert!((end.x - 0.35).abs() < 1e-5, "{end}");
+    }
+}
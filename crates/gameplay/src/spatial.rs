@@ -0,0 +1,274 @@
+//! 空间索引
+//!
+//! 每帧将玩家与敌人的位置写入均匀网格（空间哈希），供以下查询使用：
+//! - 碰撞分离：只检查相邻单元格内的实体对
+//! - 敌人检测：只处理玩家附近的敌人
+//! - 玩家朝向：查找最近的敌人
+//!
+//! 索引在 `SpatialIndexSet` 中重建，移动与 AI 系统都排在它之后。
+
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+
+use crate::movement::CollisionRadius;
+use crate::{Enemy, Player};
+
+/// 空间索引重建系统集合
+///
+/// 依赖空间索引的系统应排在此集合之后
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SpatialIndexSet;
+
+/// 索引条目类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SpatialKind {
+    Player,
+    Enemy,
+}
+
+/// 索引条目
+#[derive(Debug, Clone, Copy)]
+pub struct SpatialEntry {
+    pub entity: Entity,
+    /// 重建索引时的位置
+    pub position: Vec3,
+    /// 碰撞半径（未挂载 `CollisionRadius` 时为 0）
+    pub radius: f32,
+    pub kind: SpatialKind,
+}
+
+/// 空间索引资源（XZ 平面均匀网格）
+#[derive(Resource, Debug, Clone)]
+pub struct SpatialIndex {
+    /// 单元格边长
+    cell_size: f32,
+    /// 单元格 -> 条目序号
+    cells: HashMap<IVec2, Vec<usize>>,
+    entries: Vec<SpatialEntry>,
+    /// 所有条目中最大的碰撞半径
+    max_radius: f32,
+}
+
+impl Default for SpatialIndex {
+    fn default() -> Self {
+        Self::new(Self::DEFAULT_CELL_SIZE)
+    }
+}
+
+impl SpatialIndex {
+    /// 默认单元格边长
+    pub const DEFAULT_CELL_SIZE: f32 = 4.0;
+
+    pub fn new(cell_size: f32) -> Self {
+        Self {
+            cell_size,
+            cells: HashMap::new(),
+            entries: Vec::new(),
+            max_radius: 0.0,
+        }
+    }
+
+    /// 清空索引（保留已分配的内存）
+    pub fn clear(&mut self) {
+        for cell in self.cells.values_mut() {
+            cell.clear();
+        }
+        self.entries.clear();
+        self.max_radius = 0.0;
+    }
+
+    pub fn insert(&mut self, entry: SpatialEntry) {
+        let index = self.entries.len();
+        let cell = self.cell_of(entry.position);
+        self.cells.entry(cell).or_default().push(index);
+        self.max_radius = self.max_radius.max(entry.radius);
+        self.entries.push(entry);
+    }
+
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// 所有条目中最大的碰撞半径
+    pub fn max_radius(&self) -> f32 {
+        self.max_radius
+    }
+
+    /// 全部条目（按插入顺序）
+    pub fn entries(&self) -> &[SpatialEntry] {
+        &self.entries
+    }
+
+    /// 查询距离 `center` 不超过 `radius` 的所有条目
+    pub fn query_radius(&self, center: Vec3, radius: f32) -> impl Iterator<Item = &SpatialEntry> {
+        let min = self.cell_of(center - Vec3::new(radius, 0.0, radius));
+        let max = self.cell_of(center + Vec3::new(radius, 0.0, radius));
+
+        (min.y..=max.y)
+            .flat_map(move |z| (min.x..=max.x).map(move |x| IVec2::new(x, z)))
+            .filter_map(|cell| self.cells.get(&cell))
+            .flatten()
+            .map(|&index| &self.entries[index])
+            .filter(move |entry| entry.position.distance(center) <= radius)
+    }
+
+    /// 查询 `max_radius` 内满足条件的最近 `k` 个条目（按距离升序，同距离按实体排序）
+    pub fn k_nearest(
+        &self,
+        center: Vec3,
+        k: usize,
+        max_radius: f32,
+        filter: impl Fn(&SpatialEntry) -> bool,
+    ) -> Vec<&SpatialEntry> {
+        let mut found: Vec<(f32, &SpatialEntry)> = self
+            .query_radius(center, max_radius)
+            .filter(|entry| filter(entry))
+            .map(|entry| (entry.position.distance(center), entry))
+            .collect();
+        found.sort_by(|a, b| a.0.total_cmp(&b.0).then(a.1.entity.cmp(&b.1.entity)));
+        found.into_iter().take(k).map(|(_, entry)| entry).collect()
+    }
+
+    /// 查询 `max_radius` 内满足条件的最近条目
+    pub fn nearest(
+        &self,
+        center: Vec3,
+        max_radius: f32,
+        filter: impl Fn(&SpatialEntry) -> bool,
+    ) -> Option<&SpatialEntry> {
+        self.k_nearest(center, 1, max_radius, filter)
+            .into_iter()
+            .next()
+    }
+
+    fn cell_of(&self, position: Vec3) -> IVec2 {
+        IVec2::new(
+            (position.x / self.cell_size).floor() as i32,
+            (position.z / self.cell_size).floor() as i32,
+        )
+    }
+}
+
+/// 空间索引插件
+pub struct SpatialIndexPlugin;
+
+impl Plugin for SpatialIndexPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<SpatialIndex>()
+            .add_systems(FixedUpdate, rebuild_spatial_index.in_set(SpatialIndexSet));
+    }
+}
+
+/// 重建空间索引
+fn rebuild_spatial_index(
+    mut index: ResMut<SpatialIndex>,
+    query: Query<
+        (Entity, &Transform, Option<&CollisionRadius>, Has<Player>),
+        Or<(With<Player>, With<Enemy>)>,
+    >,
+) {
+    index.clear();
+    for (entity, transform, radius, is_player) in query.iter() {
+        index.insert(SpatialEntry {
+            entity,
+            position: transform.translation,
+            radius: radius.map_or(0.0, |radius| radius.radius),
+            kind: if is_player {
+                SpatialKind::Player
+            } else {
+                SpatialKind::Enemy
+            },
+        });
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(index: u32, position: Vec3) -> SpatialEntry {
+        SpatialEntry {
+            entity: Entity::from_raw_u32(index).unwrap(),
+            position,
+            radius: 0.6,
+            kind: SpatialKind::Enemy,
+        }
+    }
+
+    /// 伪随机散布的测试点（确定性）
+    fn scattered(count: u32) -> Vec<SpatialEntry> {
+        (0..count)
+            .map(|i| {
+                let x = ((i * 7919) % 1000) as f32 * 0.12 - 60.0;
+                let z = ((i * 104_729) % 1000) as f32 * 0.06 - 30.0;
+                entry(i + 1, Vec3::new(x, 0.0, z))
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_query_radius_matches_brute_force() {
+        let entries = scattered(2000);
+        let mut index = SpatialIndex::default();
+        for entry in &entries {
+            index.insert(*entry);
+        }
+
+        for center in [
+            Vec3::ZERO,
+            Vec3::new(-58.0, 0.0, 29.0),
+            Vec3::new(13.7, 0.0, -4.2),
+        ] {
+            for radius in [0.5, 3.0, 17.5] {
+                let mut expected: Vec<Entity> = entries
+                    .iter()
+                    .filter(|entry| entry.position.distance(center) <= radius)
+                    .map(|entry| entry.entity)
+                    .collect();
+                let mut actual: Vec<Entity> = index
+                    .query_radius(center, radius)
+                    .map(|entry| entry.entity)
+                    .collect();
+                expected.sort();
+                actual.sort();
+                assert_eq!(actual, expected);
+            }
+        }
+    }
+
+    #[test]
+    fn test_k_nearest_and_filter() {
+        let mut index = SpatialIndex::new(2.0);
+        index.insert(entry(1, Vec3::new(5.0, 0.0, 0.0)));
+        index.insert(entry(2, Vec3::new(1.0, 0.0, 0.0)));
+        index.insert(entry(3, Vec3::new(-3.0, 0.0, 0.0)));
+        index.insert(SpatialEntry {
+            kind: SpatialKind::Player,
+            ..entry(4, Vec3::new(0.5, 0.0, 0.0))
+        });
+
+        let nearest: Vec<u32> = index
+            .k_nearest(Vec3::ZERO, 2, 10.0, |entry| {
+                entry.kind == SpatialKind::Enemy
+            })
+            .iter()
+            .map(|entry| entry.entity.index())
+            .collect();
+        assert_eq!(nearest, vec![2, 3]);
+
+        assert!(
+            index
+                .nearest(Vec3::ZERO, 0.8, |entry| entry.kind == SpatialKind::Enemy)
+                .is_none()
+        );
+
+        index.clear();
+        assert!(index.is_empty());
+        assert_eq!(index.query_radius(Vec3::ZERO, 100.0).count(), 0);
+    }
+}
@@ -0,0 +1,332 @@
+//! 锁定目标
+//!
+//! 玩家可显式锁定敌人：
+//! - 锁定键：未锁定时锁定范围内最近的敌人，已锁定时解除
+//! - 切换键：按绕玩家的角度顺序切换到下一个范围内的敌人
+//! - 软锁定：移动时朝向移动方向，停下时朝向目标；目标丢失后自动锁定最近的敌人
+//! - 硬锁定：始终朝向目标（侧移）；目标丢失后解除锁定
+//!
+//! 未锁定时是否自动朝向最近的敌人由 `TargetingSettings::auto_face` 控制。
+//! 锁定的目标在场景中以指示圈标出。
+
+use bevy::prelude::*;
+use vigilant_doodle_core::{state::GameState, BalanceConfig};
+use vigilant_doodle_input::actions::InputActions;
+
+use crate::player::Player;
+use crate::spatial::{SpatialEntry, SpatialIndex, SpatialIndexSet, SpatialKind};
+
+/// 指示圈离地高度
+const INDICATOR_HEIGHT: f32 = 0.1;
+/// 指示圈半径
+const INDICATOR_RADIUS: f32 = 0.9;
+/// 指示圈旋转速度（弧度/秒）
+const INDICATOR_SPIN_SPEED: f32 = 1.5;
+const SOFT_LOCK_COLOR: Color = Color::srgb(0.95, 0.85, 0.2);
+const HARD_LOCK_COLOR: Color = Color::srgb(0.95, 0.2, 0.2);
+
+/// 锁定模式
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum TargetingMode {
+    /// 软锁定：移动时朝向移动方向，目标丢失后自动切换
+    #[default]
+    Soft,
+    /// 硬锁定：始终朝向目标，目标丢失后解除
+    Hard,
+}
+
+impl TargetingMode {
+    /// 切换模式
+    pub fn toggle(&self) -> Self {
+        match self {
+            Self::Soft => Self::Hard,
+            Self::Hard => Self::Soft,
+        }
+    }
+}
+
+/// 锁定目标设置（由设置菜单修改）
+#[derive(Resource, Debug, Clone)]
+pub struct TargetingSettings {
+    /// 未锁定时是否自动朝向最近的敌人
+    pub auto_face: bool,
+    /// 锁定模式
+    pub mode: TargetingMode,
+}
+
+impl Default for TargetingSettings {
+    fn default() -> Self {
+        Self {
+            auto_face: true,
+            mode: TargetingMode::Soft,
+        }
+    }
+}
+
+/// 锁定目标组件（挂在玩家上）
+#[derive(Component, Debug, Clone)]
+pub struct LockOn {
+    /// 可锁定目标的最大距离
+    pub lock_range: f32,
+    /// 锁定目标超出该距离时解除锁定
+    pub break_range: f32,
+    /// 当前锁定的目标
+    target: Option<Entity>,
+}
+
+impl LockOn {
+    /// 从平衡配置创建锁定组件
+    pub fn from_balance(balance: &BalanceConfig) -> Self {
+        let targeting = &balance.player.targeting;
+        Self {
+            lock_range: targeting.lock_range,
+            break_range: targeting.break_range,
+            target: None,
+        }
+    }
+
+    /// 当前锁定的目标
+    pub fn target(&self) -> Option<Entity> {
+        self.target
+    }
+
+    /// 解除锁定
+    pub fn release(&mut self) {
+        self.target = None;
+    }
+}
+
+/// 锁定目标指示圈
+#[derive(Component)]
+struct TargetIndicator;
+
+/// 指示圈的两种材质
+#[derive(Resource)]
+struct TargetIndicatorMaterials {
+    soft: Handle<StandardMaterial>,
+    hard: Handle<StandardMaterial>,
+}
+
+/// 锁定目标插件
+pub struct TargetingPlugin;
+
+impl Plugin for TargetingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TargetingSettings>()
+            .add_systems(OnExit(GameState::AssetLoading), spawn_target_indicator)
+            .add_systems(
+                FixedUpdate,
+                update_lock_on
+                    .after(SpatialIndexSet)
+                    .before(crate::player::player_movement)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
+                update_target_indicator.run_if(resource_exists::<TargetIndicatorMaterials>),
+            );
+    }
+}
+
+/// 范围内最近的敌人
+pub fn nearest_target(position: Vec3, range: f32, index: &SpatialIndex) -> Option<Entity> {
+    index
+        .nearest(position, range, |entry| entry.kind == SpatialKind::Enemy)
+        .map(|entry| entry.entity)
+}
+
+/// 绕玩家按角度顺序排列的下一个目标
+///
+/// 从当前目标的方位开始顺时针（俯视）寻找；没有当前目标时返回最近的敌人
+pub fn next_target(
+    position: Vec3,
+    range: f32,
+    current: Option<Entity>,
+    index: &SpatialIndex,
+) -> Option<Entity> {
+    let bearing = |entry: &SpatialEntry| {
+        let offset = entry.position - position;
+        offset.x.atan2(-offset.z)
+    };
+    let mut candidates: Vec<&SpatialEntry> = index
+        .query_radius(position, range)
+        .filter(|entry| entry.kind == SpatialKind::Enemy)
+        .collect();
+    candidates.sort_by(|a, b| {
+        bearing(a)
+            .total_cmp(&bearing(b))
+            .then(a.entity.cmp(&b.entity))
+    });
+
+    let Some(current_position) =
+        current.and_then(|current| candidates.iter().position(|entry| entry.entity == current))
+    else {
+        return nearest_target(position, range, index);
+    };
+    candidates
+        .get((current_position + 1) % candidates.len())
+        .map(|entry| entry.entity)
+}
+
+/// 处理锁定输入并维护目标有效性
+fn update_lock_on(
+    mut player_query: Query<(&Transform, &mut LockOn), With<Player>>,
+    mut actions: ResMut<InputActions>,
+    spatial_index: Res<SpatialIndex>,
+    settings: Res<TargetingSettings>,
+) {
+    let Ok((transform, mut lock_on)) = player_query.single_mut() else {
+        return;
+    };
+    let position = transform.translation;
+
+    if std::mem::take(&mut actions.lock_on) {
+        lock_on.target = match lock_on.target {
+            Some(_) => None,
+            None => nearest_target(position, lock_on.lock_range, &spatial_index),
+        };
+        info!("[Targeting] 锁定目标: {:?}", lock_on.target);
+    }
+    if std::mem::take(&mut actions.cycle_target) && lock_on.target.is_some() {
+        lock_on.target = next_target(position, lock_on.lock_range, lock_on.target, &spatial_index);
+        info!("[Targeting] 切换目标: {:?}", lock_on.target);
+    }
+
+    // 目标消失或超出范围
+    let Some(target) = lock_on.target else {
+        return;
+    };
+    let in_range = spatial_index
+        .entries()
+        .iter()
+        .find(|entry| entry.entity == target)
+        .is_some_and(|entry| entry.position.distance(position) <= lock_on.break_range);
+    if !in_range {
+        lock_on.target = match settings.mode {
+            TargetingMode::Soft => nearest_target(position, lock_on.lock_range, &spatial_index),
+            TargetingMode::Hard => None,
+        };
+        info!("[Targeting] 目标丢失，当前目标: {:?}", lock_on.target);
+    }
+}
+
+/// 生成指示圈（初始隐藏）
+fn spawn_target_indicator(
+    mut commands: Commands,
+    mut meshes: ResMut<Assets<Mesh>>,
+    mut materials: ResMut<Assets<StandardMaterial>>,
+) {
+    let material = |color: Color| StandardMaterial {
+        base_color: color,
+        emissive: color.to_linear() * 4.0,
+        unlit: true,
+        ..default()
+    };
+    let indicator_materials = TargetIndicatorMaterials {
+        soft: materials.add(material(SOFT_LOCK_COLOR)),
+        hard: materials.add(material(HARD_LOCK_COLOR)),
+    };
+
+    commands.spawn((
+        Mesh3d(meshes.add(Torus::new(INDICATOR_RADIUS - 0.05, INDICATOR_RADIUS + 0.05))),
+        MeshMaterial3d(indicator_materials.soft.clone()),
+        Transform::default(),
+        Visibility::Hidden,
+        TargetIndicator,
+        Name::new("TargetIndicator"),
+    ));
+    commands.insert_resource(indicator_materials);
+}
+
+/// 指示圈跟随锁定目标
+fn update_target_indicator(
+    player_query: Query<&LockOn, With<Player>>,
+    target_query: Query<&Transform, Without<TargetIndicator>>,
+    mut indicator_query: Query<
+        (
+            &mut Transform,
+            &mut Visibility,
+            &mut MeshMaterial3d<StandardMaterial>,
+        ),
+        With<TargetIndicator>,
+    >,
+    settings: Res<TargetingSettings>,
+    materials: Res<TargetIndicatorMaterials>,
+    time: Res<Time>,
+) {
+    let target_transform = player_query
+        .single()
+        .ok()
+        .and_then(LockOn::target)
+        .and_then(|target| target_query.get(target).ok());
+
+    for (mut transform, mut visibility, mut material) in indicator_query.iter_mut() {
+        let Some(target_transform) = target_transform else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        *visibility = Visibility::Inherited;
+        transform.translation = target_transform.translation.with_y(INDICATOR_HEIGHT);
+        transform.rotate_y(INDICATOR_SPIN_SPEED * time.delta_secs());
+
+        let handle = match settings.mode {
+            TargetingMode::Soft => &materials.soft,
+            TargetingMode::Hard => &materials.hard,
+        };
+        if material.0 != *handle {
+            material.0 = handle.clone();
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn index(positions: &[Vec3]) -> (SpatialIndex, Vec<Entity>) {
+        let mut index = SpatialIndex::default();
+        let mut entities = Vec::new();
+        for (i, position) in positions.iter().enumerate() {
+            let entity = Entity::from_raw_u32(i as u32 + 1).unwrap();
+            entities.push(entity);
+            index.insert(SpatialEntry {
+                entity,
+                position: *position,
+                radius: 0.6,
+                kind: SpatialKind::Enemy,
+            });
+        }
+        (index, entities)
+    }
+
+    #[test]
+    fn test_cycle_targets_by_bearing() {
+        // 正前方（-Z）、右侧、后方、左侧，外加一个超出范围的敌人
+        let (index, e) = index(&[
+            Vec3::new(0.0, 0.0, -5.0),
+            Vec3::new(-4.0, 0.0, 0.0),
+            Vec3::new(0.0, 0.0, 6.0),
+            Vec3::new(3.0, 0.0, 0.0),
+            Vec3::new(40.0, 0.0, 0.0),
+        ]);
+
+        // 无当前目标时选择最近的
+        assert_eq!(next_target(Vec3::ZERO, 15.0, None, &index), Some(e[3]));
+
+        // 顺时针依次切换并循环，跳过范围外的
+        let mut target = Some(e[0]);
+        let mut visited = Vec::new();
+        for _ in 0..4 {
+            target = next_target(Vec3::ZERO, 15.0, target, &index);
+            visited.push(target.unwrap());
+        }
+        assert_eq!(visited, vec![e[3], e[2], e[1], e[0]]);
+    }
+
+    #[test]
+    fn test_nearest_target_respects_range() {
+        let (index, e) = index(&[Vec3::new(0.0, 0.0, 12.0), Vec3::new(30.0, 0.0, 0.0)]);
+        assert_eq!(nearest_target(Vec3::ZERO, 15.0, &index), Some(e[0]));
+        assert_eq!(nearest_target(Vec3::ZERO, 10.0, &index), None);
+    }
+}
@@ -1,11 +1,24 @@
 use vigilant_doodle_core::state::GameState;
+use bevy::app::RunFixedMainLoopSystems;
+use bevy::input::mouse::AccumulatedMouseMotion;
 use bevy::prelude::*;
 
 /// 输入动作资源
 #[derive(Resource, Default)]
 pub struct InputActions {
     pub movement: Vec2,
-    pub jump: bool, // 本帧是否按下跳跃键
+    pub jump: bool,         // 是否有尚未处理的跳跃输入（由固定步长的玩家移动系统消费）
+    pub jump_held: bool,    // 是否按住跳跃键（上升中松开则降低跳跃高度）
+    pub sprint: bool,       // 是否按住冲刺键
+    pub dodge: bool,        // 是否有尚未处理的翻滚输入（同跳跃，由玩家移动系统消费）
+    pub lock_on: bool,      // 是否有尚未处理的锁定/解除锁定输入（由锁定目标系统消费）
+    pub cycle_target: bool, // 是否有尚未处理的切换锁定目标输入（由锁定目标系统消费）
+    pub toggle_flashlight: bool, // 是否有尚未处理的手电筒开关输入（由手电筒系统消费）
+    pub aim: Vec2,          // 尚未处理的鼠标移动量（像素，由手电筒系统消费后清零）
+    pub use_slot: Option<usize>, // 尚未处理的使用背包物品输入（格子下标，由背包系统消费）
+    pub toggle_inventory: bool, // 是否有尚未处理的背包界面开关输入（由背包界面消费）
+    pub interact: bool,     // 是否有尚未处理的交互输入（由交互系统消费）
+    pub attack: bool,       // 是否有尚未处理的攻击输入（由近战攻击系统消费）
 }
 
 /// 游戏控制键位
@@ -15,8 +28,31 @@ pub enum GameControl {
     Left,
     Right,
     Jump,
+    Sprint,
+    Dodge,
+    LockOn,
+    CycleTarget,
+    Flashlight,
+    Inventory,
+    Interact,
 }
 
+/// 攻击按键
+pub const ATTACK_BUTTON: MouseButton = MouseButton::Left;
+
+/// 使用背包第 1~9 格物品的按键
+pub const SLOT_KEYS: [KeyCode; 9] = [
+    KeyCode::Digit1,
+    KeyCode::Digit2,
+    KeyCode::Digit3,
+    KeyCode::Digit4,
+    KeyCode::Digit5,
+    KeyCode::Digit6,
+    KeyCode::Digit7,
+    KeyCode::Digit8,
+    KeyCode::Digit9,
+];
+
 impl GameControl {
     pub fn key(&self) -> KeyCode {
         match self {
@@ -25,6 +61,13 @@ impl GameControl {
             Self::Left => KeyCode::KeyA,
             Self::Right => KeyCode::KeyD,
             Self::Jump => KeyCode::Space,
+            Self::Sprint => KeyCode::ShiftLeft,
+            Self::Dodge => KeyCode::ControlLeft,
+            Self::LockOn => KeyCode::KeyQ,
+            Self::CycleTarget => KeyCode::Tab,
+            Self::Flashlight => KeyCode::KeyF,
+            Self::Inventory => KeyCode::KeyI,
+            Self::Interact => KeyCode::KeyE,
         }
     }
 }
@@ -33,17 +76,27 @@ pub struct InputPlugin;
 
 impl Plugin for InputPlugin {
     fn build(&self, app: &mut App) {
-        app.init_resource::<InputActions>().add_systems(
-            Update,
-            (
-                process_input.run_if(in_state(GameState::Playing)),
+        app.init_resource::<InputActions>()
+            // 在固定步长模拟之前读取输入，本帧的模拟步即可使用
+            .add_systems(
+                RunFixedMainLoop,
+                process_input
+                    .in_set(RunFixedMainLoopSystems::BeforeFixedMainLoop)
+                    .run_if(in_state(GameState::Playing)),
+            )
+            .add_systems(
+                Update,
                 handle_pause_toggle, // 处理暂停/恢复（在任何状态都监听）
-            ),
-        );
+            );
     }
 }
 
-fn process_input(keyboard: Res<ButtonInput<KeyCode>>, mut actions: ResMut<InputActions>) {
+fn process_input(
+    keyboard: Res<ButtonInput<KeyCode>>,
+    mouse_buttons: Res<ButtonInput<MouseButton>>,
+    mouse_motion: Res<AccumulatedMouseMotion>,
+    mut actions: ResMut<InputActions>,
+) {
     let mut direction = Vec2::ZERO;
 
     // 基于相机方向的移动输入
@@ -68,7 +121,49 @@ fn process_input(keyboard: Res<ButtonInput<KeyCode>>, mut actions: ResMut<InputA
     actions.movement = direction;
 
     // 跳跃输入（just_pressed 确保只在按下瞬间触发）
-    actions.jump = keyboard.just_pressed(GameControl::Jump.key());
+    // 本帧可能没有模拟步，保留到被消费为止
+    if keyboard.just_pressed(GameControl::Jump.key()) {
+        actions.jump = true;
+    }
+    actions.jump_held = keyboard.pressed(GameControl::Jump.key());
+
+    // 冲刺为持续输入，翻滚与跳跃一样保留到被消费为止
+    actions.sprint = keyboard.pressed(GameControl::Sprint.key());
+    if keyboard.just_pressed(GameControl::Dodge.key()) {
+        actions.dodge = true;
+    }
+
+    // 锁定与切换目标
+    if keyboard.just_pressed(GameControl::LockOn.key()) {
+        actions.lock_on = true;
+    }
+    if keyboard.just_pressed(GameControl::CycleTarget.key()) {
+        actions.cycle_target = true;
+    }
+
+    // 手电筒开关与瞄准（鼠标移动量累积到被消费为止）
+    if keyboard.just_pressed(GameControl::Flashlight.key()) {
+        actions.toggle_flashlight = true;
+    }
+    actions.aim += mouse_motion.delta;
+
+    // 背包：打开/关闭界面，数字键使用对应格的物品
+    if keyboard.just_pressed(GameControl::Inventory.key()) {
+        actions.toggle_inventory = true;
+    }
+    if let Some(index) = SLOT_KEYS.iter().position(|key| keyboard.just_pressed(*key)) {
+        actions.use_slot = Some(index);
+    }
+
+    // 与面前的物体交互
+    if keyboard.just_pressed(GameControl::Interact.key()) {
+        actions.interact = true;
+    }
+
+    // 近战攻击
+    if mouse_buttons.just_pressed(ATTACK_BUTTON) {
+        actions.attack = true;
+    }
 }
 
 /// 处理 ESC 键的暂停/恢复切换
@@ -0,0 +1,651 @@
+//! 游戏内 HUD
+//!
+//! - 屏幕左下角显示玩家体力条：填充长度对应当前体力比例，冲刺中高亮，力竭时变为红色
+//! - 体力条上方显示手电筒电量条：关闭时变暗，电量不足时变为红色
+//! - 电量条上方显示生命条
+//! - 屏幕左上角显示玩家等级与经验值，下方为当前波次、分数，以及场上无敌人时下一波的倒计时
+//! - 波次信息下方显示关卡目标及进度，已完成的目标带“√”标记
+//! - 屏幕下方中央显示当前交互目标的提示（如“[E] 开门”）
+//!
+//! HUD 在资源加载完成后生成，只在 `GameState::Playing` 时显示。
+
+use bevy::prelude::*;
+use vigilant_doodle_assets::GameAssets;
+use vigilant_doodle_core::balance::ObjectiveKind;
+use vigilant_doodle_core::localization::{
+    keys, CurrentLanguage, Language, LocalizedText, TranslationResources,
+};
+use vigilant_doodle_core::state::GameState;
+use vigilant_doodle_core::BalanceConfig;
+use vigilant_doodle_gameplay::{
+    EncounterState, Flashlight, Health, Interactable, InteractionFocus, MovementAbilities,
+    Objective, Objectives, Player, Progression, Stamina,
+};
+
+// ============================================================================
+// 常量定义
+// ============================================================================
+
+const BAR_WIDTH: f32 = 240.0;
+const BAR_HEIGHT: f32 = 12.0;
+const BAR_MARGIN: f32 = 24.0;
+const BAR_BORDER: f32 = 2.0;
+const BAR_SPACING: f32 = 6.0;
+
+const BAR_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+const BAR_BORDER_COLOR: Color = Color::srgba(0.6, 0.7, 0.8, 0.8);
+const STAMINA_NORMAL: Color = Color::srgb(0.35, 0.75, 0.35);
+const STAMINA_SPRINTING: Color = Color::srgb(0.55, 0.95, 0.45);
+const STAMINA_EXHAUSTED: Color = Color::srgb(0.8, 0.25, 0.2);
+const BATTERY_ON: Color = Color::srgb(0.95, 0.8, 0.2);
+const BATTERY_OFF: Color = Color::srgb(0.45, 0.42, 0.3);
+const BATTERY_LOW: Color = Color::srgb(0.8, 0.25, 0.2);
+const HEALTH_COLOR: Color = Color::srgb(0.85, 0.2, 0.25);
+
+const ENCOUNTER_FONT_SIZE: f32 = 24.0;
+const ENCOUNTER_TEXT_COLOR: Color = Color::srgb(0.95, 0.95, 0.95);
+
+const OBJECTIVE_FONT_SIZE: f32 = 20.0;
+const OBJECTIVE_DONE_COLOR: Color = Color::srgb(0.55, 0.85, 0.5);
+
+const PROMPT_FONT_SIZE: f32 = 22.0;
+const PROMPT_BOTTOM: f32 = 140.0;
+
+// ============================================================================
+// 组件定义
+// ============================================================================
+
+/// 标记：HUD 根节点
+#[derive(Component)]
+struct HudRoot;
+
+/// 标记：体力条填充部分
+#[derive(Component)]
+struct StaminaBarFill;
+
+/// 标记：电量条填充部分
+#[derive(Component)]
+struct BatteryBarFill;
+
+/// 标记：生命条填充部分
+#[derive(Component)]
+struct HealthBarFill;
+
+/// 标记：等级与经验值文本
+#[derive(Component)]
+struct ProgressionText;
+
+/// 标记：关卡目标列表
+#[derive(Component)]
+struct ObjectiveTracker;
+
+/// 标记：交互提示文本
+#[derive(Component)]
+struct InteractionPrompt;
+
+/// 关卡目标的一行（第 n 个目标）
+#[derive(Component, Clone, Copy)]
+struct ObjectiveLine(usize);
+
+/// 波次信息文本
+#[derive(Component, Clone, Copy)]
+enum EncounterText {
+    Wave,
+    Score,
+    NextWave,
+}
+
+impl EncounterText {
+    /// 当前状态对应的文本参数，返回 None 时隐藏
+    fn args(&self, state: &EncounterState) -> Option<(&'static str, String)> {
+        match self {
+            Self::Wave => Some(("wave", state.wave().to_string())),
+            Self::Score => Some(("score", state.score().to_string())),
+            Self::NextWave if state.enemies_alive() == 0 && state.next_wave_in().is_finite() => {
+                Some(("seconds", format!("{:.0}", state.next_wave_in().ceil())))
+            }
+            Self::NextWave => None,
+        }
+    }
+}
+
+// ============================================================================
+// 插件定义
+// ============================================================================
+
+pub struct HudPlugin;
+
+impl Plugin for HudPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnExit(GameState::AssetLoading), setup_hud)
+            .add_systems(OnEnter(GameState::Playing), show_hud)
+            .add_systems(OnExit(GameState::Playing), hide_hud)
+            .add_systems(
+                Update,
+                (
+                    update_stamina_bar,
+                    update_battery_bar,
+                    update_health_bar,
+                    update_progression_text,
+                    update_encounter_text.run_if(resource_exists::<EncounterState>),
+                    update_interaction_prompt.run_if(resource_exists::<InteractionFocus>),
+                    update_objective_tracker.run_if(resource_exists::<Objectives>),
+                )
+                    .run_if(in_state(GameState::Playing)),
+            );
+
+        info!("[HUD] HUD 插件已加载");
+    }
+}
+
+// ============================================================================
+// 系统实现
+// ============================================================================
+
+/// 生成 HUD（初始隐藏）
+fn setup_hud(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    current_language: Res<CurrentLanguage>,
+    translation_resources: Res<TranslationResources>,
+) {
+    // 波次信息（左上角）
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(BAR_MARGIN),
+                top: Val::Px(BAR_MARGIN),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(4.0),
+                ..default()
+            },
+            Visibility::Hidden,
+            HudRoot,
+            Name::new("HudEncounter"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                TextFont {
+                    font: assets.font.clone(),
+                    font_size: ENCOUNTER_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(ENCOUNTER_TEXT_COLOR),
+                ProgressionText,
+            ));
+
+            for (key, text) in [
+                (keys::HUD_WAVE, EncounterText::Wave),
+                (keys::HUD_SCORE, EncounterText::Score),
+                (keys::HUD_NEXT_WAVE, EncounterText::NextWave),
+            ] {
+                let localized = LocalizedText::new(key);
+                parent.spawn((
+                    Text::new(localized.format(&translation_resources, current_language.language)),
+                    TextFont {
+                        font: assets.font.clone(),
+                        font_size: ENCOUNTER_FONT_SIZE,
+                        ..default()
+                    },
+                    TextColor(ENCOUNTER_TEXT_COLOR),
+                    localized,
+                    text,
+                ));
+            }
+
+            // 关卡目标（波次信息下方，每个目标一行，按目标数量增减）
+            parent
+                .spawn((
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(2.0),
+                        margin: UiRect::top(Val::Px(12.0)),
+                        ..default()
+                    },
+                    ObjectiveTracker,
+                ))
+                .with_children(|tracker| {
+                    let title = LocalizedText::new(keys::HUD_OBJECTIVES);
+                    tracker.spawn((
+                        Text::new(title.format(&translation_resources, current_language.language)),
+                        TextFont {
+                            font: assets.font.clone(),
+                            font_size: ENCOUNTER_FONT_SIZE,
+                            ..default()
+                        },
+                        TextColor(ENCOUNTER_TEXT_COLOR),
+                        title,
+                    ));
+                });
+        });
+
+    // 体力条（左下角）
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(BAR_MARGIN),
+                bottom: Val::Px(BAR_MARGIN),
+                width: Val::Px(BAR_WIDTH),
+                height: Val::Px(BAR_HEIGHT),
+                border: UiRect::all(Val::Px(BAR_BORDER)),
+                ..default()
+            },
+            BackgroundColor(BAR_BACKGROUND),
+            BorderColor::all(BAR_BORDER_COLOR),
+            Visibility::Hidden,
+            HudRoot,
+            Name::new("Hud"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(STAMINA_NORMAL),
+                StaminaBarFill,
+            ));
+        });
+
+    // 电量条（体力条上方）
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(BAR_MARGIN),
+                bottom: Val::Px(BAR_MARGIN + BAR_HEIGHT + BAR_SPACING),
+                width: Val::Px(BAR_WIDTH),
+                height: Val::Px(BAR_HEIGHT),
+                border: UiRect::all(Val::Px(BAR_BORDER)),
+                ..default()
+            },
+            BackgroundColor(BAR_BACKGROUND),
+            BorderColor::all(BAR_BORDER_COLOR),
+            Visibility::Hidden,
+            HudRoot,
+            Name::new("HudBattery"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(BATTERY_ON),
+                BatteryBarFill,
+            ));
+        });
+
+    // 生命条（电量条上方）
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                left: Val::Px(BAR_MARGIN),
+                bottom: Val::Px(BAR_MARGIN + (BAR_HEIGHT + BAR_SPACING) * 2.0),
+                width: Val::Px(BAR_WIDTH),
+                height: Val::Px(BAR_HEIGHT),
+                border: UiRect::all(Val::Px(BAR_BORDER)),
+                ..default()
+            },
+            BackgroundColor(BAR_BACKGROUND),
+            BorderColor::all(BAR_BORDER_COLOR),
+            Visibility::Hidden,
+            HudRoot,
+            Name::new("HudHealth"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Node {
+                    width: Val::Percent(100.0),
+                    height: Val::Percent(100.0),
+                    ..default()
+                },
+                BackgroundColor(HEALTH_COLOR),
+                HealthBarFill,
+            ));
+        });
+
+    // 交互提示（下方中央，有交互目标时显示）
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                bottom: Val::Px(PROMPT_BOTTOM),
+                width: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                ..default()
+            },
+            Visibility::Hidden,
+            HudRoot,
+            Name::new("HudInteraction"),
+        ))
+        .with_children(|parent| {
+            parent.spawn((
+                Text::default(),
+                TextFont {
+                    font: assets.font.clone(),
+                    font_size: PROMPT_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(ENCOUNTER_TEXT_COLOR),
+                Visibility::Hidden,
+                InteractionPrompt,
+            ));
+        });
+
+    info!("[HUD] HUD 已生成");
+}
+
+fn show_hud(mut query: Query<&mut Visibility, With<HudRoot>>) {
+    for mut visibility in query.iter_mut() {
+        *visibility = Visibility::Inherited;
+    }
+}
+
+fn hide_hud(mut query: Query<&mut Visibility, With<HudRoot>>) {
+    for mut visibility in query.iter_mut() {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+/// 按玩家体力更新体力条
+fn update_stamina_bar(
+    player_query: Query<(&Stamina, &MovementAbilities), With<Player>>,
+    mut fill_query: Query<(&mut Node, &mut BackgroundColor), With<StaminaBarFill>>,
+) {
+    let Ok((stamina, abilities)) = player_query.single() else {
+        return;
+    };
+
+    let color = if stamina.is_exhausted() {
+        STAMINA_EXHAUSTED
+    } else if abilities.is_sprinting() {
+        STAMINA_SPRINTING
+    } else {
+        STAMINA_NORMAL
+    };
+
+    for (mut node, mut background) in fill_query.iter_mut() {
+        node.width = Val::Percent(stamina.fraction() * 100.0);
+        background.0 = color;
+    }
+}
+
+/// 按手电筒电量更新电量条
+fn update_battery_bar(
+    player_query: Query<&Flashlight, With<Player>>,
+    mut fill_query: Query<(&mut Node, &mut BackgroundColor), With<BatteryBarFill>>,
+) {
+    let Ok(flashlight) = player_query.single() else {
+        return;
+    };
+
+    let color = if flashlight.is_low() {
+        BATTERY_LOW
+    } else if flashlight.is_on() {
+        BATTERY_ON
+    } else {
+        BATTERY_OFF
+    };
+
+    for (mut node, mut background) in fill_query.iter_mut() {
+        node.width = Val::Percent(flashlight.fraction() * 100.0);
+        background.0 = color;
+    }
+}
+
+/// 按玩家生命值更新生命条
+fn update_health_bar(
+    player_query: Query<&Health, With<Player>>,
+    mut fill_query: Query<&mut Node, With<HealthBarFill>>,
+) {
+    let Ok(health) = player_query.single() else {
+        return;
+    };
+
+    for mut node in fill_query.iter_mut() {
+        node.width = Val::Percent(health.fraction() * 100.0);
+    }
+}
+
+/// 按玩家等级与经验值更新等级文本
+fn update_progression_text(
+    player_query: Query<Ref<Progression>, With<Player>>,
+    balance: Res<BalanceConfig>,
+    current_language: Res<CurrentLanguage>,
+    translation_resources: Res<TranslationResources>,
+    mut text_query: Query<&mut Text, With<ProgressionText>>,
+) {
+    let Ok(progression) = player_query.single() else {
+        return;
+    };
+    if !progression.is_changed()
+        && !current_language.is_changed()
+        && !translation_resources.is_changed()
+    {
+        return;
+    }
+
+    let level = progression.level().to_string();
+    let localized = match progression.xp_to_next(&balance.player.progression) {
+        Some(next) => {
+            let xp = progression.xp().to_string();
+            let next = next.to_string();
+            LocalizedText::with_args(
+                keys::HUD_LEVEL,
+                vec![("level", &level), ("xp", &xp), ("next", &next)],
+            )
+        }
+        None => LocalizedText::with_args(keys::HUD_LEVEL_MAX, vec![("level", &level)]),
+    };
+    for mut text in text_query.iter_mut() {
+        **text = localized.format(&translation_resources, current_language.language);
+    }
+}
+
+/// 按遭遇状态更新波次信息
+fn update_encounter_text(
+    state: Res<EncounterState>,
+    current_language: Res<CurrentLanguage>,
+    translation_resources: Res<TranslationResources>,
+    mut text_query: Query<(
+        &EncounterText,
+        &mut LocalizedText,
+        &mut Text,
+        &mut Visibility,
+    )>,
+) {
+    for (kind, mut localized, mut text, mut visibility) in text_query.iter_mut() {
+        let Some((name, value)) = kind.args(&state) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        *visibility = Visibility::Inherited;
+
+        let args = Some(vec![(name.to_string(), value)]);
+        if localized.args != args {
+            localized.args = args;
+            **text = localized.format(&translation_resources, current_language.language);
+        }
+    }
+}
+
+/// 按交互目标更新交互提示
+fn update_interaction_prompt(
+    focus: Res<InteractionFocus>,
+    interactable_query: Query<Ref<Interactable>>,
+    current_language: Res<CurrentLanguage>,
+    translation_resources: Res<TranslationResources>,
+    mut prompt_query: Query<(&mut Text, &mut Visibility), With<InteractionPrompt>>,
+) {
+    let interactable = focus
+        .target
+        .and_then(|target| interactable_query.get(target).ok());
+    let changed = focus.is_changed()
+        || interactable
+            .as_ref()
+            .is_some_and(|interactable| interactable.is_changed())
+        || current_language.is_changed()
+        || translation_resources.is_changed();
+    if !changed {
+        return;
+    }
+
+    for (mut text, mut visibility) in prompt_query.iter_mut() {
+        let Some(interactable) = &interactable else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        *visibility = Visibility::Inherited;
+
+        let language = current_language.language;
+        let action = interactable.prompt.format(&translation_resources, language);
+        **text = LocalizedText::with_args(keys::HUD_INTERACT, vec![("action", &action)])
+            .format(&translation_resources, language);
+    }
+}
+
+/// 按关卡目标更新目标列表
+fn update_objective_tracker(
+    mut commands: Commands,
+    objectives: Res<Objectives>,
+    assets: Res<GameAssets>,
+    current_language: Res<CurrentLanguage>,
+    translation_resources: Res<TranslationResources>,
+    tracker_query: Query<Entity, With<ObjectiveTracker>>,
+    mut line_query: Query<(Entity, &ObjectiveLine, &mut Text, &mut TextColor)>,
+) {
+    if !objectives.is_changed()
+        && !current_language.is_changed()
+        && !translation_resources.is_changed()
+    {
+        return;
+    }
+    let Ok(tracker) = tracker_query.single() else {
+        return;
+    };
+
+    let language = current_language.language;
+    let lines: Vec<(String, bool)> = objectives
+        .iter()
+        .map(|objective| {
+            let text = objective_text(&translation_resources, language, objective);
+            let key = if objective.is_completed() {
+                keys::OBJECTIVE_DONE
+            } else {
+                keys::OBJECTIVE_PENDING
+            };
+            let line = LocalizedText::with_args(key, vec![("text", &text)])
+                .format(&translation_resources, language);
+            (line, objective.is_completed())
+        })
+        .collect();
+
+    // 更新已有的行，移除多余的行
+    let mut present = vec![false; lines.len()];
+    for (entity, line, mut text, mut color) in line_query.iter_mut() {
+        let Some((content, done)) = lines.get(line.0) else {
+            commands.entity(entity).despawn();
+            continue;
+        };
+        present[line.0] = true;
+        if **text != *content {
+            **text = content.clone();
+        }
+        color.0 = objective_color(*done);
+    }
+
+    // 补齐缺少的行
+    for (index, (content, done)) in lines.into_iter().enumerate() {
+        if present[index] {
+            continue;
+        }
+        let line = commands
+            .spawn((
+                Text::new(content),
+                TextFont {
+                    font: assets.font.clone(),
+                    font_size: OBJECTIVE_FONT_SIZE,
+                    ..default()
+                },
+                TextColor(objective_color(done)),
+                ObjectiveLine(index),
+            ))
+            .id();
+        commands.entity(tracker).add_child(line);
+    }
+}
+
+fn objective_color(done: bool) -> Color {
+    if done {
+        OBJECTIVE_DONE_COLOR
+    } else {
+        ENCOUNTER_TEXT_COLOR
+    }
+}
+
+/// 目标的描述文本（不含完成标记）
+fn objective_text(
+    resources: &TranslationResources,
+    language: Language,
+    objective: &Objective,
+) -> String {
+    let current = format!("{:.0}", objective.progress().floor());
+    let target = format!("{:.0}", objective.target());
+    let localized = match &objective.kind {
+        ObjectiveKind::Kill {
+            archetype: Some(archetype),
+            ..
+        } => {
+            let name = display_name(resources, language, keys::ENEMY_PREFIX, archetype);
+            LocalizedText::with_args(
+                keys::OBJECTIVE_KILL_ARCHETYPE,
+                vec![("name", &name), ("current", &current), ("target", &target)],
+            )
+        }
+        ObjectiveKind::Kill {
+            archetype: None, ..
+        } => LocalizedText::with_args(
+            keys::OBJECTIVE_KILL,
+            vec![("current", &current), ("target", &target)],
+        ),
+        ObjectiveKind::Reach { .. } => LocalizedText::new(keys::OBJECTIVE_REACH),
+        ObjectiveKind::Collect { item, .. } => {
+            let name = display_name(resources, language, keys::ITEM_PREFIX, item);
+            LocalizedText::with_args(
+                keys::OBJECTIVE_COLLECT,
+                vec![("name", &name), ("current", &current), ("target", &target)],
+            )
+        }
+        ObjectiveKind::Survive { .. } => {
+            let seconds = if objective.is_completed() {
+                target.clone()
+            } else {
+                format!("{:.0}", (objective.target() - objective.progress()).ceil())
+            };
+            LocalizedText::with_args(keys::OBJECTIVE_SURVIVE, vec![("seconds", &seconds)])
+        }
+    };
+    localized.format(resources, language)
+}
+
+/// 带前缀的翻译键对应的名称，缺少翻译时直接显示名称
+fn display_name(
+    resources: &TranslationResources,
+    language: Language,
+    prefix: &str,
+    name: &str,
+) -> String {
+    let key = format!("{}{}", prefix, name);
+    if resources.has_message(language, &key) {
+        resources.get(language, &key)
+    } else {
+        name.to_string()
+    }
+}
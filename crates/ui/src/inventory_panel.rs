@@ -0,0 +1,211 @@
+//! 背包界面
+//!
+//! 游戏中按 I 打开/关闭，显示在屏幕右侧：
+//! - 每格一行：编号（对应使用物品的数字键）、物品名称与数量，空格显示为“—”
+//! - 最后一行显示当前装备的武器
+//!
+//! 物品名称取自翻译键 `item-<物品名称>`，缺少翻译时直接显示物品名称。
+//! 离开 `GameState::Playing` 时自动关闭。
+
+use bevy::prelude::*;
+use vigilant_doodle_assets::GameAssets;
+use vigilant_doodle_core::balance::EquipmentSlot;
+use vigilant_doodle_core::localization::{
+    keys, CurrentLanguage, Language, LocalizedText, TranslationResources,
+};
+use vigilant_doodle_core::state::GameState;
+use vigilant_doodle_core::BalanceConfig;
+use vigilant_doodle_gameplay::{Inventory, Player};
+use vigilant_doodle_input::actions::InputActions;
+
+// ============================================================================
+// 常量定义
+// ============================================================================
+
+const PANEL_WIDTH: f32 = 280.0;
+const PANEL_MARGIN: f32 = 24.0;
+const PANEL_PADDING: f32 = 16.0;
+const PANEL_BACKGROUND: Color = Color::srgba(0.0, 0.0, 0.0, 0.7);
+const PANEL_BORDER_COLOR: Color = Color::srgba(0.6, 0.7, 0.8, 0.8);
+
+const TITLE_FONT_SIZE: f32 = 28.0;
+const ROW_FONT_SIZE: f32 = 20.0;
+const TEXT_COLOR: Color = Color::srgb(0.95, 0.95, 0.95);
+
+// ============================================================================
+// 组件定义
+// ============================================================================
+
+/// 标记：背包界面根节点
+#[derive(Component)]
+struct InventoryPanel;
+
+/// 背包界面的一行
+#[derive(Component, Clone, Copy)]
+enum InventoryRow {
+    /// 第 n 格（从 0 开始）
+    Slot(usize),
+    /// 武器装备槽
+    Weapon,
+}
+
+// ============================================================================
+// 插件定义
+// ============================================================================
+
+pub struct InventoryPanelPlugin;
+
+impl Plugin for InventoryPanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnExit(GameState::AssetLoading), setup_inventory_panel)
+            .add_systems(OnExit(GameState::Playing), close_inventory_panel)
+            .add_systems(
+                Update,
+                (toggle_inventory_panel, refresh_inventory_panel)
+                    .run_if(in_state(GameState::Playing)),
+            );
+
+        info!("[Inventory] 背包界面插件已加载");
+    }
+}
+
+// ============================================================================
+// 系统实现
+// ============================================================================
+
+/// 生成背包界面（初始隐藏）
+fn setup_inventory_panel(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    balance: Res<BalanceConfig>,
+    current_language: Res<CurrentLanguage>,
+    translation_resources: Res<TranslationResources>,
+) {
+    let font = |font_size| TextFont {
+        font: assets.font.clone(),
+        font_size,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                right: Val::Px(PANEL_MARGIN),
+                top: Val::Px(PANEL_MARGIN),
+                width: Val::Px(PANEL_WIDTH),
+                padding: UiRect::all(Val::Px(PANEL_PADDING)),
+                border: UiRect::all(Val::Px(2.0)),
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(6.0),
+                ..default()
+            },
+            BackgroundColor(PANEL_BACKGROUND),
+            BorderColor::all(PANEL_BORDER_COLOR),
+            Visibility::Hidden,
+            InventoryPanel,
+            Name::new("InventoryPanel"),
+        ))
+        .with_children(|parent| {
+            let title = LocalizedText::new(keys::INVENTORY_TITLE);
+            parent.spawn((
+                Text::new(title.format(&translation_resources, current_language.language)),
+                font(TITLE_FONT_SIZE),
+                TextColor(TEXT_COLOR),
+                title,
+            ));
+
+            let rows = (0..balance.player.inventory.capacity)
+                .map(InventoryRow::Slot)
+                .chain(std::iter::once(InventoryRow::Weapon));
+            for row in rows {
+                parent.spawn((
+                    Text::default(),
+                    font(ROW_FONT_SIZE),
+                    TextColor(TEXT_COLOR),
+                    row,
+                ));
+            }
+        });
+}
+
+/// 处理背包界面开关输入
+fn toggle_inventory_panel(
+    mut actions: ResMut<InputActions>,
+    mut panel_query: Query<&mut Visibility, With<InventoryPanel>>,
+) {
+    if !std::mem::take(&mut actions.toggle_inventory) {
+        return;
+    }
+    for mut visibility in panel_query.iter_mut() {
+        *visibility = match *visibility {
+            Visibility::Hidden => Visibility::Inherited,
+            _ => Visibility::Hidden,
+        };
+    }
+}
+
+fn close_inventory_panel(mut panel_query: Query<&mut Visibility, With<InventoryPanel>>) {
+    for mut visibility in panel_query.iter_mut() {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+/// 背包内容或语言变化时更新每一行
+fn refresh_inventory_panel(
+    player_query: Query<Ref<Inventory>, With<Player>>,
+    current_language: Res<CurrentLanguage>,
+    translation_resources: Res<TranslationResources>,
+    mut row_query: Query<(&InventoryRow, &mut Text)>,
+) {
+    let Ok(inventory) = player_query.single() else {
+        return;
+    };
+    if !inventory.is_changed()
+        && !current_language.is_changed()
+        && !translation_resources.is_changed()
+    {
+        return;
+    }
+
+    let language = current_language.language;
+    for (row, mut text) in row_query.iter_mut() {
+        let localized = match *row {
+            InventoryRow::Slot(index) => {
+                let number = (index + 1).to_string();
+                match inventory.slots().get(index) {
+                    Some(stack) => {
+                        let name = item_name(&translation_resources, language, &stack.item);
+                        let count = stack.count.to_string();
+                        LocalizedText::with_args(
+                            keys::INVENTORY_SLOT,
+                            vec![("index", &number), ("name", &name), ("count", &count)],
+                        )
+                    }
+                    None => LocalizedText::with_args(
+                        keys::INVENTORY_EMPTY_SLOT,
+                        vec![("index", &number)],
+                    ),
+                }
+            }
+            InventoryRow::Weapon => {
+                let name = match inventory.equipped(EquipmentSlot::Weapon) {
+                    Some(item) => item_name(&translation_resources, language, item),
+                    None => translation_resources.get(language, keys::INVENTORY_NOTHING),
+                };
+                LocalizedText::with_args(keys::INVENTORY_WEAPON, vec![("name", &name)])
+            }
+        };
+        **text = localized.format(&translation_resources, language);
+    }
+}
+
+/// 物品的显示名称
+fn item_name(resources: &TranslationResources, language: Language, item: &str) -> String {
+    let key = format!("{}{}", keys::ITEM_PREFIX, item);
+    if resources.has_message(language, &key) {
+        resources.get(language, &key)
+    } else {
+        item.to_string()
+    }
+}
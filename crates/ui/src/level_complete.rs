@@ -0,0 +1,194 @@
+//! 通关界面
+//!
+//! 关卡目标全部完成后（`GameState::LevelComplete`）显示：
+//! - 标题与累计通关数
+//! - “下一关”：加载下一关并继续游戏
+//! - “返回主菜单”：同样先加载下一关，之后从主菜单继续时从下一关开始
+//!
+//! 离开 `GameState::LevelComplete` 时清理界面。
+
+use bevy::prelude::*;
+use vigilant_doodle_assets::GameAssets;
+use vigilant_doodle_core::localization::{
+    keys, CurrentLanguage, LocalizedText, TranslationResources,
+};
+use vigilant_doodle_core::state::{GameProgress, GameState};
+use vigilant_doodle_gameplay::AdvanceLevel;
+
+// ============================================================================
+// 常量定义
+// ============================================================================
+
+const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+const TITLE_FONT_SIZE: f32 = 64.0;
+const SUMMARY_FONT_SIZE: f32 = 28.0;
+const BUTTON_FONT_SIZE: f32 = 32.0;
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+
+const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
+const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
+const PRESSED_BUTTON: Color = Color::srgb(0.35, 0.75, 0.35);
+
+// ============================================================================
+// 组件定义
+// ============================================================================
+
+/// 标记：通关界面根节点
+#[derive(Component)]
+struct LevelCompleteRoot;
+
+/// 按钮动作
+#[derive(Component, Clone, Copy)]
+enum LevelCompleteAction {
+    NextLevel,  // 下一关
+    BackToMenu, // 返回主菜单
+}
+
+// ============================================================================
+// 插件定义
+// ============================================================================
+
+pub struct LevelCompletePlugin;
+
+impl Plugin for LevelCompletePlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnEnter(GameState::LevelComplete), setup_level_complete)
+            .add_systems(OnExit(GameState::LevelComplete), cleanup_level_complete)
+            .add_systems(
+                Update,
+                level_complete_buttons.run_if(in_state(GameState::LevelComplete)),
+            );
+
+        info!("[LevelComplete] 通关界面插件已加载");
+    }
+}
+
+// ============================================================================
+// 系统实现
+// ============================================================================
+
+/// 生成通关界面
+fn setup_level_complete(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    current_language: Res<CurrentLanguage>,
+    translation_resources: Res<TranslationResources>,
+    game_progress: Res<GameProgress>,
+) {
+    let language = current_language.language;
+    let font = |font_size| TextFont {
+        font: assets.font.clone(),
+        font_size,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(OVERLAY_COLOR),
+            LevelCompleteRoot,
+            Name::new("LevelCompleteRoot"),
+        ))
+        .with_children(|parent| {
+            let title = LocalizedText::new(keys::LEVEL_COMPLETE_TITLE);
+            parent.spawn((
+                Text::new(title.format(&translation_resources, language)),
+                font(TITLE_FONT_SIZE),
+                TextColor(TEXT_COLOR),
+                title,
+            ));
+
+            let count = game_progress.levels_completed.to_string();
+            let summary =
+                LocalizedText::with_args(keys::LEVEL_COMPLETE_SUMMARY, vec![("count", &count)]);
+            parent.spawn((
+                Text::new(summary.format(&translation_resources, language)),
+                font(SUMMARY_FONT_SIZE),
+                TextColor(TEXT_COLOR),
+                Node {
+                    margin: UiRect::bottom(Val::Px(30.0)),
+                    ..default()
+                },
+                summary,
+            ));
+
+            for (action, key) in [
+                (LevelCompleteAction::NextLevel, keys::LEVEL_COMPLETE_NEXT),
+                (LevelCompleteAction::BackToMenu, keys::GAME_BACK_TO_MENU),
+            ] {
+                let label = LocalizedText::new(key);
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            width: Val::Px(300.0),
+                            height: Val::Px(65.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BackgroundColor(NORMAL_BUTTON),
+                        action,
+                    ))
+                    .with_child((
+                        Text::new(label.format(&translation_resources, language)),
+                        font(BUTTON_FONT_SIZE),
+                        TextColor(TEXT_COLOR),
+                        label,
+                    ));
+            }
+        });
+}
+
+fn cleanup_level_complete(
+    mut commands: Commands,
+    root_query: Query<Entity, With<LevelCompleteRoot>>,
+) {
+    for entity in root_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 通关界面按钮交互
+fn level_complete_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &LevelCompleteAction),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut advance: MessageWriter<AdvanceLevel>,
+    mut next_state: ResMut<NextState<GameState>>,
+) {
+    for (interaction, mut color, action) in &mut interaction_query {
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                advance.write(AdvanceLevel);
+                match action {
+                    LevelCompleteAction::NextLevel => {
+                        info!("[LevelComplete] Clicked: NextLevel - 进入下一关");
+                        next_state.set(GameState::Playing);
+                    }
+                    LevelCompleteAction::BackToMenu => {
+                        info!("[LevelComplete] Clicked: BackToMenu - 返回主菜单");
+                        next_state.set(GameState::MainMenu);
+                    }
+                }
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
@@ -0,0 +1,240 @@
+//! 升级界面
+//!
+//! 玩家有未使用的属性选择时（`GameState::LevelUp`）显示：
+//! - 标题为当前等级，下方提示剩余的选择次数
+//! - 每项属性一个按钮，显示提升前后的数值；跳跃次数已达上限时按钮变暗且不可选
+//!
+//! 点击按钮发出 `UpgradeChosen`，选择用完后由 `gameplay` 回到游戏。
+//! 每次选择后按新的进度重建界面，离开 `GameState::LevelUp` 时清理。
+
+use bevy::prelude::*;
+use vigilant_doodle_assets::GameAssets;
+use vigilant_doodle_core::localization::{
+    keys, CurrentLanguage, LocalizedText, TranslationResources,
+};
+use vigilant_doodle_core::state::GameState;
+use vigilant_doodle_core::BalanceConfig;
+use vigilant_doodle_gameplay::{Player, Progression, Upgrade, UpgradeChosen};
+
+// ============================================================================
+// 常量定义
+// ============================================================================
+
+const OVERLAY_COLOR: Color = Color::srgba(0.0, 0.0, 0.0, 0.6);
+const TITLE_FONT_SIZE: f32 = 64.0;
+const CHOICES_FONT_SIZE: f32 = 28.0;
+const BUTTON_FONT_SIZE: f32 = 28.0;
+const TEXT_COLOR: Color = Color::srgb(0.9, 0.9, 0.9);
+const DISABLED_TEXT_COLOR: Color = Color::srgb(0.5, 0.5, 0.5);
+
+const NORMAL_BUTTON: Color = Color::srgb(0.15, 0.15, 0.15);
+const HOVERED_BUTTON: Color = Color::srgb(0.25, 0.25, 0.25);
+const PRESSED_BUTTON: Color = Color::srgb(0.35, 0.75, 0.35);
+const DISABLED_BUTTON: Color = Color::srgb(0.1, 0.1, 0.1);
+
+// ============================================================================
+// 组件定义
+// ============================================================================
+
+/// 标记：升级界面根节点
+#[derive(Component)]
+struct LevelUpRoot;
+
+/// 属性按钮（`enabled` 为 false 时不可选择）
+#[derive(Component, Clone, Copy)]
+struct UpgradeButton {
+    upgrade: Upgrade,
+    enabled: bool,
+}
+
+// ============================================================================
+// 插件定义
+// ============================================================================
+
+pub struct LevelUpPlugin;
+
+impl Plugin for LevelUpPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnExit(GameState::LevelUp), cleanup_level_up)
+            .add_systems(
+                Update,
+                (refresh_level_up, upgrade_buttons)
+                    .chain()
+                    .run_if(in_state(GameState::LevelUp)),
+            );
+
+        info!("[LevelUp] 升级界面插件已加载");
+    }
+}
+
+// ============================================================================
+// 系统实现
+// ============================================================================
+
+/// 界面尚未生成或进度变化时（重新）生成升级界面
+fn refresh_level_up(
+    mut commands: Commands,
+    player_query: Query<Ref<Progression>, With<Player>>,
+    root_query: Query<Entity, With<LevelUpRoot>>,
+    assets: Res<GameAssets>,
+    balance: Res<BalanceConfig>,
+    current_language: Res<CurrentLanguage>,
+    translation_resources: Res<TranslationResources>,
+) {
+    // 选择用完后保留原界面，等待回到游戏
+    let Ok(progression) = player_query.single() else {
+        return;
+    };
+    if progression.pending_choices() == 0 {
+        return;
+    }
+    if !progression.is_changed()
+        && !current_language.is_changed()
+        && !translation_resources.is_changed()
+        && !root_query.is_empty()
+    {
+        return;
+    }
+    for entity in root_query.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let language = current_language.language;
+    let config = &balance.player;
+    let font = |font_size| TextFont {
+        font: assets.font.clone(),
+        font_size,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                row_gap: Val::Px(20.0),
+                ..default()
+            },
+            BackgroundColor(OVERLAY_COLOR),
+            LevelUpRoot,
+            Name::new("LevelUpRoot"),
+        ))
+        .with_children(|parent| {
+            let level = progression.level().to_string();
+            let title = LocalizedText::with_args(keys::LEVEL_UP_TITLE, vec![("level", &level)]);
+            parent.spawn((
+                Text::new(title.format(&translation_resources, language)),
+                font(TITLE_FONT_SIZE),
+                TextColor(TEXT_COLOR),
+            ));
+
+            let remaining = progression.pending_choices().to_string();
+            let choices =
+                LocalizedText::with_args(keys::LEVEL_UP_CHOICES, vec![("remaining", &remaining)]);
+            parent.spawn((
+                Text::new(choices.format(&translation_resources, language)),
+                font(CHOICES_FONT_SIZE),
+                TextColor(TEXT_COLOR),
+                Node {
+                    margin: UiRect::bottom(Val::Px(30.0)),
+                    ..default()
+                },
+            ));
+
+            for upgrade in Upgrade::ALL {
+                let enabled = progression.can_choose(upgrade, config);
+                let (key, from, to) = match upgrade {
+                    Upgrade::Speed => (
+                        keys::UPGRADE_SPEED,
+                        format!("{:.1}", progression.speed(config)),
+                        format!(
+                            "{:.1}",
+                            progression.speed(config) + config.progression.speed_bonus
+                        ),
+                    ),
+                    Upgrade::MaxHealth => (
+                        keys::UPGRADE_MAX_HEALTH,
+                        format!("{:.0}", progression.max_health(config)),
+                        format!(
+                            "{:.0}",
+                            progression.max_health(config) + config.progression.max_health_bonus
+                        ),
+                    ),
+                    Upgrade::JumpCount if enabled => (
+                        keys::UPGRADE_JUMP_COUNT,
+                        progression.max_jump_count(config).to_string(),
+                        (progression.max_jump_count(config) + 1).to_string(),
+                    ),
+                    Upgrade::JumpCount => (
+                        keys::UPGRADE_JUMP_COUNT_MAXED,
+                        progression.max_jump_count(config).to_string(),
+                        String::new(),
+                    ),
+                };
+                let label = LocalizedText::with_args(key, vec![("from", &from), ("to", &to)]);
+                let (background, text_color) = if enabled {
+                    (NORMAL_BUTTON, TEXT_COLOR)
+                } else {
+                    (DISABLED_BUTTON, DISABLED_TEXT_COLOR)
+                };
+                parent
+                    .spawn((
+                        Button,
+                        Node {
+                            width: Val::Px(420.0),
+                            height: Val::Px(65.0),
+                            justify_content: JustifyContent::Center,
+                            align_items: AlignItems::Center,
+                            ..default()
+                        },
+                        BackgroundColor(background),
+                        UpgradeButton { upgrade, enabled },
+                    ))
+                    .with_child((
+                        Text::new(label.format(&translation_resources, language)),
+                        font(BUTTON_FONT_SIZE),
+                        TextColor(text_color),
+                    ));
+            }
+        });
+}
+
+fn cleanup_level_up(mut commands: Commands, root_query: Query<Entity, With<LevelUpRoot>>) {
+    for entity in root_query.iter() {
+        commands.entity(entity).despawn();
+    }
+}
+
+/// 属性按钮交互
+fn upgrade_buttons(
+    mut interaction_query: Query<
+        (&Interaction, &mut BackgroundColor, &UpgradeButton),
+        (Changed<Interaction>, With<Button>),
+    >,
+    mut chosen: MessageWriter<UpgradeChosen>,
+) {
+    for (interaction, mut color, button) in &mut interaction_query {
+        if !button.enabled {
+            continue;
+        }
+        match *interaction {
+            Interaction::Pressed => {
+                *color = PRESSED_BUTTON.into();
+                info!("[LevelUp] Clicked: {:?}", button.upgrade);
+                chosen.write(UpgradeChosen {
+                    upgrade: button.upgrade,
+                });
+            }
+            Interaction::Hovered => {
+                *color = HOVERED_BUTTON.into();
+            }
+            Interaction::None => {
+                *color = NORMAL_BUTTON.into();
+            }
+        }
+    }
+}
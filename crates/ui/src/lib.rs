@@ -1,6 +1,6 @@
 //! Vigilant Doodle UI - 用户界面系统
 //!
-//! 提供游戏的用户界面，包括主菜单、设置菜单等。
+//! 提供游戏的用户界面，包括主菜单、设置菜单、游戏内 HUD、背包界面、纸条界面、通关界面、升级界面等。
 
 #![allow(clippy::type_complexity)]
 
@@ -8,6 +8,18 @@
 pub mod menu;
 pub mod settings_menu;
 
+// 游戏内 HUD、背包界面、纸条界面、通关界面与升级界面
+pub mod hud;
+pub mod inventory_panel;
+pub mod level_complete;
+pub mod level_up;
+pub mod note_panel;
+
 // 重新导出常用类型
+pub use hud::HudPlugin;
+pub use inventory_panel::InventoryPanelPlugin;
+pub use level_complete::LevelCompletePlugin;
+pub use level_up::LevelUpPlugin;
 pub use menu::MenuPlugin;
+pub use note_panel::NotePanelPlugin;
 pub use settings_menu::SettingsMenuPlugin;
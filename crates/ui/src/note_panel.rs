@@ -0,0 +1,159 @@
+//! 纸条界面
+//!
+//! 玩家阅读纸条时在屏幕中央显示纸条文本，下方提示关闭方式；
+//! 再次交互或离开纸条后关闭（见 `gameplay` 的 `ReadingNote`）。
+//! 离开 `GameState::Playing` 时隐藏，回到游戏后若仍在阅读则重新显示。
+
+use bevy::prelude::*;
+use vigilant_doodle_assets::GameAssets;
+use vigilant_doodle_core::localization::{
+    keys, CurrentLanguage, LocalizedText, TranslationResources,
+};
+use vigilant_doodle_core::state::GameState;
+use vigilant_doodle_gameplay::{Note, ReadingNote};
+
+// ============================================================================
+// 常量定义
+// ============================================================================
+
+const PANEL_WIDTH: f32 = 520.0;
+const PANEL_PADDING: f32 = 24.0;
+const PANEL_BACKGROUND: Color = Color::srgba(0.93, 0.9, 0.8, 0.95);
+const PANEL_BORDER_COLOR: Color = Color::srgba(0.45, 0.38, 0.28, 1.0);
+
+const TEXT_FONT_SIZE: f32 = 22.0;
+const HINT_FONT_SIZE: f32 = 16.0;
+const TEXT_COLOR: Color = Color::srgb(0.15, 0.12, 0.1);
+const HINT_COLOR: Color = Color::srgb(0.4, 0.35, 0.3);
+
+// ============================================================================
+// 组件定义
+// ============================================================================
+
+/// 标记：纸条界面根节点
+#[derive(Component)]
+struct NotePanel;
+
+/// 标记：纸条正文
+#[derive(Component)]
+struct NoteText;
+
+// ============================================================================
+// 插件定义
+// ============================================================================
+
+pub struct NotePanelPlugin;
+
+impl Plugin for NotePanelPlugin {
+    fn build(&self, app: &mut App) {
+        app.add_systems(OnExit(GameState::AssetLoading), setup_note_panel)
+            .add_systems(OnExit(GameState::Playing), hide_note_panel)
+            .add_systems(
+                Update,
+                refresh_note_panel.run_if(in_state(GameState::Playing)),
+            );
+
+        info!("[Note] 纸条界面插件已加载");
+    }
+}
+
+// ============================================================================
+// 系统实现
+// ============================================================================
+
+/// 生成纸条界面（初始隐藏）
+fn setup_note_panel(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    current_language: Res<CurrentLanguage>,
+    translation_resources: Res<TranslationResources>,
+) {
+    let font = |font_size| TextFont {
+        font: assets.font.clone(),
+        font_size,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            Node {
+                position_type: PositionType::Absolute,
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            Visibility::Hidden,
+            NotePanel,
+            Name::new("NotePanel"),
+        ))
+        .with_children(|parent| {
+            parent
+                .spawn((
+                    Node {
+                        width: Val::Px(PANEL_WIDTH),
+                        padding: UiRect::all(Val::Px(PANEL_PADDING)),
+                        border: UiRect::all(Val::Px(2.0)),
+                        flex_direction: FlexDirection::Column,
+                        row_gap: Val::Px(16.0),
+                        ..default()
+                    },
+                    BackgroundColor(PANEL_BACKGROUND),
+                    BorderColor::all(PANEL_BORDER_COLOR),
+                ))
+                .with_children(|panel| {
+                    panel.spawn((
+                        Text::default(),
+                        font(TEXT_FONT_SIZE),
+                        TextColor(TEXT_COLOR),
+                        NoteText,
+                    ));
+
+                    let hint = LocalizedText::new(keys::NOTE_CLOSE_HINT);
+                    panel.spawn((
+                        Text::new(hint.format(&translation_resources, current_language.language)),
+                        font(HINT_FONT_SIZE),
+                        TextColor(HINT_COLOR),
+                        hint,
+                    ));
+                });
+        });
+}
+
+fn hide_note_panel(mut panel_query: Query<&mut Visibility, With<NotePanel>>) {
+    for mut visibility in panel_query.iter_mut() {
+        *visibility = Visibility::Hidden;
+    }
+}
+
+/// 按正在阅读的纸条更新界面
+fn refresh_note_panel(
+    reading: Res<ReadingNote>,
+    note_query: Query<&Note>,
+    current_language: Res<CurrentLanguage>,
+    translation_resources: Res<TranslationResources>,
+    mut panel_query: Query<&mut Visibility, With<NotePanel>>,
+    mut text_query: Query<&mut Text, With<NoteText>>,
+) {
+    for mut visibility in panel_query.iter_mut() {
+        // 暂停后回到游戏时界面已被隐藏，需要重新显示
+        let reopened = *visibility == Visibility::Hidden && reading.note.is_some();
+        if !reading.is_changed()
+            && !reopened
+            && !current_language.is_changed()
+            && !translation_resources.is_changed()
+        {
+            continue;
+        }
+
+        let Some(note) = reading.note.and_then(|note| note_query.get(note).ok()) else {
+            *visibility = Visibility::Hidden;
+            continue;
+        };
+        *visibility = Visibility::Inherited;
+        for mut text in text_query.iter_mut() {
+            **text = translation_resources.get(current_language.language, &note.text);
+        }
+    }
+}
@@ -5,6 +5,7 @@
 use vigilant_doodle_assets::GameAssets;
 use vigilant_doodle_core::localization::{CurrentLanguage, Language, LocalizedText, TranslationResources};
 use vigilant_doodle_core::state::GameState;
+use vigilant_doodle_gameplay::{TargetingMode, TargetingSettings};
 use bevy::prelude::*;
 
 // ============================================================================
@@ -50,6 +51,31 @@ enum SettingsButtonAction {
     // 语言选择
     LanguageChinese,
     LanguageEnglish,
+
+    // 游戏性选项
+    ToggleAutoFace,
+    ToggleLockMode,
+}
+
+/// 游戏性选项的当前值文本
+#[derive(Component, Clone, Copy)]
+enum GameplaySettingValue {
+    AutoFace,
+    LockMode,
+}
+
+impl GameplaySettingValue {
+    /// 当前值对应的翻译键
+    fn key(&self, settings: &TargetingSettings) -> &'static str {
+        match self {
+            Self::AutoFace if settings.auto_face => "settings-on",
+            Self::AutoFace => "settings-off",
+            Self::LockMode => match settings.mode {
+                TargetingMode::Soft => "settings-lock-mode-soft",
+                TargetingMode::Hard => "settings-lock-mode-hard",
+            },
+        }
+    }
 }
 
 // ============================================================================
@@ -72,10 +98,18 @@ impl Plugin for SettingsMenuPlugin {
                 setup_appearance_settings,
             )
             .add_systems(OnExit(SettingsMenuState::Appearance), cleanup_settings_menu)
+            // 游戏性设置
+            .add_systems(
+                OnEnter(SettingsMenuState::Gameplay),
+                setup_gameplay_settings,
+            )
+            .add_systems(OnExit(SettingsMenuState::Gameplay), cleanup_settings_menu)
             // 按钮交互
             .add_systems(
                 Update,
-                settings_button_system.run_if(not(in_state(SettingsMenuState::Hidden))),
+                (settings_button_system, update_gameplay_setting_values)
+                    .chain()
+                    .run_if(not(in_state(SettingsMenuState::Hidden))),
             );
 
         info!("[SettingsMenu] 设置菜单插件已加载");
@@ -505,6 +539,156 @@ fn setup_appearance_settings(
     info!("[SettingsMenu] 外观设置生成完成");
 }
 
+/// 生成游戏性设置菜单
+fn setup_gameplay_settings(
+    mut commands: Commands,
+    assets: Res<GameAssets>,
+    current_language: Res<CurrentLanguage>,
+    translation_resources: Res<TranslationResources>,
+    targeting_settings: Res<TargetingSettings>,
+) {
+    info!("[SettingsMenu] ========== 生成游戏性设置 ==========");
+
+    let language = current_language.language;
+    let text_font = |font_size: f32| TextFont {
+        font: assets.font.clone(),
+        font_size,
+        ..default()
+    };
+
+    commands
+        .spawn((
+            Node {
+                width: Val::Percent(100.0),
+                height: Val::Percent(100.0),
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                flex_direction: FlexDirection::Column,
+                ..default()
+            },
+            BackgroundColor(Color::srgb(0.1, 0.1, 0.1)),
+            SettingsRoot,
+            Name::new("GameplaySettings"),
+        ))
+        .with_children(|parent| {
+            // 标题
+            parent.spawn((
+                Text::new(translation_resources.get(language, "settings-gameplay")),
+                text_font(60.0),
+                TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                LocalizedText::new("settings-gameplay"),
+            ));
+
+            // 选项容器
+            parent
+                .spawn(Node {
+                    flex_direction: FlexDirection::Column,
+                    align_items: AlignItems::Center,
+                    row_gap: Val::Px(15.0),
+                    margin: UiRect::top(Val::Px(40.0)),
+                    ..default()
+                })
+                .with_children(|options_parent| {
+                    // 每个选项一行：名称 + 切换按钮（显示当前值）
+                    for (label_key, action, value) in [
+                        (
+                            "settings-auto-face",
+                            SettingsButtonAction::ToggleAutoFace,
+                            GameplaySettingValue::AutoFace,
+                        ),
+                        (
+                            "settings-lock-mode",
+                            SettingsButtonAction::ToggleLockMode,
+                            GameplaySettingValue::LockMode,
+                        ),
+                    ] {
+                        let value_key = value.key(&targeting_settings);
+                        options_parent
+                            .spawn(Node {
+                                flex_direction: FlexDirection::Row,
+                                align_items: AlignItems::Center,
+                                column_gap: Val::Px(20.0),
+                                ..default()
+                            })
+                            .with_children(|row| {
+                                row.spawn((
+                                    Text::new(translation_resources.get(language, label_key)),
+                                    text_font(28.0),
+                                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                                    LocalizedText::new(label_key),
+                                    Node {
+                                        width: Val::Px(300.0),
+                                        ..default()
+                                    },
+                                ));
+                                row.spawn((
+                                    Button,
+                                    Node {
+                                        width: Val::Px(180.0),
+                                        height: Val::Px(50.0),
+                                        justify_content: JustifyContent::Center,
+                                        align_items: AlignItems::Center,
+                                        ..default()
+                                    },
+                                    BackgroundColor(NORMAL_BUTTON),
+                                    action,
+                                ))
+                                .with_child((
+                                    Text::new(translation_resources.get(language, value_key)),
+                                    text_font(24.0),
+                                    TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                                    LocalizedText::new(value_key),
+                                    value,
+                                ));
+                            });
+                    }
+
+                    // 返回按钮
+                    options_parent
+                        .spawn((
+                            Button,
+                            Node {
+                                width: Val::Px(300.0),
+                                height: Val::Px(60.0),
+                                justify_content: JustifyContent::Center,
+                                align_items: AlignItems::Center,
+                                margin: UiRect::top(Val::Px(30.0)),
+                                ..default()
+                            },
+                            BackgroundColor(NORMAL_BUTTON),
+                            SettingsButtonAction::Back,
+                        ))
+                        .with_child((
+                            Text::new(translation_resources.get(language, "settings-back")),
+                            text_font(28.0),
+                            TextColor(Color::srgb(0.9, 0.9, 0.9)),
+                            LocalizedText::new("settings-back"),
+                        ));
+                });
+        });
+
+    info!("[SettingsMenu] 游戏性设置生成完成");
+}
+
+/// 游戏性选项变化后更新按钮上的当前值
+fn update_gameplay_setting_values(
+    targeting_settings: Res<TargetingSettings>,
+    current_language: Res<CurrentLanguage>,
+    translation_resources: Res<TranslationResources>,
+    mut value_query: Query<(&GameplaySettingValue, &mut LocalizedText, &mut Text)>,
+) {
+    if !targeting_settings.is_changed() {
+        return;
+    }
+    for (value, mut localized, mut text) in value_query.iter_mut() {
+        let key = value.key(&targeting_settings);
+        if localized.key != key {
+            localized.key = key.to_string();
+            **text = translation_resources.get(current_language.language, key);
+        }
+    }
+}
+
 /// 按钮交互系统
 fn settings_button_system(
     mut interaction_query: Query<
@@ -513,6 +697,7 @@ fn settings_button_system(
     >,
     mut settings_state: ResMut<NextState<SettingsMenuState>>,
     mut current_language: ResMut<CurrentLanguage>,
+    mut targeting_settings: ResMut<TargetingSettings>,
     current_menu_state: Res<State<SettingsMenuState>>,
 ) {
     for (interaction, mut color, action) in &mut interaction_query {
@@ -558,6 +743,17 @@ fn settings_button_system(
                         info!("[SettingsMenu] 切换到英文");
                         current_language.language = Language::English;
                     }
+                    SettingsButtonAction::ToggleAutoFace => {
+                        targeting_settings.auto_face = !targeting_settings.auto_face;
+                        info!(
+                            "[SettingsMenu] 自动朝向最近敌人: {}",
+                            targeting_settings.auto_face
+                        );
+                    }
+                    SettingsButtonAction::ToggleLockMode => {
+                        targeting_settings.mode = targeting_settings.mode.toggle();
+                        info!("[SettingsMenu] 锁定模式: {:?}", targeting_settings.mode);
+                    }
                 }
             }
             Interaction::Hovered => {
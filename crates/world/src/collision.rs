@@ -0,0 +1,672 @@
+//! 静态碰撞体
+//!
+//! 场景中不可移动的碰撞几何（边界墙、墙体、平台、斜坡等；地面为高度图地形，见 `terrain`），
+//! 供角色控制器查询：
+//! - 球体投射（shape cast）：沿方向移动球体，返回最先接触的表面
+//! - 穿透检测：球体与碰撞体重叠时给出推出方向与深度
+//!
+//! 碰撞体以 `Transform` 为中心，形状为有向长方体（OBB，旋转后即可表示斜坡）、
+//! 竖直圆柱或由模型网格生成的凸包。
+//! 球体投射在局部空间中按“外扩半径后的形状”求交，
+//! 棱角处会比精确的圆角外扩略早接触，对角色移动足够。
+//!
+//! 碰撞体注册到 `CollisionLayer`（见 `collision_layer`）后供玩家与敌人的角色控制器查询。
+
+use std::sync::Arc;
+
+use bevy::prelude::*;
+
+use crate::terrain::{Heightfield, FLOOR_MARGIN};
+
+/// 平地地表高度（地形高度的基准面）
+pub const FLOOR_TOP: f32 = -0.5;
+/// 边界墙高出地形最高点的高度与厚度
+const BOUNDARY_HEIGHT: f32 = 20.0;
+const BOUNDARY_THICKNESS: f32 = 1.0;
+
+/// 碰撞体形状（局部空间，以 `Transform` 为中心）
+#[derive(Debug, Clone)]
+pub enum ColliderShape {
+    /// 有向长方体
+    Cuboid { half_extents: Vec3 },
+    /// 沿局部 Y 轴竖直的圆柱
+    Cylinder { radius: f32, half_height: f32 },
+    /// 凸包（由模型网格顶点生成，多个碰撞体可共享同一凸包）
+    ConvexHull(Arc<ConvexHull>),
+}
+
+/// 静态碰撞体组件
+#[derive(Component, Debug, Clone)]
+pub struct StaticCollider {
+    pub shape: ColliderShape,
+    /// 是否参与碰撞（如打开的门保留组件但不阻挡）
+    pub enabled: bool,
+}
+
+/// 投射命中结果
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct ShapeHit {
+    /// 沿投射方向移动的距离
+    pub distance: f32,
+    /// 接触表面的世界法线
+    pub normal: Vec3,
+}
+
+impl StaticCollider {
+    pub fn cuboid(half_extents: Vec3) -> Self {
+        Self {
+            shape: ColliderShape::Cuboid { half_extents },
+            enabled: true,
+        }
+    }
+
+    pub fn cylinder(radius: f32, half_height: f32) -> Self {
+        Self {
+            shape: ColliderShape::Cylinder {
+                radius,
+                half_height,
+            },
+            enabled: true,
+        }
+    }
+
+    pub fn convex_hull(hull: ConvexHull) -> Self {
+        Self {
+            shape: ColliderShape::ConvexHull(Arc::new(hull)),
+            enabled: true,
+        }
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// 局部空间包围盒 `(最小点, 最大点)`
+    pub fn local_aabb(&self) -> (Vec3, Vec3) {
+        match &self.shape {
+            ColliderShape::Cuboid { half_extents } => (-*half_extents, *half_extents),
+            ColliderShape::Cylinder {
+                radius,
+                half_height,
+            } => {
+                let half = Vec3::new(*radius, *half_height, *radius);
+                (-half, half)
+            }
+            ColliderShape::ConvexHull(hull) => (hull.min, hull.max),
+        }
+    }
+
+    /// 世界空间包围盒 `(最小点, 最大点)`
+    pub fn world_aabb(&self, transform: &Transform) -> (Vec3, Vec3) {
+        let (min, max) = self.local_aabb();
+        let mut world_min = Vec3::INFINITY;
+        let mut world_max = Vec3::NEG_INFINITY;
+        for corner in 0..8 {
+            let local = Vec3::select(
+                BVec3::new(corner & 1 != 0, corner & 2 != 0, corner & 4 != 0),
+                max,
+                min,
+            );
+            let point = transform.translation + transform.rotation * local;
+            world_min = world_min.min(point);
+            world_max = world_max.max(point);
+        }
+        (world_min, world_max)
+    }
+
+    /// 球体投射
+    ///
+    /// 球心从 `origin` 沿单位向量 `direction` 移动至多 `max_distance`；
+    /// 起点已与碰撞体重叠时不视为命中（由穿透修正处理）
+    pub fn sphere_cast(
+        &self,
+        transform: &Transform,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        radius: f32,
+    ) -> Option<ShapeHit> {
+        let inverse = transform.rotation.inverse();
+        let local_origin = inverse * (origin - transform.translation);
+        let local_direction = inverse * direction;
+
+        let (enter, exit, enter_normal) = match &self.shape {
+            ColliderShape::Cuboid { half_extents } => cuboid_interval(
+                *half_extents + Vec3::splat(radius),
+                local_origin,
+                local_direction,
+            )?,
+            ColliderShape::Cylinder {
+                radius: cylinder_radius,
+                half_height,
+            } => cylinder_interval(
+                cylinder_radius + radius,
+                half_height + radius,
+                local_origin,
+                local_direction,
+            )?,
+            ColliderShape::ConvexHull(hull) => {
+                hull.interval(radius, local_origin, local_direction)?
+            }
+        };
+
+        if enter > exit || enter < 0.0 || enter > max_distance {
+            return None;
+        }
+        Some(ShapeHit {
+            distance: enter,
+            normal: transform.rotation * enter_normal,
+        })
+    }
+
+    /// 球体穿透检测，返回 `(推出方向, 穿透深度)`
+    pub fn sphere_penetration(
+        &self,
+        transform: &Transform,
+        center: Vec3,
+        radius: f32,
+    ) -> Option<(Vec3, f32)> {
+        let local = transform.rotation.inverse() * (center - transform.translation);
+        let (normal, depth) = match &self.shape {
+            ColliderShape::Cuboid { half_extents } => {
+                cuboid_penetration(*half_extents, local, radius)?
+            }
+            ColliderShape::Cylinder {
+                radius: cylinder_radius,
+                half_height,
+            } => cylinder_penetration(*cylinder_radius, *half_height, local, radius)?,
+            ColliderShape::ConvexHull(hull) => hull.penetration(local, radius)?,
+        };
+        Some((transform.rotation * normal, depth))
+    }
+}
+
+/// 外扩后长方体的分轴（slab）求交，返回 `(进入距离, 离开距离, 进入面法线)`
+fn cuboid_interval(half: Vec3, origin: Vec3, direction: Vec3) -> Option<(f32, f32, Vec3)> {
+    let mut enter = f32::NEG_INFINITY;
+    let mut exit = f32::INFINITY;
+    let mut enter_normal = Vec3::ZERO;
+    for axis in 0..3 {
+        let o = origin[axis];
+        let d = direction[axis];
+        if d.abs() < f32::EPSILON {
+            if o.abs() > half[axis] {
+                return None;
+            }
+            continue;
+        }
+        let t1 = (-half[axis] - o) / d;
+        let t2 = (half[axis] - o) / d;
+        let (near, far) = if t1 < t2 { (t1, t2) } else { (t2, t1) };
+        if near > enter {
+            enter = near;
+            enter_normal = Vec3::ZERO;
+            enter_normal[axis] = -d.signum();
+        }
+        exit = exit.min(far);
+    }
+    Some((enter, exit, enter_normal))
+}
+
+/// 外扩后圆柱的求交：侧面为无限圆柱，顶底面为 Y 方向的 slab
+fn cylinder_interval(
+    radius: f32,
+    half_height: f32,
+    origin: Vec3,
+    direction: Vec3,
+) -> Option<(f32, f32, Vec3)> {
+    let mut enter = f32::NEG_INFINITY;
+    let mut exit = f32::INFINITY;
+    let mut enter_normal = Vec3::ZERO;
+
+    if direction.y.abs() < f32::EPSILON {
+        if origin.y.abs() > half_height {
+            return None;
+        }
+    } else {
+        let t1 = (-half_height - origin.y) / direction.y;
+        let t2 = (half_height - origin.y) / direction.y;
+        enter = t1.min(t2);
+        exit = t1.max(t2);
+        enter_normal = Vec3::new(0.0, -direction.y.signum(), 0.0);
+    }
+
+    let a = direction.x * direction.x + direction.z * direction.z;
+    let b = origin.x * direction.x + origin.z * direction.z;
+    let c = origin.x * origin.x + origin.z * origin.z - radius * radius;
+    if a < f32::EPSILON {
+        if c > 0.0 {
+            return None;
+        }
+    } else {
+        let discriminant = b * b - a * c;
+        if discriminant < 0.0 {
+            return None;
+        }
+        let root = discriminant.sqrt();
+        let near = (-b - root) / a;
+        let far = (-b + root) / a;
+        if near > enter {
+            enter = near;
+            let contact = origin + direction * near;
+            enter_normal = Vec3::new(contact.x, 0.0, contact.z).normalize_or(Vec3::X);
+        }
+        exit = exit.min(far);
+    }
+    Some((enter, exit, enter_normal))
+}
+
+fn cuboid_penetration(half_extents: Vec3, local: Vec3, radius: f32) -> Option<(Vec3, f32)> {
+    let closest = local.clamp(-half_extents, half_extents);
+    let offset = local - closest;
+    let distance = offset.length();
+    if distance > f32::EPSILON {
+        if distance >= radius {
+            return None;
+        }
+        return Some((offset / distance, radius - distance));
+    }
+
+    // 球心在长方体内部：从最近的面推出
+    let depths = half_extents - local.abs();
+    let axis = if depths.x <= depths.y && depths.x <= depths.z {
+        0
+    } else if depths.y <= depths.z {
+        1
+    } else {
+        2
+    };
+    let mut normal = Vec3::ZERO;
+    normal[axis] = if local[axis] >= 0.0 { 1.0 } else { -1.0 };
+    Some((normal, depths[axis] + radius))
+}
+
+fn cylinder_penetration(
+    cylinder_radius: f32,
+    half_height: f32,
+    local: Vec3,
+    radius: f32,
+) -> Option<(Vec3, f32)> {
+    let radial = Vec2::new(local.x, local.z);
+    let closest_radial = radial.clamp_length_max(cylinder_radius);
+    let closest = Vec3::new(
+        closest_radial.x,
+        local.y.clamp(-half_height, half_height),
+        closest_radial.y,
+    );
+    let offset = local - closest;
+    let distance = offset.length();
+    if distance > f32::EPSILON {
+        if distance >= radius {
+            return None;
+        }
+        return Some((offset / distance, radius - distance));
+    }
+
+    // 球心在圆柱内部：从侧面或顶底面中较近的一侧推出
+    let side_depth = cylinder_radius - radial.length();
+    let cap_depth = half_height - local.y.abs();
+    if side_depth < cap_depth {
+        let outward = radial.normalize_or(Vec2::X);
+        Some((Vec3::new(outward.x, 0.0, outward.y), side_depth + radius))
+    } else {
+        Some((Vec3::Y * local.y.signum(), cap_depth + radius))
+    }
+}
+
+/// 凸包碰撞形状
+///
+/// 以一组平面 `normal · p <= offset` 的交集表示。球体投射与穿透检测把平面外推球半径，
+/// 与长方体一样在棱角处略早接触。
+#[derive(Debug, Clone)]
+pub struct ConvexHull {
+    /// 面平面 `(外法线, 偏移)`
+    planes: Vec<(Vec3, f32)>,
+    /// 顶点（局部空间）
+    vertices: Vec<Vec3>,
+    /// 棱（顶点索引对），用于调试绘制
+    edges: Vec<(usize, usize)>,
+    min: Vec3,
+    max: Vec3,
+}
+
+impl ConvexHull {
+    /// 由点集生成凸包；点数不足或共面时返回 `None`
+    ///
+    /// 先取若干方向上的极点把点集缩减到 `HULL_DIRECTIONS` 个以内，
+    /// 再枚举三点组合求出所有支撑平面。模型顶点数较多时凸包为近似，
+    /// 只在加载时计算一次。
+    pub fn from_points(points: &[Vec3]) -> Option<Self> {
+        let mut candidates: Vec<Vec3> = Vec::new();
+        for direction in sphere_directions(HULL_DIRECTIONS) {
+            let extreme = points
+                .iter()
+                .copied()
+                .max_by(|a, b| a.dot(direction).total_cmp(&b.dot(direction)))?;
+            if !candidates
+                .iter()
+                .any(|point| point.distance_squared(extreme) < HULL_EPSILON * HULL_EPSILON)
+            {
+                candidates.push(extreme);
+            }
+        }
+        if candidates.len() < 4 {
+            return None;
+        }
+
+        let min = candidates.iter().copied().fold(Vec3::INFINITY, Vec3::min);
+        let max = candidates
+            .iter()
+            .copied()
+            .fold(Vec3::NEG_INFINITY, Vec3::max);
+        let epsilon = HULL_EPSILON * (max - min).max_element().max(1.0);
+
+        // 所有点都在其一侧的三点平面即为凸包的面
+        let mut planes: Vec<(Vec3, f32)> = Vec::new();
+        for i in 0..candidates.len() {
+            for j in i + 1..candidates.len() {
+                for k in j + 1..candidates.len() {
+                    let a = candidates[i];
+                    let Some(normal) = (candidates[j] - a).cross(candidates[k] - a).try_normalize()
+                    else {
+                        continue;
+                    };
+                    for normal in [normal, -normal] {
+                        let offset = normal.dot(a);
+                        let outside = candidates
+                            .iter()
+                            .any(|point| normal.dot(*point) - offset > epsilon);
+                        let duplicate = planes.iter().any(|(n, d)| {
+                            n.dot(normal) > 1.0 - HULL_EPSILON && (d - offset).abs() < epsilon
+                        });
+                        if !outside && !duplicate {
+                            planes.push((normal, offset));
+                        }
+                    }
+                }
+            }
+        }
+        // 共面点集的两侧平面互为反向，围不出体积
+        if planes.len() < 4 {
+            return None;
+        }
+
+        let on_planes = |point: Vec3| -> Vec<usize> {
+            (0..planes.len())
+                .filter(|&index| {
+                    let (normal, offset) = planes[index];
+                    (normal.dot(point) - offset).abs() <= epsilon
+                })
+                .collect()
+        };
+        // 顶点至少位于三个面上（排除棱上的中间点）
+        let vertices: Vec<Vec3> = candidates
+            .into_iter()
+            .filter(|point| on_planes(*point).len() >= 3)
+            .collect();
+        let incident: Vec<Vec<usize>> = vertices.iter().map(|point| on_planes(*point)).collect();
+        let mut edges = Vec::new();
+        for i in 0..vertices.len() {
+            for j in i + 1..vertices.len() {
+                let shared = incident[i]
+                    .iter()
+                    .filter(|plane| incident[j].contains(plane))
+                    .count();
+                if shared >= 2 {
+                    edges.push((i, j));
+                }
+            }
+        }
+
+        Some(Self {
+            planes,
+            vertices,
+            edges,
+            min,
+            max,
+        })
+    }
+
+    pub fn vertices(&self) -> &[Vec3] {
+        &self.vertices
+    }
+
+    pub fn edges(&self) -> impl Iterator<Item = (Vec3, Vec3)> + '_ {
+        self.edges
+            .iter()
+            .map(|&(a, b)| (self.vertices[a], self.vertices[b]))
+    }
+
+    /// 外推 `radius` 后的平面裁剪（Cyrus-Beck）求交
+    fn interval(&self, radius: f32, origin: Vec3, direction: Vec3) -> Option<(f32, f32, Vec3)> {
+        let mut enter = f32::NEG_INFINITY;
+        let mut exit = f32::INFINITY;
+        let mut enter_normal = Vec3::ZERO;
+        for &(normal, offset) in &self.planes {
+            let distance = normal.dot(origin) - (offset + radius);
+            let speed = normal.dot(direction);
+            if speed.abs() < f32::EPSILON {
+                if distance > 0.0 {
+                    return None;
+                }
+                continue;
+            }
+            let t = -distance / speed;
+            if speed < 0.0 {
+                if t > enter {
+                    enter = t;
+                    enter_normal = normal;
+                }
+            } else {
+                exit = exit.min(t);
+            }
+        }
+        Some((enter, exit, enter_normal))
+    }
+
+    /// 以距离最大的面作为推出方向
+    fn penetration(&self, local: Vec3, radius: f32) -> Option<(Vec3, f32)> {
+        let (normal, distance) = self
+            .planes
+            .iter()
+            .map(|&(normal, offset)| (normal, normal.dot(local) - offset))
+            .max_by(|a, b| a.1.total_cmp(&b.1))?;
+        (distance < radius).then_some((normal, radius - distance))
+    }
+}
+
+/// 凸包缩减点集时采样的方向数
+const HULL_DIRECTIONS: usize = 64;
+/// 凸包平面判定的相对容差
+const HULL_EPSILON: f32 = 1e-4;
+
+/// 球面上近似均匀分布的方向（斐波那契点阵）
+fn sphere_directions(count: usize) -> impl Iterator<Item = Vec3> {
+    let golden_angle = std::f32::consts::PI * (3.0 - 5f32.sqrt());
+    (0..count).map(move |i| {
+        let y = 1.0 - 2.0 * (i as f32 + 0.5) / count as f32;
+        let ring = (1.0 - y * y).sqrt();
+        let angle = golden_angle * i as f32;
+        Vec3::new(ring * angle.cos(), y, ring * angle.sin())
+    })
+}
+
+/// 边界墙标记（其围成的范围即世界边界，见 `CollisionLayer::bounds`）
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct WorldBoundary;
+
+/// 边界墙的碰撞体（位置, 碰撞体）
+///
+/// 边界墙不可见，位于地形边缘内 `FLOOR_MARGIN` 处，从地形最低点延伸到最高点之上，阻止角色离开地面范围
+pub fn boundary_colliders(terrain: &Heightfield) -> Vec<(Transform, StaticCollider)> {
+    let (min_height, max_height) = terrain.height_range();
+    let bottom = min_height - 1.0;
+    let half_height = (max_height + BOUNDARY_HEIGHT - bottom) * 0.5;
+    let wall_y = bottom + half_height;
+
+    let half = (terrain.size() * 0.5 - FLOOR_MARGIN).max(Vec2::ZERO);
+    let half_thickness = BOUNDARY_THICKNESS * 0.5;
+    let x = half.x + half_thickness;
+    let z = half.y + half_thickness;
+    let along_x = Vec3::new(half.x + BOUNDARY_THICKNESS, half_height, half_thickness);
+    let along_z = Vec3::new(half_thickness, half_height, half.y + BOUNDARY_THICKNESS);
+
+    vec![
+        (
+            Transform::from_xyz(0.0, wall_y, z),
+            StaticCollider::cuboid(along_x),
+        ),
+        (
+            Transform::from_xyz(0.0, wall_y, -z),
+            StaticCollider::cuboid(along_x),
+        ),
+        (
+            Transform::from_xyz(x, wall_y, 0.0),
+            StaticCollider::cuboid(along_z),
+        ),
+        (
+            Transform::from_xyz(-x, wall_y, 0.0),
+            StaticCollider::cuboid(along_z),
+        ),
+    ]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_sphere_cast_hits_faces() {
+        let wall = StaticCollider::cuboid(Vec3::new(1.0, 2.0, 1.0));
+        let transform = Transform::from_xyz(5.0, 0.0, 0.0);
+
+        let hit = wall
+            .sphere_cast(&transform, Vec3::ZERO, Vec3::X, 10.0, 0.5)
+            .unwrap();
+        assert!((hit.distance - 3.5).abs() < 1e-5);
+        assert!(hit.normal.abs_diff_eq(Vec3::NEG_X, 1e-5));
+
+        // 距离不足、方向背离都不命中
+        assert!(
+            wall.sphere_cast(&transform, Vec3::ZERO, Vec3::X, 3.0, 0.5)
+                .is_none()
+        );
+        assert!(
+            wall.sphere_cast(&transform, Vec3::ZERO, Vec3::NEG_X, 10.0, 0.5)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_sloped_collider_normal() {
+        // 绕 Z 轴旋转 30° 的斜坡，顶面法线倾斜 30°
+        let ramp = StaticCollider::cuboid(Vec3::new(4.0, 0.5, 2.0));
+        let transform = Transform::from_rotation(Quat::from_rotation_z(30f32.to_radians()));
+
+        let hit = ramp
+            .sphere_cast(&transform, Vec3::new(0.0, 5.0, 0.0), Vec3::NEG_Y, 10.0, 0.3)
+            .unwrap();
+        assert!((hit.normal.y - 30f32.to_radians().cos()).abs() < 1e-5);
+
+        let (normal, depth) = ramp
+            .sphere_penetration(&transform, Vec3::new(0.0, 0.6, 0.0), 0.3)
+            .unwrap();
+        assert!(normal.y > 0.8);
+        assert!(depth > 0.0);
+    }
+
+    #[test]
+    fn test_cylinder_cast_and_penetration() {
+        let pillar = StaticCollider::cylinder(1.0, 2.0);
+        let transform = Transform::from_xyz(5.0, 0.0, 0.0);
+
+        // 侧面：法线沿径向
+        let hit = pillar
+            .sphere_cast(&transform, Vec3::new(0.0, 0.0, 0.5), Vec3::X, 10.0, 0.5)
+            .unwrap();
+        assert!(hit.normal.y.abs() < 1e-5 && hit.normal.x < -0.8);
+        // 长方体在同样的位置会更早命中
+        assert!(hit.distance > 3.5);
+
+        // 顶面
+        let hit = pillar
+            .sphere_cast(&transform, Vec3::new(5.0, 5.0, 0.0), Vec3::NEG_Y, 10.0, 0.5)
+            .unwrap();
+        assert!((hit.distance - 2.5).abs() < 1e-5);
+        assert!(hit.normal.abs_diff_eq(Vec3::Y, 1e-5));
+
+        // 从侧面擦过
+        assert!(
+            pillar
+                .sphere_cast(&transform, Vec3::new(0.0, 0.0, 1.6), Vec3::X, 10.0, 0.5)
+                .is_none()
+        );
+
+        let (normal, depth) = pillar
+            .sphere_penetration(&transform, Vec3::new(6.2, 0.0, 0.0), 0.5)
+            .unwrap();
+        assert!(normal.abs_diff_eq(Vec3::X, 1e-5));
+        assert!((depth - 0.3).abs() < 1e-5);
+        // 方块的角落处圆柱不重叠
+        assert!(
+            pillar
+                .sphere_penetration(&transform, Vec3::new(6.0, 0.0, 1.0), 0.3)
+                .is_none()
+        );
+    }
+
+    #[test]
+    fn test_convex_hull_from_points() {
+        // 长方体的 8 个角加上内部与棱上的点
+        let mut points: Vec<Vec3> = (0..8)
+            .map(|i| {
+                Vec3::new(
+                    if i & 1 == 0 { -1.0 } else { 1.0 },
+                    if i & 2 == 0 { -0.5 } else { 0.5 },
+                    if i & 4 == 0 { -2.0 } else { 2.0 },
+                )
+            })
+            .collect();
+        points.extend([
+            Vec3::ZERO,
+            Vec3::new(0.2, 0.1, -0.3),
+            Vec3::new(1.0, 0.5, 0.0),
+        ]);
+
+        let hull = ConvexHull::from_points(&points).unwrap();
+        assert_eq!(hull.vertices().len(), 8);
+        assert_eq!(hull.edges().count(), 12);
+
+        // 与同尺寸的长方体碰撞体结果一致
+        let collider = StaticCollider::convex_hull(hull);
+        let cuboid = StaticCollider::cuboid(Vec3::new(1.0, 0.5, 2.0));
+        let transform =
+            Transform::from_xyz(0.0, 1.0, 0.0).with_rotation(Quat::from_rotation_y(0.4));
+        for (origin, direction) in [
+            (Vec3::new(-5.0, 1.0, 0.3), Vec3::X),
+            (Vec3::new(0.2, 6.0, 0.5), Vec3::NEG_Y),
+            (
+                Vec3::new(3.0, 1.2, 3.0),
+                Vec3::new(-1.0, 0.0, -1.0).normalize(),
+            ),
+        ] {
+            let expected = cuboid.sphere_cast(&transform, origin, direction, 20.0, 0.4);
+            let actual = collider.sphere_cast(&transform, origin, direction, 20.0, 0.4);
+            assert!(expected.is_some());
+            let (expected, actual) = (expected.unwrap(), actual.unwrap());
+            assert!((expected.distance - actual.distance).abs() < 1e-4);
+            assert!(expected.normal.abs_diff_eq(actual.normal, 1e-4));
+        }
+        let (normal, depth) = collider
+            .sphere_penetration(&transform, Vec3::new(0.0, 1.7, 0.0), 0.4)
+            .unwrap();
+        assert!(normal.abs_diff_eq(Vec3::Y, 1e-4) && (depth - 0.2).abs() < 1e-4);
+
+        // 共面点集不构成凸包
+        assert!(
+            ConvexHull::from_points(&[Vec3::ZERO, Vec3::X, Vec3::Z, Vec3::new(1.0, 0.0, 1.0)])
+                .is_none()
+        );
+    }
+}
@@ -0,0 +1,102 @@
+//! 碰撞体调试可视化
+//!
+//! 开启后用 Gizmos 绘制碰撞层中的所有碰撞体：
+//! - 长方体、圆柱（绿），边界墙（红）
+//! - 模型凸包的棱（青）
+//! - 世界边界（黄）
+//!
+//! 开关按键由集成层负责，这里只提供开关资源与绘制。
+
+use bevy::prelude::*;
+
+use crate::collision::ColliderShape;
+use crate::collision_layer::CollisionLayer;
+
+const SHAPE_COLOR: Color = Color::srgb(0.3, 0.9, 0.4);
+const BOUNDARY_COLOR: Color = Color::srgb(0.95, 0.25, 0.2);
+const HULL_COLOR: Color = Color::srgb(0.2, 0.85, 0.9);
+const BOUNDS_COLOR: Color = Color::srgb(0.95, 0.85, 0.2);
+
+/// 世界边界的绘制高度
+const BOUNDS_HEIGHT: f32 = 0.1;
+
+/// 碰撞体调试可视化开关
+#[derive(Resource, Debug, Clone, Default)]
+pub struct CollisionDebugOverlay {
+    /// 是否绘制碰撞体
+    pub enabled: bool,
+}
+
+impl CollisionDebugOverlay {
+    /// 切换开关
+    pub fn toggle(&mut self) {
+        self.enabled = !self.enabled;
+        info!(
+            "[Collision] 碰撞体可视化已{}",
+            if self.enabled { "开启" } else { "关闭" }
+        );
+    }
+}
+
+/// 碰撞体调试可视化插件
+pub struct CollisionDebugPlugin;
+
+impl Plugin for CollisionDebugPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CollisionDebugOverlay>().add_systems(
+            Update,
+            draw_colliders_system.run_if(|overlay: Res<CollisionDebugOverlay>| overlay.enabled),
+        );
+    }
+}
+
+fn draw_colliders_system(mut gizmos: Gizmos, layer: Res<CollisionLayer>) {
+    for entry in layer.iter() {
+        let transform = entry.transform;
+        match &entry.collider.shape {
+            ColliderShape::Cuboid { half_extents } => {
+                let color = if entry.boundary {
+                    BOUNDARY_COLOR
+                } else {
+                    SHAPE_COLOR
+                };
+                gizmos.cuboid(transform.with_scale(*half_extents * 2.0), color);
+            }
+            ColliderShape::Cylinder {
+                radius,
+                half_height,
+            } => {
+                gizmos.primitive_3d(
+                    &Cylinder::new(*radius, half_height * 2.0),
+                    Isometry3d::new(transform.translation, transform.rotation),
+                    SHAPE_COLOR,
+                );
+            }
+            ColliderShape::ConvexHull(hull) => {
+                for (start, end) in hull.edges() {
+                    gizmos.line(
+                        transform.transform_point(start),
+                        transform.transform_point(end),
+                        HULL_COLOR,
+                    );
+                }
+            }
+        }
+    }
+
+    if let Some(bounds) = layer.bounds() {
+        let corners = [
+            Vec2::new(bounds.min.x, bounds.min.y),
+            Vec2::new(bounds.max.x, bounds.min.y),
+            Vec2::new(bounds.max.x, bounds.max.y),
+            Vec2::new(bounds.min.x, bounds.max.y),
+        ];
+        gizmos.linestrip(
+            corners
+                .iter()
+                .chain(corners.first())
+                .map(|corner| Vec3::new(corner.x, BOUNDS_HEIGHT, corner.y)),
+            BOUNDS_COLOR,
+        );
+    }
+}
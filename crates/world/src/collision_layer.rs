@@ -0,0 +1,363 @@
+//! 世界碰撞层
+//!
+//! 收集场景中所有 `StaticCollider`（边界墙、墙体、道具、模型凸包等），
+//! 按 XZ 平面网格建立宽相位索引，角色控制器只取角色附近的碰撞体做精确检测。
+//! 碰撞体增删、移动或启用/禁用时整体重建（静态几何很少变化），禁用的碰撞体不参与检测。
+//!
+//! 世界边界由 `WorldBoundary` 碰撞体围成的范围得出，而不是写死的矩形。
+//!
+//! 带 `MeshCollider` 的模型在场景实例化、网格加载完成后，
+//! 由其所有子网格顶点生成凸包碰撞体。
+
+use bevy::mesh::VertexAttributeValues;
+use bevy::platform::collections::HashMap;
+use bevy::prelude::*;
+
+use crate::collision::{ConvexHull, StaticCollider, WorldBoundary};
+
+/// 宽相位网格的格子尺寸
+const CELL_SIZE: f32 = 8.0;
+
+/// 由子网格顶点生成凸包碰撞体的标记（放在模型场景根实体上）
+///
+/// 凸包烘焙了根实体的缩放，碰撞体本身只使用平移与旋转
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct MeshCollider;
+
+/// 碰撞层中的碰撞体
+#[derive(Debug, Clone)]
+pub struct LayerCollider {
+    pub entity: Entity,
+    pub transform: Transform,
+    pub collider: StaticCollider,
+    /// 是否为边界墙
+    pub boundary: bool,
+    /// 世界空间包围盒
+    pub min: Vec3,
+    pub max: Vec3,
+}
+
+/// 世界碰撞层资源
+#[derive(Resource, Debug, Default)]
+pub struct CollisionLayer {
+    colliders: Vec<LayerCollider>,
+    /// 格子 -> 碰撞体索引
+    cells: HashMap<IVec2, Vec<usize>>,
+    /// 边界墙围成的范围（XZ）
+    bounds: Option<Rect>,
+}
+
+impl CollisionLayer {
+    /// 由碰撞体列表重建（`bool` 表示是否为边界墙）
+    pub fn rebuild(
+        &mut self,
+        colliders: impl IntoIterator<Item = (Entity, Transform, StaticCollider, bool)>,
+    ) {
+        self.colliders.clear();
+        self.cells.clear();
+        let mut boundaries = Vec::new();
+
+        for (entity, transform, collider, boundary) in colliders {
+            let (min, max) = collider.world_aabb(&transform);
+            let index = self.colliders.len();
+            let (first, last) = (cell_of(min.xz()), cell_of(max.xz()));
+            for x in first.x..=last.x {
+                for z in first.y..=last.y {
+                    self.cells.entry(IVec2::new(x, z)).or_default().push(index);
+                }
+            }
+            if boundary {
+                boundaries.push(Rect::from_corners(min.xz(), max.xz()));
+            }
+            self.colliders.push(LayerCollider {
+                entity,
+                transform,
+                collider,
+                boundary,
+                min,
+                max,
+            });
+        }
+
+        self.bounds = enclosed_bounds(&boundaries);
+    }
+
+    /// 碰撞体数量
+    pub fn len(&self) -> usize {
+        self.colliders.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.colliders.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &LayerCollider> {
+        self.colliders.iter()
+    }
+
+    /// 世界边界（没有边界墙时为 `None`）
+    pub fn bounds(&self) -> Option<Rect> {
+        self.bounds
+    }
+
+    /// 将 XZ 位置限制在世界边界内，并与边界保持 `margin` 距离
+    pub fn clamp_to_bounds(&self, point: Vec2, margin: f32) -> Vec2 {
+        match self.bounds {
+            Some(bounds) => {
+                let half = (bounds.half_size() - margin).max(Vec2::ZERO);
+                point.clamp(bounds.center() - half, bounds.center() + half)
+            }
+            None => point,
+        }
+    }
+
+    /// 收集包围盒与 `center` 距离不超过 `reach` 的碰撞体（先清空 `out`）
+    pub fn query(&self, center: Vec3, reach: f32, out: &mut Vec<(Transform, StaticCollider)>) {
+        out.clear();
+        let first = cell_of(center.xz() - Vec2::splat(reach));
+        let last = cell_of(center.xz() + Vec2::splat(reach));
+        let mut indices: Vec<usize> = Vec::new();
+        for x in first.x..=last.x {
+            for z in first.y..=last.y {
+                if let Some(cell) = self.cells.get(&IVec2::new(x, z)) {
+                    indices.extend_from_slice(cell);
+                }
+            }
+        }
+        indices.sort_unstable();
+        indices.dedup();
+
+        out.extend(
+            indices
+                .into_iter()
+                .map(|index| &self.colliders[index])
+                .filter(|entry| center.clamp(entry.min, entry.max).distance(center) <= reach)
+                .map(|entry| (entry.transform, entry.collider.clone())),
+        );
+    }
+}
+
+fn cell_of(point: Vec2) -> IVec2 {
+    (point / CELL_SIZE).floor().as_ivec2()
+}
+
+/// 边界墙围成的矩形
+///
+/// 每面墙按较长的方向判断走向，取其朝向中心的一侧作为边界
+fn enclosed_bounds(walls: &[Rect]) -> Option<Rect> {
+    let outer = walls.iter().copied().reduce(|a, b| a.union(b))?;
+    let center = outer.center();
+    let mut inner = outer;
+    for wall in walls {
+        if wall.width() >= wall.height() {
+            if wall.center().y > center.y {
+                inner.max.y = inner.max.y.min(wall.min.y);
+            } else {
+                inner.min.y = inner.min.y.max(wall.max.y);
+            }
+        } else if wall.center().x > center.x {
+            inner.max.x = inner.max.x.min(wall.min.x);
+        } else {
+            inner.min.x = inner.min.x.max(wall.max.x);
+        }
+    }
+    (!inner.is_empty()).then_some(inner)
+}
+
+/// 碰撞层插件
+pub struct CollisionLayerPlugin;
+
+impl Plugin for CollisionLayerPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<CollisionLayer>()
+            // 固定步长模拟之前同步，当帧生成的碰撞体在下一次模拟中生效
+            .add_systems(FixedPreUpdate, sync_collision_layer)
+            .add_systems(
+                Update,
+                build_mesh_colliders.run_if(resource_exists::<Assets<Mesh>>),
+            );
+    }
+}
+
+/// 碰撞体变化时重建碰撞层
+///
+/// 移除的碰撞体由数量变化发现：移除事件只保留两帧，没有模拟步的帧里会丢失
+fn sync_collision_layer(
+    mut layer: ResMut<CollisionLayer>,
+    changed: Query<
+        (),
+        (
+            With<StaticCollider>,
+            Or<(Changed<StaticCollider>, Changed<Transform>)>,
+        ),
+    >,
+    colliders: Query<(Entity, &Transform, &StaticCollider, Has<WorldBoundary>)>,
+    mut collider_count: Local<usize>,
+) {
+    let count = colliders.iter().len();
+    if changed.is_empty() && count == *collider_count {
+        return;
+    }
+    *collider_count = count;
+
+    layer.rebuild(
+        colliders
+            .iter()
+            .filter(|(_, _, collider, _)| collider.enabled)
+            .map(|(entity, transform, collider, boundary)| {
+                (entity, *transform, collider.clone(), boundary)
+            }),
+    );
+    debug!(
+        "[Collision] Rebuilt collision layer ({} colliders)",
+        layer.len()
+    );
+}
+
+/// 为模型生成凸包碰撞体
+///
+/// 场景尚未实例化或网格尚未加载时等待下一帧
+fn build_mesh_colliders(
+    mut commands: Commands,
+    roots: Query<(Entity, &Transform), (With<MeshCollider>, Without<StaticCollider>)>,
+    children: Query<&Children>,
+    nodes: Query<(&Transform, Option<&Mesh3d>)>,
+    meshes: Res<Assets<Mesh>>,
+) {
+    for (root, transform) in roots.iter() {
+        let mut points = Vec::new();
+        let mut found = false;
+        let mut ready = true;
+        let mut stack = vec![(root, Transform::from_scale(transform.scale))];
+
+        while let Some((entity, local)) = stack.pop() {
+            let Ok(entity_children) = children.get(entity) else {
+                continue;
+            };
+            for &child in entity_children {
+                let Ok((child_transform, mesh)) = nodes.get(child) else {
+                    continue;
+                };
+                let child_local = local * *child_transform;
+                if let Some(mesh) = mesh {
+                    found = true;
+                    match meshes.get(&mesh.0) {
+                        Some(mesh) => points.extend(
+                            mesh_positions(mesh).map(|point| child_local.transform_point(point)),
+                        ),
+                        None => ready = false,
+                    }
+                }
+                stack.push((child, child_local));
+            }
+        }
+        if !found || !ready {
+            continue;
+        }
+
+        match ConvexHull::from_points(&points) {
+            Some(hull) => {
+                commands
+                    .entity(root)
+                    .insert(StaticCollider::convex_hull(hull));
+            }
+            None => {
+                warn!("[Collision] {} 的网格无法生成凸包，已忽略", root);
+                commands.entity(root).remove::<MeshCollider>();
+            }
+        }
+    }
+}
+
+/// 网格顶点位置
+fn mesh_positions(mesh: &Mesh) -> impl Iterator<Item = Vec3> + '_ {
+    let positions = match mesh.attribute(Mesh::ATTRIBUTE_POSITION) {
+        Some(VertexAttributeValues::Float32x3(positions)) => positions.as_slice(),
+        _ => &[],
+    };
+    positions.iter().copied().map(Vec3::from_array)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::collision::boundary_colliders;
+    use crate::terrain::Heightfield;
+
+    #[test]
+    fn test_query_and_bounds() {
+        let terrain = Heightfield::flat(Vec2::new(40.0, 20.0), 0.0);
+        let mut colliders: Vec<_> = boundary_colliders(&terrain)
+            .into_iter()
+            .map(|(transform, collider)| (Entity::PLACEHOLDER, transform, collider, true))
+            .collect();
+        colliders.push((
+            Entity::PLACEHOLDER,
+            Transform::from_xyz(10.0, 1.0, 0.0),
+            StaticCollider::cylinder(1.0, 1.0),
+            false,
+        ));
+
+        let mut layer = CollisionLayer::default();
+        layer.rebuild(colliders);
+        assert_eq!(layer.len(), 5);
+
+        // 边界由边界墙得出（地形边缘内缩边距）
+        assert_eq!(layer.bounds(), Some(Rect::new(-19.0, -9.0, 19.0, 9.0)));
+        assert_eq!(
+            layer.clamp_to_bounds(Vec2::new(30.0, -30.0), 0.5),
+            Vec2::new(18.5, -8.5)
+        );
+
+        // 只返回附近的碰撞体
+        let mut nearby = Vec::new();
+        layer.query(Vec3::new(0.0, 1.0, 0.0), 2.0, &mut nearby);
+        assert!(nearby.is_empty());
+        layer.query(Vec3::new(8.0, 1.0, 0.0), 2.0, &mut nearby);
+        assert_eq!(nearby.len(), 1);
+        layer.query(Vec3::new(17.5, 1.0, 0.0), 2.0, &mut nearby);
+        assert_eq!(nearby.len(), 1);
+        layer.query(Vec3::new(18.0, 1.0, 8.0), 2.0, &mut nearby);
+        assert_eq!(nearby.len(), 2);
+    }
+
+    #[test]
+    fn test_layer_follows_disabled_and_removed_colliders() {
+        use bevy::time::TimeUpdateStrategy;
+        use std::time::Duration;
+
+        // 每 10 帧才有一个模拟步，中间帧的移除事件会过期
+        let mut app = App::new();
+        app.add_plugins(MinimalPlugins)
+            .insert_resource(TimeUpdateStrategy::ManualDuration(Duration::from_millis(5)))
+            .insert_resource(Time::<Fixed>::from_duration(Duration::from_millis(50)))
+            .add_plugins(CollisionLayerPlugin);
+        let door = app
+            .world_mut()
+            .spawn((Transform::default(), StaticCollider::cuboid(Vec3::ONE)))
+            .id();
+        let wall = app
+            .world_mut()
+            .spawn((
+                Transform::from_xyz(5.0, 0.0, 0.0),
+                StaticCollider::cuboid(Vec3::ONE),
+            ))
+            .id();
+        let layer_len = |app: &mut App| {
+            for _ in 0..20 {
+                app.update();
+            }
+            app.world().resource::<CollisionLayer>().len()
+        };
+        assert_eq!(layer_len(&mut app), 2);
+
+        app.world_mut()
+            .get_mut::<StaticCollider>(door)
+            .unwrap()
+            .enabled = false;
+        assert_eq!(layer_len(&mut app), 1);
+
+        app.world_mut().despawn(wall);
+        assert_eq!(layer_len(&mut app), 0);
+    }
+}
@@ -0,0 +1,317 @@
+//! 昼夜循环
+//!
+//! `TimeOfDay` 记录当前时刻，游戏进行中按平衡配置的一天时长推进。
+//! 太阳（带 `Sun` 的方向光）在 6 点升起、18 点落下，沿经过关卡太阳方向（正午）的弧线转动；
+//! 太阳落到地平线以下后，同一盏方向光改为从对侧照下的月光。
+//! 方向光颜色、照度与环境光在关卡设置（白天）与夜间设置之间按日照程度插值。
+//!
+//! 关卡重置时回到开始时刻（关卡文件可覆盖），读档时由存档直接写入时刻。
+
+use std::f32::consts::PI;
+
+use bevy::prelude::*;
+use vigilant_doodle_core::balance::DayNightBalance;
+use vigilant_doodle_core::state::GameState;
+use vigilant_doodle_core::BalanceConfig;
+
+use crate::level::{LevelChanged, LevelLayout, LevelLights, LevelSet};
+
+/// 日出时刻
+const SUNRISE_HOUR: f32 = 6.0;
+
+/// 太阳颜色开始偏暖的高度（太阳高度正弦值低于该值时向晨昏颜色过渡）
+const DUSK_HEIGHT: f32 = 0.4;
+
+/// 标记：随时刻转动的太阳（方向光）
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Sun;
+
+/// 当前时刻
+#[derive(Resource, Debug, Clone)]
+pub struct TimeOfDay {
+    /// 当前时刻（小时，0..24）
+    pub hour: f32,
+    /// 一整天的时长（秒）
+    pub day_length: f32,
+    /// 晨昏过渡的宽度（太阳高度正弦值）
+    pub twilight: f32,
+}
+
+impl Default for TimeOfDay {
+    fn default() -> Self {
+        Self::from_balance(&BalanceConfig::default())
+    }
+}
+
+impl TimeOfDay {
+    /// 从平衡配置创建（处于开始时刻）
+    pub fn from_balance(balance: &BalanceConfig) -> Self {
+        let config = &balance.level.day_night;
+        Self {
+            hour: config.start_hour.rem_euclid(24.0),
+            day_length: config.day_length,
+            twilight: config.twilight,
+        }
+    }
+
+    /// 设置时刻（超出 0..24 时折回）
+    pub fn set_hour(&mut self, hour: f32) {
+        self.hour = hour.rem_euclid(24.0);
+    }
+
+    /// 推进若干秒
+    pub fn advance(&mut self, seconds: f32) {
+        if self.day_length > 0.0 {
+            self.set_hour(self.hour + seconds / self.day_length * 24.0);
+        }
+    }
+
+    /// 太阳高度（正弦值，正午为 1，午夜为 -1）
+    pub fn sun_height(&self) -> f32 {
+        self.sun_angle().sin()
+    }
+
+    /// 日照程度（0=夜间，1=白天，日出日落前后平滑过渡）
+    pub fn daylight(&self) -> f32 {
+        smoothstep(-self.twilight, self.twilight, self.sun_height())
+    }
+
+    /// 是否为夜间
+    pub fn is_night(&self) -> bool {
+        self.daylight() < 0.5
+    }
+
+    /// 指向太阳的方向（`noon` 为正午时指向太阳的方向）
+    pub fn sun_direction(&self, noon: Vec3) -> Vec3 {
+        let (east, noon) = sun_arc(noon);
+        let angle = self.sun_angle();
+        east * angle.cos() + noon * angle.sin()
+    }
+
+    /// 方向光朝向（太阳在地平线以下时为对侧的月光）
+    pub fn light_rotation(&self, noon: Vec3) -> Quat {
+        let (east, noon_dir) = sun_arc(noon);
+        let sun = self.sun_direction(noon);
+        let toward = if self.sun_height() >= 0.0 { sun } else { -sun };
+        Transform::default()
+            .looking_to(-toward, east.cross(noon_dir))
+            .rotation
+    }
+
+    /// 太阳在弧线上的角度（日出为 0，正午为 π/2）
+    fn sun_angle(&self) -> f32 {
+        (self.hour - SUNRISE_HOUR) / 12.0 * PI
+    }
+}
+
+/// 太阳弧线的基准方向：日出方向（水平）与正午方向（单位向量）
+fn sun_arc(noon: Vec3) -> (Vec3, Vec3) {
+    let noon = noon.try_normalize().unwrap_or(Vec3::Y);
+    let horizontal = noon.with_y(0.0).try_normalize().unwrap_or(Vec3::Z);
+    (horizontal.cross(Vec3::Y), noon)
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    if edge1 <= edge0 {
+        return if x < edge0 { 0.0 } else { 1.0 };
+    }
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn lerp_color(from: [f32; 3], to: [f32; 3], t: f32) -> [f32; 3] {
+    Vec3::from_array(from)
+        .lerp(Vec3::from_array(to), t)
+        .to_array()
+}
+
+/// 某一时刻的光照
+#[derive(Debug, Clone, PartialEq)]
+pub struct SkyLighting {
+    /// 方向光颜色（sRGB）
+    pub light_color: [f32; 3],
+    /// 方向光照度
+    pub illuminance: f32,
+    /// 环境光颜色（sRGB）
+    pub ambient_color: [f32; 3],
+    /// 环境光亮度
+    pub ambient_brightness: f32,
+}
+
+impl SkyLighting {
+    /// 按时刻在关卡光照（白天）与夜间光照之间插值
+    ///
+    /// 方向光在地平线处照度降为 0，太阳与月光切换时不会跳变
+    pub fn at(time: &TimeOfDay, lights: &LevelLights, config: &DayNightBalance) -> Self {
+        let height = time.sun_height();
+        let daylight = time.daylight();
+        let fade = 2.0 * time.twilight;
+
+        let (light_color, illuminance) = match &lights.sun {
+            Some(sun) if height >= 0.0 => {
+                let warmth = 1.0 - smoothstep(0.0, DUSK_HEIGHT, height);
+                (
+                    lerp_color(sun.color, config.dusk_color, warmth),
+                    sun.illuminance * smoothstep(0.0, fade, height),
+                )
+            }
+            _ => (
+                config.moon_color,
+                config.moon_illuminance * smoothstep(0.0, fade, -height),
+            ),
+        };
+
+        Self {
+            light_color,
+            illuminance,
+            ambient_color: lerp_color(config.night_ambient_color, lights.ambient_color, daylight),
+            ambient_brightness: config
+                .night_ambient_brightness
+                .lerp(lights.ambient_brightness, daylight),
+        }
+    }
+}
+
+/// 昼夜循环插件
+pub struct DayNightPlugin;
+
+impl Plugin for DayNightPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<TimeOfDay>().add_systems(
+            Update,
+            (
+                reset_time_of_day,
+                advance_time_of_day.run_if(in_state(GameState::Playing)),
+                apply_sky_lighting,
+            )
+                .chain()
+                .after(LevelSet),
+        );
+    }
+}
+
+/// 关卡重置时回到开始时刻
+fn reset_time_of_day(
+    mut changed: MessageReader<LevelChanged>,
+    balance: Res<BalanceConfig>,
+    level: Res<LevelLayout>,
+    mut time: ResMut<TimeOfDay>,
+) {
+    if !changed.read().any(|event| event.respawn) {
+        return;
+    }
+    *time = TimeOfDay::from_balance(&balance);
+    if let Some(hour) = level.lights.start_hour {
+        time.set_hour(hour);
+    }
+    info!("[DayNight] Time of day reset to {:.1}h", time.hour);
+}
+
+/// 推进时刻
+fn advance_time_of_day(clock: Res<Time>, mut time: ResMut<TimeOfDay>) {
+    time.advance(clock.delta_secs());
+}
+
+/// 按时刻更新太阳与环境光
+fn apply_sky_lighting(
+    time: Res<TimeOfDay>,
+    balance: Res<BalanceConfig>,
+    level: Res<LevelLayout>,
+    ambient: Option<ResMut<AmbientLight>>,
+    mut suns: Query<(&mut DirectionalLight, &mut Transform), With<Sun>>,
+) {
+    let lighting = SkyLighting::at(&time, &level.lights, &balance.level.day_night);
+
+    if let Some(mut ambient) = ambient {
+        ambient.color = Color::srgb_from_array(lighting.ambient_color);
+        ambient.brightness = lighting.ambient_brightness;
+    }
+
+    let Some(sun) = &level.lights.sun else {
+        return;
+    };
+    let noon = sun.rotation() * Vec3::Z;
+    for (mut light, mut transform) in suns.iter_mut() {
+        light.color = Color::srgb_from_array(lighting.light_color);
+        light.illuminance = lighting.illuminance;
+        transform.rotation = time.light_rotation(noon);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::level::LevelSunLight;
+
+    fn time_at(hour: f32) -> TimeOfDay {
+        let mut time = TimeOfDay::default();
+        time.set_hour(hour);
+        time
+    }
+
+    #[test]
+    fn test_advance_wraps() {
+        let mut time = time_at(23.0);
+        time.day_length = 240.0;
+        // 每 10 秒一小时
+        time.advance(20.0);
+        assert!((time.hour - 1.0).abs() < 1e-4);
+
+        time.day_length = 0.0;
+        time.advance(20.0);
+        assert!((time.hour - 1.0).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_daylight() {
+        assert_eq!(time_at(12.0).daylight(), 1.0);
+        assert_eq!(time_at(0.0).daylight(), 0.0);
+        assert!((time_at(6.0).daylight() - 0.5).abs() < 1e-4);
+        assert!((time_at(18.0).daylight() - 0.5).abs() < 1e-4);
+        assert!(!time_at(9.0).is_night());
+        assert!(time_at(21.0).is_night());
+    }
+
+    #[test]
+    fn test_sun_path() {
+        let noon = LevelSunLight::default().rotation() * Vec3::Z;
+        assert!(noon.y > 0.0);
+
+        // 正午为关卡太阳方向，日出日落在地平线的两侧，午夜在地平线以下
+        assert!(time_at(12.0).sun_direction(noon).distance(noon) < 1e-4);
+        let sunrise = time_at(6.0).sun_direction(noon);
+        let sunset = time_at(18.0).sun_direction(noon);
+        assert!(sunrise.y.abs() < 1e-4 && sunset.y.abs() < 1e-4);
+        assert!(sunrise.distance(-sunset) < 1e-4);
+        assert!(time_at(0.0).sun_direction(noon).y < 0.0);
+
+        // 方向光总是从上方照下（夜间为月光）
+        for hour in [3.0, 9.0, 12.0, 15.0, 21.0] {
+            let forward = time_at(hour).light_rotation(noon) * Vec3::NEG_Z;
+            assert!(forward.y < 0.0, "{hour}h: {forward}");
+        }
+    }
+
+    #[test]
+    fn test_sky_lighting() {
+        let lights = LevelLights::default();
+        let config = DayNightBalance::default();
+        let sun = lights.sun.clone().unwrap();
+
+        let noon = SkyLighting::at(&time_at(12.0), &lights, &config);
+        assert_eq!(noon.light_color, sun.color);
+        assert_eq!(noon.illuminance, sun.illuminance);
+        assert_eq!(noon.ambient_brightness, lights.ambient_brightness);
+
+        let midnight = SkyLighting::at(&time_at(0.0), &lights, &config);
+        assert_eq!(midnight.light_color, config.moon_color);
+        assert_eq!(midnight.illuminance, config.moon_illuminance);
+        assert_eq!(midnight.ambient_brightness, config.night_ambient_brightness);
+
+        // 日落时方向光熄灭，颜色偏向晨昏颜色
+        let sunset = SkyLighting::at(&time_at(18.0), &lights, &config);
+        assert!(sunset.illuminance < 1.0);
+        let dusk = SkyLighting::at(&time_at(17.0), &lights, &config);
+        assert!(dusk.light_color[2] < sun.color[2]);
+    }
+}
@@ -0,0 +1,770 @@
+//! 关卡布局与程序化关卡生成
+//!
+//! 关卡有两种来源（`LevelSource`）：程序化生成与手工编写的关卡文件（见 `level_file`），
+//! 两者都产出 `LevelLayout`，由 `spawning` 生成实体。
+//!
+//! 程序化关卡由种子确定地生成户外布局（同一种子、同一配置得到完全相同的关卡）：
+//! - 地形：噪声高度图，玩家出生点周围压平
+//! - 墙体：随机朝向与长度，中间留门洞
+//! - 障碍物：较高的方块（岩石）或圆柱（柱子）
+//! - 道具：箱子等矮物体
+//!
+//! 所有物体之间、物体与边界之间至少保留 `clearance` 宽的通道，玩家出生点周围保持空旷。
+//! 生成结果导出：
+//! - 可行走区域网格（物体按角色半径外扩后剔除，过陡的坡面剔除，并只保留与玩家出生点连通的部分）
+//! - 玩家出生点与敌人生成点
+//!
+//! 导航网格直接使用布局中的物体占地；物体实体（带 `StaticCollider`）按区块加载（见 `streaming`），
+//! 碰撞体注册到碰撞层供角色控制器查询。
+
+use bevy::prelude::*;
+use serde::Deserialize;
+use rand::rngs::StdRng;
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use std::collections::VecDeque;
+use vigilant_doodle_core::balance::{LevelBalance, ObjectiveKind};
+
+use crate::collision::FLOOR_TOP;
+use crate::terrain::{Heightfield, FLOOR_LENGTH, FLOOR_MARGIN, FLOOR_WIDTH};
+
+/// 关卡随机流名称（用于从 `GameRng` 取得关卡种子）
+pub const LEVEL_RNG_STREAM: &str = "level";
+
+/// 每个物体的最多放置尝试次数
+const MAX_PLACEMENT_ATTEMPTS: usize = 64;
+/// 玩家出生点所在的中央区域（占半尺寸的比例）
+const PLAYER_SPAWN_REGION: f32 = 0.3;
+/// 默认（空旷）关卡的网格尺寸
+const DEFAULT_CELL_SIZE: f32 = 1.0;
+
+/// 关卡物体类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum LevelFeatureKind {
+    /// 墙体
+    Wall,
+    /// 障碍物（柱子、岩石）
+    Obstacle,
+    /// 道具（箱子等）
+    Prop,
+}
+
+/// 关卡物体形状
+#[derive(Debug, Clone, PartialEq, Default)]
+pub enum LevelFeatureShape {
+    /// 轴对齐方块
+    #[default]
+    Box,
+    /// 竖直圆柱（半径取半尺寸的较小分量）
+    Cylinder,
+    /// glTF 模型（相对 assets 目录的路径），碰撞体为模型网格的凸包
+    Model(String),
+}
+
+/// 关卡物体
+///
+/// 占地范围（导航与放置约束）总是轴对齐矩形，碰撞形状由 `shape` 决定
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelFeature {
+    pub kind: LevelFeatureKind,
+    pub shape: LevelFeatureShape,
+    /// 中心位置（XZ）
+    pub center: Vec2,
+    /// 半尺寸（XZ）
+    pub half_extents: Vec2,
+    /// 高度
+    pub height: f32,
+}
+
+impl LevelFeature {
+    /// XZ 平面上的占地矩形
+    pub fn footprint(&self) -> Rect {
+        Rect::from_center_half_size(self.center, self.half_extents)
+    }
+}
+
+/// 可行走区域网格
+#[derive(Debug, Clone)]
+pub struct WalkableMap {
+    /// 网格左下角（最小 XZ）
+    origin: Vec2,
+    cell_size: f32,
+    columns: usize,
+    rows: usize,
+    cells: Vec<bool>,
+}
+
+impl WalkableMap {
+    /// 覆盖 `bounds` 的全部可行走网格
+    fn new(bounds: Rect, cell_size: f32) -> Self {
+        let size = bounds.size();
+        let columns = (size.x / cell_size).floor().max(1.0) as usize;
+        let rows = (size.y / cell_size).floor().max(1.0) as usize;
+        Self {
+            origin: bounds.min,
+            cell_size,
+            columns,
+            rows,
+            cells: vec![true; columns * rows],
+        }
+    }
+
+    /// 格子尺寸
+    pub fn cell_size(&self) -> f32 {
+        self.cell_size
+    }
+
+    /// 网格列数与行数
+    pub fn dimensions(&self) -> (usize, usize) {
+        (self.columns, self.rows)
+    }
+
+    /// 点所在的格子
+    pub fn cell_of(&self, point: Vec2) -> Option<(usize, usize)> {
+        let local = (point - self.origin) / self.cell_size;
+        if local.x < 0.0 || local.y < 0.0 {
+            return None;
+        }
+        let (column, row) = (local.x as usize, local.y as usize);
+        (column < self.columns && row < self.rows).then_some((column, row))
+    }
+
+    /// 格子中心
+    pub fn cell_center(&self, column: usize, row: usize) -> Vec2 {
+        self.origin + (Vec2::new(column as f32, row as f32) + 0.5) * self.cell_size
+    }
+
+    /// 点是否可行走
+    pub fn is_walkable(&self, point: Vec2) -> bool {
+        self.cell_of(point)
+            .is_some_and(|(column, row)| self.cells[row * self.columns + column])
+    }
+
+    /// 所有可行走格子的中心
+    pub fn walkable_cells(&self) -> impl Iterator<Item = Vec2> + '_ {
+        self.cells
+            .iter()
+            .enumerate()
+            .filter(|(_, walkable)| **walkable)
+            .map(|(i, _)| self.cell_center(i % self.columns, i / self.columns))
+    }
+
+    /// 剔除与 `start` 不连通的格子（四邻接）
+    fn retain_connected(&mut self, start: Vec2) {
+        let mut reached = vec![false; self.cells.len()];
+        let mut queue = VecDeque::new();
+        if let Some((column, row)) = self.cell_of(start) {
+            let index = row * self.columns + column;
+            if self.cells[index] {
+                reached[index] = true;
+                queue.push_back((column, row));
+            }
+        }
+
+        while let Some((column, row)) = queue.pop_front() {
+            let neighbors = [
+                (column.wrapping_sub(1), row),
+                (column + 1, row),
+                (column, row.wrapping_sub(1)),
+                (column, row + 1),
+            ];
+            for (c, r) in neighbors {
+                if c >= self.columns || r >= self.rows {
+                    continue;
+                }
+                let index = r * self.columns + c;
+                if self.cells[index] && !reached[index] {
+                    reached[index] = true;
+                    queue.push_back((c, r));
+                }
+            }
+        }
+        self.cells = reached;
+    }
+}
+
+/// 关卡来源
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LevelSource {
+    /// 按种子程序化生成
+    Procedural { seed: u64 },
+    /// 关卡文件（相对 assets 目录的路径）
+    File { path: String },
+}
+
+impl Default for LevelSource {
+    fn default() -> Self {
+        Self::Procedural { seed: 0 }
+    }
+}
+
+/// 请求加载关卡
+#[derive(Message, Debug, Clone)]
+pub struct LoadLevel {
+    pub source: LevelSource,
+    /// 是否重置关卡内容（玩家回到出生点、重新放置敌人）；读档时为 false
+    pub respawn: bool,
+}
+
+/// 关卡实体已生成（新关卡或热重载）
+#[derive(Message, Debug, Clone, Copy)]
+pub struct LevelChanged {
+    /// 是否需要重置关卡内容（与 `LoadLevel::respawn` 一致，热重载时为 false）
+    pub respawn: bool,
+}
+
+/// 关卡加载与实体生成系统集（Update）
+///
+/// 读取 `LevelChanged` 的系统应排在其后，以便同一帧内响应
+#[derive(SystemSet, Debug, Clone, PartialEq, Eq, Hash)]
+pub struct LevelSet;
+
+/// 标记：关卡实体（切换关卡时整体移除）
+#[derive(Component)]
+pub struct LevelEntity;
+
+/// 地面
+#[derive(Debug, Clone, PartialEq)]
+pub struct LevelFloor {
+    /// 尺寸（XZ）
+    pub size: Vec2,
+    pub color: Color,
+}
+
+impl Default for LevelFloor {
+    fn default() -> Self {
+        Self {
+            size: Vec2::new(FLOOR_LENGTH, FLOOR_WIDTH),
+            color: Color::srgb(0.3, 0.3, 0.35),
+        }
+    }
+}
+
+impl LevelFloor {
+    /// 可活动范围（地面边缘内缩边距）
+    pub fn bounds(&self) -> Rect {
+        Rect::from_center_half_size(Vec2::ZERO, (self.size * 0.5 - FLOOR_MARGIN).max(Vec2::ZERO))
+    }
+}
+
+/// 关卡灯光
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct LevelLights {
+    /// 环境光颜色（sRGB）
+    pub ambient_color: [f32; 3],
+    /// 环境光亮度
+    pub ambient_brightness: f32,
+    /// 太阳光（方向光）
+    pub sun: Option<LevelSunLight>,
+    /// 点光源
+    pub points: Vec<LevelPointLight>,
+    /// 关卡开始时的时刻（小时，为空时使用平衡配置）
+    pub start_hour: Option<f32>,
+}
+
+impl Default for LevelLights {
+    fn default() -> Self {
+        Self {
+            ambient_color: [0.8, 0.8, 1.0],
+            ambient_brightness: 200.0,
+            sun: Some(LevelSunLight::default()),
+            points: Vec::new(),
+            start_hour: None,
+        }
+    }
+}
+
+/// 太阳光（正午时的设置，昼夜循环以此为基准）
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+#[serde(default)]
+pub struct LevelSunLight {
+    /// 颜色（sRGB）
+    pub color: [f32; 3],
+    /// 照度
+    pub illuminance: f32,
+    /// 俯角（度，负值向下）
+    pub pitch: f32,
+    /// 侧向角（度）
+    pub yaw: f32,
+    pub shadows: bool,
+}
+
+impl Default for LevelSunLight {
+    fn default() -> Self {
+        Self {
+            color: [1.0, 0.95, 0.9],
+            illuminance: 5000.0,
+            pitch: -45.0,
+            yaw: 45.0,
+            shadows: true,
+        }
+    }
+}
+
+impl LevelSunLight {
+    /// 方向光朝向
+    pub fn rotation(&self) -> Quat {
+        Quat::from_euler(
+            EulerRot::XYZ,
+            self.pitch.to_radians(),
+            self.yaw.to_radians(),
+            0.0,
+        )
+    }
+}
+
+/// 点光源
+#[derive(Debug, Clone, PartialEq, Deserialize)]
+pub struct LevelPointLight {
+    pub position: [f32; 3],
+    /// 颜色（sRGB）
+    pub color: [f32; 3],
+    pub intensity: f32,
+    pub range: f32,
+}
+
+/// 关卡中预先放置的敌人
+#[derive(Debug, Clone, PartialEq)]
+pub struct EnemyPlacement {
+    /// 敌人原型名称（对应平衡配置中的 `enemy.archetypes`）
+    pub archetype: String,
+    /// 位置（XZ）
+    pub position: Vec2,
+    /// 巡逻路线（XZ），为空时使用默认的出生点方形路线
+    pub patrol: Vec<Vec2>,
+}
+
+/// 关卡中预先放置的拾取物
+#[derive(Debug, Clone, PartialEq)]
+pub struct ItemPlacement {
+    /// 物品名称（对应平衡配置中的 `items`）
+    pub item: String,
+    /// 数量
+    pub count: u32,
+    /// 位置（XZ）
+    pub position: Vec2,
+}
+
+/// 可交互物体类型
+#[derive(Debug, Clone, PartialEq)]
+pub enum InteractableKind {
+    /// 门：关闭时阻挡通行（不计入可行走区域的计算）；
+    /// 指定 `channel` 时只能由同一频道的开关控制
+    Door {
+        /// 门板半尺寸（XZ），较长的方向为门板走向
+        half_extents: Vec2,
+        height: f32,
+        channel: Option<String>,
+        /// 初始是否打开
+        open: bool,
+    },
+    /// 开关：切换同一频道的所有门
+    Switch { channel: String },
+    /// 宝箱：打开后物品放入背包（放不下的留在箱中）
+    Chest { items: Vec<(String, u32)> },
+    /// 纸条：阅读时显示翻译键 `text` 对应的文本
+    Note { text: String },
+}
+
+/// 关卡中放置的可交互物体
+#[derive(Debug, Clone, PartialEq)]
+pub struct InteractablePlacement {
+    pub kind: InteractableKind,
+    /// 位置（XZ）
+    pub position: Vec2,
+}
+
+/// 当前关卡布局
+#[derive(Resource, Debug, Clone)]
+pub struct LevelLayout {
+    /// 关卡来源
+    pub source: LevelSource,
+    /// 关卡名称
+    pub name: String,
+    /// 地面
+    pub floor: LevelFloor,
+    /// 地形高度图（覆盖整个地面）
+    pub terrain: Heightfield,
+    /// 关卡边界（XZ）
+    pub bounds: Rect,
+    /// 所有物体
+    pub features: Vec<LevelFeature>,
+    /// 灯光
+    pub lights: LevelLights,
+    /// 可行走区域
+    pub walkable: WalkableMap,
+    /// 玩家出生点（XZ）
+    pub player_spawn: Vec2,
+    /// 敌人波次生成点（XZ）
+    pub enemy_spawns: Vec<Vec2>,
+    /// 预先放置的敌人
+    pub enemy_placements: Vec<EnemyPlacement>,
+    /// 预先放置的拾取物
+    pub item_placements: Vec<ItemPlacement>,
+    /// 可交互物体
+    pub interactables: Vec<InteractablePlacement>,
+    /// 关卡目标（全部完成即通关，为空时没有终点）
+    pub objectives: Vec<ObjectiveKind>,
+    /// 通关后进入的关卡文件（为空时进入新的程序化关卡）
+    pub next_level: Option<String>,
+}
+
+impl Default for LevelLayout {
+    /// 空旷的地面（尚未生成关卡时使用）
+    fn default() -> Self {
+        let floor = LevelFloor::default();
+        let bounds = floor.bounds();
+        Self {
+            source: LevelSource::default(),
+            name: String::new(),
+            terrain: Heightfield::flat(floor.size, FLOOR_TOP),
+            floor,
+            bounds,
+            features: Vec::new(),
+            lights: LevelLights::default(),
+            walkable: WalkableMap::new(bounds, DEFAULT_CELL_SIZE),
+            player_spawn: Vec2::ZERO,
+            enemy_spawns: Vec::new(),
+            enemy_placements: Vec::new(),
+            item_placements: Vec::new(),
+            interactables: Vec::new(),
+            objectives: Vec::new(),
+            next_level: None,
+        }
+    }
+}
+
+fn random_in(rng: &mut impl Rng, range: [f32; 2]) -> f32 {
+    if range[0] < range[1] {
+        rng.random_range(range[0]..=range[1])
+    } else {
+        range[0]
+    }
+}
+
+/// 放置约束
+struct Placer<'a> {
+    config: &'a LevelBalance,
+    bounds: Rect,
+    player_spawn: Vec2,
+    features: Vec<LevelFeature>,
+}
+
+impl Placer<'_> {
+    /// 物体是否满足边界、通道宽度与出生点空旷的约束
+    fn fits(&self, feature: &LevelFeature) -> bool {
+        let footprint = feature.footprint();
+        let padded = footprint.inflate(self.config.clearance);
+        let inside =
+            padded.min.cmpge(self.bounds.min).all() && padded.max.cmple(self.bounds.max).all();
+        let closest = self.player_spawn.clamp(footprint.min, footprint.max);
+
+        inside
+            && closest.distance(self.player_spawn) >= self.config.spawn_clear_radius
+            && self
+                .features
+                .iter()
+                .all(|other| padded.intersect(other.footprint()).is_empty())
+    }
+
+    /// 反复生成候选，放置第一组满足约束的物体
+    fn place(&mut self, mut candidate: impl FnMut(Vec2) -> Vec<LevelFeature>, rng: &mut impl Rng) {
+        for _ in 0..MAX_PLACEMENT_ATTEMPTS {
+            let center = Vec2::new(
+                rng.random_range(self.bounds.min.x..=self.bounds.max.x),
+                rng.random_range(self.bounds.min.y..=self.bounds.max.y),
+            );
+            let features = candidate(center);
+            if !features.is_empty() && features.iter().all(|feature| self.fits(feature)) {
+                self.features.extend(features);
+                return;
+            }
+        }
+    }
+}
+
+/// 中间留门洞的墙体（两段）
+fn wall_segments(
+    center: Vec2,
+    length: f32,
+    horizontal: bool,
+    config: &LevelBalance,
+) -> Vec<LevelFeature> {
+    let segment = (length - config.doorway_width) * 0.5;
+    if segment <= 0.0 {
+        return Vec::new();
+    }
+    let axis = if horizontal { Vec2::X } else { Vec2::Y };
+    let half_extents = if horizontal {
+        Vec2::new(segment * 0.5, config.wall_thickness * 0.5)
+    } else {
+        Vec2::new(config.wall_thickness * 0.5, segment * 0.5)
+    };
+    let offset = (config.doorway_width + segment) * 0.5;
+
+    [-offset, offset]
+        .into_iter()
+        .map(|offset| LevelFeature {
+            kind: LevelFeatureKind::Wall,
+            shape: LevelFeatureShape::Box,
+            center: center + axis * offset,
+            half_extents,
+            height: config.wall_height,
+        })
+        .collect()
+}
+
+/// 由种子生成关卡布局
+pub fn generate_level(seed: u64, config: &LevelBalance) -> LevelLayout {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let floor = LevelFloor::default();
+    let bounds = floor.bounds();
+
+    let region = bounds.half_size() * PLAYER_SPAWN_REGION;
+    let player_spawn = Vec2::new(
+        rng.random_range(-region.x..=region.x),
+        rng.random_range(-region.y..=region.y),
+    );
+    let mut terrain = Heightfield::from_noise(floor.size, rng.random(), &config.terrain);
+    terrain.flatten(player_spawn, config.terrain.flat_radius);
+
+    let mut placer = Placer {
+        config,
+        bounds,
+        player_spawn,
+        features: Vec::new(),
+    };
+
+    for _ in 0..config.wall_count {
+        let length = random_in(&mut rng, config.wall_length);
+        let horizontal = rng.random_bool(0.5);
+        placer.place(
+            |center| wall_segments(center, length, horizontal, config),
+            &mut rng,
+        );
+    }
+    for (kind, count, size, height) in [
+        (
+            LevelFeatureKind::Obstacle,
+            config.obstacle_count,
+            config.obstacle_size,
+            config.obstacle_height,
+        ),
+        (
+            LevelFeatureKind::Prop,
+            config.prop_count,
+            config.prop_size,
+            config.prop_height,
+        ),
+    ] {
+        for _ in 0..count {
+            let mut half_extents = Vec2::new(random_in(&mut rng, size), random_in(&mut rng, size));
+            let height = random_in(&mut rng, height);
+            let shape = if kind == LevelFeatureKind::Obstacle
+                && rng.random_bool(config.pillar_chance.clamp(0.0, 1.0))
+            {
+                half_extents = Vec2::splat(half_extents.min_element());
+                LevelFeatureShape::Cylinder
+            } else {
+                LevelFeatureShape::Box
+            };
+            placer.place(
+                |center| {
+                    vec![LevelFeature {
+                        kind,
+                        shape: shape.clone(),
+                        center,
+                        half_extents,
+                        height,
+                    }]
+                },
+                &mut rng,
+            );
+        }
+    }
+
+    LevelLayout {
+        source: LevelSource::Procedural { seed },
+        name: format!("procedural-{seed}"),
+        floor,
+        terrain,
+        bounds,
+        features: placer.features,
+        player_spawn,
+        objectives: config.objectives.clone(),
+        ..default()
+    }
+    .with_navigation(config, &mut rng, Vec::new())
+}
+
+impl LevelLayout {
+    /// 计算可行走区域；`spawns` 为空时按配置自动选取敌人生成点
+    pub(crate) fn with_navigation(
+        mut self,
+        config: &LevelBalance,
+        rng: &mut impl Rng,
+        spawns: Vec<Vec2>,
+    ) -> Self {
+        // 可行走区域：剔除外扩后的物体占地与过陡的坡面，只保留与出生点连通的部分
+        let mut walkable = WalkableMap::new(self.bounds, config.cell_size);
+        let blocked: Vec<Rect> = self
+            .features
+            .iter()
+            .map(|feature| feature.footprint().inflate(config.agent_radius))
+            .collect();
+        let min_normal_y = config.terrain.max_slope.to_radians().cos();
+        for row in 0..walkable.rows {
+            for column in 0..walkable.columns {
+                let center = walkable.cell_center(column, row);
+                if blocked.iter().any(|rect| rect.contains(center))
+                    || self.terrain.normal_at(center).y < min_normal_y
+                {
+                    walkable.cells[row * walkable.columns + column] = false;
+                }
+            }
+        }
+        walkable.retain_connected(self.player_spawn);
+        self.walkable = walkable;
+
+        self.enemy_spawns = if spawns.is_empty() {
+            self.pick_enemy_spawns(config, rng)
+        } else {
+            spawns
+        };
+        self
+    }
+
+    /// 敌人生成点：远离玩家出生点，彼此保持间距
+    fn pick_enemy_spawns(&self, config: &LevelBalance, rng: &mut impl Rng) -> Vec<Vec2> {
+        let mut candidates: Vec<Vec2> = self
+            .walkable
+            .walkable_cells()
+            .filter(|cell| cell.distance(self.player_spawn) >= config.enemy_spawn_min_distance)
+            .collect();
+        candidates.shuffle(rng);
+
+        let mut spawns: Vec<Vec2> = Vec::with_capacity(config.enemy_spawn_count as usize);
+        for candidate in candidates {
+            if spawns.len() >= config.enemy_spawn_count as usize {
+                break;
+            }
+            if spawns
+                .iter()
+                .all(|spawn| spawn.distance(candidate) >= config.enemy_spawn_spacing)
+            {
+                spawns.push(candidate);
+            }
+        }
+        spawns
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_seed_same_layout() {
+        let config = LevelBalance::default();
+        let a = generate_level(11, &config);
+        let b = generate_level(11, &config);
+        assert_eq!(a.features, b.features);
+        assert_eq!(a.terrain, b.terrain);
+        assert_eq!(a.player_spawn, b.player_spawn);
+        assert_eq!(a.enemy_spawns, b.enemy_spawns);
+
+        let c = generate_level(12, &config);
+        assert_ne!(a.features, c.features);
+    }
+
+    #[test]
+    fn test_layout_respects_constraints() {
+        let config = LevelBalance::default();
+        for seed in 0..8 {
+            let level = generate_level(seed, &config);
+            assert!(
+                level
+                    .features
+                    .iter()
+                    .any(|f| f.kind == LevelFeatureKind::Wall)
+            );
+            assert!(
+                level
+                    .features
+                    .iter()
+                    .any(|f| f.kind == LevelFeatureKind::Prop)
+            );
+
+            // 物体之间保留通道，且不挡住出生点
+            for (i, a) in level.features.iter().enumerate() {
+                let padded = a.footprint().inflate(config.clearance - 1e-3);
+                for b in &level.features[i + 1..] {
+                    assert!(padded.intersect(b.footprint()).is_empty(), "seed {seed}");
+                }
+                assert!(!a.footprint().contains(level.player_spawn));
+            }
+
+            // 出生点可行走，敌人生成点都在可行走区域内且远离玩家
+            assert!(level.walkable.is_walkable(level.player_spawn));
+            assert!(!level.enemy_spawns.is_empty());
+            for spawn in &level.enemy_spawns {
+                assert!(level.walkable.is_walkable(*spawn));
+                assert!(spawn.distance(level.player_spawn) >= config.enemy_spawn_min_distance);
+            }
+        }
+    }
+
+    #[test]
+    fn test_walkable_excludes_enclosed_area() {
+        // 四面封闭的小屋：屋内不可达
+        let bounds = Rect::new(-10.0, -10.0, 10.0, 10.0);
+        let mut walkable = WalkableMap::new(bounds, 1.0);
+        let walls = [
+            Rect::new(2.0, 2.0, 8.0, 3.0),
+            Rect::new(2.0, 7.0, 8.0, 8.0),
+            Rect::new(2.0, 2.0, 3.0, 8.0),
+            Rect::new(7.0, 2.0, 8.0, 8.0),
+        ];
+        for row in 0..walkable.rows {
+            for column in 0..walkable.columns {
+                let center = walkable.cell_center(column, row);
+                if walls.iter().any(|wall| wall.contains(center)) {
+                    walkable.cells[row * walkable.columns + column] = false;
+                }
+            }
+        }
+        assert!(walkable.is_walkable(Vec2::new(5.0, 5.0)));
+        walkable.retain_connected(Vec2::new(-5.0, -5.0));
+        assert!(!walkable.is_walkable(Vec2::new(5.0, 5.0)));
+        assert!(walkable.is_walkable(Vec2::new(-5.0, -5.0)));
+        assert!(walkable.is_walkable(Vec2::new(9.5, 9.5)));
+        assert!(!walkable.is_walkable(Vec2::new(20.0, 0.0)));
+    }
+
+    #[test]
+    fn test_walkable_excludes_steep_terrain() {
+        let floor = LevelFloor {
+            size: Vec2::new(40.0, 20.0),
+            ..default()
+        };
+        // x > 5 为 45° 陡坡
+        let terrain = Heightfield::from_fn(floor.size, 1.0, |p| (p.x - 5.0).max(0.0));
+        let layout = LevelLayout {
+            bounds: floor.bounds(),
+            terrain,
+            floor,
+            ..default()
+        }
+        .with_navigation(
+            &LevelBalance::default(),
+            &mut StdRng::seed_from_u64(0),
+            Vec::new(),
+        );
+
+        assert!(layout.walkable.is_walkable(Vec2::new(0.0, 0.0)));
+        assert!(layout.walkable.is_walkable(Vec2::new(4.5, 0.0)));
+        assert!(!layout.walkable.is_walkable(Vec2::new(10.0, 0.0)));
+    }
+}
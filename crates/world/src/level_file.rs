@@ -0,0 +1,562 @@
+//! 手工编写的关卡文件
+//!
+//! 关卡文件为 JSON（扩展名 `.level.json`，放在 `assets/levels/` 下），描述：
+//! - 地面尺寸与颜色
+//! - 地形：灰度高度图图片（相对 assets 目录）或噪声种子，省略时为平地
+//! - 障碍物（墙体、障碍物、道具），形状为方块、圆柱或 glTF 模型（碰撞体为模型凸包）
+//! - 灯光（环境光、太阳光、点光源）
+//! - 玩家出生点
+//! - 预先放置的敌人（原型与巡逻路线）与波次生成点
+//! - 预先放置的拾取物（物品名称与数量）
+//! - 可交互物体（门、开关、宝箱、纸条）
+//! - 关卡目标（省略时使用程序化关卡的目标）与通关后进入的下一关
+//!
+//! 坐标均为 XZ 平面上的 `[x, z]`。省略的字段使用程序化关卡的默认值；
+//! 未给出波次生成点时，按平衡配置从可行走区域中自动选取。
+//!
+//! 文件通过 Bevy 资源系统加载，启用 `file_watcher` 时修改文件（或其高度图）会热重载关卡。
+
+use bevy::asset::io::Reader;
+use bevy::asset::{AssetLoader, LoadContext};
+use bevy::image::ImageLoaderSettings;
+use bevy::prelude::*;
+use bevy::tasks::ConditionalSendFuture;
+use rand::SeedableRng;
+use rand::rngs::StdRng;
+use serde::Deserialize;
+use vigilant_doodle_core::balance::{LevelBalance, ObjectiveKind};
+
+use crate::collision::FLOOR_TOP;
+use crate::level::{
+    EnemyPlacement, InteractableKind, InteractablePlacement, ItemPlacement, LevelFeature,
+    LevelFeatureKind, LevelFeatureShape, LevelFloor, LevelLayout, LevelLights, LevelSource,
+};
+use crate::terrain::Heightfield;
+
+/// 关卡文件资源
+#[derive(Asset, TypePath, Debug, Clone, Deserialize)]
+pub struct LevelAsset {
+    /// 关卡名称
+    pub name: String,
+    #[serde(default)]
+    pub floor: FloorDef,
+    #[serde(default)]
+    pub terrain: TerrainDef,
+    #[serde(default)]
+    pub obstacles: Vec<ObstacleDef>,
+    #[serde(default)]
+    pub lights: LevelLights,
+    /// 玩家出生点
+    pub player_start: [f32; 2],
+    #[serde(default)]
+    pub enemies: Vec<EnemyDef>,
+    /// 波次生成点
+    #[serde(default)]
+    pub spawn_points: Vec<[f32; 2]>,
+    #[serde(default)]
+    pub items: Vec<ItemDef>,
+    #[serde(default)]
+    pub interactables: Vec<InteractableDef>,
+    /// 关卡目标
+    #[serde(default)]
+    pub objectives: Option<Vec<ObjectiveKind>>,
+    /// 下一关（相对 assets 目录）
+    #[serde(default)]
+    pub next_level: Option<String>,
+    /// 加载器读取的高度图采样
+    #[serde(skip)]
+    pub heightmap: Option<HeightmapSamples>,
+}
+
+/// 地形定义
+#[derive(Debug, Clone, Default, Deserialize)]
+#[serde(default)]
+pub struct TerrainDef {
+    /// 灰度高度图（相对 assets 目录），黑色为基准面
+    pub heightmap: Option<String>,
+    /// 高度图白色处高出基准面的高度
+    pub height_scale: f32,
+    /// 噪声种子（未给出高度图时按平衡配置的地形参数生成）
+    pub noise_seed: Option<u64>,
+}
+
+/// 高度图采样（0..1，按行，第一行对应 -Z 边缘）
+#[derive(Debug, Clone)]
+pub struct HeightmapSamples {
+    pub width: usize,
+    pub height: usize,
+    pub values: Vec<f32>,
+}
+
+impl HeightmapSamples {
+    /// 读取图片的红色通道
+    fn from_image(image: &Image) -> Self {
+        let (width, height) = (image.width(), image.height());
+        let values = (0..height)
+            .flat_map(|y| (0..width).map(move |x| (x, y)))
+            .map(|(x, y)| {
+                image
+                    .get_color_at(x, y)
+                    .map_or(0.0, |color| color.to_linear().red)
+            })
+            .collect();
+        Self {
+            width: width as usize,
+            height: height as usize,
+            values,
+        }
+    }
+}
+
+/// 地面定义
+#[derive(Debug, Clone, Deserialize)]
+#[serde(default)]
+pub struct FloorDef {
+    /// 尺寸 `[长, 宽]`
+    pub size: [f32; 2],
+    /// 颜色（sRGB）
+    pub color: [f32; 3],
+}
+
+impl Default for FloorDef {
+    fn default() -> Self {
+        let floor = LevelFloor::default();
+        let color = floor.color.to_srgba();
+        Self {
+            size: floor.size.to_array(),
+            color: [color.red, color.green, color.blue],
+        }
+    }
+}
+
+/// 障碍物定义
+#[derive(Debug, Clone, Deserialize)]
+pub struct ObstacleDef {
+    pub kind: LevelFeatureKind,
+    /// 碰撞形状，给出 `model` 时忽略
+    #[serde(default)]
+    pub shape: ShapeDef,
+    /// glTF 模型（相对 assets 目录）
+    #[serde(default)]
+    pub model: Option<String>,
+    pub center: [f32; 2],
+    /// 占地半尺寸（模型按原尺寸放置，占地用于导航与放置检查）
+    pub half_extents: [f32; 2],
+    pub height: f32,
+}
+
+/// 障碍物形状
+#[derive(Debug, Clone, Copy, Default, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ShapeDef {
+    #[default]
+    Box,
+    Cylinder,
+}
+
+/// 敌人定义
+#[derive(Debug, Clone, Deserialize)]
+pub struct EnemyDef {
+    /// 原型名称
+    pub archetype: String,
+    pub position: [f32; 2],
+    /// 巡逻路线
+    #[serde(default)]
+    pub patrol: Vec<[f32; 2]>,
+}
+
+/// 拾取物定义
+#[derive(Debug, Clone, Deserialize)]
+pub struct ItemDef {
+    /// 物品名称
+    pub item: String,
+    /// 数量（省略时为 1）
+    #[serde(default = "default_item_count")]
+    pub count: u32,
+    pub position: [f32; 2],
+}
+
+fn default_item_count() -> u32 {
+    1
+}
+
+/// 一组物品（宝箱内容）
+#[derive(Debug, Clone, Deserialize)]
+pub struct ItemStackDef {
+    pub item: String,
+    /// 数量（省略时为 1）
+    #[serde(default = "default_item_count")]
+    pub count: u32,
+}
+
+/// 可交互物体定义
+#[derive(Debug, Clone, Deserialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum InteractableDef {
+    Door {
+        position: [f32; 2],
+        /// 门板半尺寸
+        half_extents: [f32; 2],
+        #[serde(default = "default_door_height")]
+        height: f32,
+        /// 控制频道（省略时玩家可直接开关）
+        #[serde(default)]
+        channel: Option<String>,
+        #[serde(default)]
+        open: bool,
+    },
+    Switch {
+        position: [f32; 2],
+        channel: String,
+    },
+    Chest {
+        position: [f32; 2],
+        #[serde(default)]
+        items: Vec<ItemStackDef>,
+    },
+    Note {
+        position: [f32; 2],
+        /// 文本的翻译键
+        text: String,
+    },
+}
+
+fn default_door_height() -> f32 {
+    3.0
+}
+
+impl InteractableDef {
+    fn to_placement(&self) -> InteractablePlacement {
+        let (kind, position) = match self {
+            Self::Door {
+                position,
+                half_extents,
+                height,
+                channel,
+                open,
+            } => (
+                InteractableKind::Door {
+                    half_extents: Vec2::from_array(*half_extents),
+                    height: *height,
+                    channel: channel.clone(),
+                    open: *open,
+                },
+                position,
+            ),
+            Self::Switch { position, channel } => (
+                InteractableKind::Switch {
+                    channel: channel.clone(),
+                },
+                position,
+            ),
+            Self::Chest { position, items } => (
+                InteractableKind::Chest {
+                    items: items
+                        .iter()
+                        .map(|stack| (stack.item.clone(), stack.count))
+                        .collect(),
+                },
+                position,
+            ),
+            Self::Note { position, text } => {
+                (InteractableKind::Note { text: text.clone() }, position)
+            }
+        };
+        InteractablePlacement {
+            kind,
+            position: Vec2::from_array(*position),
+        }
+    }
+}
+
+impl LevelAsset {
+    /// 转换为关卡布局（计算可行走区域与生成点）
+    pub fn to_layout(&self, path: &str, config: &LevelBalance) -> LevelLayout {
+        let floor = LevelFloor {
+            size: Vec2::from_array(self.floor.size),
+            color: Color::srgb_from_array(self.floor.color),
+        };
+        let bounds = floor.bounds();
+        let terrain = match (&self.heightmap, self.terrain.noise_seed) {
+            (Some(samples), _) => Heightfield::from_samples(
+                floor.size,
+                samples.width,
+                samples.height,
+                &samples.values,
+                self.terrain.height_scale,
+            ),
+            (None, Some(seed)) => Heightfield::from_noise(floor.size, seed, &config.terrain),
+            (None, None) => Heightfield::flat(floor.size, FLOOR_TOP),
+        };
+        let features: Vec<LevelFeature> = self
+            .obstacles
+            .iter()
+            .map(|obstacle| LevelFeature {
+                kind: obstacle.kind,
+                shape: match (&obstacle.model, obstacle.shape) {
+                    (Some(model), _) => LevelFeatureShape::Model(model.clone()),
+                    (None, ShapeDef::Box) => LevelFeatureShape::Box,
+                    (None, ShapeDef::Cylinder) => LevelFeatureShape::Cylinder,
+                },
+                center: Vec2::from_array(obstacle.center),
+                half_extents: Vec2::from_array(obstacle.half_extents),
+                height: obstacle.height,
+            })
+            .collect();
+        for feature in &features {
+            if !bounds.contains(feature.center) {
+                warn!("[Level] {}: 物体 {:?} 位于地面范围外", path, feature.center);
+            }
+        }
+
+        let enemy_placements = self
+            .enemies
+            .iter()
+            .map(|enemy| EnemyPlacement {
+                archetype: enemy.archetype.clone(),
+                position: Vec2::from_array(enemy.position),
+                patrol: enemy.patrol.iter().copied().map(Vec2::from_array).collect(),
+            })
+            .collect();
+
+        let item_placements = self
+            .items
+            .iter()
+            .map(|item| ItemPlacement {
+                item: item.item.clone(),
+                count: item.count,
+                position: Vec2::from_array(item.position),
+            })
+            .collect();
+
+        let interactables = self
+            .interactables
+            .iter()
+            .map(InteractableDef::to_placement)
+            .collect();
+
+        // 自动选取生成点时使用固定种子，同一文件总是得到相同结果
+        let mut rng = StdRng::seed_from_u64(0);
+        LevelLayout {
+            source: LevelSource::File {
+                path: path.to_string(),
+            },
+            name: self.name.clone(),
+            floor,
+            terrain,
+            bounds,
+            features,
+            lights: self.lights.clone(),
+            player_spawn: Vec2::from_array(self.player_start),
+            enemy_placements,
+            item_placements,
+            interactables,
+            objectives: self
+                .objectives
+                .clone()
+                .unwrap_or_else(|| config.objectives.clone()),
+            next_level: self.next_level.clone(),
+            ..default()
+        }
+        .with_navigation(
+            config,
+            &mut rng,
+            self.spawn_points
+                .iter()
+                .copied()
+                .map(Vec2::from_array)
+                .collect(),
+        )
+    }
+}
+
+/// 关卡文件加载器
+#[derive(Default)]
+pub struct LevelAssetLoader;
+
+impl AssetLoader for LevelAssetLoader {
+    type Asset = LevelAsset;
+    type Settings = ();
+    type Error = std::io::Error;
+
+    fn load(
+        &self,
+        reader: &mut dyn Reader,
+        _settings: &Self::Settings,
+        load_context: &mut LoadContext,
+    ) -> impl ConditionalSendFuture<Output = Result<Self::Asset, Self::Error>> {
+        async move {
+            let mut bytes = Vec::new();
+            reader.read_to_end(&mut bytes).await?;
+            let mut asset: LevelAsset = serde_json::from_slice(&bytes)
+                .map_err(|e| std::io::Error::new(std::io::ErrorKind::InvalidData, e))?;
+
+            // 高度图作为依赖直接加载（修改图片同样触发热重载），按线性值读取
+            if let Some(path) = asset.terrain.heightmap.clone() {
+                let image = load_context
+                    .loader()
+                    .with_settings(|settings: &mut ImageLoaderSettings| settings.is_srgb = false)
+                    .immediate()
+                    .load::<Image>(path)
+                    .await
+                    .map_err(std::io::Error::other)?;
+                asset.heightmap = Some(HeightmapSamples::from_image(image.get()));
+            }
+            Ok(asset)
+        }
+    }
+
+    fn extensions(&self) -> &[&str] {
+        &["level.json"]
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const LEVEL: &str = r#"{
+        "name": "Test",
+        "floor": { "size": [40.0, 30.0] },
+        "obstacles": [
+            { "kind": "wall", "center": [0.0, 5.0], "half_extents": [8.0, 0.5], "height": 3.0 },
+            { "kind": "prop", "center": [10.0, -6.0], "half_extents": [0.6, 0.6], "height": 1.0 },
+            { "kind": "obstacle", "shape": "cylinder", "center": [-10.0, -6.0], "half_extents": [1.0, 1.0], "height": 3.0 }
+        ],
+        "player_start": [0.0, -10.0],
+        "enemies": [
+            { "archetype": "scout", "position": [0.0, 10.0], "patrol": [[-5.0, 10.0], [5.0, 10.0]] }
+        ],
+        "spawn_points": [[-15.0, 10.0], [15.0, 10.0]],
+        "items": [
+            { "item": "medkit", "count": 2, "position": [5.0, -10.0] },
+            { "item": "knife", "position": [-5.0, -10.0] }
+        ],
+        "interactables": [
+            { "kind": "door", "position": [12.0, 5.0], "half_extents": [4.0, 0.3], "channel": "gate" },
+            { "kind": "switch", "position": [-12.0, -8.0], "channel": "gate" },
+            { "kind": "chest", "position": [8.0, -10.0], "items": [{ "item": "battery", "count": 3 }, { "item": "medkit" }] },
+            { "kind": "note", "position": [-8.0, -10.0], "text": "note-test" }
+        ],
+        "objectives": [
+            { "type": "kill", "count": 3, "archetype": "scout" },
+            { "type": "reach", "position": [0.0, 10.0], "radius": 2.0 }
+        ],
+        "next_level": "levels/next.level.json"
+    }"#;
+
+    #[test]
+    fn test_parse_level_file() {
+        let asset: LevelAsset = serde_json::from_str(LEVEL).unwrap();
+        let level = asset.to_layout("levels/test.level.json", &LevelBalance::default());
+
+        assert_eq!(
+            level.source,
+            LevelSource::File {
+                path: "levels/test.level.json".to_string()
+            }
+        );
+        assert_eq!(level.bounds, Rect::new(-19.0, -14.0, 19.0, 14.0));
+        assert_eq!(level.features.len(), 3);
+        assert_eq!(level.features[0].kind, LevelFeatureKind::Wall);
+        assert_eq!(level.features[0].shape, LevelFeatureShape::Box);
+        assert_eq!(level.features[2].shape, LevelFeatureShape::Cylinder);
+        // 省略的灯光使用默认值
+        assert_eq!(level.lights, LevelLights::default());
+
+        assert_eq!(level.enemy_placements[0].archetype, "scout");
+        assert_eq!(level.enemy_placements[0].patrol.len(), 2);
+        assert_eq!(level.item_placements[0].count, 2);
+        assert_eq!(level.item_placements[1].item, "knife");
+        assert_eq!(level.item_placements[1].count, 1);
+        assert_eq!(level.interactables.len(), 4);
+        assert_eq!(
+            level.interactables[0].kind,
+            InteractableKind::Door {
+                half_extents: Vec2::new(4.0, 0.3),
+                height: 3.0,
+                channel: Some("gate".to_string()),
+                open: false,
+            }
+        );
+        assert_eq!(
+            level.interactables[2].kind,
+            InteractableKind::Chest {
+                items: vec![("battery".to_string(), 3), ("medkit".to_string(), 1)]
+            }
+        );
+        assert_eq!(level.interactables[3].position, Vec2::new(-8.0, -10.0));
+        assert_eq!(
+            level.objectives[0],
+            ObjectiveKind::Kill {
+                count: 3,
+                archetype: Some("scout".to_string())
+            }
+        );
+        assert_eq!(level.next_level.as_deref(), Some("levels/next.level.json"));
+        assert_eq!(
+            level.enemy_spawns,
+            vec![Vec2::new(-15.0, 10.0), Vec2::new(15.0, 10.0)]
+        );
+
+        // 墙体不可行走，墙后经两端绕行可达
+        assert!(level.walkable.is_walkable(level.player_spawn));
+        assert!(!level.walkable.is_walkable(Vec2::new(0.0, 5.0)));
+        assert!(level.walkable.is_walkable(Vec2::new(0.0, 10.0)));
+    }
+
+    #[test]
+    fn test_bundled_levels() {
+        let levels = std::path::Path::new(env!("CARGO_MANIFEST_DIR")).join("../../assets/levels");
+        for entry in std::fs::read_dir(levels).unwrap() {
+            let path = entry.unwrap().path();
+            if !path.to_string_lossy().ends_with(".level.json") {
+                continue;
+            }
+            let content = std::fs::read_to_string(&path).unwrap();
+            let asset: LevelAsset = serde_json::from_str(&content)
+                .unwrap_or_else(|e| panic!("{}: {}", path.display(), e));
+            let level = asset.to_layout(&path.display().to_string(), &LevelBalance::default());
+
+            // 出生点、敌人、生成点、拾取物、可交互物体与目标区域都位于可行走区域（门不计入可行走区域的计算）
+            assert!(
+                level.walkable.is_walkable(level.player_spawn),
+                "{}",
+                path.display()
+            );
+            for point in level
+                .enemy_placements
+                .iter()
+                .flat_map(|enemy| {
+                    std::iter::once(enemy.position).chain(enemy.patrol.iter().copied())
+                })
+                .chain(level.enemy_spawns.iter().copied())
+                .chain(level.item_placements.iter().map(|item| item.position))
+                .chain(
+                    level
+                        .interactables
+                        .iter()
+                        .map(|interactable| interactable.position),
+                )
+                .chain(
+                    level
+                        .objectives
+                        .iter()
+                        .filter_map(|objective| match objective {
+                            ObjectiveKind::Reach { position, .. } => {
+                                Some(Vec2::from_array(*position))
+                            }
+                            _ => None,
+                        }),
+                )
+            {
+                assert!(
+                    level.walkable.is_walkable(point),
+                    "{}: {:?}",
+                    path.display(),
+                    point
+                );
+            }
+        }
+    }
+}
@@ -1,11 +1,30 @@
 //! Vigilant Doodle World - 世界生成系统
 //!
-//! 提供世界生成、地形管理和实体生成系统。
+//! 提供世界生成、地形管理、程序化关卡、关卡文件、碰撞层、分块加载、昼夜循环和实体生成系统。
 
 #![allow(clippy::type_complexity)]
 
+pub mod collision;
+pub mod collision_debug;
+pub mod collision_layer;
+pub mod day_night;
+pub mod level;
+pub mod level_file;
+pub mod obstacles;
 pub mod spawning;
+pub mod streaming;
 pub mod terrain;
 
 // 重新导出常用类型
+pub use collision::{ColliderShape, ConvexHull, ShapeHit, StaticCollider, WorldBoundary};
+pub use collision_debug::{CollisionDebugOverlay, CollisionDebugPlugin};
+pub use collision_layer::{CollisionLayer, CollisionLayerPlugin, MeshCollider};
+pub use day_night::{DayNightPlugin, SkyLighting, Sun, TimeOfDay};
+pub use level::{
+    generate_level, InteractableKind, InteractablePlacement, ItemPlacement, LevelChanged, LevelLayout,
+    LevelSet, LevelSource, LoadLevel,
+};
+pub use level_file::LevelAsset;
+pub use obstacles::StaticObstacle;
 pub use spawning::SpawningPlugin;
+pub use streaming::{Frozen, Streamed, StreamingFocus, StreamingPlugin, WorldChunks};
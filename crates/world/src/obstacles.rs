@@ -0,0 +1,40 @@
+//! 静态障碍物
+//!
+//! 定义关卡布局之外的静态障碍物及其水平占地范围，供导航网格生成使用
+//! （关卡物体的占地直接取自 `LevelLayout::features`，不随区块卸载）。
+//! 碰撞由同一实体上的 `StaticCollider` 负责。
+
+use bevy::prelude::*;
+
+/// 静态障碍物组件
+///
+/// 障碍物占地为以 `Transform` 平移为中心、轴对齐的矩形（XZ 平面）
+#[derive(Component, Debug, Clone, Copy)]
+pub struct StaticObstacle {
+    /// XZ 平面上的半尺寸（x 对应世界 X，y 对应世界 Z）
+    pub half_extents: Vec2,
+    /// 高度
+    pub height: f32,
+    /// 是否阻挡导航（如打开的门保留组件但不阻挡）
+    pub enabled: bool,
+}
+
+impl StaticObstacle {
+    pub fn new(half_extents: Vec2, height: f32) -> Self {
+        Self {
+            half_extents,
+            height,
+            enabled: true,
+        }
+    }
+
+    pub fn with_enabled(mut self, enabled: bool) -> Self {
+        self.enabled = enabled;
+        self
+    }
+
+    /// 计算障碍物在 XZ 平面上的占地矩形
+    pub fn footprint(&self, translation: Vec3) -> Rect {
+        Rect::from_center_half_size(Vec2::new(translation.x, translation.z), self.half_extents)
+    }
+}
@@ -1,70 +1,310 @@
+use vigilant_doodle_core::args;
+use vigilant_doodle_core::rng::GameRng;
 use vigilant_doodle_core::state::GameState;
-use crate::terrain::{FLOOR_LENGTH, FLOOR_WIDTH};
+use vigilant_doodle_core::BalanceConfig;
+use crate::collision::{boundary_colliders, StaticCollider, WorldBoundary};
+use crate::collision_layer::MeshCollider;
+use crate::day_night::Sun;
+use crate::level::{
+    generate_level, LevelChanged, LevelEntity, LevelFeatureKind, LevelFeatureShape, LevelLayout,
+    LevelLights, LevelSet, LevelSource, LoadLevel, LEVEL_RNG_STREAM,
+};
+use crate::level_file::{LevelAsset, LevelAssetLoader};
 use bevy::prelude::*;
+use rand::Rng;
+
+/// 关卡文件命令行参数（相对 assets 目录的路径）
+pub const LEVEL_ARG: &str = "--level";
 
 pub struct SpawningPlugin;
 
 impl Plugin for SpawningPlugin {
     fn build(&self, app: &mut App) {
-        app
-            // 在资源加载完成后立即生成所有实体
+        app.init_resource::<LevelLayout>()
+            .init_resource::<ActiveLevel>()
+            .init_asset::<LevelAsset>()
+            .register_asset_loader(LevelAssetLoader)
+            .add_message::<LoadLevel>()
+            .add_message::<LevelChanged>()
+            // 关卡在平衡配置加载后请求（命令行指定关卡文件，否则取全局随机数作为种子）
+            .add_systems(OnExit(GameState::AssetLoading), request_initial_level)
             .add_systems(
-                OnEnter(GameState::AssetLoading),
-                (spawn_terrain, spawn_lights).chain(),
+                Update,
+                (begin_level_load, resolve_level, respawn_level_entities)
+                    .chain()
+                    .in_set(LevelSet),
             );
     }
 }
 
-fn spawn_terrain(
+/// 关卡加载状态
+#[derive(Resource, Default)]
+struct ActiveLevel {
+    /// 当前关卡文件句柄（程序化关卡为 None）
+    handle: Option<Handle<LevelAsset>>,
+    /// 等待构建的请求（关卡文件尚未加载完成）
+    pending: Option<LoadLevel>,
+}
+
+/// 请求初始关卡
+fn request_initial_level(mut game_rng: ResMut<GameRng>, mut load_level: MessageWriter<LoadLevel>) {
+    let source = match args::value(LEVEL_ARG) {
+        Some(path) => LevelSource::File { path },
+        None => LevelSource::Procedural {
+            seed: game_rng.stream(LEVEL_RNG_STREAM).random(),
+        },
+    };
+    load_level.write(LoadLevel {
+        source,
+        respawn: true,
+    });
+}
+
+/// 接收加载请求（关卡相同且无需重置时忽略）
+fn begin_level_load(
+    mut requests: MessageReader<LoadLevel>,
+    mut active: ResMut<ActiveLevel>,
+    asset_server: Res<AssetServer>,
+    layout: Res<LevelLayout>,
+) {
+    let Some(request) = requests.read().last().cloned() else {
+        return;
+    };
+    if !request.respawn && active.pending.is_none() && request.source == layout.source {
+        return;
+    }
+
+    active.handle = match &request.source {
+        LevelSource::File { path } => Some(asset_server.load(path.clone())),
+        LevelSource::Procedural { .. } => None,
+    };
+    info!("[World] Loading level {:?}", request.source);
+    active.pending = Some(request);
+}
+
+/// 构建等待中的关卡，或在关卡文件修改后热重载
+fn resolve_level(
+    mut active: ResMut<ActiveLevel>,
+    mut asset_events: MessageReader<AssetEvent<LevelAsset>>,
+    asset_server: Res<AssetServer>,
+    level_assets: Res<Assets<LevelAsset>>,
+    balance: Res<BalanceConfig>,
+    mut layout: ResMut<LevelLayout>,
+    mut changed: MessageWriter<LevelChanged>,
+) {
+    let modified = asset_events.read().any(|event| {
+        active
+            .handle
+            .as_ref()
+            .is_some_and(|handle| event.is_modified(handle))
+    });
+
+    let (source, respawn) = match &active.pending {
+        Some(request) => (request.source.clone(), request.respawn),
+        None if modified => (layout.source.clone(), false),
+        None => return,
+    };
+
+    let new_layout = match &source {
+        LevelSource::Procedural { seed } => generate_level(*seed, &balance.level),
+        LevelSource::File { path } => {
+            let Some(handle) = &active.handle else {
+                return;
+            };
+            match level_assets.get(handle) {
+                Some(asset) => asset.to_layout(path, &balance.level),
+                None => {
+                    if asset_server.load_state(handle).is_failed() {
+                        error!("[World] Failed to load level file {}", path);
+                        active.pending = None;
+                    }
+                    return;
+                }
+            }
+        }
+    };
+
+    active.pending = None;
+    *layout = new_layout;
+    changed.write(LevelChanged { respawn });
+}
+
+/// 关卡物体与地形共用的材质（关卡变化时重新创建，分块加载时复用）
+#[derive(Resource)]
+pub(crate) struct LevelMaterials {
+    pub terrain: Handle<StandardMaterial>,
+    wall: Handle<StandardMaterial>,
+    obstacle: Handle<StandardMaterial>,
+    prop: Handle<StandardMaterial>,
+}
+
+/// 关卡变化后重新生成关卡实体
+///
+/// 这里只生成边界墙与灯光，地形与物体由 `streaming` 按区块加载
+fn respawn_level_entities(
     mut commands: Commands,
-    mut meshes: ResMut<Assets<Mesh>>,
+    mut changed: MessageReader<LevelChanged>,
     mut materials: ResMut<Assets<StandardMaterial>>,
+    layout: Res<LevelLayout>,
+    level_entities: Query<Entity, With<LevelEntity>>,
 ) {
-    commands.spawn((
-        Mesh3d(
-            meshes.add(
-                Plane3d::default()
-                    .mesh()
-                    .size(FLOOR_LENGTH, FLOOR_WIDTH)
-                    .build(),
-            ),
-        ),
-        MeshMaterial3d(materials.add(StandardMaterial {
-            base_color: Color::srgb(0.3, 0.3, 0.35),
-            perceptual_roughness: 0.8,
+    if changed.read().last().is_none() {
+        return;
+    }
+
+    for entity in level_entities.iter() {
+        commands.entity(entity).despawn();
+    }
+
+    let mut material = |color: Color, perceptual_roughness: f32| {
+        materials.add(StandardMaterial {
+            base_color: color,
+            perceptual_roughness,
             ..default()
-        })),
-        Transform::from_xyz(0.0, -0.5, 0.0),
-        Name::new("Floor"),
-    ));
+        })
+    };
+    commands.insert_resource(LevelMaterials {
+        terrain: material(layout.floor.color, 0.8),
+        wall: material(Color::srgb(0.45, 0.42, 0.4), 0.9),
+        obstacle: material(Color::srgb(0.38, 0.4, 0.42), 0.9),
+        prop: material(Color::srgb(0.5, 0.38, 0.25), 0.9),
+    });
+
+    spawn_boundaries(&mut commands, &layout);
+    spawn_lights(&mut commands, &layout.lights);
+
+    info!(
+        "[World] Level '{}' spawned ({}x{}, {} features, {} enemy spawns)",
+        layout.name,
+        layout.floor.size.x,
+        layout.floor.size.y,
+        layout.features.len(),
+        layout.enemy_spawns.len()
+    );
+}
+
+/// 生成边界墙（不可见，常驻；注册到碰撞层并决定世界边界）
+///
+/// 地形碰撞由角色控制器直接查询 `LevelLayout::terrain`，不随区块卸载
+fn spawn_boundaries(commands: &mut Commands, layout: &LevelLayout) {
+    for (i, (transform, collider)) in boundary_colliders(&layout.terrain).into_iter().enumerate() {
+        commands.spawn((
+            transform,
+            collider,
+            WorldBoundary,
+            LevelEntity,
+            Name::new(format!("BoundaryCollider_{}", i)),
+        ));
+    }
+}
 
-    info!("[World] Terrain spawned ({}x{})", FLOOR_LENGTH, FLOOR_WIDTH);
+/// 生成关卡物体 `layout.features[index]`
+///
+/// 作为区块根实体 `parent` 的子实体；区块根位于原点，局部坐标即世界坐标
+pub(crate) fn spawn_feature(
+    commands: &mut Commands,
+    meshes: &mut Assets<Mesh>,
+    materials: &LevelMaterials,
+    asset_server: &AssetServer,
+    layout: &LevelLayout,
+    index: usize,
+    parent: Entity,
+) {
+    let feature = &layout.features[index];
+    let size = feature.half_extents * 2.0;
+    // 坡面上的物体底部沉到占地范围内的最低点，顶部高度按最高点计
+    let footprint = feature.footprint();
+    let samples = [
+        footprint.center(),
+        footprint.min,
+        footprint.max,
+        Vec2::new(footprint.min.x, footprint.max.y),
+        Vec2::new(footprint.max.x, footprint.min.y),
+    ]
+    .map(|point| layout.terrain.height_at(point));
+    let base = samples.iter().copied().fold(f32::INFINITY, f32::min);
+    let top = samples.iter().copied().fold(f32::NEG_INFINITY, f32::max);
+    let height = feature.height + (top - base);
+    let (material, name) = match feature.kind {
+        LevelFeatureKind::Wall => (&materials.wall, "Wall"),
+        LevelFeatureKind::Obstacle => (&materials.obstacle, "Obstacle"),
+        LevelFeatureKind::Prop => (&materials.prop, "Prop"),
+    };
+
+    // 底部贴合地面
+    let transform = Transform::from_xyz(feature.center.x, base + height * 0.5, feature.center.y);
+    let common = (ChildOf(parent), Name::new(format!("{}_{}", name, index)));
+    match &feature.shape {
+        LevelFeatureShape::Box => {
+            commands.spawn((
+                Mesh3d(meshes.add(Cuboid::new(size.x, height, size.y))),
+                MeshMaterial3d(material.clone()),
+                transform,
+                StaticCollider::cuboid(Vec3::new(
+                    feature.half_extents.x,
+                    height * 0.5,
+                    feature.half_extents.y,
+                )),
+                common,
+            ));
+        }
+        LevelFeatureShape::Cylinder => {
+            let radius = feature.half_extents.min_element();
+            commands.spawn((
+                Mesh3d(meshes.add(Cylinder::new(radius, height))),
+                MeshMaterial3d(material.clone()),
+                transform,
+                StaticCollider::cylinder(radius, height * 0.5),
+                common,
+            ));
+        }
+        LevelFeatureShape::Model(path) => {
+            // 模型原点位于底部，碰撞体在场景加载后由网格凸包生成
+            commands.spawn((
+                SceneRoot(asset_server.load(GltfAssetLabel::Scene(0).from_asset(path.clone()))),
+                Transform::from_xyz(feature.center.x, base, feature.center.y),
+                MeshCollider,
+                common,
+            ));
+        }
+    }
 }
 
-fn spawn_lights(mut commands: Commands) {
+fn spawn_lights(commands: &mut Commands, lights: &LevelLights) {
     // 环境光
     commands.insert_resource(AmbientLight {
-        color: Color::srgb(0.8, 0.8, 1.0),
-        brightness: 200.0,
+        color: Color::srgb_from_array(lights.ambient_color),
+        brightness: lights.ambient_brightness,
         affects_lightmapped_meshes: true,
     });
 
-    // 主方向光（太阳光）
-    commands.spawn((
-        DirectionalLight {
-            color: Color::srgb(1.0, 0.95, 0.9),
-            illuminance: 5000.0,
-            shadows_enabled: true,
-            ..default()
-        },
-        Transform::from_rotation(Quat::from_euler(
-            EulerRot::XYZ,
-            -std::f32::consts::FRAC_PI_4, // -45 度俯角
-            std::f32::consts::FRAC_PI_4,  // 45 度侧向
-            0.0,
-        )),
-        Name::new("SunLight"),
-    ));
-
-    info!("[World] Lights spawned (ambient + directional)");
+    // 主方向光（太阳光，由昼夜循环转动并调整颜色与照度）
+    if let Some(sun) = &lights.sun {
+        commands.spawn((
+            DirectionalLight {
+                color: Color::srgb_from_array(sun.color),
+                illuminance: sun.illuminance,
+                shadows_enabled: sun.shadows,
+                ..default()
+            },
+            Transform::from_rotation(sun.rotation()),
+            Sun,
+            LevelEntity,
+            Name::new("SunLight"),
+        ));
+    }
+
+    for (i, point) in lights.points.iter().enumerate() {
+        commands.spawn((
+            PointLight {
+                color: Color::srgb_from_array(point.color),
+                intensity: point.intensity,
+                range: point.range,
+                ..default()
+            },
+            Transform::from_translation(Vec3::from_array(point.position)),
+            LevelEntity,
+            Name::new(format!("PointLight_{}", i)),
+        ));
+    }
 }
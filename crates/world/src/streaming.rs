@@ -0,0 +1,382 @@
+//! 分块加载
+//!
+//! 地形与关卡物体按正方形区块划分（区块尺寸见平衡配置 `level.streaming`）：
+//! - 与 `StreamingFocus`（玩家）距离在加载半径内、或与相机视野在地面上的范围相交的区块加载
+//! - 超出卸载半径且不在视野内的区块卸载（连同其物体与碰撞体）
+//! - 区块的物体在加载时立即生成，地形网格在异步任务中生成，完成后挂到区块上
+//! - 物体按中心所在区块归属；加载半径应大于最长物体的半长，保证附近物体的碰撞体已加载
+//!
+//! 地形碰撞、导航网格与边界墙使用关卡布局数据，不随区块卸载。
+//! 带 `Streamed` 的实体（敌人）位于未加载区块时被冻结（`Frozen`，隐藏且不移动），
+//! 区块重新加载后恢复。
+
+use std::sync::Arc;
+
+use bevy::platform::collections::{HashMap, HashSet};
+use bevy::prelude::*;
+use bevy::tasks::futures::check_ready;
+use bevy::tasks::{AsyncComputeTaskPool, Task};
+use vigilant_doodle_core::BalanceConfig;
+use vigilant_doodle_core::balance::StreamingBalance;
+
+use crate::level::{LevelChanged, LevelEntity, LevelLayout, LevelSet};
+use crate::spawning::{spawn_feature, LevelMaterials};
+use crate::terrain::Heightfield;
+
+/// 相机视线与地面求交的最远距离（视线接近水平时）
+const MAX_VIEW_DISTANCE: f32 = 200.0;
+/// 每帧最多加载的区块数（由近到远）
+const MAX_LOADS_PER_FRAME: usize = 4;
+
+/// 区块加载中心（玩家）
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct StreamingFocus;
+
+/// 所在区块卸载时冻结的实体
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Streamed;
+
+/// 冻结标记：实体所在区块未加载，角色控制器与分离不再移动它
+#[derive(Component, Debug, Clone, Copy, Default)]
+pub struct Frozen;
+
+/// 区块根实体（位于原点，子实体为地形网格与物体）
+#[derive(Component, Debug, Clone, Copy)]
+pub struct Chunk {
+    pub coord: IVec2,
+}
+
+/// 区块划分与加载状态
+#[derive(Resource, Debug, Default)]
+pub struct WorldChunks {
+    chunk_size: f32,
+    /// 地形覆盖的区块范围（含两端）
+    first: IVec2,
+    last: IVec2,
+    /// 区块 -> 物体索引（`LevelLayout::features`）
+    features: HashMap<IVec2, Vec<usize>>,
+    /// 已加载区块的根实体
+    loaded: HashMap<IVec2, Entity>,
+    /// 正在生成的地形网格
+    pending: HashMap<IVec2, Task<Mesh>>,
+    terrain: Option<Arc<Heightfield>>,
+}
+
+impl WorldChunks {
+    /// 按关卡布局重新划分区块（已加载的区块全部作废）
+    pub fn reset(&mut self, layout: &LevelLayout, config: &StreamingBalance) {
+        self.chunk_size = config.chunk_size.max(1.0);
+        let (origin, size) = (layout.terrain.origin(), layout.terrain.size());
+        self.first = self.chunk_of(origin);
+        self.last =
+            (((origin + size) / self.chunk_size).ceil().as_ivec2() - IVec2::ONE).max(self.first);
+        self.features.clear();
+        for (index, feature) in layout.features.iter().enumerate() {
+            let coord = self.chunk_of(feature.center);
+            self.features.entry(coord).or_default().push(index);
+        }
+        self.loaded.clear();
+        self.pending.clear();
+        self.terrain = Some(Arc::new(layout.terrain.clone()));
+    }
+
+    /// 点所在的区块
+    pub fn chunk_of(&self, point: Vec2) -> IVec2 {
+        (point / self.chunk_size).floor().as_ivec2()
+    }
+
+    /// 区块范围（XZ）
+    pub fn chunk_rect(&self, coord: IVec2) -> Rect {
+        let min = coord.as_vec2() * self.chunk_size;
+        Rect::from_corners(min, min + Vec2::splat(self.chunk_size))
+    }
+
+    /// 点所在区块是否已加载（地形范围外视为已加载，不冻结）
+    pub fn is_loaded(&self, point: Vec2) -> bool {
+        let coord = self.chunk_of(point);
+        !self.contains(coord) || self.loaded.contains_key(&coord)
+    }
+
+    /// 已加载的区块数
+    pub fn loaded_count(&self) -> usize {
+        self.loaded.len()
+    }
+
+    fn contains(&self, coord: IVec2) -> bool {
+        coord.cmpge(self.first).all() && coord.cmple(self.last).all()
+    }
+
+    /// 与任一加载中心距离不超过 `radius`，或与视野范围相交的区块，按与最近中心的距离排序
+    pub fn chunks_in_range(&self, focuses: &[Vec2], view: Option<Rect>, radius: f32) -> Vec<IVec2> {
+        let mut chunks = Vec::new();
+        for x in self.first.x..=self.last.x {
+            for z in self.first.y..=self.last.y {
+                let coord = IVec2::new(x, z);
+                let rect = self.chunk_rect(coord);
+                let distance = focuses
+                    .iter()
+                    .map(|focus| focus.clamp(rect.min, rect.max).distance(*focus))
+                    .fold(f32::INFINITY, f32::min);
+                let in_view = view.is_some_and(|view| !view.intersect(rect).is_empty());
+                if distance <= radius || in_view {
+                    chunks.push((distance, coord));
+                }
+            }
+        }
+        chunks.sort_by(|a, b| {
+            a.0.total_cmp(&b.0)
+                .then(a.1.to_array().cmp(&b.1.to_array()))
+        });
+        chunks.into_iter().map(|(_, coord)| coord).collect()
+    }
+}
+
+/// 分块加载插件
+pub struct StreamingPlugin;
+
+impl Plugin for StreamingPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<WorldChunks>().add_systems(
+            Update,
+            (
+                reset_chunks,
+                update_chunks.run_if(resource_exists::<LevelMaterials>),
+                finish_chunk_meshes.run_if(resource_exists::<LevelMaterials>),
+                freeze_streamed_entities,
+            )
+                .chain()
+                .after(LevelSet),
+        );
+    }
+}
+
+/// 关卡变化后重新划分区块（旧区块实体已作为关卡实体被移除）
+fn reset_chunks(
+    mut changed: MessageReader<LevelChanged>,
+    mut chunks: ResMut<WorldChunks>,
+    layout: Res<LevelLayout>,
+    balance: Res<BalanceConfig>,
+) {
+    if changed.read().last().is_none() {
+        return;
+    }
+    chunks.reset(&layout, &balance.level.streaming);
+}
+
+/// 按加载中心与相机视野加载、卸载区块
+#[allow(clippy::too_many_arguments)]
+fn update_chunks(
+    mut commands: Commands,
+    mut chunks: ResMut<WorldChunks>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    materials: Res<LevelMaterials>,
+    asset_server: Res<AssetServer>,
+    layout: Res<LevelLayout>,
+    balance: Res<BalanceConfig>,
+    focus_query: Query<&GlobalTransform, With<StreamingFocus>>,
+    camera_query: Query<(&Camera, &GlobalTransform), With<Camera3d>>,
+) {
+    let Some(terrain) = chunks.terrain.clone() else {
+        return;
+    };
+    let config = &balance.level.streaming;
+    let focuses: Vec<Vec2> = focus_query
+        .iter()
+        .map(|transform| transform.translation().xz())
+        .collect();
+    let ground = terrain.height_range().0;
+    let view = camera_query
+        .iter()
+        .filter(|(camera, _)| camera.is_active)
+        .filter_map(|(camera, transform)| view_footprint(camera, transform, ground))
+        .reduce(|a, b| a.union(b))
+        .map(|view| view.inflate(config.frustum_margin));
+
+    // 卸载（卸载半径大于加载半径，边界附近不会反复加载）
+    let keep: HashSet<IVec2> = chunks
+        .chunks_in_range(&focuses, view, config.unload_radius.max(config.load_radius))
+        .into_iter()
+        .collect();
+    let unload: Vec<IVec2> = chunks
+        .loaded
+        .keys()
+        .copied()
+        .filter(|coord| !keep.contains(coord))
+        .collect();
+    for coord in unload {
+        if let Some(root) = chunks.loaded.remove(&coord) {
+            commands.entity(root).despawn();
+        }
+        chunks.pending.remove(&coord);
+    }
+
+    // 加载（由近到远，每帧有上限）
+    let load: Vec<IVec2> = chunks
+        .chunks_in_range(&focuses, view, config.load_radius)
+        .into_iter()
+        .filter(|coord| !chunks.loaded.contains_key(coord))
+        .take(MAX_LOADS_PER_FRAME)
+        .collect();
+    for coord in load {
+        let root = commands
+            .spawn((
+                Chunk { coord },
+                Transform::IDENTITY,
+                Visibility::default(),
+                LevelEntity,
+                Name::new(format!("Chunk_{}_{}", coord.x, coord.y)),
+            ))
+            .id();
+        for &index in chunks.features.get(&coord).into_iter().flatten() {
+            spawn_feature(
+                &mut commands,
+                &mut meshes,
+                &materials,
+                &asset_server,
+                &layout,
+                index,
+                root,
+            );
+        }
+
+        let terrain = terrain.clone();
+        let rect = chunks.chunk_rect(coord);
+        let task = AsyncComputeTaskPool::get().spawn(async move { terrain.region_mesh(rect) });
+        chunks.pending.insert(coord, task);
+        chunks.loaded.insert(coord, root);
+    }
+}
+
+/// 地形网格生成完成后挂到区块上
+fn finish_chunk_meshes(
+    mut commands: Commands,
+    mut chunks: ResMut<WorldChunks>,
+    mut meshes: ResMut<Assets<Mesh>>,
+    materials: Res<LevelMaterials>,
+) {
+    let chunks = &mut *chunks;
+    chunks.pending.retain(|coord, task| {
+        let Some(mesh) = check_ready(task) else {
+            return true;
+        };
+        if let Some(&root) = chunks.loaded.get(coord) {
+            commands.spawn((
+                Mesh3d(meshes.add(mesh)),
+                MeshMaterial3d(materials.terrain.clone()),
+                Transform::IDENTITY,
+                ChildOf(root),
+                Name::new("Terrain"),
+            ));
+        }
+        false
+    });
+}
+
+/// 冻结位于未加载区块的实体，区块加载后恢复
+fn freeze_streamed_entities(
+    mut commands: Commands,
+    chunks: Res<WorldChunks>,
+    query: Query<(Entity, &Transform, Has<Frozen>), With<Streamed>>,
+) {
+    if chunks.terrain.is_none() {
+        return;
+    }
+    for (entity, transform, frozen) in query.iter() {
+        let loaded = chunks.is_loaded(transform.translation.xz());
+        if frozen && loaded {
+            commands
+                .entity(entity)
+                .remove::<Frozen>()
+                .insert(Visibility::Inherited);
+        } else if !frozen && !loaded {
+            commands.entity(entity).insert((Frozen, Visibility::Hidden));
+        }
+    }
+}
+
+/// 相机视野在高度 `ground` 的地面上覆盖的范围（XZ）
+fn view_footprint(camera: &Camera, transform: &GlobalTransform, ground: f32) -> Option<Rect> {
+    let size = camera.logical_viewport_size()?;
+    let mut footprint: Option<Rect> = None;
+    for corner in [
+        Vec2::ZERO,
+        Vec2::new(size.x, 0.0),
+        size,
+        Vec2::new(0.0, size.y),
+    ] {
+        let ray = camera.viewport_to_world(transform, corner).ok()?;
+        let distance = ray
+            .intersect_plane(Vec3::Y * ground, InfinitePlane3d::new(Vec3::Y))
+            .map_or(MAX_VIEW_DISTANCE, |distance| {
+                distance.min(MAX_VIEW_DISTANCE)
+            });
+        let point = ray.get_point(distance).xz();
+        footprint = Some(
+            footprint.map_or(Rect::from_center_size(point, Vec2::ZERO), |rect| {
+                rect.union_point(point)
+            }),
+        );
+    }
+    footprint
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn chunks() -> WorldChunks {
+        let layout = LevelLayout {
+            terrain: Heightfield::flat(Vec2::new(128.0, 64.0), 0.0),
+            ..default()
+        };
+        let mut chunks = WorldChunks::default();
+        chunks.reset(
+            &layout,
+            &StreamingBalance {
+                chunk_size: 32.0,
+                ..default()
+            },
+        );
+        chunks
+    }
+
+    #[test]
+    fn test_chunks_in_range() {
+        let chunks = chunks();
+        // 128x64 的地形划分为 4x2 个区块
+        assert_eq!(chunks.first, IVec2::new(-2, -1));
+        assert_eq!(chunks.last, IVec2::new(1, 0));
+
+        // 由近到远排序
+        let near = chunks.chunks_in_range(&[Vec2::new(40.0, 10.0)], None, 20.0);
+        assert_eq!(
+            near,
+            vec![
+                IVec2::new(1, 0),
+                IVec2::new(0, 0),
+                IVec2::new(1, -1),
+                IVec2::new(0, -1)
+            ]
+        );
+
+        // 视野范围内的区块即使远离加载中心也加载
+        let view = Rect::new(-60.0, -20.0, -50.0, -10.0);
+        let with_view = chunks.chunks_in_range(&[Vec2::new(40.0, 10.0)], Some(view), 20.0);
+        assert_eq!(with_view.len(), 5);
+        assert!(with_view.contains(&IVec2::new(-2, -1)));
+
+        // 没有加载中心与视野时不加载
+        assert!(chunks.chunks_in_range(&[], None, 100.0).is_empty());
+    }
+
+    #[test]
+    fn test_unloaded_chunks_freeze_entities() {
+        let mut chunks = chunks();
+        chunks.loaded.insert(IVec2::new(0, 0), Entity::PLACEHOLDER);
+
+        assert!(chunks.is_loaded(Vec2::new(5.0, 5.0)));
+        assert!(!chunks.is_loaded(Vec2::new(-5.0, 5.0)));
+        // 地形范围外不冻结
+        assert!(chunks.is_loaded(Vec2::new(500.0, 5.0)));
+    }
+}
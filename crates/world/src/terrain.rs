@@ -1,6 +1,470 @@
-/// 地形边界常量
+//! 高度图地形
+//!
+//! 地面由规则网格上的高度采样（`Heightfield`）描述，来源：
+//! - 程序化关卡：按种子生成的分形值噪声，玩家出生点周围压平
+//! - 关卡文件：灰度高度图图片，或指定噪声种子；都未给出时为平地
+//!
+//! 每个格子沿对角线分成两个三角形，高度查询、法线与生成的网格使用同一组三角形，
+//! 角色站立的高度与看到的地表完全一致。角色控制器通过球体投射与穿透检测与地形碰撞。
+
+use bevy::asset::RenderAssetUsages;
+use bevy::mesh::{Indices, PrimitiveTopology};
+use bevy::prelude::*;
+use vigilant_doodle_core::balance::TerrainBalance;
+
+use crate::collision::{ShapeHit, FLOOR_TOP};
+
+/// 地形边界常量（程序化关卡的默认地面）
 pub const FLOOR_HALF_LENGTH: f32 = 74.0; // 150/2 - 1 (留边距)
 pub const FLOOR_HALF_WIDTH: f32 = 36.5; // 75/2 - 1
 
 pub const FLOOR_LENGTH: f32 = 150.0;
 pub const FLOOR_WIDTH: f32 = 75.0;
+
+/// 可活动范围距地面边缘的边距
+pub const FLOOR_MARGIN: f32 = 1.0;
+
+/// 角色原点距地表的高度（与角色控制器默认的 `foot_offset` 一致）
+pub const GROUND_CLEARANCE: f32 = 0.5;
+
+/// 球体投射的二分细化次数
+const CAST_REFINE_ITERATIONS: usize = 8;
+/// 球体投射的最小步长
+const MIN_CAST_STEP: f32 = 0.05;
+
+/// 高度图地形
+#[derive(Debug, Clone, PartialEq)]
+pub struct Heightfield {
+    /// 网格左下角（最小 XZ）
+    origin: Vec2,
+    /// 格子尺寸（XZ）
+    cell: Vec2,
+    columns: usize,
+    rows: usize,
+    /// 顶点高度（世界 Y），`(columns + 1) * (rows + 1)` 个，按行存储
+    heights: Vec<f32>,
+    min_height: f32,
+    max_height: f32,
+}
+
+impl Heightfield {
+    /// 由函数采样生成，`size` 为地形尺寸（XZ，以原点为中心）
+    pub fn from_fn(size: Vec2, cell_size: f32, mut height: impl FnMut(Vec2) -> f32) -> Self {
+        let columns = (size.x / cell_size).ceil().max(1.0) as usize;
+        let rows = (size.y / cell_size).ceil().max(1.0) as usize;
+        let origin = -size * 0.5;
+        let cell = size / Vec2::new(columns as f32, rows as f32);
+
+        let mut heights = Vec::with_capacity((columns + 1) * (rows + 1));
+        for row in 0..=rows {
+            for column in 0..=columns {
+                heights.push(height(origin + Vec2::new(column as f32, row as f32) * cell));
+            }
+        }
+        Self::from_heights(origin, cell, columns, rows, heights)
+    }
+
+    /// 高度为 `height` 的平地
+    pub fn flat(size: Vec2, height: f32) -> Self {
+        Self::from_fn(size, size.max_element().max(1.0), |_| height)
+    }
+
+    /// 分形值噪声地形：高度在 `FLOOR_TOP ± amplitude` 之间起伏
+    pub fn from_noise(size: Vec2, seed: u64, config: &TerrainBalance) -> Self {
+        Self::from_fn(size, config.cell_size, |point| {
+            FLOOR_TOP + fractal_noise(seed, point, config) * config.amplitude
+        })
+    }
+
+    /// 由灰度采样生成：`values` 为 `width * height` 个 0..1 的值（按行，第一行对应 -Z 边缘），
+    /// 0 为 `FLOOR_TOP`，1 高出 `height_scale`
+    pub fn from_samples(
+        size: Vec2,
+        width: usize,
+        height: usize,
+        values: &[f32],
+        height_scale: f32,
+    ) -> Self {
+        let columns = width.max(2) - 1;
+        let rows = height.max(2) - 1;
+        let origin = -size * 0.5;
+        let cell = size / Vec2::new(columns as f32, rows as f32);
+
+        let mut heights = Vec::with_capacity((columns + 1) * (rows + 1));
+        for row in 0..=rows {
+            for column in 0..=columns {
+                let value = values
+                    .get(
+                        row.min(height.saturating_sub(1)) * width
+                            + column.min(width.saturating_sub(1)),
+                    )
+                    .copied()
+                    .unwrap_or(0.0);
+                heights.push(FLOOR_TOP + value.clamp(0.0, 1.0) * height_scale);
+            }
+        }
+        Self::from_heights(origin, cell, columns, rows, heights)
+    }
+
+    fn from_heights(
+        origin: Vec2,
+        cell: Vec2,
+        columns: usize,
+        rows: usize,
+        heights: Vec<f32>,
+    ) -> Self {
+        let (min_height, max_height) = heights
+            .iter()
+            .fold((f32::INFINITY, f32::NEG_INFINITY), |(min, max), &h| {
+                (min.min(h), max.max(h))
+            });
+        Self {
+            origin,
+            cell,
+            columns,
+            rows,
+            heights,
+            min_height,
+            max_height,
+        }
+    }
+
+    /// 地形最小角（XZ）
+    pub fn origin(&self) -> Vec2 {
+        self.origin
+    }
+
+    /// 地形尺寸（XZ）
+    pub fn size(&self) -> Vec2 {
+        self.cell * Vec2::new(self.columns as f32, self.rows as f32)
+    }
+
+    /// 最低与最高高度
+    pub fn height_range(&self) -> (f32, f32) {
+        (self.min_height, self.max_height)
+    }
+
+    fn vertex(&self, column: usize, row: usize) -> f32 {
+        self.heights[row * (self.columns + 1) + column]
+    }
+
+    /// 点所在的格子与格内坐标（0..1），范围外的点取最近的边缘
+    fn locate(&self, point: Vec2) -> (usize, usize, Vec2) {
+        let local = ((point - self.origin) / self.cell)
+            .clamp(Vec2::ZERO, Vec2::new(self.columns as f32, self.rows as f32));
+        let column = (local.x as usize).min(self.columns - 1);
+        let row = (local.y as usize).min(self.rows - 1);
+        (column, row, local - Vec2::new(column as f32, row as f32))
+    }
+
+    /// 点所在三角形的高度与沿 X、Z 的斜率
+    fn sample(&self, point: Vec2) -> (f32, Vec2) {
+        let (column, row, f) = self.locate(point);
+        let h00 = self.vertex(column, row);
+        let h10 = self.vertex(column + 1, row);
+        let h01 = self.vertex(column, row + 1);
+        let h11 = self.vertex(column + 1, row + 1);
+
+        // 沿 (0,0)-(1,1) 对角线分成两个三角形
+        let (dx, dz) = if f.x >= f.y {
+            (h10 - h00, h11 - h10)
+        } else {
+            (h11 - h01, h01 - h00)
+        };
+        (h00 + f.x * dx + f.y * dz, Vec2::new(dx, dz) / self.cell)
+    }
+
+    /// 地表高度（世界 Y）
+    pub fn height_at(&self, point: Vec2) -> f32 {
+        self.sample(point).0
+    }
+
+    /// 地表法线
+    pub fn normal_at(&self, point: Vec2) -> Vec3 {
+        let slope = self.sample(point).1;
+        Vec3::new(-slope.x, 1.0, -slope.y).normalize()
+    }
+
+    /// 将位置限制在地形范围内（边缘内缩边距）并放到地表上（生成、传送角色时使用）
+    pub fn clamp_to_terrain(&self, position: Vec3) -> Vec3 {
+        let half = (self.size() * 0.5 - FLOOR_MARGIN).max(Vec2::ZERO);
+        let center = self.origin + self.size() * 0.5;
+        let point = position.xz().clamp(center - half, center + half);
+        Vec3::new(point.x, self.height_at(point) + GROUND_CLEARANCE, point.y)
+    }
+
+    /// 将 `center` 周围 `radius` 内压平为中心高度，向外 `radius` 内平滑过渡
+    pub fn flatten(&mut self, center: Vec2, radius: f32) {
+        if radius <= 0.0 {
+            return;
+        }
+        let target = self.height_at(center);
+        for row in 0..=self.rows {
+            for column in 0..=self.columns {
+                let point = self.origin + Vec2::new(column as f32, row as f32) * self.cell;
+                let t = ((point.distance(center) - radius) / radius).clamp(0.0, 1.0);
+                let blend = t * t * (3.0 - 2.0 * t);
+                let index = row * (self.columns + 1) + column;
+                self.heights[index] = target + (self.heights[index] - target) * blend;
+            }
+        }
+        *self = Self::from_heights(
+            self.origin,
+            self.cell,
+            self.columns,
+            self.rows,
+            std::mem::take(&mut self.heights),
+        );
+    }
+
+    /// 球心到地表切平面的有符号距离减去半径（负数表示重叠）
+    fn clearance(&self, center: Vec3, radius: f32) -> f32 {
+        let normal = self.normal_at(center.xz());
+        (center.y - self.height_at(center.xz())) * normal.y - radius
+    }
+
+    /// 球体投射（与 `StaticCollider::sphere_cast` 约定一致）
+    ///
+    /// 沿路径步进找到首次接触，再二分细化；起点已与地表重叠时不视为命中
+    pub fn sphere_cast(
+        &self,
+        origin: Vec3,
+        direction: Vec3,
+        max_distance: f32,
+        radius: f32,
+    ) -> Option<ShapeHit> {
+        // 整段路径都高于最高点时直接跳过
+        if origin.y.min(origin.y + direction.y * max_distance) - radius > self.max_height {
+            return None;
+        }
+        if self.clearance(origin, radius) <= 0.0 {
+            return None;
+        }
+
+        let step = (self.cell.min_element() * 0.25)
+            .min(radius)
+            .max(MIN_CAST_STEP);
+        let steps = (max_distance / step).ceil().max(1.0) as usize;
+        let mut previous = 0.0;
+        for i in 1..=steps {
+            let distance = (i as f32 * step).min(max_distance);
+            if self.clearance(origin + direction * distance, radius) > 0.0 {
+                previous = distance;
+                continue;
+            }
+
+            let (mut low, mut high) = (previous, distance);
+            for _ in 0..CAST_REFINE_ITERATIONS {
+                let mid = (low + high) * 0.5;
+                if self.clearance(origin + direction * mid, radius) > 0.0 {
+                    low = mid;
+                } else {
+                    high = mid;
+                }
+            }
+            return Some(ShapeHit {
+                distance: low,
+                normal: self.normal_at((origin + direction * low).xz()),
+            });
+        }
+        None
+    }
+
+    /// 球体穿透检测，返回 `(推出方向, 穿透深度)`
+    pub fn sphere_penetration(&self, center: Vec3, radius: f32) -> Option<(Vec3, f32)> {
+        let depth = -self.clearance(center, radius);
+        (depth > 0.0).then(|| (self.normal_at(center.xz()), depth))
+    }
+
+    /// 生成地形网格（世界坐标，顶点法线平滑）
+    pub fn mesh(&self) -> Mesh {
+        self.cells_mesh(0, 0, self.columns, self.rows)
+    }
+
+    /// 生成覆盖 `region`（XZ）的局部地形网格，用于分块加载；与整体网格的顶点、法线一致，块之间无缝
+    pub fn region_mesh(&self, region: Rect) -> Mesh {
+        let first = ((region.min - self.origin) / self.cell)
+            .floor()
+            .max(Vec2::ZERO);
+        let last = ((region.max - self.origin) / self.cell).ceil();
+        let last_column = (last.x.max(0.0) as usize).min(self.columns);
+        let last_row = (last.y.max(0.0) as usize).min(self.rows);
+        let first_column = (first.x as usize).min(last_column);
+        let first_row = (first.y as usize).min(last_row);
+        self.cells_mesh(first_column, first_row, last_column, last_row)
+    }
+
+    /// 格点范围 `[first, last]` 内的网格
+    fn cells_mesh(
+        &self,
+        first_column: usize,
+        first_row: usize,
+        last_column: usize,
+        last_row: usize,
+    ) -> Mesh {
+        let count = (last_column - first_column + 1) * (last_row - first_row + 1);
+        let mut positions = Vec::with_capacity(count);
+        let mut normals = Vec::with_capacity(count);
+        let mut uvs = Vec::with_capacity(count);
+        for row in first_row..=last_row {
+            for column in first_column..=last_column {
+                let point = self.origin + Vec2::new(column as f32, row as f32) * self.cell;
+                positions.push([point.x, self.vertex(column, row), point.y]);
+
+                // 中心差分（边缘处单侧）
+                let (left, right) = (column.saturating_sub(1), (column + 1).min(self.columns));
+                let (back, front) = (row.saturating_sub(1), (row + 1).min(self.rows));
+                let dx = (self.vertex(right, row) - self.vertex(left, row))
+                    / ((right - left) as f32 * self.cell.x);
+                let dz = (self.vertex(column, front) - self.vertex(column, back))
+                    / ((front - back) as f32 * self.cell.y);
+                normals.push(Vec3::new(-dx, 1.0, -dz).normalize().to_array());
+
+                uvs.push([
+                    column as f32 / self.columns as f32,
+                    row as f32 / self.rows as f32,
+                ]);
+            }
+        }
+
+        let stride = (last_column - first_column + 1) as u32;
+        let mut indices = Vec::with_capacity(count * 6);
+        for row in 0..(last_row - first_row) as u32 {
+            for column in 0..(last_column - first_column) as u32 {
+                let i00 = row * stride + column;
+                let (i10, i01, i11) = (i00 + 1, i00 + stride, i00 + stride + 1);
+                // 逆时针（从上方看），与高度查询的三角形划分一致
+                indices.extend_from_slice(&[i00, i11, i10, i00, i01, i11]);
+            }
+        }
+
+        Mesh::new(
+            PrimitiveTopology::TriangleList,
+            RenderAssetUsages::default(),
+        )
+        .with_inserted_attribute(Mesh::ATTRIBUTE_POSITION, positions)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_NORMAL, normals)
+        .with_inserted_attribute(Mesh::ATTRIBUTE_UV_0, uvs)
+        .with_inserted_indices(Indices::U32(indices))
+    }
+}
+
+/// 格点上的伪随机值（-1..1）
+fn lattice(seed: u64, x: i32, z: i32) -> f32 {
+    let mut h = seed
+        ^ u64::from(x as u32).wrapping_mul(0x9e37_79b9_7f4a_7c15)
+        ^ u64::from(z as u32).wrapping_mul(0xc2b2_ae3d_27d4_eb4f);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xff51_afd7_ed55_8ccd);
+    h ^= h >> 33;
+    h = h.wrapping_mul(0xc4ce_b9fe_1a85_ec53);
+    h ^= h >> 33;
+    (h >> 40) as f32 / (1u64 << 24) as f32 * 2.0 - 1.0
+}
+
+/// 值噪声（-1..1），格点间按 smoothstep 插值
+fn value_noise(seed: u64, point: Vec2) -> f32 {
+    let base = point.floor();
+    let f = point - base;
+    let t = f * f * (3.0 - 2.0 * f);
+    let (x, z) = (base.x as i32, base.y as i32);
+
+    let a = lattice(seed, x, z);
+    let b = lattice(seed, x + 1, z);
+    let c = lattice(seed, x, z + 1);
+    let d = lattice(seed, x + 1, z + 1);
+    let top = a + (b - a) * t.x;
+    let bottom = c + (d - c) * t.x;
+    top + (bottom - top) * t.y
+}
+
+/// 分形噪声（-1..1）：每层波长与幅度减半
+fn fractal_noise(seed: u64, point: Vec2, config: &TerrainBalance) -> f32 {
+    let mut sum = 0.0;
+    let mut total = 0.0;
+    let mut amplitude = 1.0;
+    let mut wavelength = config.wavelength.max(f32::EPSILON);
+    for octave in 0..config.octaves.max(1) {
+        sum += value_noise(seed.wrapping_add(u64::from(octave)), point / wavelength) * amplitude;
+        total += amplitude;
+        amplitude *= 0.5;
+        wavelength *= 0.5;
+    }
+    sum / total
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_height_matches_mesh_triangles() {
+        // 高度 = x + 2z 的平面：三角形插值应精确还原
+        let terrain = Heightfield::from_fn(Vec2::new(10.0, 10.0), 2.0, |p| p.x + 2.0 * p.y);
+        for point in [
+            Vec2::new(0.3, -1.7),
+            Vec2::new(4.9, 4.9),
+            Vec2::new(-3.0, 2.5),
+        ] {
+            assert!((terrain.height_at(point) - (point.x + 2.0 * point.y)).abs() < 1e-4);
+        }
+        let normal = terrain.normal_at(Vec2::ZERO);
+        assert!(normal.abs_diff_eq(Vec3::new(-1.0, 1.0, -2.0).normalize(), 1e-5));
+
+        let mesh = terrain.mesh();
+        assert_eq!(mesh.count_vertices(), 36);
+        assert_eq!(mesh.indices().unwrap().len(), 5 * 5 * 6);
+
+        // 分块网格覆盖区域所在的格子，边缘顶点与整体网格相同
+        let chunk = terrain.region_mesh(Rect::new(-5.0, -5.0, 0.0, 0.0));
+        assert_eq!(chunk.count_vertices(), 16);
+        assert_eq!(chunk.indices().unwrap().len(), 3 * 3 * 6);
+        let Some(bevy::mesh::VertexAttributeValues::Float32x3(positions)) =
+            chunk.attribute(Mesh::ATTRIBUTE_POSITION)
+        else {
+            panic!("missing positions");
+        };
+        assert!(positions.iter().all(|p| p[0] <= 1.0 && p[2] <= 1.0));
+    }
+
+    #[test]
+    fn test_noise_terrain() {
+        let config = TerrainBalance::default();
+        let size = Vec2::new(60.0, 40.0);
+        let a = Heightfield::from_noise(size, 5, &config);
+        assert_eq!(a, Heightfield::from_noise(size, 5, &config));
+        assert_ne!(a, Heightfield::from_noise(size, 6, &config));
+
+        let (min, max) = a.height_range();
+        assert!(min >= FLOOR_TOP - config.amplitude && max <= FLOOR_TOP + config.amplitude);
+        assert!(max - min > 0.1);
+
+        // 压平后中心附近高度一致
+        let mut flattened = a.clone();
+        flattened.flatten(Vec2::ZERO, 5.0);
+        let center = flattened.height_at(Vec2::ZERO);
+        assert!((flattened.height_at(Vec2::new(3.0, 2.0)) - center).abs() < 1e-4);
+    }
+
+    #[test]
+    fn test_sphere_cast_and_penetration() {
+        let terrain = Heightfield::from_fn(Vec2::new(20.0, 20.0), 1.0, |p| p.x * 0.5);
+
+        // 竖直向下落到坡面：接触时球心到切平面的距离等于半径
+        let origin = Vec3::new(2.0, 5.0, 0.0);
+        let hit = terrain.sphere_cast(origin, Vec3::NEG_Y, 10.0, 0.5).unwrap();
+        let contact = origin + Vec3::NEG_Y * hit.distance;
+        assert!((terrain.clearance(contact, 0.5)).abs() < 0.01);
+        assert!(hit.normal.y < 1.0 && hit.normal.x < 0.0);
+        assert!(terrain.sphere_cast(origin, Vec3::Y, 10.0, 0.5).is_none());
+
+        let (normal, depth) = terrain
+            .sphere_penetration(Vec3::new(2.0, 1.0, 0.0), 0.5)
+            .unwrap();
+        assert!(normal.y > 0.8 && depth > 0.0);
+
+        let placed = terrain.clamp_to_terrain(Vec3::new(50.0, 0.0, 3.0));
+        assert_eq!(placed.x, 9.0);
+        assert!((placed.y - (4.5 + GROUND_CLEARANCE)).abs() < 1e-4);
+    }
+}